@@ -0,0 +1,316 @@
+use std::{fmt::Write, path::PathBuf};
+
+use alloy_json_abi::{Function, JsonAbi, Param, StateMutability};
+use proc_macro::TokenStream;
+use proc_macro2::Span;
+use quote::{format_ident, quote};
+use syn::{
+    parse::{Parse, ParseStream},
+    parse_macro_input, Ident, LitStr, Token,
+};
+
+/// Input of the `abi_interface!` macro.
+struct Input {
+    /// Name of the generated interface, e.g., `IPriceFeed`.
+    name: Ident,
+    /// Path of the ABI JSON file, relative to the crate's manifest.
+    path: LitStr,
+}
+
+impl Parse for Input {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let name = input.parse()?;
+        input.parse::<Token![,]>()?;
+        let path = input.parse()?;
+        input.parse::<Option<Token![,]>>()?;
+        Ok(Input { name, path })
+    }
+}
+
+/// Generates call bindings and error decoding from an ABI JSON file.
+///
+/// For more information see [`crate::abi_interface`].
+pub(crate) fn abi_interface(input: TokenStream) -> TokenStream {
+    let Input { name, path } = parse_macro_input!(input as Input);
+
+    let (abi_path, abi) = match read_abi(&path.value()) {
+        Ok(abi) => abi,
+        Err(e) => error!(path, "failed to read the abi: {e}"),
+    };
+    // Makes Cargo recompile the crate using the macro when the ABI changes.
+    let abi_path = abi_path.to_string_lossy();
+    let tracked = quote! { const _: &str = include_str!(#abi_path); };
+
+    let functions = match declare_functions(&abi) {
+        Ok(functions) => functions,
+        Err(e) => error!(path, "{e}"),
+    };
+    let interface = format!("interface {name} {{ {functions} }}");
+    let interface: proc_macro2::TokenStream = match interface.parse() {
+        Ok(interface) => interface,
+        Err(e) => error!(path, "failed to generate the interface: {e}"),
+    };
+
+    let errors: Vec<_> = abi.errors().collect();
+    if errors.is_empty() {
+        return quote! {
+            #tracked
+            stylus_sdk::prelude::sol_interface! { #interface }
+        }
+        .into();
+    }
+
+    let mut declarations = String::new();
+    for error in &errors {
+        let Ok(inputs) = params(&error.inputs, "") else {
+            error!(
+                path,
+                "`{}` uses tuples, which are not supported", error.name
+            );
+        };
+        let _ = write!(
+            declarations,
+            "#[derive(Debug)] #[allow(missing_docs)] error {}({inputs});",
+            error.name
+        );
+    }
+    let declarations: proc_macro2::TokenStream = match declarations.parse() {
+        Ok(declarations) => declarations,
+        Err(e) => error!(path, "failed to generate the errors: {e}"),
+    };
+
+    let module = format_ident!("{}", snake_case(&name.to_string()));
+    let variants: Vec<_> = errors
+        .iter()
+        .map(|error| Ident::new(&error.name, Span::call_site()))
+        .collect();
+    let module_doc = format!(" Custom errors of [`{name}`].");
+    let error_doc = format!(" An error returned by [`{name}`].");
+
+    quote! {
+        #tracked
+        stylus_sdk::prelude::sol_interface! { #interface }
+
+        #[doc = #module_doc]
+        pub mod #module {
+            alloy_sol_types::sol! { #declarations }
+
+            #[doc = #error_doc]
+            #[derive(Debug)]
+            #[allow(missing_docs)]
+            pub enum Error {
+                #( #variants(#variants), )*
+            }
+
+            impl Error {
+                /// Decodes the data a call reverted with, e.g., from
+                /// `stylus_sdk::call::Error::Revert`, if it is one of the
+                /// errors of the interface.
+                #[must_use]
+                pub fn decode(data: &[u8]) -> Option<Self> {
+                    use alloy_sol_types::SolError;
+                    #(
+                        if data.starts_with(&#variants::SELECTOR) {
+                            return #variants::decode(data, true)
+                                .ok()
+                                .map(Self::#variants);
+                        }
+                    )*
+                    None
+                }
+            }
+        }
+    }
+    .into()
+}
+
+/// Renders the functions of `abi` as Solidity interface function
+/// declarations.
+///
+/// Errors if a function is overloaded, since `sol_interface!` would generate
+/// several methods with the same name, or if it has tuple parameters.
+fn declare_functions(abi: &JsonAbi) -> Result<String, String> {
+    let mut functions = String::new();
+    for (name, overloads) in &abi.functions {
+        if overloads.len() > 1 {
+            return Err(format!(
+                "`{name}` is overloaded, which is not supported"
+            ));
+        }
+        for function in overloads {
+            let Ok(declaration) = declare(function) else {
+                return Err(format!(
+                    "`{name}` uses tuples, which are not supported"
+                ));
+            };
+            functions.push_str(&declaration);
+        }
+    }
+    Ok(functions)
+}
+
+/// Renders `function` as a Solidity interface function declaration.
+///
+/// Errors if one of its parameters is a tuple.
+fn declare(function: &Function) -> Result<String, ()> {
+    let inputs = params(&function.inputs, "")?;
+    let outputs = params(&function.outputs, " memory")?;
+    let mutability = match function.state_mutability {
+        StateMutability::Pure => " pure",
+        StateMutability::View => " view",
+        StateMutability::Payable => " payable",
+        StateMutability::NonPayable => "",
+    };
+    let returns = if outputs.is_empty() {
+        String::new()
+    } else {
+        format!(" returns ({outputs})")
+    };
+    Ok(format!(
+        "function {}({inputs}) external{mutability}{returns};",
+        function.name
+    ))
+}
+
+/// Reads the ABI at `path`, relative to the manifest of the crate being
+/// compiled, and returns it along with its absolute path.
+///
+/// Both a plain ABI and a compiler artifact, with the ABI under an `abi`
+/// key, are supported.
+fn read_abi(path: &str) -> Result<(PathBuf, JsonAbi), String> {
+    let root =
+        std::env::var("CARGO_MANIFEST_DIR").map_err(|e| e.to_string())?;
+    let path = PathBuf::from(root).join(path);
+    let content = std::fs::read_to_string(&path)
+        .map_err(|e| format!("{}: {e}", path.display()))?;
+
+    let mut json: serde_json::Value =
+        serde_json::from_str(&content).map_err(|e| e.to_string())?;
+    if let Some(abi) = json.get_mut("abi") {
+        json = abi.take();
+    }
+    // `JsonAbi` only deserializes from a buffered string.
+    let abi =
+        serde_json::from_str(&json.to_string()).map_err(|e| e.to_string())?;
+    Ok((path, abi))
+}
+
+/// Renders `params` as a Solidity parameter list, naming unnamed ones after
+/// their position.
+///
+/// Dynamic types get the `location` storage location.
+///
+/// Errors if a parameter is a tuple.
+fn params(params: &[Param], location: &str) -> Result<String, ()> {
+    let params = params
+        .iter()
+        .enumerate()
+        .map(|(i, param)| {
+            if param.ty.starts_with("tuple") {
+                return Err(());
+            }
+            let dynamic = param.ty == "string"
+                || param.ty == "bytes"
+                || param.ty.ends_with(']');
+            let location = if dynamic { location } else { "" };
+            let name = if param.name.is_empty() {
+                format!("arg{i}")
+            } else {
+                param.name.clone()
+            };
+            Ok(format!("{}{location} {name}", param.ty))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(params.join(", "))
+}
+
+/// Converts a `PascalCase` name to `snake_case`, e.g., `IAggregatorV3` to
+/// `iaggregator_v3`.
+fn snake_case(name: &str) -> String {
+    let mut snake = String::with_capacity(name.len());
+    let mut previous_lowercase = false;
+    for c in name.chars() {
+        if c.is_uppercase() && previous_lowercase {
+            snake.push('_');
+        }
+        previous_lowercase = c.is_lowercase();
+        snake.extend(c.to_lowercase());
+    }
+    snake
+}
+
+#[cfg(test)]
+mod tests {
+    use alloy_json_abi::JsonAbi;
+
+    use super::declare_functions;
+
+    fn abi(json: &str) -> JsonAbi {
+        serde_json::from_str(json).expect("should parse the abi")
+    }
+
+    #[test]
+    fn declares_functions() {
+        let abi = abi(r#"[{
+            "type": "function",
+            "name": "balanceOf",
+            "inputs": [{ "name": "account", "type": "address" }],
+            "outputs": [{ "name": "", "type": "uint256" }],
+            "stateMutability": "view"
+        }]"#);
+        assert_eq!(
+            declare_functions(&abi).unwrap(),
+            "function balanceOf(address account) external view returns \
+             (uint256 arg0);"
+        );
+    }
+
+    #[test]
+    fn rejects_overloaded_functions() {
+        let abi = abi(r#"[{
+            "type": "function",
+            "name": "safeTransferFrom",
+            "inputs": [
+                { "name": "from", "type": "address" },
+                { "name": "to", "type": "address" },
+                { "name": "tokenId", "type": "uint256" }
+            ],
+            "outputs": [],
+            "stateMutability": "nonpayable"
+        }, {
+            "type": "function",
+            "name": "safeTransferFrom",
+            "inputs": [
+                { "name": "from", "type": "address" },
+                { "name": "to", "type": "address" },
+                { "name": "tokenId", "type": "uint256" },
+                { "name": "data", "type": "bytes" }
+            ],
+            "outputs": [],
+            "stateMutability": "nonpayable"
+        }]"#);
+        assert_eq!(
+            declare_functions(&abi).unwrap_err(),
+            "`safeTransferFrom` is overloaded, which is not supported"
+        );
+    }
+
+    #[test]
+    fn rejects_tuple_parameters() {
+        let abi = abi(r#"[{
+            "type": "function",
+            "name": "swap",
+            "inputs": [{
+                "name": "key",
+                "type": "tuple",
+                "components": [{ "name": "fee", "type": "uint24" }]
+            }],
+            "outputs": [],
+            "stateMutability": "nonpayable"
+        }]"#);
+        assert_eq!(
+            declare_functions(&abi).unwrap_err(),
+            "`swap` uses tuples, which are not supported"
+        );
+    }
+}
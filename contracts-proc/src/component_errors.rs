@@ -0,0 +1,69 @@
+use proc_macro::TokenStream;
+use quote::quote;
+use syn::{parse_macro_input, Data, DeriveInput, Fields};
+
+/// Generates the conversions of an error enum wrapping component errors.
+///
+/// For more information see [`crate::FromComponentErrors`].
+pub(crate) fn from_component_errors(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) =
+        input.generics.split_for_impl();
+
+    let Data::Enum(data) = &input.data else {
+        error!(input, "`FromComponentErrors` can only be derived for enums");
+    };
+
+    let mut from_impls = Vec::with_capacity(data.variants.len());
+    let mut encode_arms = Vec::with_capacity(data.variants.len());
+    let mut fields = Vec::with_capacity(data.variants.len());
+    for variant in &data.variants {
+        let variant_name = &variant.ident;
+        let field = match &variant.fields {
+            Fields::Unnamed(fields) if fields.unnamed.len() == 1 => {
+                &fields.unnamed[0].ty
+            }
+            _ => error!(
+                variant,
+                "variants must wrap exactly one component error, e.g., \
+                 `Erc20(erc20::Error)`"
+            ),
+        };
+
+        from_impls.push(quote! {
+            impl #impl_generics ::core::convert::From<#field> for #name #ty_generics #where_clause {
+                fn from(value: #field) -> Self {
+                    #name::#variant_name(value)
+                }
+            }
+        });
+        encode_arms.push(quote! {
+            #name::#variant_name(error) => error.into(),
+        });
+        fields.push(field);
+    }
+
+    quote! {
+        #( #from_impls )*
+
+        impl #impl_generics ::core::convert::From<#name #ty_generics> for ::alloc::vec::Vec<u8> #where_clause {
+            fn from(value: #name #ty_generics) -> Self {
+                match value {
+                    #( #encode_arms )*
+                }
+            }
+        }
+
+        // Lists the errors of every component in the exported interface.
+        #[cfg(feature = "export-abi")]
+        impl #impl_generics stylus_sdk::abi::export::internal::InnerTypes for #name #ty_generics #where_clause {
+            fn inner_types() -> ::alloc::vec::Vec<stylus_sdk::abi::export::internal::InnerType> {
+                let mut types = ::alloc::vec::Vec::new();
+                #( types.extend(<#fields as stylus_sdk::abi::export::internal::InnerTypes>::inner_types()); )*
+                types
+            }
+        }
+    }
+    .into()
+}
@@ -0,0 +1,48 @@
+use proc_macro::TokenStream;
+use quote::quote;
+use syn::{parse_macro_input, Data, DeriveInput, Fields};
+
+/// Generates the `Display` and `core::error::Error` implementations of an
+/// error enum.
+///
+/// For more information see [`crate::ErrorDisplay`].
+pub(crate) fn error_display(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) =
+        input.generics.split_for_impl();
+
+    let Data::Enum(data) = &input.data else {
+        error!(input, "`ErrorDisplay` can only be derived for enums");
+    };
+
+    let mut display_arms = Vec::with_capacity(data.variants.len());
+    for variant in &data.variants {
+        let variant_name = &variant.ident;
+        if !matches!(&variant.fields, Fields::Unnamed(fields) if fields.unnamed.len() == 1)
+        {
+            error!(
+                variant,
+                "variants must wrap exactly one error, e.g., \
+                 `InsufficientBalance(ERC20InsufficientBalance)`"
+            );
+        }
+
+        display_arms.push(quote! {
+            #name::#variant_name(error) => ::core::write!(f, "{error:?}"),
+        });
+    }
+
+    quote! {
+        impl #impl_generics ::core::fmt::Display for #name #ty_generics #where_clause {
+            fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                match self {
+                    #( #display_arms )*
+                }
+            }
+        }
+
+        impl #impl_generics ::core::error::Error for #name #ty_generics #where_clause {}
+    }
+    .into()
+}
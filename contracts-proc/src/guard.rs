@@ -0,0 +1,97 @@
+use proc_macro::TokenStream;
+use quote::quote;
+use syn::{
+    parse::{Parse, ParseStream},
+    parse_macro_input, parse_quote, Attribute, Expr, Ident, ItemFn, Token,
+};
+
+/// Arguments of the `#[only_role]` attribute.
+struct OnlyRole {
+    /// Field of the contract holding its `AccessControl`.
+    access: Ident,
+    /// Id of the role, e.g., `Self::MINTER_ROLE`.
+    role: Expr,
+}
+
+impl Parse for OnlyRole {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let access = input.parse()?;
+        input.parse::<Token![,]>()?;
+        let role = input.parse()?;
+        input.parse::<Option<Token![,]>>()?;
+        Ok(OnlyRole { access, role })
+    }
+}
+
+impl OnlyRole {
+    /// Returns the check the attribute stands for.
+    fn into_guard(self) -> Expr {
+        let OnlyRole { access, role } = self;
+        parse_quote! {
+            self.#access.only_role(
+                stylus_sdk::alloy_primitives::B256::from(#role),
+            )
+        }
+    }
+}
+
+/// Prepends the checks of a method's `#[guard(..)]` attributes to its body.
+///
+/// For more information see [`crate::guard`].
+pub(crate) fn guard(attr: TokenStream, input: TokenStream) -> TokenStream {
+    let first = parse_macro_input!(attr as Expr);
+    let item_fn = parse_macro_input!(input as ItemFn);
+    expand(first, item_fn)
+}
+
+/// Prepends an access control check to a method's body.
+///
+/// For more information see [`crate::only_role`].
+pub(crate) fn only_role(attr: TokenStream, input: TokenStream) -> TokenStream {
+    let first = parse_macro_input!(attr as OnlyRole);
+    let item_fn = parse_macro_input!(input as ItemFn);
+    expand(first.into_guard(), item_fn)
+}
+
+/// Returns the check of a `#[guard]` or `#[only_role]` attribute, or `None`
+/// for other attributes.
+fn parse_check(attr: &Attribute) -> Option<syn::Result<Expr>> {
+    if attr.path().is_ident("guard") {
+        Some(attr.parse_args::<Expr>())
+    } else if attr.path().is_ident("only_role") {
+        Some(attr.parse_args::<OnlyRole>().map(OnlyRole::into_guard))
+    } else {
+        None
+    }
+}
+
+/// Prepends `first`, and the checks of the remaining guarding attributes of
+/// `item_fn`, to its body.
+fn expand(first: Expr, mut item_fn: ItemFn) -> TokenStream {
+    // The outermost guarding attribute gets expanded first, so it also
+    // expands the remaining ones to keep the checks in declaration order.
+    let mut guards = vec![first];
+    let mut attrs = Vec::with_capacity(item_fn.attrs.len());
+    for attr in std::mem::take(&mut item_fn.attrs) {
+        match parse_check(&attr) {
+            Some(Ok(guard)) => guards.push(guard),
+            Some(Err(error)) => return error.to_compile_error().into(),
+            None => attrs.push(attr),
+        }
+    }
+
+    if matches!(item_fn.sig.output, syn::ReturnType::Default) {
+        error!(item_fn.sig, "guarded functions must return a `Result`");
+    }
+
+    let ItemFn { vis, sig, block, .. } = item_fn;
+    let stmts = &block.stmts;
+    quote! {
+        #( #attrs )*
+        #vis #sig {
+            #( #guards?; )*
+            #( #stmts )*
+        }
+    }
+    .into()
+}
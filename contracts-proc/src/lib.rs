@@ -0,0 +1,308 @@
+#![doc = include_str!("../README.md")]
+use proc_macro::TokenStream;
+
+/// Shorthand to print nice errors.
+///
+/// Note that it's defined before the module declarations.
+macro_rules! error {
+    ($tokens:expr, $($msg:expr),+ $(,)?) => {{
+        let error = syn::Error::new(syn::spanned::Spanned::span(&$tokens), format!($($msg),+));
+        return error.to_compile_error().into();
+    }};
+    (@ $tokens:expr, $($msg:expr),+ $(,)?) => {{
+        return Err(syn::Error::new(syn::spanned::Spanned::span(&$tokens), format!($($msg),+)))
+    }};
+}
+
+mod abi_interface;
+mod component_errors;
+mod error_display;
+mod guard;
+mod namespaced_storage;
+mod roles;
+
+/// Guards a method with a check that runs before its body, returning early
+/// with the check's error if it fails.
+///
+/// The guard is an expression evaluating to a `Result<(), E>`, where the
+/// method returns a `Result` whose error type implements `From<E>`. A method
+/// can have several guards, which run in the order they are declared.
+///
+/// Declaring guards next to the signature makes it harder to forget one on
+/// some of several methods that need it.
+///
+/// Note that, inside an `#[external]` block, the attribute must be imported
+/// and used as `#[guard(..)]`. The Stylus SDK silently drops attributes with a
+/// path there, so a method guarded with `#[openzeppelin_stylus::guard(..)]`
+/// compiles, but runs without the check.
+///
+/// # Examples
+///
+/// ```rust,ignore
+/// use openzeppelin_stylus::guard;
+///
+/// #[external]
+/// impl Erc20Example {
+///     #[guard(self.pausable.when_not_paused())]
+///     pub fn transfer(
+///         &mut self,
+///         to: Address,
+///         value: U256,
+///     ) -> Result<bool, Vec<u8>> {
+///         self.erc20.transfer(to, value).map_err(|e| e.into())
+///     }
+/// }
+/// ```
+#[proc_macro_attribute]
+pub fn guard(attr: TokenStream, input: TokenStream) -> TokenStream {
+    guard::guard(attr, input)
+}
+
+/// Restricts a method to accounts that have a role, checking it with the
+/// `AccessControl` held in a field of the contract before the method's body.
+///
+/// The first argument is the field, and the second the id of the role, e.g., a
+/// constant generated by [`define_roles!`]. It can be combined with
+/// [`macro@guard`], with all checks running in the order they are declared.
+///
+/// The method must return a `Result` whose error type implements
+/// `From<openzeppelin_stylus::access::control::Error>`, like `Vec<u8>` does.
+///
+/// Like `#[guard]`, it must be imported and used as `#[only_role(..)]` inside
+/// an `#[external]` block, where a method restricted with
+/// `#[openzeppelin_stylus::only_role(..)]` would be callable by anyone.
+///
+/// # Examples
+///
+/// ```rust,ignore
+/// #[external]
+/// impl AccessControlExample {
+///     #[only_role(access, Self::MINTER_ROLE)]
+///     pub fn mint(&mut self, to: Address, value: U256) -> Result<(), Vec<u8>> {
+///         // ...
+///     }
+/// }
+/// ```
+///
+/// Expands to:
+///
+/// ```rust,ignore
+/// pub fn mint(&mut self, to: Address, value: U256) -> Result<(), Vec<u8>> {
+///     self.access.only_role(B256::from(Self::MINTER_ROLE))?;
+///     // ...
+/// }
+/// ```
+#[proc_macro_attribute]
+pub fn only_role(attr: TokenStream, input: TokenStream) -> TokenStream {
+    guard::only_role(attr, input)
+}
+
+/// Derives the conversions of an error enum whose variants wrap the errors of
+/// the components a contract is made of.
+///
+/// For every variant, this generates a `From` implementation from the wrapped
+/// error, so that `?` converts component errors, and the conversion into
+/// `Vec<u8>` required by `#[external]` methods, which encodes the wrapped
+/// error as is.
+///
+/// With the `export-abi` feature enabled, the errors of every component are
+/// also listed in the exported interface.
+///
+/// Note that it refers to `alloc::vec::Vec`, so the crate using it needs an
+/// `extern crate alloc;` declaration.
+///
+/// # Examples
+///
+/// ```rust,ignore
+/// use openzeppelin_stylus::{
+///     token::erc20,
+///     utils::pausable,
+///     FromComponentErrors,
+/// };
+///
+/// #[derive(FromComponentErrors, Debug)]
+/// pub enum Error {
+///     Erc20(erc20::Error),
+///     Pausable(pausable::Error),
+/// }
+///
+/// #[external]
+/// impl Erc20Example {
+///     #[guard(self.pausable.when_not_paused())]
+///     pub fn burn(&mut self, value: U256) -> Result<(), Error> {
+///         Ok(self.erc20.burn(value)?)
+///     }
+/// }
+/// ```
+#[proc_macro_derive(FromComponentErrors)]
+pub fn from_component_errors(input: TokenStream) -> TokenStream {
+    component_errors::from_component_errors(input)
+}
+
+/// Implements `Display` and `core::error::Error` for an error enum whose
+/// variants each wrap one error, like the ones deriving `SolidityError`.
+///
+/// Every variant is displayed as the `Debug` representation of the error it
+/// wraps, which for errors declared with `sol!` renders their name and
+/// fields, e.g., `ERC20InsufficientBalance { sender: 0x..., balance: 1,
+/// needed: 2 }`. This way, errors propagate through `eyre` or `anyhow` in
+/// tests as something readable, rather than as encoded bytes.
+///
+/// # Examples
+///
+/// ```rust,ignore
+/// #[derive(SolidityError, Debug, ErrorDisplay)]
+/// pub enum Error {
+///     InsufficientBalance(ERC20InsufficientBalance),
+///     InvalidSender(ERC20InvalidSender),
+/// }
+/// ```
+#[proc_macro_derive(ErrorDisplay)]
+pub fn error_display(input: TokenStream) -> TokenStream {
+    error_display::error_display(input)
+}
+
+/// Defines access control roles on a contract.
+///
+/// For every role, this generates an associated constant with its id, which is
+/// the `keccak256` hash of its name, and a getter returning that id as a
+/// `B256`, named after the role in lowercase.
+///
+/// When the field holding the contract's `AccessControl` is given after the
+/// contract's name, this also generates an `only_<role>` guard, which checks
+/// that `msg::sender` has the role.
+///
+/// # Examples
+///
+/// ```rust,ignore
+/// define_roles! {
+///     AccessControlExample.access {
+///         MINTER_ROLE,
+///         BURNER_ROLE,
+///     }
+/// }
+///
+/// #[external]
+/// impl AccessControlExample {
+///     #[guard(self.only_minter_role())]
+///     pub fn mint(&mut self, to: Address, value: U256) -> Result<(), Vec<u8>> {
+///         // ...
+///     }
+/// }
+/// ```
+///
+/// Expands to:
+///
+/// ```rust,ignore
+/// impl AccessControlExample {
+///     pub const MINTER_ROLE: [u8; 32] = [/* keccak256("MINTER_ROLE") */];
+///     pub fn minter_role() -> B256 { /* ... */ }
+///     pub fn only_minter_role(&self) -> Result<(), control::Error> { /* ... */ }
+///     // Same for `BURNER_ROLE`.
+/// }
+/// ```
+#[proc_macro]
+pub fn define_roles(input: TokenStream) -> TokenStream {
+    roles::define_roles(input)
+}
+
+/// Generates an upgrade-safe twin of a storage struct, stored in an
+/// [ERC-7201] namespace instead of where it is declared.
+///
+/// Next to the struct, this generates a `<Name>Upgradeable` wrapper, which
+/// lays the struct out from the slot derived from the namespace id, and
+/// derefs to it, so that all of its methods can be used. The wrapper takes no
+/// room in the struct declaring it, so a component can't be moved by fields
+/// added before it in a later version.
+///
+/// To expose the external methods of the component with `#[inherit]`, the
+/// contract needs to borrow the wrapped struct, rather than the wrapper.
+///
+/// [ERC-7201]: https://eips.ethereum.org/EIPS/eip-7201
+///
+/// # Examples
+///
+/// ```rust,ignore
+/// sol_storage! {
+///     #[namespaced_storage("openzeppelin.storage.ERC20")]
+///     pub struct Erc20 {
+///         mapping(address => uint256) _balances;
+///         // ...
+///     }
+/// }
+///
+/// sol_storage! {
+///     #[entrypoint]
+///     struct Token {
+///         Erc20Upgradeable erc20;
+///     }
+/// }
+///
+/// impl Borrow<Erc20> for Token {
+///     fn borrow(&self) -> &Erc20 {
+///         &self.erc20
+///     }
+/// }
+///
+/// impl BorrowMut<Erc20> for Token {
+///     fn borrow_mut(&mut self) -> &mut Erc20 {
+///         &mut self.erc20
+///     }
+/// }
+///
+/// #[external]
+/// #[inherit(Erc20)]
+/// impl Token {}
+/// ```
+#[proc_macro_attribute]
+pub fn namespaced_storage(
+    attr: TokenStream,
+    input: TokenStream,
+) -> TokenStream {
+    namespaced_storage::namespaced_storage(attr, input)
+}
+
+/// Generates typed call bindings, like `sol_interface!`, from an ABI JSON
+/// file, so that existing contracts can be called without transcribing their
+/// interface by hand.
+///
+/// The path is relative to the manifest of the crate using the macro, and can
+/// point to a plain ABI or to a compiler artifact with the ABI under an `abi`
+/// key, as emitted by Foundry or Hardhat.
+///
+/// When the ABI declares custom errors, they are generated in a module named
+/// after the interface in `snake_case`, along with an `Error` enum that decodes
+/// the data a call reverted with. Note that the errors are declared with
+/// `alloy_sol_types::sol!`, so the crate using the macro needs to depend on
+/// `alloy-sol-types`.
+///
+/// Events are not generated. Functions with tuple parameters and overloaded
+/// functions, which `sol_interface!` doesn't support, are rejected with a
+/// compile error.
+///
+/// # Examples
+///
+/// ```rust,ignore
+/// abi_interface!(IPriceFeed, "abi/AggregatorV3Interface.json");
+///
+/// #[external]
+/// impl Oracle {
+///     pub fn price(&mut self) -> Result<I256, Vec<u8>> {
+///         let feed = IPriceFeed::new(self.feed.get());
+///         match feed.latest_answer(self) {
+///             Ok(answer) => Ok(answer),
+///             Err(call::Error::Revert(data)) => {
+///                 match iprice_feed::Error::decode(&data) {
+///                     Some(iprice_feed::Error::StalePrice(_)) => /* ... */,
+///                     _ => Err(data),
+///                 }
+///             }
+///             Err(e) => Err(e.into()),
+///         }
+///     }
+/// }
+/// ```
+#[proc_macro]
+pub fn abi_interface(input: TokenStream) -> TokenStream {
+    abi_interface::abi_interface(input)
+}
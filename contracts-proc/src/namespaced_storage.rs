@@ -0,0 +1,117 @@
+use proc_macro::TokenStream;
+use quote::{format_ident, quote};
+use syn::{parse_macro_input, ItemStruct, LitStr};
+use tiny_keccak::{Hasher, Keccak};
+
+/// Generates the ERC-7201 namespaced twin of a storage struct.
+///
+/// For more information see [`crate::namespaced_storage`].
+pub(crate) fn namespaced_storage(
+    attr: TokenStream,
+    input: TokenStream,
+) -> TokenStream {
+    let namespace = parse_macro_input!(attr as LitStr);
+    let item = parse_macro_input!(input as ItemStruct);
+
+    let id = namespace.value();
+    if id.is_empty() {
+        error!(namespace, "the namespace id can't be empty");
+    }
+
+    let name = &item.ident;
+    let vis = &item.vis;
+    let twin = format_ident!("{name}Upgradeable");
+    let (impl_generics, ty_generics, where_clause) =
+        item.generics.split_for_impl();
+    let slot = erc7201_slot(&id);
+
+    let twin_doc = format!(
+        " [`{name}`] stored in the ERC-7201 namespace `{id}`, rather than \
+         where it is declared."
+    );
+    let slot_doc = format!(
+        " Storage slot of the namespace, `keccak256(abi.encode(uint256(\
+         keccak256(\"{id}\")) - 1)) & ~bytes32(uint256(0xff))`."
+    );
+
+    quote! {
+        #item
+
+        #[doc = #twin_doc]
+        ///
+        /// It derefs to the wrapped storage, and takes no room in the struct
+        /// declaring it, so that adding fields to the latter doesn't move the
+        /// namespaced state.
+        #vis struct #twin #impl_generics (#name #ty_generics) #where_clause;
+
+        impl #impl_generics #twin #ty_generics #where_clause {
+            #[doc = #slot_doc]
+            pub const STORAGE_SLOT: [u8; 32] = [#(#slot),*];
+        }
+
+        impl #impl_generics stylus_sdk::storage::StorageType for #twin #ty_generics #where_clause {
+            type Wraps<'a> = stylus_sdk::storage::StorageGuard<'a, Self> where Self: 'a;
+            type WrapsMut<'a> = stylus_sdk::storage::StorageGuardMut<'a, Self> where Self: 'a;
+
+            const SLOT_BYTES: usize = 0;
+            const REQUIRED_SLOTS: usize = 0;
+
+            unsafe fn new(_slot: stylus_sdk::alloy_primitives::U256, _offset: u8) -> Self {
+                let slot = stylus_sdk::alloy_primitives::U256::from_be_bytes(
+                    Self::STORAGE_SLOT,
+                );
+                Self(<#name #ty_generics as stylus_sdk::storage::StorageType>::new(slot, 0))
+            }
+
+            fn load<'s>(self) -> Self::Wraps<'s> {
+                stylus_sdk::storage::StorageGuard::new(self)
+            }
+
+            fn load_mut<'s>(self) -> Self::WrapsMut<'s> {
+                stylus_sdk::storage::StorageGuardMut::new(self)
+            }
+        }
+
+        impl #impl_generics ::core::ops::Deref for #twin #ty_generics #where_clause {
+            type Target = #name #ty_generics;
+
+            fn deref(&self) -> &Self::Target {
+                &self.0
+            }
+        }
+
+        impl #impl_generics ::core::ops::DerefMut for #twin #ty_generics #where_clause {
+            fn deref_mut(&mut self) -> &mut Self::Target {
+                &mut self.0
+            }
+        }
+    }
+    .into()
+}
+
+/// Returns the ERC-7201 storage slot of the namespace `id`.
+fn erc7201_slot(id: &str) -> [u8; 32] {
+    let mut hash = keccak256(id.as_bytes());
+
+    // `uint256(keccak256(id)) - 1`, borrowing from the upper bytes.
+    for byte in hash.iter_mut().rev() {
+        let (value, borrow) = byte.overflowing_sub(1);
+        *byte = value;
+        if !borrow {
+            break;
+        }
+    }
+
+    let mut slot = keccak256(&hash);
+    slot[31] = 0;
+    slot
+}
+
+/// Returns the `keccak256` hash of `bytes`.
+fn keccak256(bytes: &[u8]) -> [u8; 32] {
+    let mut output = [0u8; 32];
+    let mut hasher = Keccak::v256();
+    hasher.update(bytes);
+    hasher.finalize(&mut output);
+    output
+}
@@ -0,0 +1,95 @@
+use proc_macro::TokenStream;
+use quote::{format_ident, quote};
+use syn::{
+    braced,
+    parse::{Parse, ParseStream},
+    parse_macro_input,
+    punctuated::Punctuated,
+    Ident, Token,
+};
+use tiny_keccak::{Hasher, Keccak};
+
+/// Input of the `define_roles!` macro.
+struct Roles {
+    /// Contract the roles are defined on.
+    contract: Ident,
+    /// Field of the contract holding its `AccessControl`, if guards should be
+    /// generated.
+    access: Option<Ident>,
+    /// Names of the roles, e.g., `MINTER_ROLE`.
+    names: Punctuated<Ident, Token![,]>,
+}
+
+impl Parse for Roles {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let contract = input.parse()?;
+        let access = if input.peek(Token![.]) {
+            input.parse::<Token![.]>()?;
+            Some(input.parse()?)
+        } else {
+            None
+        };
+
+        let content;
+        braced!(content in input);
+        let names = content.parse_terminated(Ident::parse, Token![,])?;
+        Ok(Roles { contract, access, names })
+    }
+}
+
+/// Generates the ids, getters and guards of access control roles.
+///
+/// For more information see [`crate::define_roles`].
+pub(crate) fn define_roles(input: TokenStream) -> TokenStream {
+    let Roles { contract, access, names } = parse_macro_input!(input as Roles);
+
+    let items = names.iter().map(|role| {
+        let name = role.to_string();
+        let getter = format_ident!("{}", name.to_lowercase());
+        let mut id = [0u8; 32];
+        let mut hasher = Keccak::v256();
+        hasher.update(name.as_bytes());
+        hasher.finalize(&mut id);
+
+        let id_doc = format!(" `keccak256(\"{name}\")`");
+        let getter_doc = format!(" Returns the id of `{name}`.");
+        let constant = quote! {
+            #[doc = #id_doc]
+            pub const #role: [u8; 32] = [#(#id),*];
+
+            #[doc = #getter_doc]
+            #[must_use]
+            pub fn #getter() -> stylus_sdk::alloy_primitives::B256 {
+                stylus_sdk::alloy_primitives::B256::new(Self::#role)
+            }
+        };
+
+        let Some(access) = &access else {
+            return constant;
+        };
+        let guard = format_ident!("only_{}", name.to_lowercase());
+        let guard_doc = format!(" Checks that `msg::sender` has `{name}`.");
+        quote! {
+            #constant
+
+            #[doc = #guard_doc]
+            ///
+            /// # Errors
+            ///
+            /// If `msg::sender` has not been granted the role, then the error
+            /// `Error::UnauthorizedAccount` is returned.
+            pub fn #guard(
+                &self,
+            ) -> Result<(), openzeppelin_stylus::access::control::Error> {
+                self.#access.only_role(Self::#getter())
+            }
+        }
+    });
+
+    quote! {
+        impl #contract {
+            #( #items )*
+        }
+    }
+    .into()
+}
@@ -0,0 +1,53 @@
+//! Tests for the `abi_interface!` macro.
+// `sol_interface!` doesn't document the items it generates.
+#![allow(missing_docs)]
+extern crate alloc;
+
+use alloy_sol_types::SolError;
+use openzeppelin_stylus_proc::abi_interface;
+use stylus_sdk::{
+    alloy_primitives::{address, Address, I256, U256},
+    call,
+    prelude::TopLevelStorage,
+};
+
+abi_interface!(IPriceFeed, "tests/abi/PriceFeed.json");
+
+/// Only checks that the calls type-check, since they need a Stylus node.
+#[allow(dead_code)]
+fn calls(
+    feed: &IPriceFeed,
+    storage: &mut impl TopLevelStorage,
+) -> Result<(), call::Error> {
+    let _: u8 = feed.decimals(&mut *storage)?;
+    let _: String = feed.description(&mut *storage)?;
+    let _: I256 = feed.get_answer(&mut *storage, U256::ZERO)?;
+    feed.set_description(storage, String::from("ETH / USD"))?;
+    Ok(())
+}
+
+#[test]
+fn generates_call_bindings() {
+    let feed = IPriceFeed::new(Address::ZERO);
+    assert_eq!(feed.address, Address::ZERO);
+}
+
+#[test]
+fn decodes_custom_errors() {
+    let stale = iprice_feed::StalePrice { updatedAt: U256::from(42) };
+    let decoded = iprice_feed::Error::decode(&stale.encode());
+    assert!(matches!(
+        decoded,
+        Some(iprice_feed::Error::StalePrice(e)) if e.updatedAt == U256::from(42)
+    ));
+
+    let account = address!("a11ce00000000000000000000000000000000000");
+    let unauthorized = iprice_feed::Unauthorized { arg0: account };
+    let decoded = iprice_feed::Error::decode(&unauthorized.encode());
+    assert!(matches!(
+        decoded,
+        Some(iprice_feed::Error::Unauthorized(e)) if e.arg0 == account
+    ));
+
+    assert!(iprice_feed::Error::decode(&[0xde, 0xad, 0xbe, 0xef]).is_none());
+}
@@ -0,0 +1,34 @@
+//! Tests for the `define_roles!` macro.
+use openzeppelin_stylus_proc::define_roles;
+use stylus_sdk::alloy_primitives::{hex, B256};
+
+struct Contract;
+
+define_roles! {
+    Contract {
+        TRANSFER_ROLE,
+        MINTER_ROLE,
+    }
+}
+
+#[test]
+fn derives_role_ids_from_names() {
+    assert_eq!(
+        Contract::TRANSFER_ROLE,
+        hex!(
+            "8502233096d909befbda0999bb8ea2f3a6be3c138b9fbf003752a4c8bce86f6c"
+        )
+    );
+    assert_eq!(
+        Contract::MINTER_ROLE,
+        hex!(
+            "9f2df0fed2c77648de5860a4cc508cd0818c85b8b8a1ab4ceeef8d981c8956a6"
+        )
+    );
+}
+
+#[test]
+fn gets_typed_role_ids() {
+    assert_eq!(Contract::transfer_role(), B256::new(Contract::TRANSFER_ROLE));
+    assert_eq!(Contract::minter_role(), B256::new(Contract::MINTER_ROLE));
+}
@@ -0,0 +1,50 @@
+//! Tests for the `ErrorDisplay` derive.
+extern crate alloc;
+
+use alloy_sol_types::sol;
+use openzeppelin_stylus_proc::ErrorDisplay;
+use stylus_sdk::{
+    alloy_primitives::{address, U256},
+    prelude::SolidityError,
+};
+
+sol! {
+    #[derive(Debug)]
+    #[allow(missing_docs)]
+    error ERC20InsufficientBalance(address sender, uint256 balance, uint256 needed);
+    #[derive(Debug)]
+    #[allow(missing_docs)]
+    error EnforcedPause();
+}
+
+#[derive(SolidityError, Debug, ErrorDisplay)]
+enum Error {
+    InsufficientBalance(ERC20InsufficientBalance),
+    EnforcedPause(EnforcedPause),
+}
+
+#[test]
+fn displays_error_name_and_fields() {
+    let error = Error::InsufficientBalance(ERC20InsufficientBalance {
+        sender: address!("a11ce00000000000000000000000000000000000"),
+        balance: U256::from(1),
+        needed: U256::from(2),
+    });
+    assert_eq!(
+        error.to_string(),
+        "ERC20InsufficientBalance { sender: \
+         0xa11ce00000000000000000000000000000000000, balance: 1, needed: 2 }"
+    );
+
+    let error = Error::EnforcedPause(EnforcedPause {});
+    assert_eq!(error.to_string(), "EnforcedPause");
+}
+
+#[test]
+fn implements_error() {
+    fn fails() -> Result<(), Box<dyn core::error::Error>> {
+        Err(Error::EnforcedPause(EnforcedPause {}))?
+    }
+
+    assert_eq!(fails().unwrap_err().to_string(), "EnforcedPause");
+}
@@ -0,0 +1,51 @@
+//! Tests for the `FromComponentErrors` derive.
+// The derive checks for the `export-abi` feature of the crate using it.
+#![allow(unexpected_cfgs)]
+extern crate alloc;
+
+use openzeppelin_stylus_proc::FromComponentErrors;
+
+#[derive(Debug, PartialEq)]
+struct Erc20Error(u8);
+
+impl From<Erc20Error> for Vec<u8> {
+    fn from(value: Erc20Error) -> Self {
+        vec![0x20, value.0]
+    }
+}
+
+#[derive(Debug, PartialEq)]
+struct PausableError;
+
+impl From<PausableError> for Vec<u8> {
+    fn from(_: PausableError) -> Self {
+        vec![0xff]
+    }
+}
+
+#[derive(FromComponentErrors, Debug, PartialEq)]
+enum Error {
+    Erc20(Erc20Error),
+    Pausable(PausableError),
+}
+
+fn when_not_paused() -> Result<(), PausableError> {
+    Err(PausableError)
+}
+
+fn transfer() -> Result<(), Error> {
+    when_not_paused()?;
+    Ok(())
+}
+
+#[test]
+fn converts_component_errors() {
+    assert_eq!(Error::from(Erc20Error(1)), Error::Erc20(Erc20Error(1)));
+    assert_eq!(transfer(), Err(Error::Pausable(PausableError)));
+}
+
+#[test]
+fn encodes_wrapped_errors() {
+    assert_eq!(Vec::from(Error::Erc20(Erc20Error(1))), vec![0x20, 0x01]);
+    assert_eq!(Vec::from(Error::Pausable(PausableError)), vec![0xff]);
+}
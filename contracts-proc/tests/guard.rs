@@ -0,0 +1,136 @@
+//! Tests for the `#[guard]` attribute.
+// `#[external]` checks the `export-abi` feature of the crate using it.
+#![allow(unexpected_cfgs)]
+extern crate alloc;
+
+use openzeppelin_stylus_proc::guard;
+
+#[derive(Debug, PartialEq)]
+enum Error {
+    Paused,
+    Unauthorized,
+}
+
+struct Contract {
+    paused: bool,
+    owner: bool,
+    calls: u32,
+}
+
+impl Contract {
+    fn when_not_paused(&self) -> Result<(), Error> {
+        if self.paused {
+            return Err(Error::Paused);
+        }
+        Ok(())
+    }
+
+    fn only_owner(&self) -> Result<(), Error> {
+        if !self.owner {
+            return Err(Error::Unauthorized);
+        }
+        Ok(())
+    }
+
+    #[guard(self.when_not_paused())]
+    #[guard(self.only_owner())]
+    fn call(&mut self) -> Result<u32, Error> {
+        self.calls += 1;
+        Ok(self.calls)
+    }
+}
+
+#[test]
+fn runs_body_when_guards_pass() {
+    let mut contract = Contract { paused: false, owner: true, calls: 0 };
+    assert_eq!(contract.call(), Ok(1));
+}
+
+#[test]
+fn returns_early_when_a_guard_fails() {
+    let mut contract = Contract { paused: false, owner: false, calls: 0 };
+    assert_eq!(contract.call(), Err(Error::Unauthorized));
+    assert_eq!(contract.calls, 0);
+}
+
+#[test]
+fn checks_guards_in_declaration_order() {
+    let mut contract = Contract { paused: true, owner: false, calls: 0 };
+    assert_eq!(contract.call(), Err(Error::Paused));
+}
+
+/// Guards are also applied inside an `#[external]` block, as long as they are
+/// used as `#[guard(..)]`.
+pub mod external {
+    use openzeppelin_stylus_proc::guard;
+    use stylus_sdk::{
+        prelude::{external, sol_storage},
+        storage::TopLevelStorage,
+    };
+
+    sol_storage! {
+        /// Counts calls unless it's paused.
+        #[derive(motsu::DefaultStorageLayout)]
+        pub struct Counter {
+            /// Whether calls are rejected.
+            bool paused;
+            /// Number of calls made.
+            uint256 calls;
+        }
+    }
+
+    unsafe impl TopLevelStorage for Counter {}
+
+    impl Counter {
+        fn when_not_paused(&self) -> Result<(), Vec<u8>> {
+            if self.paused.get() {
+                return Err(b"paused".to_vec());
+            }
+            Ok(())
+        }
+    }
+
+    #[external]
+    impl Counter {
+        /// Counts a call.
+        ///
+        /// # Errors
+        ///
+        /// If the counter is paused.
+        #[guard(self.when_not_paused())]
+        pub fn increment(&mut self) -> Result<(), Vec<u8>> {
+            let calls = self.calls.get();
+            self.calls.set(calls + stylus_sdk::alloy_primitives::U256::from(1));
+            Ok(())
+        }
+
+        /// Counts a call, even if the counter is paused.
+        ///
+        /// Stylus drops attributes with a path inside `#[external]`, so the
+        /// guard never reaches the macro, and the method runs unguarded.
+        ///
+        /// # Errors
+        ///
+        /// Never.
+        #[openzeppelin_stylus_proc::guard(self.when_not_paused())]
+        pub fn increment_unguarded(&mut self) -> Result<(), Vec<u8>> {
+            let calls = self.calls.get();
+            self.calls.set(calls + stylus_sdk::alloy_primitives::U256::from(1));
+            Ok(())
+        }
+    }
+
+    #[motsu::test]
+    fn applies_guards(contract: Counter) {
+        contract.paused.set(true);
+        assert_eq!(contract.increment(), Err(b"paused".to_vec()));
+        assert!(contract.calls.get().is_zero());
+    }
+
+    #[motsu::test]
+    fn drops_path_qualified_guards(contract: Counter) {
+        contract.paused.set(true);
+        assert_eq!(contract.increment_unguarded(), Ok(()));
+        assert!(!contract.calls.get().is_zero());
+    }
+}
@@ -0,0 +1,77 @@
+//! Tests for the `#[namespaced_storage]` attribute.
+extern crate alloc;
+
+use motsu::{prelude::VM, slot};
+use openzeppelin_stylus_proc::namespaced_storage;
+use stylus_sdk::{
+    alloy_primitives::{address, hex, uint, U256},
+    prelude::sol_storage,
+    storage::StorageType,
+};
+
+sol_storage! {
+    /// An ERC-20 component.
+    #[namespaced_storage("openzeppelin.storage.ERC20")]
+    pub struct Erc20 {
+        /// Balances.
+        mapping(address => uint256) balances;
+        /// Allowances.
+        mapping(address => mapping(address => uint256)) allowances;
+        /// Total supply.
+        uint256 total_supply;
+    }
+
+    /// A contract using the namespaced component.
+    #[derive(motsu::DefaultStorageLayout)]
+    pub struct Token {
+        /// Namespaced component.
+        Erc20Upgradeable erc20;
+        /// Cap on the supply.
+        uint256 cap;
+    }
+}
+
+#[test]
+fn derives_slot_from_namespace() {
+    // Slot of `ERC20Storage` in OpenZeppelin's `ERC20Upgradeable`.
+    assert_eq!(
+        Erc20Upgradeable::STORAGE_SLOT,
+        hex!(
+            "52c63247e1f47db19d5ce0460030c497f067ca4cebf71ba98eeadabe20bace00"
+        )
+    );
+}
+
+#[test]
+fn takes_no_room_where_declared() {
+    assert_eq!(<Erc20Upgradeable as StorageType>::SLOT_BYTES, 0);
+    assert_eq!(<Erc20Upgradeable as StorageType>::REQUIRED_SLOTS, 0);
+    assert_eq!(<Erc20 as StorageType>::REQUIRED_SLOTS, 3);
+    // Only `cap` takes room in `Token`.
+    assert_eq!(<Token as StorageType>::REQUIRED_SLOTS, 1);
+
+    // Derefs to the namespaced storage.
+    let token = unsafe { Token::new(U256::ZERO, 0) };
+    let _: &Erc20 = &token.erc20;
+}
+
+#[motsu::test]
+fn stores_state_in_namespace(contract: Token) {
+    let alice = address!("A11CEacF9aa32246d767FCCD72e02d6bCbcC375d");
+    let supply = uint!(100_U256);
+    let cap = uint!(1_000_U256);
+    contract.erc20.balances.setter(alice).set(supply);
+    contract.erc20.total_supply.set(supply);
+    contract.cap.set(cap);
+
+    let namespace = U256::from_be_bytes(Erc20Upgradeable::STORAGE_SLOT);
+    VM::context().assert_slot(slot::mapping(namespace, alice), supply);
+    VM::context().assert_slot(namespace + uint!(2_U256), supply);
+    // `cap` is the first field of `Token` taking room.
+    VM::context().assert_slot(U256::ZERO, cap);
+
+    // The state is read back from the namespace.
+    let token = unsafe { Token::new(U256::ZERO, 0) };
+    assert_eq!(token.erc20.total_supply.get(), supply);
+    assert_eq!(token.erc20.balances.get(alice), supply);
+}
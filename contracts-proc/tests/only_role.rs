@@ -0,0 +1,177 @@
+//! Tests for the `#[only_role]` attribute.
+// `#[external]` checks the `export-abi` feature of the crate using it.
+#![allow(unexpected_cfgs)]
+extern crate alloc;
+
+use openzeppelin_stylus_proc::{guard, only_role};
+use stylus_sdk::alloy_primitives::B256;
+
+#[derive(Debug, PartialEq)]
+enum Error {
+    Paused,
+    Unauthorized(B256),
+}
+
+struct AccessControl {
+    granted: Vec<B256>,
+}
+
+impl AccessControl {
+    fn only_role(&self, role: B256) -> Result<(), Error> {
+        if !self.granted.contains(&role) {
+            return Err(Error::Unauthorized(role));
+        }
+        Ok(())
+    }
+}
+
+struct Contract {
+    paused: bool,
+    access: AccessControl,
+}
+
+impl Contract {
+    const BURNER_ROLE: [u8; 32] = [2; 32];
+    const MINTER_ROLE: [u8; 32] = [1; 32];
+
+    fn when_not_paused(&self) -> Result<(), Error> {
+        if self.paused {
+            return Err(Error::Paused);
+        }
+        Ok(())
+    }
+
+    #[only_role(access, Self::MINTER_ROLE)]
+    fn mint(&self) -> Result<(), Error> {
+        Ok(())
+    }
+
+    #[guard(self.when_not_paused())]
+    #[only_role(access, Self::MINTER_ROLE)]
+    #[only_role(access, Self::BURNER_ROLE)]
+    fn burn(&self) -> Result<(), Error> {
+        Ok(())
+    }
+}
+
+#[test]
+fn checks_role() {
+    let contract = Contract {
+        paused: false,
+        access: AccessControl { granted: vec![Contract::MINTER_ROLE.into()] },
+    };
+    assert_eq!(contract.mint(), Ok(()));
+
+    let contract =
+        Contract { paused: false, access: AccessControl { granted: vec![] } };
+    assert_eq!(
+        contract.mint(),
+        Err(Error::Unauthorized(Contract::MINTER_ROLE.into()))
+    );
+}
+
+#[test]
+fn checks_in_declaration_order_with_guards() {
+    let contract = Contract {
+        paused: true,
+        access: AccessControl { granted: vec![Contract::MINTER_ROLE.into()] },
+    };
+    assert_eq!(contract.burn(), Err(Error::Paused));
+
+    let contract = Contract {
+        paused: false,
+        access: AccessControl { granted: vec![Contract::MINTER_ROLE.into()] },
+    };
+    assert_eq!(
+        contract.burn(),
+        Err(Error::Unauthorized(Contract::BURNER_ROLE.into()))
+    );
+}
+
+/// Roles are also checked inside an `#[external]` block, as long as the
+/// attribute is used as `#[only_role(..)]`.
+pub mod external {
+    use openzeppelin_stylus_proc::only_role;
+    use stylus_sdk::{
+        alloy_primitives::B256,
+        prelude::{external, sol_storage},
+        storage::TopLevelStorage,
+    };
+
+    sol_storage! {
+        /// Grants a single role.
+        #[derive(motsu::DefaultStorageLayout)]
+        pub struct AccessControl {
+            /// Whether `msg::sender` has the role.
+            bool granted;
+        }
+    }
+
+    impl AccessControl {
+        fn only_role(&self, _role: B256) -> Result<(), Vec<u8>> {
+            if !self.granted.get() {
+                return Err(b"unauthorized".to_vec());
+            }
+            Ok(())
+        }
+    }
+
+    sol_storage! {
+        /// Mints if `msg::sender` has the minter role.
+        #[derive(motsu::DefaultStorageLayout)]
+        pub struct Token {
+            /// Access control of the token.
+            AccessControl access;
+            /// Number of mints made.
+            uint256 mints;
+        }
+    }
+
+    unsafe impl TopLevelStorage for Token {}
+
+    impl Token {
+        const MINTER_ROLE: [u8; 32] = [1; 32];
+    }
+
+    #[external]
+    impl Token {
+        /// Mints.
+        ///
+        /// # Errors
+        ///
+        /// If `msg::sender` doesn't have the minter role.
+        #[only_role(access, Self::MINTER_ROLE)]
+        pub fn mint(&mut self) -> Result<(), Vec<u8>> {
+            let mints = self.mints.get();
+            self.mints.set(mints + stylus_sdk::alloy_primitives::U256::from(1));
+            Ok(())
+        }
+
+        /// Mints, even without the minter role.
+        ///
+        /// Stylus drops attributes with a path inside `#[external]`, so the
+        /// check never reaches the macro, and anyone can call the method.
+        ///
+        /// # Errors
+        ///
+        /// Never.
+        #[openzeppelin_stylus_proc::only_role(access, Self::MINTER_ROLE)]
+        pub fn mint_unchecked(&mut self) -> Result<(), Vec<u8>> {
+            let mints = self.mints.get();
+            self.mints.set(mints + stylus_sdk::alloy_primitives::U256::from(1));
+            Ok(())
+        }
+    }
+
+    #[motsu::test]
+    fn checks_role(contract: Token) {
+        assert_eq!(contract.mint(), Err(b"unauthorized".to_vec()));
+        assert!(contract.mints.get().is_zero());
+    }
+
+    #[motsu::test]
+    fn drops_path_qualified_checks(contract: Token) {
+        assert_eq!(contract.mint_unchecked(), Ok(()));
+        assert!(!contract.mints.get().is_zero());
+    }
+}
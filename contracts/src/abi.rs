@@ -0,0 +1,303 @@
+//! JSON ABI export.
+//!
+//! `cargo stylus export-abi` prints the Solidity interface of a contract.
+//! Frontends and indexers usually consume the standard JSON ABI instead, so
+//! this module builds it from the same interface, behind the `export-abi`
+//! feature:
+//!
+//! ```rust,ignore
+//! use openzeppelin_stylus::{abi, token::erc20::Transfer};
+//!
+//! abi::print_json_abi::<Erc20Example>(&[abi::event::<Transfer>()]);
+//! ```
+//!
+//! The Stylus SDK doesn't export events as part of the interface, so the
+//! events the contract emits are passed along explicitly, generated from
+//! their `sol!` declarations with [`event`].
+use alloc::{format, string::ToString, vec, vec::Vec};
+use core::{fmt, marker::PhantomData};
+
+use alloy_json_abi::{AbiItem, Event, Function, JsonAbi, StateMutability};
+use alloy_sol_types::{SolEvent, SolType};
+use stylus_sdk::abi::export::GenerateAbi;
+
+/// Renders the Solidity interface of `T`.
+struct Interface<T>(PhantomData<T>);
+
+impl<T: GenerateAbi> fmt::Display for Interface<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        T::fmt_abi(f)
+    }
+}
+
+/// Returns the JSON ABI of the contract `T`: its functions and custom
+/// errors, along with `events`.
+///
+/// # Arguments
+///
+/// * `events` - Events the contract emits, see [`event`].
+///
+/// # Errors
+///
+/// If the interface of `T` is not a valid Solidity declaration, e.g.,
+/// because it uses a struct, which is not supported.
+pub fn json_abi<T: GenerateAbi>(
+    events: &[Event],
+) -> Result<JsonAbi, alloy_json_abi::parser::Error> {
+    let interface = Interface::<T>(PhantomData).to_string();
+    parse(&interface, events)
+}
+
+/// Prints the JSON ABI of the contract `T` to standard out.
+///
+/// See [`json_abi`].
+///
+/// # Panics
+///
+/// If the ABI of `T` can't be built, see [`json_abi`].
+pub fn print_json_abi<T: GenerateAbi>(events: &[Event]) {
+    let abi = json_abi::<T>(events).expect("should build the json abi");
+    let json =
+        serde_json::to_string_pretty(&abi).expect("should serialize the abi");
+    println!("{json}");
+}
+
+/// Returns the JSON ABI of the event `E`, declared with `sol!`.
+///
+/// Like the custom errors of the interface, its parameters are unnamed.
+/// Which of them are indexed is told apart from the types of its topics and
+/// data.
+///
+/// # Panics
+///
+/// If which parameters are indexed can't be told apart, e.g., for
+/// `event Swap(address indexed a, address b)`, whose parameters could be
+/// swapped.
+#[must_use]
+pub fn event<E: SolEvent>() -> Event {
+    let start = E::SIGNATURE.find('(').expect("should be an event signature");
+    let (name, params) = E::SIGNATURE.split_at(start);
+    let params = split_types(params);
+    let topics = <E::TopicList as SolType>::sol_type_name();
+    // The first topic of events that aren't anonymous is their selector.
+    let topics = &split_types(&topics)[usize::from(!E::ANONYMOUS)..];
+    let data = <E::DataTuple<'static> as SolType>::sol_type_name();
+    let data = split_types(&data);
+
+    // `ways[i][j]` is the number of ways to split `params[i..]` into
+    // `topics[j..]` and the data left, `data[i - j..]`.
+    let mut ways = vec![vec![0_usize; topics.len() + 1]; params.len() + 1];
+    for i in (0..=params.len()).rev() {
+        for j in (0..=topics.len().min(i)).rev() {
+            let k = i - j;
+            if i == params.len() {
+                ways[i][j] = usize::from(j == topics.len() && k == data.len());
+                continue;
+            }
+            if j < topics.len() && is_topic_of(params[i], topics[j]) {
+                ways[i][j] += ways[i + 1][j + 1];
+            }
+            if k < data.len() && params[i] == data[k] {
+                ways[i][j] += ways[i + 1][j];
+            }
+        }
+    }
+    assert_eq!(
+        ways[0][0], 1,
+        "can't tell which parameters of `{name}` are indexed"
+    );
+
+    let mut declaration = format!("event {name}(");
+    let mut j = 0;
+    for (i, param) in params.iter().enumerate() {
+        if i > 0 {
+            declaration.push_str(", ");
+        }
+        declaration.push_str(param);
+        if j < topics.len()
+            && is_topic_of(param, topics[j])
+            && ways[i + 1][j + 1] > 0
+        {
+            declaration.push_str(" indexed");
+            j += 1;
+        }
+    }
+    declaration.push(')');
+    if E::ANONYMOUS {
+        declaration.push_str(" anonymous");
+    }
+    Event::parse(&declaration).expect("should parse the event declaration")
+}
+
+/// Splits the canonical tuple type `tuple`, e.g., `(uint256,(bool,bytes))`,
+/// into the types of its elements.
+fn split_types(tuple: &str) -> Vec<&str> {
+    let inner = tuple
+        .strip_prefix('(')
+        .and_then(|inner| inner.strip_suffix(')'))
+        .expect("should be a tuple type");
+    if inner.is_empty() {
+        return Vec::new();
+    }
+
+    let mut types = Vec::new();
+    let (mut depth, mut start) = (0_usize, 0);
+    for (i, c) in inner.char_indices() {
+        match c {
+            '(' => depth += 1,
+            ')' => depth -= 1,
+            ',' if depth == 0 => {
+                types.push(&inner[start..i]);
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    types.push(&inner[start..]);
+    types
+}
+
+/// Returns true if a parameter of type `param` is indexed as a topic of
+/// type `topic`: dynamic types are indexed by their hash.
+fn is_topic_of(param: &str, topic: &str) -> bool {
+    param == topic || (topic == "bytes32" && is_dynamic(param))
+}
+
+/// Returns true if the canonical type `ty` is dynamic.
+fn is_dynamic(ty: &str) -> bool {
+    if ty == "string" || ty == "bytes" || ty.ends_with("[]") {
+        return true;
+    }
+    if let Some((element, _)) =
+        ty.strip_suffix(']').and_then(|ty| ty.rsplit_once('['))
+    {
+        return is_dynamic(element);
+    }
+    ty.starts_with('(') && split_types(ty).into_iter().any(is_dynamic)
+}
+
+/// Parses the functions and errors declared in a Solidity `interface`, as
+/// printed by the Stylus SDK, along with `events`.
+fn parse(
+    interface: &str,
+    events: &[Event],
+) -> Result<JsonAbi, alloy_json_abi::parser::Error> {
+    let declarations = interface
+        .lines()
+        .map(str::trim)
+        .filter(|line| {
+            line.starts_with("function ") || line.starts_with("error ")
+        })
+        .map(|declaration| declaration.trim_end_matches(';'));
+
+    let mut items = Vec::new();
+    for declaration in declarations {
+        let Some(function) = declaration.strip_prefix("function ") else {
+            items.push(AbiItem::parse(declaration)?);
+            continue;
+        };
+
+        // Human-readable ABIs don't support modifiers, so they are taken out
+        // and only the state mutability is kept, e.g., `name() external view
+        // returns (string memory)` is parsed as `name()(string)`.
+        let (head, outputs) =
+            function.split_once(" returns ").unwrap_or((function, ""));
+        let end = head.rfind(')').map_or(head.len(), |i| i + 1);
+        let (signature, modifiers) = head.split_at(end);
+        let signature = format!("{signature}{outputs}").replace(" memory", "");
+
+        let mut function = Function::parse(&signature)?;
+        function.state_mutability = modifiers
+            .split_whitespace()
+            .find_map(|modifier| match modifier {
+                "pure" => Some(StateMutability::Pure),
+                "view" => Some(StateMutability::View),
+                "payable" => Some(StateMutability::Payable),
+                _ => None,
+            })
+            .unwrap_or(StateMutability::NonPayable);
+        items.push(function.into());
+    }
+    items.extend(events.iter().cloned().map(AbiItem::from));
+
+    Ok(items.into_iter().collect())
+}
+
+#[cfg(all(test, feature = "std", feature = "erc20"))]
+mod tests {
+    use alloy_json_abi::StateMutability;
+    use alloy_sol_types::{sol, SolError, SolEvent};
+
+    use super::{event, parse};
+    use crate::{token::erc20, utils::pausable};
+
+    const INTERFACE: &str = "
+interface IErc20Example is IErc20, IPausable {
+    function name() external view returns (string memory);
+
+    function transfer(address to, uint256 value) external returns (bool);
+
+    error ERC20InsufficientBalance(address, uint256, uint256);
+}
+";
+
+    sol! {
+        event Renamed(string indexed name, uint256 version, (bool, bytes)[] data);
+        event Logged(uint256 value) anonymous;
+        event Swapped(address indexed from, address to);
+    }
+
+    #[test]
+    fn parses_functions_errors_and_events() {
+        let abi = parse(
+            INTERFACE,
+            &[event::<pausable::Paused>(), event::<pausable::Unpaused>()],
+        )
+        .unwrap();
+
+        let name = &abi.function("name").unwrap()[0];
+        assert!(name.inputs.is_empty());
+        assert_eq!(name.outputs[0].ty, "string");
+        assert_eq!(name.state_mutability, StateMutability::View);
+
+        let transfer = &abi.function("transfer").unwrap()[0];
+        assert_eq!(transfer.signature(), "transfer(address,uint256)");
+        assert_eq!(transfer.outputs[0].ty, "bool");
+        assert_eq!(transfer.state_mutability, StateMutability::NonPayable);
+
+        let error = &abi.error("ERC20InsufficientBalance").unwrap()[0];
+        assert_eq!(
+            error.selector().0,
+            erc20::ERC20InsufficientBalance::SELECTOR
+        );
+
+        let paused = &abi.event("Paused").unwrap()[0];
+        assert_eq!(paused.selector().0, pausable::Paused::SIGNATURE_HASH.0);
+        assert_eq!(abi.events().count(), 2);
+    }
+
+    #[test]
+    fn generates_events() {
+        let transfer = event::<erc20::Transfer>();
+        assert_eq!(transfer.selector().0, erc20::Transfer::SIGNATURE_HASH.0);
+        let indexed: Vec<_> =
+            transfer.inputs.iter().map(|param| param.indexed).collect();
+        assert_eq!(indexed, [true, true, false]);
+
+        let renamed = event::<Renamed>();
+        assert_eq!(renamed.selector().0, Renamed::SIGNATURE_HASH.0);
+        let indexed: Vec<_> =
+            renamed.inputs.iter().map(|param| param.indexed).collect();
+        assert_eq!(indexed, [true, false, false]);
+
+        let logged = event::<Logged>();
+        assert!(logged.anonymous);
+        assert!(!logged.inputs[0].indexed);
+    }
+
+    #[test]
+    #[should_panic = "can't tell which parameters of `Swapped` are indexed"]
+    fn panics_on_ambiguous_events() {
+        let _ = event::<Swapped>();
+    }
+}
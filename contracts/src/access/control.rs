@@ -45,12 +45,15 @@
 //! [enumerable ext]: TBD
 use alloy_primitives::{Address, B256};
 use alloy_sol_types::sol;
+use openzeppelin_stylus_proc::ErrorDisplay;
 use stylus_proc::SolidityError;
 use stylus_sdk::{
     evm, msg,
     stylus_proc::{external, sol_storage},
 };
 
+use crate::utils::structs::gap::StorageGap;
+
 sol! {
     /// Emitted when `new_admin_role` is set as `role`'s admin role, replacing
     /// `previous_admin_role`.
@@ -94,7 +97,7 @@ sol! {
 
 /// An error that occurred in the implementation of an [`AccessControl`]
 /// contract.
-#[derive(SolidityError, Debug)]
+#[derive(SolidityError, Debug, ErrorDisplay)]
 pub enum Error {
     /// The caller account is missing a role.
     UnauthorizedAccount(AccessControlUnauthorizedAccount),
@@ -116,6 +119,8 @@ sol_storage! {
     pub struct AccessControl {
         /// Role identifier -> Role information.
         mapping(bytes32 => RoleData) _roles;
+        /// Reserved storage slots, see [`StorageGap`].
+        StorageGap<49> __gap;
     }
 }
 
@@ -1,3 +1,4 @@
 //! Contracts implementing access control mechanisms.
 pub mod control;
 pub mod ownable;
+pub mod signed_grants;
@@ -10,12 +10,15 @@
 //! to the owner.
 use alloy_primitives::Address;
 use alloy_sol_types::sol;
+use openzeppelin_stylus_proc::ErrorDisplay;
 use stylus_proc::SolidityError;
 use stylus_sdk::{
     evm, msg,
     stylus_proc::{external, sol_storage},
 };
 
+use crate::utils::structs::gap::StorageGap;
+
 sol! {
     /// Emitted when ownership gets transferred between accounts.
     #[allow(missing_docs)]
@@ -38,7 +41,7 @@ sol! {
 }
 
 /// An error that occurred in the implementation of an [`Ownable`] contract.
-#[derive(SolidityError, Debug)]
+#[derive(SolidityError, Debug, ErrorDisplay)]
 pub enum Error {
     /// The caller account is not authorized to perform an operation.
     UnauthorizedAccount(OwnableUnauthorizedAccount),
@@ -52,6 +55,8 @@ sol_storage! {
     pub struct Ownable {
         /// The current owner of this contract.
         address _owner;
+        /// Reserved storage slots, see [`StorageGap`].
+        StorageGap<49> __gap;
     }
 }
 
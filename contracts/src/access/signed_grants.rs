@@ -0,0 +1,466 @@
+//! Extension of [`AccessControl`] letting role admins grant roles with
+//! signatures.
+//!
+//! A role admin signs an [EIP-712] `RoleGrant` off-chain, and anyone can
+//! submit it, so that onboarding flows approved off-chain don't require the
+//! admin to pay for gas. Signatures use sequential nonces per admin, and an
+//! admin revokes its pending grant by invalidating its current nonce with
+//! [`SignedRoleGrants::invalidate_nonce`].
+//!
+//! Grants are signed in the domain named [`SignedRoleGrants::DOMAIN_NAME`],
+//! with version [`SignedRoleGrants::DOMAIN_VERSION`], for the chain and
+//! address of the contract. The getters of the nonces and of the domain
+//! separator are prefixed with `role_grant_`, so that they don't clash with
+//! the `nonces` and `DOMAIN_SEPARATOR` functions of [ERC-2612] tokens, which
+//! are signed in another domain. The contract exposes the submission of
+//! grants itself, since it owns the [`AccessControl`] state:
+//!
+//! ```rust,ignore
+//! #[external]
+//! #[inherit(AccessControl, SignedRoleGrants)]
+//! impl MyContract {
+//!     pub fn grant_role_by_sig(
+//!         &mut self,
+//!         role: B256,
+//!         account: Address,
+//!         deadline: U256,
+//!         nonce: U256,
+//!         signature: Bytes,
+//!     ) -> Result<(), Vec<u8>> {
+//!         let grant = RoleGrant { role, account, deadline, nonce };
+//!         self.grants._grant_role_by_sig(&mut self.access, &grant, &signature)?;
+//!         Ok(())
+//!     }
+//! }
+//! ```
+//!
+//! [EIP-712]: https://eips.ethereum.org/EIPS/eip-712
+//! [ERC-2612]: https://eips.ethereum.org/EIPS/eip-2612
+use alloy_primitives::{fixed_bytes, Address, B256, U256};
+use alloy_sol_types::{
+    sol,
+    sol_data::{self, FixedBytes, Uint},
+    SolType,
+};
+use openzeppelin_stylus_proc::ErrorDisplay;
+use stylus_proc::SolidityError;
+use stylus_sdk::{
+    block,
+    crypto::keccak,
+    evm, msg,
+    stylus_proc::{external, sol_storage},
+};
+
+use crate::{
+    access::control::{AccessControl, AccessControlUnauthorizedAccount},
+    utils::{
+        cryptography::{
+            ecdsa::{
+                self, ECDSAInvalidSignature, ECDSAInvalidSignatureLength,
+                ECDSAInvalidSignatureS,
+            },
+            eip712,
+        },
+        structs::gap::StorageGap,
+    },
+};
+
+sol! {
+    /// Emitted when `signer` invalidates its nonce `nonce`, revoking the
+    /// grant signed with it.
+    #[allow(missing_docs)]
+    event RoleGrantNonceInvalidated(address indexed signer, uint256 nonce);
+}
+
+sol! {
+    /// The grant expired at `deadline`.
+    ///
+    /// * `deadline` - Timestamp after which the grant is invalid.
+    #[derive(Debug)]
+    #[allow(missing_docs)]
+    error AccessControlExpiredGrant(uint256 deadline);
+    /// The grant was signed with a nonce other than the current nonce of
+    /// `signer`.
+    ///
+    /// * `signer` - Signer of the grant.
+    /// * `current_nonce` - Current nonce of `signer`.
+    #[derive(Debug)]
+    #[allow(missing_docs)]
+    error AccessControlInvalidGrantNonce(address signer, uint256 current_nonce);
+}
+
+/// An error that occurred in the implementation of a [`SignedRoleGrants`]
+/// contract.
+#[derive(SolidityError, Debug, ErrorDisplay)]
+pub enum Error {
+    /// The grant expired.
+    ExpiredGrant(AccessControlExpiredGrant),
+    /// The nonce of the grant isn't the current nonce of its signer.
+    InvalidGrantNonce(AccessControlInvalidGrantNonce),
+    /// The signer doesn't have the admin role of the granted role.
+    UnauthorizedAccount(AccessControlUnauthorizedAccount),
+    /// The signature derives the `Address::ZERO`.
+    InvalidSignature(ECDSAInvalidSignature),
+    /// The signature has an invalid length.
+    InvalidSignatureLength(ECDSAInvalidSignatureLength),
+    /// The signature has an `s` value in the upper half order.
+    InvalidSignatureS(ECDSAInvalidSignatureS),
+}
+
+impl From<ecdsa::Error> for Error {
+    fn from(error: ecdsa::Error) -> Self {
+        match error {
+            ecdsa::Error::InvalidSignature(e) => Error::InvalidSignature(e),
+            ecdsa::Error::InvalidSignatureLength(e) => {
+                Error::InvalidSignatureLength(e)
+            }
+            ecdsa::Error::InvalidSignatureS(e) => Error::InvalidSignatureS(e),
+        }
+    }
+}
+
+/// A grant of `role` to `account`, signed by an admin of `role`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RoleGrant {
+    /// The role identifier.
+    pub role: B256,
+    /// The account which will be granted the role.
+    pub account: Address,
+    /// Timestamp after which the grant is invalid.
+    pub deadline: U256,
+    /// Nonce of the signer the grant was signed with.
+    pub nonce: U256,
+}
+
+impl RoleGrant {
+    /// Type hash of a grant, i.e., `keccak256("RoleGrant(bytes32 role,address
+    /// account,uint256 deadline,uint256 nonce)")`.
+    pub const TYPE_HASH: B256 = fixed_bytes!(
+        "927dea863991ba92f6340bab7930523b2d2b9234a223cd1b02671ac2cc58f02b"
+    );
+
+    /// Returns the EIP-712 `hashStruct` of the grant.
+    #[must_use]
+    pub fn struct_hash(&self) -> B256 {
+        let encoded = <(
+            FixedBytes<32>,
+            FixedBytes<32>,
+            sol_data::Address,
+            Uint<256>,
+            Uint<256>,
+        )>::encode_params(&(
+            Self::TYPE_HASH.0,
+            self.role.0,
+            self.account,
+            self.deadline,
+            self.nonce,
+        ));
+        keccak(encoded)
+    }
+}
+
+sol_storage! {
+    /// State of a `SignedRoleGrants` contract.
+    #[cfg_attr(all(test, feature = "std"), derive(motsu::DefaultStorageLayout))]
+    pub struct SignedRoleGrants {
+        /// Signer -> Nonce of its next grant.
+        mapping(address => uint256) _nonces;
+        /// Reserved storage slots, see [`StorageGap`].
+        StorageGap<49> __gap;
+    }
+}
+
+#[external]
+impl SignedRoleGrants {
+    /// Name of the EIP-712 domain grants are signed in.
+    pub const DOMAIN_NAME: &'static str = "AccessControl";
+    /// Version of the EIP-712 domain grants are signed in.
+    pub const DOMAIN_VERSION: &'static str = "1";
+
+    /// Returns the nonce `signer` must sign its next grant with.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the contract's state.
+    /// * `signer` - The signer of grants.
+    #[must_use]
+    pub fn role_grant_nonces(&self, signer: Address) -> U256 {
+        self._nonces.get(signer)
+    }
+
+    /// Returns the domain separator grants are signed in.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the contract's state.
+    #[must_use]
+    pub fn role_grant_domain_separator(&self) -> B256 {
+        eip712::domain_separator(Self::DOMAIN_NAME, Self::DOMAIN_VERSION)
+    }
+
+    /// Invalidates the current nonce of [`msg::sender`], revoking the grant
+    /// signed with it, if any.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the contract's state.
+    ///
+    /// # Events
+    ///
+    /// Emits a [`RoleGrantNonceInvalidated`] event.
+    pub fn invalidate_nonce(&mut self) {
+        let signer = msg::sender();
+        let nonce = self._use_nonce(signer);
+        evm::log(RoleGrantNonceInvalidated { signer, nonce });
+    }
+}
+
+impl SignedRoleGrants {
+    /// Grants `grant.role` to `grant.account` if the grant was signed by an
+    /// admin of the role, and returns whether the account didn't have the
+    /// role yet.
+    ///
+    /// Consumes the nonce the grant was signed with. The [`RoleGranted`]
+    /// event emitted by `access` names [`msg::sender`], i.e., the account
+    /// that submitted the grant, as its sender.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the contract's state.
+    /// * `access` - Write access to the roles of the contract.
+    /// * `grant` - The signed grant.
+    /// * `signature` - Signature of the grant, as `r ++ s ++ v`.
+    ///
+    /// # Errors
+    ///
+    /// If the deadline of the grant passed, then the error
+    /// [`Error::ExpiredGrant`] is returned.
+    /// If no signer can be recovered from `signature`, then one of the errors
+    /// [`Error::InvalidSignature`], [`Error::InvalidSignatureLength`] or
+    /// [`Error::InvalidSignatureS`] is returned.
+    /// If the grant wasn't signed with the current nonce of the signer, then
+    /// the error [`Error::InvalidGrantNonce`] is returned.
+    /// If the signer doesn't have the admin role of `grant.role`, then the
+    /// error [`Error::UnauthorizedAccount`] is returned.
+    ///
+    /// # Events
+    ///
+    /// May emit a [`RoleGranted`] event.
+    ///
+    /// [`RoleGranted`]: crate::access::control::RoleGranted
+    pub fn _grant_role_by_sig(
+        &mut self,
+        access: &mut AccessControl,
+        grant: &RoleGrant,
+        signature: &[u8],
+    ) -> Result<bool, Error> {
+        if U256::from(block::timestamp()) > grant.deadline {
+            return Err(Error::ExpiredGrant(AccessControlExpiredGrant {
+                deadline: grant.deadline,
+            }));
+        }
+
+        let digest = eip712::hash_typed_data(
+            self.role_grant_domain_separator(),
+            grant.struct_hash(),
+        );
+        let signer = ecdsa::recover(digest, signature)?;
+
+        let current_nonce = self.role_grant_nonces(signer);
+        if grant.nonce != current_nonce {
+            return Err(Error::InvalidGrantNonce(
+                AccessControlInvalidGrantNonce { signer, current_nonce },
+            ));
+        }
+
+        let admin_role = access.get_role_admin(grant.role);
+        if !access.has_role(admin_role, signer) {
+            return Err(Error::UnauthorizedAccount(
+                AccessControlUnauthorizedAccount {
+                    account: signer,
+                    needed_role: admin_role.0,
+                },
+            ));
+        }
+
+        self._use_nonce(signer);
+        Ok(access._grant_role(grant.role, grant.account))
+    }
+
+    /// Increments the nonce of `signer`, and returns its previous value.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the contract's state.
+    /// * `signer` - The signer of grants.
+    pub fn _use_nonce(&mut self, signer: Address) -> U256 {
+        let mut nonce = self._nonces.setter(signer);
+        let current = nonce.get();
+        // Nonces can't realistically overflow.
+        nonce.set(current + U256::from(1));
+        current
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use alloy_primitives::{address, keccak256, Address, B256, U256};
+    use k256::ecdsa::SigningKey;
+    use motsu::prelude::VM;
+
+    use super::{
+        Error, RoleGrant, RoleGrantNonceInvalidated, SignedRoleGrants,
+    };
+    use crate::{access::control::AccessControl, utils::cryptography::eip712};
+
+    const ROLE: B256 = B256::repeat_byte(1);
+    const ALICE: Address = address!("A11CEacF9aa32246d767FCCD72e02d6bCbcC375d");
+
+    fn admin_key() -> SigningKey {
+        SigningKey::from_slice(&[7; 32]).expect("should be a valid key")
+    }
+
+    fn address_of(key: &SigningKey) -> Address {
+        let point = key.verifying_key().to_encoded_point(false);
+        Address::from_slice(&keccak256(&point.as_bytes()[1..])[12..])
+    }
+
+    fn grant(nonce: u64) -> RoleGrant {
+        RoleGrant {
+            role: ROLE,
+            account: ALICE,
+            deadline: U256::from(1_000),
+            nonce: U256::from(nonce),
+        }
+    }
+
+    fn sign(
+        contract: &SignedRoleGrants,
+        key: &SigningKey,
+        grant: &RoleGrant,
+    ) -> Vec<u8> {
+        let digest = eip712::hash_typed_data(
+            contract.role_grant_domain_separator(),
+            grant.struct_hash(),
+        );
+        let (signature, recovery_id) = key
+            .sign_prehash_recoverable(digest.as_slice())
+            .expect("should sign the digest");
+        let mut signature = signature.to_bytes().to_vec();
+        signature.push(27 + recovery_id.to_byte());
+        signature
+    }
+
+    fn access_with_admin(admin: Address) -> AccessControl {
+        let mut access = AccessControl::default();
+        access._grant_role(AccessControl::DEFAULT_ADMIN_ROLE.into(), admin);
+        access
+    }
+
+    #[test]
+    fn type_hash_matches_definition() {
+        assert_eq!(
+            RoleGrant::TYPE_HASH,
+            keccak256(
+                "RoleGrant(bytes32 role,address account,uint256 deadline,\
+                 uint256 nonce)"
+            )
+        );
+    }
+
+    #[motsu::test]
+    fn grants_role_by_sig(contract: SignedRoleGrants) {
+        let key = admin_key();
+        let admin = address_of(&key);
+        let mut access = access_with_admin(admin);
+
+        let signature = sign(contract, &key, &grant(0));
+        let granted = contract
+            ._grant_role_by_sig(&mut access, &grant(0), &signature)
+            .expect("should grant the role");
+
+        assert!(granted);
+        assert!(access.has_role(ROLE, ALICE));
+        assert_eq!(contract.role_grant_nonces(admin), U256::from(1));
+
+        // The grant can't be replayed.
+        let err = contract
+            ._grant_role_by_sig(&mut access, &grant(0), &signature)
+            .unwrap_err();
+        assert!(matches!(err, Error::InvalidGrantNonce(_)));
+    }
+
+    #[motsu::test]
+    fn rejects_grants_of_non_admins(contract: SignedRoleGrants) {
+        let key = admin_key();
+        let mut access = AccessControl::default();
+
+        let signature = sign(contract, &key, &grant(0));
+        let err = contract
+            ._grant_role_by_sig(&mut access, &grant(0), &signature)
+            .unwrap_err();
+
+        assert!(matches!(
+            err,
+            Error::UnauthorizedAccount(error)
+                if error.account == address_of(&key)
+        ));
+        assert!(!access.has_role(ROLE, ALICE));
+    }
+
+    #[motsu::test]
+    fn rejects_expired_grants(contract: SignedRoleGrants) {
+        let key = admin_key();
+        let mut access = access_with_admin(address_of(&key));
+        VM::context().set_block_timestamp(1_001);
+
+        let signature = sign(contract, &key, &grant(0));
+        let err = contract
+            ._grant_role_by_sig(&mut access, &grant(0), &signature)
+            .unwrap_err();
+        assert!(matches!(err, Error::ExpiredGrant(_)));
+    }
+
+    #[motsu::test]
+    fn rejects_tampered_grants(contract: SignedRoleGrants) {
+        let key = admin_key();
+        let mut access = access_with_admin(address_of(&key));
+
+        let signature = sign(contract, &key, &grant(0));
+        let tampered = RoleGrant { role: B256::repeat_byte(2), ..grant(0) };
+        // The signer recovered from the signature of another grant is a
+        // random account.
+        let err = contract
+            ._grant_role_by_sig(&mut access, &tampered, &signature)
+            .unwrap_err();
+        assert!(matches!(err, Error::UnauthorizedAccount(_)));
+
+        let err = contract
+            ._grant_role_by_sig(&mut access, &grant(0), &signature[..64])
+            .unwrap_err();
+        assert!(matches!(err, Error::InvalidSignatureLength(_)));
+    }
+
+    #[motsu::test]
+    fn invalidating_nonce_revokes_grant(contract: SignedRoleGrants) {
+        let key = admin_key();
+        let admin = address_of(&key);
+        VM::context().set_msg_sender(admin);
+        let mut access = access_with_admin(admin);
+        let signature = sign(contract, &key, &grant(0));
+
+        contract.invalidate_nonce();
+
+        let events =
+            VM::context().emitted_events::<RoleGrantNonceInvalidated>();
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].signer, admin);
+        assert_eq!(events[0].nonce, U256::ZERO);
+
+        let err = contract
+            ._grant_role_by_sig(&mut access, &grant(0), &signature)
+            .unwrap_err();
+        assert!(matches!(err, Error::InvalidGrantNonce(_)));
+        assert!(!access.has_role(ROLE, ALICE));
+    }
+}
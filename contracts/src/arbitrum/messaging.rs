@@ -0,0 +1,210 @@
+//! Helpers for messages sent between a parent chain (L1) and an Arbitrum
+//! chain (L2) through the canonical bridge.
+//!
+//! Messages from L1 contracts, including retryable tickets, are executed on
+//! L2 with an aliased sender, so that an L1 contract can't impersonate the L2
+//! contract deployed at the same address. [`apply_l1_to_l2_alias`] and
+//! [`undo_l1_to_l2_alias`] convert between both addresses, and
+//! [`only_from_l1`] restricts a function to messages sent by a given L1
+//! contract, e.g., the counterpart of a token gateway:
+//!
+//! ```rust,ignore
+//! #[external]
+//! impl L2Gateway {
+//!     pub fn finalize_deposit(&mut self, to: Address, amount: U256) -> Result<(), Vec<u8>> {
+//!         only_from_l1(&*self, self.l1_counterpart.get())?;
+//!         ...
+//!     }
+//! }
+//! ```
+//!
+//! For more information see [address aliasing].
+//!
+//! [address aliasing]: https://docs.arbitrum.io/how-arbitrum-works/l1-to-l2-messaging#address-aliasing
+use alloy_primitives::{address, aliases::U160, Address};
+use alloy_sol_types::sol;
+use openzeppelin_stylus_proc::ErrorDisplay;
+use stylus_proc::SolidityError;
+use stylus_sdk::call::StaticCallContext;
+
+use super::precompiles::{IArbSys, ARB_SYS};
+
+/// Offset added to the address of an L1 contract to get the address it sends
+/// messages from on L2.
+pub const L1_TO_L2_ALIAS_OFFSET: Address =
+    address!("1111000000000000000000000000000000001111");
+
+sol! {
+    /// The function was not called from the parent chain.
+    #[derive(Debug)]
+    #[allow(missing_docs)]
+    error NotCrossChainCall();
+    /// The function was called from the parent chain, but by an account other
+    /// than the expected one.
+    ///
+    /// * `actual` - L1 account that sent the message.
+    /// * `expected` - L1 account allowed to call the function.
+    #[derive(Debug)]
+    #[allow(missing_docs)]
+    error InvalidCrossChainSender(address actual, address expected);
+}
+
+/// An error that occurred while checking the sender of a cross-chain message.
+#[derive(SolidityError, Debug, ErrorDisplay)]
+pub enum Error {
+    /// The function was not called from the parent chain.
+    NotCrossChainCall(NotCrossChainCall),
+    /// The function was called from the parent chain, but by an account
+    /// other than the expected one.
+    InvalidCrossChainSender(InvalidCrossChainSender),
+}
+
+/// Returns the address `l1` sends messages from on L2.
+#[must_use]
+pub fn apply_l1_to_l2_alias(l1: Address) -> Address {
+    from_u160(to_u160(l1).wrapping_add(to_u160(L1_TO_L2_ALIAS_OFFSET)))
+}
+
+/// Returns the L1 address that sends messages from `l2` on L2.
+///
+/// Inverse of [`apply_l1_to_l2_alias`].
+#[must_use]
+pub fn undo_l1_to_l2_alias(l2: Address) -> Address {
+    from_u160(to_u160(l2).wrapping_sub(to_u160(L1_TO_L2_ALIAS_OFFSET)))
+}
+
+/// Returns the L1 account that sent the message being executed, or `None` if
+/// the function was not called from L1.
+///
+/// This relies on the `ArbSys` precompile rather than on [`msg::sender`], so
+/// that it also works when the message is a retryable ticket redeemed by
+/// someone other than the L1 sender. Outside of an Arbitrum chain, where the
+/// precompile can't be called, no call is considered to come from L1.
+///
+/// [`msg::sender`]: stylus_sdk::msg::sender
+pub fn cross_chain_sender(
+    context: impl StaticCallContext + Copy,
+) -> Option<Address> {
+    let arb_sys = IArbSys::new(ARB_SYS);
+    if !arb_sys.was_my_callers_address_aliased(context).unwrap_or(false) {
+        return None;
+    }
+    arb_sys.my_callers_address_without_aliasing(context).ok()
+}
+
+/// Checks that the function is called from L1 by `counterpart`.
+///
+/// # Errors
+///
+/// If the function was not called from L1, then the error
+/// [`Error::NotCrossChainCall`] is returned.
+///
+/// If it was called from L1 by an account other than `counterpart`, then the
+/// error [`Error::InvalidCrossChainSender`] is returned.
+pub fn only_from_l1(
+    context: impl StaticCallContext + Copy,
+    counterpart: Address,
+) -> Result<(), Error> {
+    let Some(actual) = cross_chain_sender(context) else {
+        return Err(Error::NotCrossChainCall(NotCrossChainCall {}));
+    };
+    if actual != counterpart {
+        return Err(Error::InvalidCrossChainSender(InvalidCrossChainSender {
+            actual,
+            expected: counterpart,
+        }));
+    }
+    Ok(())
+}
+
+/// Converts `address` to an integer.
+fn to_u160(address: Address) -> U160 {
+    U160::from_be_bytes(address.0 .0)
+}
+
+/// Converts `value` to an address.
+fn from_u160(value: U160) -> Address {
+    Address::from(value.to_be_bytes::<20>())
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use alloy_primitives::{address, Address, U256};
+    use alloy_sol_types::{sol, SolCall};
+    use motsu::prelude::{Caller, VM};
+
+    use super::{
+        apply_l1_to_l2_alias, cross_chain_sender, only_from_l1,
+        undo_l1_to_l2_alias, Error, ARB_SYS,
+    };
+
+    const COUNTERPART: Address =
+        address!("F4EaCDAbEf3c8f1EdE91b6f2A6840bc2E4DD3526");
+
+    sol! {
+        function wasMyCallersAddressAliased() external view returns (bool);
+        function myCallersAddressWithoutAliasing() external view returns (address);
+    }
+
+    fn mock_l1_sender(sender: Address) {
+        VM::context().mock_call(
+            ARB_SYS,
+            &wasMyCallersAddressAliasedCall::SELECTOR,
+            U256::from(1).to_be_bytes::<32>(),
+        );
+        VM::context().mock_call(
+            ARB_SYS,
+            &myCallersAddressWithoutAliasingCall::SELECTOR,
+            sender.into_word().0,
+        );
+    }
+
+    #[motsu::test]
+    fn aliases_addresses() {
+        let l1 = address!("0000000000000000000000000000000000000001");
+        let l2 = apply_l1_to_l2_alias(l1);
+        assert_eq!(l2, address!("1111000000000000000000000000000000001112"));
+        assert_eq!(undo_l1_to_l2_alias(l2), l1);
+    }
+
+    #[motsu::test]
+    fn aliases_addresses_with_overflow() {
+        let l1 = address!("ffffffffffffffffffffffffffffffffffffffff");
+        let l2 = apply_l1_to_l2_alias(l1);
+        assert_eq!(l2, address!("1111000000000000000000000000000000001110"));
+        assert_eq!(undo_l1_to_l2_alias(l2), l1);
+    }
+
+    #[motsu::test]
+    fn reads_cross_chain_sender() {
+        assert_eq!(cross_chain_sender(&Caller), None);
+
+        mock_l1_sender(COUNTERPART);
+        assert_eq!(cross_chain_sender(&Caller), Some(COUNTERPART));
+    }
+
+    #[motsu::test]
+    fn allows_counterpart() {
+        mock_l1_sender(COUNTERPART);
+        assert!(only_from_l1(&Caller, COUNTERPART).is_ok());
+    }
+
+    #[motsu::test]
+    fn rejects_calls_not_from_l1() {
+        let result = only_from_l1(&Caller, COUNTERPART);
+        assert!(matches!(result, Err(Error::NotCrossChainCall(_))));
+    }
+
+    #[motsu::test]
+    fn rejects_other_l1_senders() {
+        let sender = address!("0BB78F7e7132d1651B4Fd884B7624394e92156F1");
+        mock_l1_sender(sender);
+
+        let result = only_from_l1(&Caller, COUNTERPART);
+        assert!(matches!(
+            result,
+            Err(Error::InvalidCrossChainSender(e))
+                if e.actual == sender && e.expected == COUNTERPART
+        ));
+    }
+}
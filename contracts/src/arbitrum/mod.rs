@@ -0,0 +1,8 @@
+//! Arbitrum-specific functionality.
+//!
+//! Stylus contracts run on Arbitrum chains, which expose chain information
+//! and cross-chain messaging through precompiled contracts at fixed
+//! addresses, see [`precompiles`]. Contracts receiving messages from the
+//! parent chain can authenticate their sender with [`messaging`].
+pub mod messaging;
+pub mod precompiles;
@@ -0,0 +1,270 @@
+//! Bindings to the Arbitrum precompiles.
+//!
+//! Precompiles are called like any other contract, so the functions below
+//! take a call context, e.g., `&*self` for reads or `self` for writes, from
+//! a type implementing [`TopLevelStorage`].
+//!
+//! Note that on Arbitrum, `block.number` returns the block number of the
+//! parent chain, while [`arb_block_number`] returns the block number of the
+//! chain the contract runs on.
+//!
+//! For more information see the [Arbitrum precompiles reference].
+//!
+//! [Arbitrum precompiles reference]: https://docs.arbitrum.io/build-decentralized-apps/precompiles/reference
+// `sol_interface!` doesn't document the items it generates.
+#![allow(missing_docs)]
+use alloc::vec::Vec;
+
+use alloy_primitives::{address, Address, B256, U256};
+use stylus_proc::sol_interface;
+use stylus_sdk::{
+    call::{Call, Error, StaticCallContext},
+    storage::TopLevelStorage,
+};
+
+/// Address of the `ArbSys` precompile.
+pub const ARB_SYS: Address =
+    address!("0000000000000000000000000000000000000064");
+/// Address of the `ArbGasInfo` precompile.
+pub const ARB_GAS_INFO: Address =
+    address!("000000000000000000000000000000000000006C");
+/// Address of the `ArbOwnerPublic` precompile.
+pub const ARB_OWNER_PUBLIC: Address =
+    address!("000000000000000000000000000000000000006b");
+
+sol_interface! {
+    /// System-level functionality, available at [`ARB_SYS`].
+    interface IArbSys {
+        function arbBlockNumber() external view returns (uint256);
+        function arbBlockHash(uint256 arb_block_num) external view returns (bytes32);
+        function arbChainID() external view returns (uint256);
+        function arbOSVersion() external view returns (uint256);
+        function isTopLevelCall() external view returns (bool);
+        function wasMyCallersAddressAliased() external view returns (bool);
+        function myCallersAddressWithoutAliasing() external view returns (address);
+        function withdrawEth(address destination) external payable returns (uint256);
+        function sendTxToL1(address destination, bytes calldata data) external payable returns (uint256);
+    }
+
+    /// Gas pricing information, available at [`ARB_GAS_INFO`].
+    interface IArbGasInfo {
+        function getPricesInWei() external view returns (uint256, uint256, uint256, uint256, uint256, uint256);
+        function getL1BaseFeeEstimate() external view returns (uint256);
+        function getMinimumGasPrice() external view returns (uint256);
+        function getCurrentTxL1GasFees() external view returns (uint256);
+    }
+
+    /// Information about the chain owners, available at
+    /// [`ARB_OWNER_PUBLIC`].
+    interface IArbOwnerPublic {
+        function isChainOwner(address addr) external view returns (bool);
+        function getAllChainOwners() external view returns (address[] memory);
+        function getNetworkFeeAccount() external view returns (address);
+        function getInfraFeeAccount() external view returns (address);
+    }
+}
+
+/// Gas prices of the chain, in wei, as returned by
+/// [`IArbGasInfo::get_prices_in_wei`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct GasPrices {
+    /// Cost of an L2 transaction, paid for its parent chain data.
+    pub per_l2_tx: U256,
+    /// Cost of a byte of calldata, paid for its parent chain data.
+    pub per_l1_calldata_byte: U256,
+    /// Cost of writing a storage slot.
+    pub per_storage_allocation: U256,
+    /// Cost of a unit of gas for computation, on top of `per_arb_gas_base`.
+    pub per_arb_gas_base: U256,
+    /// Cost of a unit of gas when the chain is congested.
+    pub per_arb_gas_congestion: U256,
+    /// Total cost of a unit of gas.
+    pub per_arb_gas_total: U256,
+}
+
+/// Returns the number of the current block of the chain the contract runs
+/// on, unlike `block::number()`.
+///
+/// # Errors
+///
+/// If the call to [`ARB_SYS`] fails.
+pub fn arb_block_number(
+    context: impl StaticCallContext,
+) -> Result<U256, Error> {
+    IArbSys::new(ARB_SYS).arb_block_number(context)
+}
+
+/// Returns the hash of the block `number` of the chain the contract runs on.
+///
+/// # Errors
+///
+/// If the call to [`ARB_SYS`] fails, e.g., because `number` is not one of
+/// the last 256 blocks.
+pub fn arb_block_hash(
+    context: impl StaticCallContext,
+    number: U256,
+) -> Result<B256, Error> {
+    IArbSys::new(ARB_SYS).arb_block_hash(context, number)
+}
+
+/// Returns the id of the chain the contract runs on.
+///
+/// # Errors
+///
+/// If the call to [`ARB_SYS`] fails.
+pub fn arb_chain_id(context: impl StaticCallContext) -> Result<U256, Error> {
+    IArbSys::new(ARB_SYS).arb_chain_id(context)
+}
+
+/// Sends `value` wei to `destination` on the parent chain, through the
+/// canonical bridge.
+///
+/// Returns the unique id of the L2-to-L1 message. The funds can be claimed on
+/// the parent chain once the message is confirmed there.
+///
+/// # Errors
+///
+/// If the call to [`ARB_SYS`] fails, e.g., because the contract doesn't hold
+/// `value` wei.
+pub fn withdraw_eth(
+    storage: &mut impl TopLevelStorage,
+    destination: Address,
+    value: U256,
+) -> Result<U256, Error> {
+    let call = Call::new_in(storage).value(value);
+    IArbSys::new(ARB_SYS).withdraw_eth(call, destination)
+}
+
+/// Sends a message with `data` to `destination` on the parent chain, along
+/// with `value` wei.
+///
+/// Returns the unique id of the L2-to-L1 message. The message can be executed
+/// on the parent chain once it is confirmed there.
+///
+/// # Errors
+///
+/// If the call to [`ARB_SYS`] fails.
+pub fn send_tx_to_l1(
+    storage: &mut impl TopLevelStorage,
+    destination: Address,
+    data: Vec<u8>,
+    value: U256,
+) -> Result<U256, Error> {
+    let call = Call::new_in(storage).value(value);
+    IArbSys::new(ARB_SYS).send_tx_to_l_1(call, destination, data)
+}
+
+/// Returns the gas prices of the chain, in wei.
+///
+/// # Errors
+///
+/// If the call to [`ARB_GAS_INFO`] fails.
+pub fn gas_prices(context: impl StaticCallContext) -> Result<GasPrices, Error> {
+    let (
+        per_l2_tx,
+        per_l1_calldata_byte,
+        per_storage_allocation,
+        per_arb_gas_base,
+        per_arb_gas_congestion,
+        per_arb_gas_total,
+    ) = IArbGasInfo::new(ARB_GAS_INFO).get_prices_in_wei(context)?;
+    Ok(GasPrices {
+        per_l2_tx,
+        per_l1_calldata_byte,
+        per_storage_allocation,
+        per_arb_gas_base,
+        per_arb_gas_congestion,
+        per_arb_gas_total,
+    })
+}
+
+/// Returns the estimated base fee of the parent chain, in wei, which is used
+/// to price the data posted to it.
+///
+/// # Errors
+///
+/// If the call to [`ARB_GAS_INFO`] fails.
+pub fn l1_base_fee_estimate(
+    context: impl StaticCallContext,
+) -> Result<U256, Error> {
+    IArbGasInfo::new(ARB_GAS_INFO).get_l_1_base_fee_estimate(context)
+}
+
+/// Returns whether `account` is one of the owners of the chain.
+///
+/// # Errors
+///
+/// If the call to [`ARB_OWNER_PUBLIC`] fails.
+pub fn is_chain_owner(
+    context: impl StaticCallContext,
+    account: Address,
+) -> Result<bool, Error> {
+    IArbOwnerPublic::new(ARB_OWNER_PUBLIC).is_chain_owner(context, account)
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use alloy_primitives::{address, uint, U256};
+    use alloy_sol_types::{sol, SolCall};
+    use motsu::prelude::{Caller, VM};
+
+    use super::{
+        arb_block_number, gas_prices, is_chain_owner, GasPrices, ARB_GAS_INFO,
+        ARB_OWNER_PUBLIC, ARB_SYS,
+    };
+
+    sol! {
+        function arbBlockNumber() external view returns (uint256);
+        function getPricesInWei() external view returns (uint256, uint256, uint256, uint256, uint256, uint256);
+        function isChainOwner(address addr) external view returns (bool);
+    }
+
+    #[motsu::test]
+    fn reads_arb_block_number() {
+        VM::context().mock_call(
+            ARB_SYS,
+            &arbBlockNumberCall::SELECTOR,
+            uint!(42_U256).to_be_bytes::<32>(),
+        );
+        assert_eq!(arb_block_number(&Caller).unwrap(), uint!(42_U256));
+    }
+
+    #[motsu::test]
+    fn reads_gas_prices() {
+        let prices: Vec<U256> = (1..=6).map(U256::from).collect();
+        VM::context().mock_call(
+            ARB_GAS_INFO,
+            &getPricesInWeiCall::SELECTOR,
+            prices.iter().flat_map(U256::to_be_bytes::<32>).collect::<Vec<_>>(),
+        );
+        assert_eq!(
+            gas_prices(&Caller).unwrap(),
+            GasPrices {
+                per_l2_tx: prices[0],
+                per_l1_calldata_byte: prices[1],
+                per_storage_allocation: prices[2],
+                per_arb_gas_base: prices[3],
+                per_arb_gas_congestion: prices[4],
+                per_arb_gas_total: prices[5],
+            }
+        );
+    }
+
+    #[motsu::test]
+    fn checks_chain_owner() {
+        let owner = address!("F4EaCDAbEf3c8f1EdE91b6f2A6840bc2E4DD3526");
+        VM::context().mock_call(
+            ARB_OWNER_PUBLIC,
+            &isChainOwnerCall { addr: owner }.encode(),
+            U256::from(1).to_be_bytes::<32>(),
+        );
+        VM::context().mock_call(
+            ARB_OWNER_PUBLIC,
+            &isChainOwnerCall { addr: ARB_SYS }.encode(),
+            U256::ZERO.to_be_bytes::<32>(),
+        );
+
+        assert!(is_chain_owner(&Caller, owner).unwrap());
+        assert!(!is_chain_owner(&Caller, ARB_SYS).unwrap());
+    }
+}
@@ -0,0 +1,463 @@
+//! Distribution of ERC-20 tokens to the accounts of a Merkle tree, e.g., for
+//! airdrops.
+//!
+//! [`MerkleDistributor`] holds the tokens to distribute, and commits to who
+//! can claim how many of them with the root of a Merkle tree. Each leaf is
+//! `keccak256(keccak256(abi.encode(index, account, amount)))`, as built by
+//! `StandardMerkleTree.of(claims, ["uint256", "address", "uint256"])` of the
+//! [OpenZeppelin Merkle tree library], with `index` numbering the claims from
+//! zero. Claims are then tracked in a [`BitMap`], so that sequential indices
+//! share storage slots.
+//!
+//! Anyone can submit a claim, and the tokens are sent to the account of the
+//! claim. Once the claim deadline passes, the contract using the distributor
+//! can sweep the unclaimed tokens:
+//!
+//! ```rust,ignore
+//! pub fn sweep(&mut self, to: Address) -> Result<(), Vec<u8>> {
+//!     self.ownable.only_owner()?;
+//!     self.distributor._sweep(to)?;
+//!     Ok(())
+//! }
+//! ```
+//!
+//! [OpenZeppelin Merkle tree library]: https://github.com/OpenZeppelin/merkle-tree
+// `sol_interface!` doesn't document the items it generates.
+#![allow(missing_docs)]
+use alloc::vec::Vec;
+
+use alloy_primitives::{Address, B256, U256, U64};
+use alloy_sol_types::{sol, sol_data, SolType};
+use crypto::merkle::Verifier;
+use openzeppelin_stylus_proc::ErrorDisplay;
+use stylus_proc::{external, sol_interface, sol_storage, SolidityError};
+use stylus_sdk::{
+    block, call::Call, contract, crypto::keccak, evm, storage::TopLevelStorage,
+};
+
+use crate::utils::structs::{bitmap::BitMap, gap::StorageGap};
+
+sol_interface! {
+    /// The ERC-20 functions [`MerkleDistributor`] calls on its token.
+    interface IERC20 {
+        function balanceOf(address account) external view returns (uint256);
+        function transfer(address to, uint256 value) external returns (bool);
+    }
+}
+
+sol! {
+    /// Emitted when the claim `index` sends `amount` tokens to `account`.
+    #[allow(missing_docs)]
+    event Claimed(uint256 index, address indexed account, uint256 amount);
+    /// Emitted when the `amount` unclaimed tokens are swept to `to`.
+    #[allow(missing_docs)]
+    event Swept(address indexed to, uint256 amount);
+}
+
+sol! {
+    /// The claim `index` was already claimed.
+    ///
+    /// * `index` - Index of the claim.
+    #[derive(Debug)]
+    #[allow(missing_docs)]
+    error MerkleDistributorAlreadyClaimed(uint256 index);
+    /// The claim `index` isn't part of the Merkle tree.
+    ///
+    /// * `index` - Index of the claim.
+    #[derive(Debug)]
+    #[allow(missing_docs)]
+    error MerkleDistributorInvalidProof(uint256 index);
+    /// Claims ended at `deadline`.
+    ///
+    /// * `deadline` - Timestamp after which claims are rejected.
+    #[derive(Debug)]
+    #[allow(missing_docs)]
+    error MerkleDistributorClaimWindowFinished(uint64 deadline);
+    /// Unclaimed tokens can only be swept after `deadline`, or never if it is
+    /// zero.
+    ///
+    /// * `deadline` - Timestamp after which claims are rejected.
+    #[derive(Debug)]
+    #[allow(missing_docs)]
+    error MerkleDistributorClaimWindowNotFinished(uint64 deadline);
+    /// The transfer of `amount` tokens to `to` failed.
+    ///
+    /// * `to` - Recipient of the tokens.
+    /// * `amount` - Number of tokens.
+    #[derive(Debug)]
+    #[allow(missing_docs)]
+    error MerkleDistributorFailedTransfer(address to, uint256 amount);
+}
+
+/// A [`MerkleDistributor`] error.
+#[derive(SolidityError, Debug, ErrorDisplay)]
+pub enum Error {
+    /// The claim was already claimed.
+    AlreadyClaimed(MerkleDistributorAlreadyClaimed),
+    /// The claim isn't part of the Merkle tree.
+    InvalidProof(MerkleDistributorInvalidProof),
+    /// The claim deadline passed.
+    ClaimWindowFinished(MerkleDistributorClaimWindowFinished),
+    /// The claim deadline didn't pass yet.
+    ClaimWindowNotFinished(MerkleDistributorClaimWindowNotFinished),
+    /// A transfer of tokens failed.
+    FailedTransfer(MerkleDistributorFailedTransfer),
+}
+
+sol_storage! {
+    /// State of a [`MerkleDistributor`] contract.
+    #[cfg_attr(all(test, feature = "std"), derive(motsu::DefaultStorageLayout))]
+    pub struct MerkleDistributor {
+        /// Token distributed.
+        address _token;
+        /// Root of the Merkle tree of claims.
+        bytes32 _merkle_root;
+        /// Timestamp after which claims are rejected, zero if there is none.
+        uint64 _deadline;
+        /// Index of a claim -> Whether it was claimed.
+        BitMap _claimed;
+        /// Reserved storage slots, see [`StorageGap`].
+        StorageGap<46> __gap;
+    }
+}
+
+/// NOTE: Implementation of [`TopLevelStorage`] to be able use `&mut self` when
+/// calling other contracts and not `&mut (impl TopLevelStorage +
+/// BorrowMut<Self>)`. Should be fixed in the future by the Stylus team.
+unsafe impl TopLevelStorage for MerkleDistributor {}
+
+#[external]
+impl MerkleDistributor {
+    /// Returns the token distributed.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the contract's state.
+    pub fn token(&self) -> Address {
+        self._token.get()
+    }
+
+    /// Returns the root of the Merkle tree of claims.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the contract's state.
+    pub fn merkle_root(&self) -> B256 {
+        self._merkle_root.get()
+    }
+
+    /// Returns the timestamp after which claims are rejected, or zero if
+    /// claims never end.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the contract's state.
+    pub fn deadline(&self) -> u64 {
+        self._deadline.get().to::<u64>()
+    }
+
+    /// Returns whether the claim `index` was claimed.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the contract's state.
+    /// * `index` - Index of the claim.
+    pub fn is_claimed(&self, index: U256) -> bool {
+        self._claimed.get(index)
+    }
+
+    /// Sends `amount` tokens to `account`, if the claim `index` granting them
+    /// is part of the Merkle tree and wasn't claimed yet.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the contract's state.
+    /// * `index` - Index of the claim.
+    /// * `account` - Account receiving the tokens.
+    /// * `amount` - Number of tokens claimed.
+    /// * `proof` - Proof that the claim is part of the Merkle tree.
+    ///
+    /// # Errors
+    ///
+    /// If the deadline passed, then the error [`Error::ClaimWindowFinished`]
+    /// is returned.
+    /// If the claim was already claimed, then the error
+    /// [`Error::AlreadyClaimed`] is returned.
+    /// If the claim isn't part of the Merkle tree, then the error
+    /// [`Error::InvalidProof`] is returned.
+    /// If the transfer of the tokens fails, then the error
+    /// [`Error::FailedTransfer`] is returned.
+    ///
+    /// # Events
+    ///
+    /// Emits a [`Claimed`] event.
+    pub fn claim(
+        &mut self,
+        index: U256,
+        account: Address,
+        amount: U256,
+        proof: Vec<B256>,
+    ) -> Result<(), Error> {
+        let deadline = self.deadline();
+        if deadline != 0 && block::timestamp() > deadline {
+            return Err(Error::ClaimWindowFinished(
+                MerkleDistributorClaimWindowFinished { deadline },
+            ));
+        }
+        if self.is_claimed(index) {
+            return Err(Error::AlreadyClaimed(
+                MerkleDistributorAlreadyClaimed { index },
+            ));
+        }
+
+        let proof: Vec<[u8; 32]> =
+            proof.into_iter().map(|node| node.0).collect();
+        let leaf = claim_leaf(index, account, amount);
+        if !Verifier::verify(&proof, self.merkle_root().0, leaf.0) {
+            return Err(Error::InvalidProof(MerkleDistributorInvalidProof {
+                index,
+            }));
+        }
+
+        self._claimed.set(index);
+        self.transfer(account, amount)?;
+        evm::log(Claimed { index, account, amount });
+        Ok(())
+    }
+}
+
+impl MerkleDistributor {
+    /// Sets the token distributed.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the contract's state.
+    /// * `token` - The distributed token.
+    pub fn _set_token(&mut self, token: Address) {
+        self._token.set(token);
+    }
+
+    /// Sets the root of the Merkle tree of claims.
+    ///
+    /// Claims are tracked by index, so that changing the root of an ongoing
+    /// distribution keeps the claims of the previous tree claimed.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the contract's state.
+    /// * `root` - Root of the Merkle tree.
+    pub fn _set_merkle_root(&mut self, root: B256) {
+        self._merkle_root.set(root);
+    }
+
+    /// Sets the timestamp after which claims are rejected.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the contract's state.
+    /// * `deadline` - The deadline, or zero for claims to never end.
+    pub fn _set_deadline(&mut self, deadline: u64) {
+        self._deadline.set(U64::from(deadline));
+    }
+
+    /// Sends the tokens left in the contract to `to` once claims ended, and
+    /// returns their number.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the contract's state.
+    /// * `to` - Account receiving the unclaimed tokens.
+    ///
+    /// # Errors
+    ///
+    /// If there is no deadline, or it didn't pass yet, then the error
+    /// [`Error::ClaimWindowNotFinished`] is returned.
+    /// If the balance of the contract can't be read, or the transfer of the
+    /// tokens fails, then the error [`Error::FailedTransfer`] is returned.
+    ///
+    /// # Events
+    ///
+    /// Emits a [`Swept`] event.
+    pub fn _sweep(&mut self, to: Address) -> Result<U256, Error> {
+        let deadline = self.deadline();
+        if deadline == 0 || block::timestamp() <= deadline {
+            return Err(Error::ClaimWindowNotFinished(
+                MerkleDistributorClaimWindowNotFinished { deadline },
+            ));
+        }
+
+        let token = IERC20::new(self.token());
+        let amount = token
+            .balance_of(Call::new_in(self), contract::address())
+            .map_err(|_| {
+                Error::FailedTransfer(MerkleDistributorFailedTransfer {
+                    to,
+                    amount: U256::ZERO,
+                })
+            })?;
+        self.transfer(to, amount)?;
+        evm::log(Swept { to, amount });
+        Ok(amount)
+    }
+
+    /// Transfers `amount` distributed tokens to `to`.
+    fn transfer(&mut self, to: Address, amount: U256) -> Result<(), Error> {
+        let transferred = IERC20::new(self.token())
+            .transfer(Call::new_in(self), to, amount)
+            .unwrap_or(false);
+        if !transferred {
+            return Err(Error::FailedTransfer(
+                MerkleDistributorFailedTransfer { to, amount },
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// Returns the leaf of a claim in the Merkle tree of a [`MerkleDistributor`],
+/// i.e., `keccak256(keccak256(abi.encode(index, account, amount)))`.
+#[must_use]
+pub fn claim_leaf(index: U256, account: Address, amount: U256) -> B256 {
+    let encoded = <(
+        sol_data::Uint<256>,
+        sol_data::Address,
+        sol_data::Uint<256>,
+    )>::encode_params(&(index, account, amount));
+    keccak(keccak(encoded))
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use alloy_primitives::{address, uint, Address, B256, U256};
+    use alloy_sol_types::{sol, SolCall};
+    use crypto::merkle::Tree;
+    use motsu::prelude::VM;
+
+    use super::{claim_leaf, Claimed, Error, MerkleDistributor, Swept};
+
+    const TOKEN: Address = address!("F4EaCDAbEf3c8f1EdE91b6f2A6840bc2E4DD3526");
+    const ALICE: Address = address!("A11CEacF9aa32246d767FCCD72e02d6bCbcC375d");
+    const BOB: Address = address!("B0B0cB49ec2e96DF5F5fFB081acaE66A2cBBc2e2");
+    const DEADLINE: u64 = 1_000;
+
+    sol! {
+        function balanceOf(address account) external view returns (uint256);
+        function transfer(address to, uint256 value) external returns (bool);
+    }
+
+    /// Distributes 100 tokens to ALICE and 200 to BOB, and returns the proofs
+    /// of their claims.
+    fn set_up(contract: &mut MerkleDistributor) -> [Vec<B256>; 2] {
+        let leaves = [
+            claim_leaf(U256::ZERO, ALICE, uint!(100_U256)).0,
+            claim_leaf(uint!(1_U256), BOB, uint!(200_U256)).0,
+        ];
+        let tree = Tree::new(&leaves);
+        contract._set_token(TOKEN);
+        contract._set_merkle_root(tree.root().into());
+        contract._set_deadline(DEADLINE);
+        VM::context().mock_call(
+            TOKEN,
+            &transferCall::SELECTOR,
+            U256::from(1).to_be_bytes_vec(),
+        );
+
+        leaves.map(|leaf| {
+            tree.proof(leaf)
+                .expect("should prove the claim")
+                .into_iter()
+                .map(B256::from)
+                .collect()
+        })
+    }
+
+    #[motsu::test]
+    fn claims_tokens(contract: MerkleDistributor) {
+        let [_, proof] = set_up(contract);
+
+        contract
+            .claim(uint!(1_U256), BOB, uint!(200_U256), proof)
+            .expect("should claim BOB's tokens");
+
+        assert!(contract.is_claimed(uint!(1_U256)));
+        assert!(!contract.is_claimed(U256::ZERO));
+        let events = VM::context().emitted_events::<Claimed>();
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].account, BOB);
+        assert_eq!(events[0].amount, uint!(200_U256));
+    }
+
+    #[motsu::test]
+    fn rejects_claiming_twice(contract: MerkleDistributor) {
+        let [proof, _] = set_up(contract);
+        contract
+            .claim(U256::ZERO, ALICE, uint!(100_U256), proof.clone())
+            .expect("should claim ALICE's tokens");
+
+        let err = contract
+            .claim(U256::ZERO, ALICE, uint!(100_U256), proof)
+            .unwrap_err();
+        assert!(matches!(err, Error::AlreadyClaimed(_)));
+    }
+
+    #[motsu::test]
+    fn rejects_invalid_claims(contract: MerkleDistributor) {
+        let [proof, _] = set_up(contract);
+
+        let err = contract
+            .claim(U256::ZERO, ALICE, uint!(101_U256), proof.clone())
+            .unwrap_err();
+        assert!(matches!(err, Error::InvalidProof(_)));
+        let err = contract
+            .claim(U256::ZERO, BOB, uint!(100_U256), proof)
+            .unwrap_err();
+        assert!(matches!(err, Error::InvalidProof(_)));
+    }
+
+    #[motsu::test]
+    fn rejects_failed_transfers(contract: MerkleDistributor) {
+        let [proof, _] = set_up(contract);
+        VM::context().mock_call(
+            TOKEN,
+            &transferCall::SELECTOR,
+            U256::ZERO.to_be_bytes_vec(),
+        );
+
+        let err = contract
+            .claim(U256::ZERO, ALICE, uint!(100_U256), proof)
+            .unwrap_err();
+        assert!(matches!(err, Error::FailedTransfer(_)));
+    }
+
+    #[motsu::test]
+    fn rejects_sweeping_before_deadline(contract: MerkleDistributor) {
+        set_up(contract);
+
+        let err = contract._sweep(ALICE).unwrap_err();
+        assert!(matches!(err, Error::ClaimWindowNotFinished(_)));
+    }
+
+    #[motsu::test]
+    fn sweeps_unclaimed_tokens_after_deadline(contract: MerkleDistributor) {
+        let [proof, _] = set_up(contract);
+        VM::context().mock_call(
+            TOKEN,
+            &balanceOfCall::SELECTOR,
+            uint!(200_U256).to_be_bytes_vec(),
+        );
+        // `block::timestamp()` is cached once read, so the deadline has to be
+        // passed before anything reads it.
+        VM::context().set_block_timestamp(DEADLINE + 1);
+
+        let err = contract
+            .claim(U256::ZERO, ALICE, uint!(100_U256), proof)
+            .unwrap_err();
+        assert!(matches!(err, Error::ClaimWindowFinished(_)));
+
+        let swept = contract._sweep(ALICE).expect("should sweep the tokens");
+        assert_eq!(swept, uint!(200_U256));
+        let events = VM::context().emitted_events::<Swept>();
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].to, ALICE);
+    }
+}
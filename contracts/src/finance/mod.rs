@@ -0,0 +1,3 @@
+//! Primitives for financial systems, e.g., selling or distributing tokens.
+pub mod merkle_distributor;
+pub mod token_sale;
@@ -0,0 +1,777 @@
+//! Sale of tokens at fixed or tiered prices, in ETH or ERC-20 tokens.
+//!
+//! [`TokenSale`] keeps the books of a sale: it checks that it is open and
+//! that the buyer is allowed, collects the payment, and returns the number of
+//! tokens bought. Delivering them is left to the contract using it, which
+//! either mints them or transfers them from its balance:
+//!
+//! ```rust,ignore
+//! #[payable]
+//! pub fn buy(
+//!     &mut self,
+//!     payment: U256,
+//!     proof: Vec<B256>,
+//! ) -> Result<(), Vec<u8>> {
+//!     let amount = self.sale._buy(payment, &proof)?;
+//!     self.erc20._mint(msg::sender(), amount)?;
+//!     Ok(())
+//! }
+//! ```
+//!
+//! Prices are set as a list of tiers, see [`TokenSale::_set_tiers`]: each
+//! tier sells tokens at its own rate until the total number of tokens sold
+//! reaches its cap, and a purchase crossing a tier boundary is priced at the
+//! rates of both tiers. A sale at a fixed price has a single tier. The cap of
+//! the last tier is the cap of the whole sale.
+//!
+//! Sales can also be limited to an allowlist, committed to as the root of a
+//! Merkle tree whose leaves are `keccak256(keccak256(abi.encode(account)))`,
+//! as built by `StandardMerkleTree.of(accounts, ["address"])` of the
+//! [OpenZeppelin Merkle tree library].
+//!
+//! [OpenZeppelin Merkle tree library]: https://github.com/OpenZeppelin/merkle-tree
+// `sol_interface!` doesn't document the items it generates.
+#![allow(missing_docs)]
+use alloc::vec::Vec;
+
+use alloy_primitives::{Address, B256, U256, U64};
+use alloy_sol_types::{sol, sol_data, SolType};
+use crypto::merkle::Verifier;
+use openzeppelin_stylus_proc::ErrorDisplay;
+use stylus_proc::{external, sol_interface, sol_storage, SolidityError};
+use stylus_sdk::{
+    block,
+    call::{transfer_eth, Call},
+    contract,
+    crypto::keccak,
+    evm, msg,
+    storage::TopLevelStorage,
+};
+
+use crate::utils::structs::gap::StorageGap;
+
+sol_interface! {
+    /// The ERC-20 functions [`TokenSale`] calls on its payment token.
+    interface IERC20 {
+        function transfer(address to, uint256 value) external returns (bool);
+        function transferFrom(address from, address to, uint256 value) external returns (bool);
+    }
+}
+
+sol! {
+    /// Emitted when `buyer` buys `amount` tokens for `payment`.
+    #[allow(missing_docs)]
+    event TokensPurchased(address indexed buyer, uint256 payment, uint256 amount);
+    /// Emitted when `amount` of the proceeds are withdrawn to `to`.
+    #[allow(missing_docs)]
+    event ProceedsWithdrawn(address indexed to, uint256 amount);
+}
+
+sol! {
+    /// The sale is only open between `opening_time` and `closing_time`.
+    ///
+    /// * `opening_time` - Timestamp the sale opens at.
+    /// * `closing_time` - Timestamp the sale closes at.
+    #[derive(Debug)]
+    #[allow(missing_docs)]
+    error TokenSaleNotOpen(uint64 opening_time, uint64 closing_time);
+    /// The sale can't close before it opens.
+    ///
+    /// * `opening_time` - Timestamp the sale opens at.
+    /// * `closing_time` - Timestamp the sale closes at.
+    #[derive(Debug)]
+    #[allow(missing_docs)]
+    error TokenSaleInvalidWindow(uint64 opening_time, uint64 closing_time);
+    /// The tiers are empty, of different lengths, have caps that don't
+    /// increase or a zero rate.
+    #[derive(Debug)]
+    #[allow(missing_docs)]
+    error TokenSaleInvalidTiers();
+    /// The `account` isn't part of the allowlist.
+    ///
+    /// * `account` - Account trying to buy tokens.
+    #[derive(Debug)]
+    #[allow(missing_docs)]
+    error TokenSaleNotAllowed(address account);
+    /// The purchase would exceed the `cap` of the sale.
+    ///
+    /// * `cap` - Maximum number of tokens sold.
+    #[derive(Debug)]
+    #[allow(missing_docs)]
+    error TokenSaleExceededCap(uint256 cap);
+    /// The purchase would exceed the `cap` of `account`.
+    ///
+    /// * `account` - Account trying to buy tokens.
+    /// * `cap` - Maximum number of tokens bought by an account.
+    #[derive(Debug)]
+    #[allow(missing_docs)]
+    error TokenSaleExceededAccountCap(address account, uint256 cap);
+    /// The payment doesn't buy any token.
+    #[derive(Debug)]
+    #[allow(missing_docs)]
+    error TokenSaleZeroAmount();
+    /// The ETH sent, `received`, isn't the `expected` payment.
+    ///
+    /// * `expected` - Payment of the purchase.
+    /// * `received` - ETH sent with the call.
+    #[derive(Debug)]
+    #[allow(missing_docs)]
+    error TokenSaleInvalidPayment(uint256 expected, uint256 received);
+    /// The payment couldn't be transferred.
+    ///
+    /// * `from` - Account paying.
+    /// * `to` - Account paid.
+    /// * `amount` - Amount of the payment.
+    #[derive(Debug)]
+    #[allow(missing_docs)]
+    error TokenSaleFailedTransfer(address from, address to, uint256 amount);
+}
+
+/// A [`TokenSale`] error.
+#[derive(SolidityError, Debug, ErrorDisplay)]
+pub enum Error {
+    /// The sale isn't open.
+    NotOpen(TokenSaleNotOpen),
+    /// The sale window is invalid.
+    InvalidWindow(TokenSaleInvalidWindow),
+    /// The tiers are invalid.
+    InvalidTiers(TokenSaleInvalidTiers),
+    /// The buyer isn't part of the allowlist.
+    NotAllowed(TokenSaleNotAllowed),
+    /// The purchase would exceed the cap of the sale.
+    ExceededCap(TokenSaleExceededCap),
+    /// The purchase would exceed the cap of the buyer.
+    ExceededAccountCap(TokenSaleExceededAccountCap),
+    /// The payment doesn't buy any token.
+    ZeroAmount(TokenSaleZeroAmount),
+    /// The ETH sent doesn't match the payment.
+    InvalidPayment(TokenSaleInvalidPayment),
+    /// A transfer of the payment token or ETH failed.
+    FailedTransfer(TokenSaleFailedTransfer),
+}
+
+sol_storage! {
+    /// A price tier of a [`TokenSale`].
+    pub struct Tier {
+        /// Total number of tokens sold up to which the tier applies.
+        uint256 cap;
+        /// Number of tokens bought per unit of the payment token.
+        uint256 rate;
+    }
+
+    /// State of a [`TokenSale`] contract.
+    #[cfg_attr(all(test, feature = "std"), derive(motsu::DefaultStorageLayout))]
+    pub struct TokenSale {
+        /// Token paid with, or `Address::ZERO` for ETH.
+        address _payment_token;
+        /// Price tiers, by increasing cap.
+        Tier[] _tiers;
+        /// Maximum number of tokens an account can buy, zero if unlimited.
+        uint256 _account_cap;
+        /// Timestamp the sale opens at.
+        uint64 _opening_time;
+        /// Timestamp the sale closes at.
+        uint64 _closing_time;
+        /// Root of the Merkle tree of allowed buyers, zero if anyone can buy.
+        bytes32 _allowlist_root;
+        /// Total number of tokens sold.
+        uint256 _sold;
+        /// Buyer -> Number of tokens bought.
+        mapping(address => uint256) _purchased;
+        /// Proceeds not withdrawn yet.
+        uint256 _proceeds;
+        /// Reserved storage slots, see [`StorageGap`].
+        StorageGap<42> __gap;
+    }
+}
+
+/// NOTE: Implementation of [`TopLevelStorage`] to be able use `&mut self` when
+/// calling other contracts and not `&mut (impl TopLevelStorage +
+/// BorrowMut<Self>)`. Should be fixed in the future by the Stylus team.
+unsafe impl TopLevelStorage for TokenSale {}
+
+#[external]
+impl TokenSale {
+    /// Returns the token paid with, or `Address::ZERO` if the sale is paid in
+    /// ETH.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the contract's state.
+    pub fn payment_token(&self) -> Address {
+        self._payment_token.get()
+    }
+
+    /// Returns the timestamp the sale opens at.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the contract's state.
+    pub fn opening_time(&self) -> u64 {
+        self._opening_time.get().to::<u64>()
+    }
+
+    /// Returns the timestamp the sale closes at.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the contract's state.
+    pub fn closing_time(&self) -> u64 {
+        self._closing_time.get().to::<u64>()
+    }
+
+    /// Returns whether the sale is open, i.e., whether the current block is
+    /// between the opening and closing times, both included.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the contract's state.
+    pub fn is_open(&self) -> bool {
+        let now = block::timestamp();
+        self.opening_time() <= now && now <= self.closing_time()
+    }
+
+    /// Returns the maximum number of tokens sold, i.e., the cap of the last
+    /// tier.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the contract's state.
+    pub fn cap(&self) -> U256 {
+        let tiers = self._tiers.len();
+        tiers
+            .checked_sub(1)
+            .and_then(|last| self._tiers.get(last))
+            .map_or(U256::ZERO, |tier| tier.cap.get())
+    }
+
+    /// Returns the maximum number of tokens an account can buy, or zero if it
+    /// is unlimited.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the contract's state.
+    pub fn account_cap(&self) -> U256 {
+        self._account_cap.get()
+    }
+
+    /// Returns the root of the Merkle tree of allowed buyers, or zero if
+    /// anyone can buy.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the contract's state.
+    pub fn allowlist_root(&self) -> B256 {
+        self._allowlist_root.get()
+    }
+
+    /// Returns the total number of tokens sold.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the contract's state.
+    pub fn tokens_sold(&self) -> U256 {
+        self._sold.get()
+    }
+
+    /// Returns the number of tokens bought by `account`.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the contract's state.
+    /// * `account` - Account to check.
+    pub fn purchased(&self, account: Address) -> U256 {
+        self._purchased.get(account)
+    }
+
+    /// Returns the proceeds of the sale not withdrawn yet.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the contract's state.
+    pub fn proceeds(&self) -> U256 {
+        self._proceeds.get()
+    }
+
+    /// Returns the number of tokens `payment` buys at the current prices.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the contract's state.
+    /// * `payment` - Amount of the payment token, or of ETH.
+    ///
+    /// # Errors
+    ///
+    /// If the purchase would exceed the cap of the sale, then the error
+    /// [`Error::ExceededCap`] is returned.
+    pub fn quote(&self, payment: U256) -> Result<U256, Error> {
+        let mut sold = self._sold.get();
+        let mut remaining = payment;
+        for i in 0..self._tiers.len() {
+            if remaining.is_zero() {
+                break;
+            }
+            let Some(tier) = self._tiers.get(i) else {
+                break;
+            };
+            let (cap, rate) = (tier.cap.get(), tier.rate.get());
+            if sold >= cap {
+                continue;
+            }
+
+            let available = cap - sold;
+            match remaining.checked_mul(rate) {
+                Some(amount) if amount <= available => {
+                    sold += amount;
+                    remaining = U256::ZERO;
+                }
+                // Buying the rest of the tier costs at most `remaining`.
+                _ => {
+                    sold = cap;
+                    remaining -= available.div_ceil(rate);
+                }
+            }
+        }
+
+        if !remaining.is_zero() {
+            return Err(Error::ExceededCap(TokenSaleExceededCap {
+                cap: self.cap(),
+            }));
+        }
+        Ok(sold - self._sold.get())
+    }
+}
+
+impl TokenSale {
+    /// Sets the token the sale is paid with, or `Address::ZERO` for ETH.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the contract's state.
+    /// * `token` - The payment token.
+    pub fn _set_payment_token(&mut self, token: Address) {
+        self._payment_token.set(token);
+    }
+
+    /// Sets the price tiers of the sale, replacing the previous ones.
+    ///
+    /// The tier at index `i` sells `rates[i]` tokens per unit of the payment
+    /// token, until the total number of tokens sold reaches `caps[i]`.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the contract's state.
+    /// * `caps` - Cap of each tier, strictly increasing.
+    /// * `rates` - Rate of each tier, non-zero.
+    ///
+    /// # Errors
+    ///
+    /// If the tiers are empty, `caps` and `rates` have different lengths, the
+    /// caps aren't strictly increasing or a rate is zero, then the error
+    /// [`Error::InvalidTiers`] is returned.
+    pub fn _set_tiers(
+        &mut self,
+        caps: &[U256],
+        rates: &[U256],
+    ) -> Result<(), Error> {
+        let increasing = caps.windows(2).all(|pair| pair[0] < pair[1]);
+        if caps.is_empty()
+            || caps.len() != rates.len()
+            || !increasing
+            || rates.iter().any(U256::is_zero)
+        {
+            return Err(Error::InvalidTiers(TokenSaleInvalidTiers {}));
+        }
+
+        self._tiers.truncate(0);
+        for (&cap, &rate) in caps.iter().zip(rates) {
+            let mut tier = self._tiers.grow();
+            tier.cap.set(cap);
+            tier.rate.set(rate);
+        }
+        Ok(())
+    }
+
+    /// Sets the maximum number of tokens an account can buy.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the contract's state.
+    /// * `cap` - The cap, or zero to remove it.
+    pub fn _set_account_cap(&mut self, cap: U256) {
+        self._account_cap.set(cap);
+    }
+
+    /// Sets the times the sale opens and closes at.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the contract's state.
+    /// * `opening_time` - Timestamp the sale opens at.
+    /// * `closing_time` - Timestamp the sale closes at.
+    ///
+    /// # Errors
+    ///
+    /// If `closing_time` is before `opening_time`, then the error
+    /// [`Error::InvalidWindow`] is returned.
+    pub fn _set_window(
+        &mut self,
+        opening_time: u64,
+        closing_time: u64,
+    ) -> Result<(), Error> {
+        if closing_time < opening_time {
+            return Err(Error::InvalidWindow(TokenSaleInvalidWindow {
+                opening_time,
+                closing_time,
+            }));
+        }
+        self._opening_time.set(U64::from(opening_time));
+        self._closing_time.set(U64::from(closing_time));
+        Ok(())
+    }
+
+    /// Restricts the sale to the accounts of the Merkle tree of root `root`.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the contract's state.
+    /// * `root` - Root of the allowlist, or zero to let anyone buy.
+    pub fn _set_allowlist_root(&mut self, root: B256) {
+        self._allowlist_root.set(root);
+    }
+
+    /// Buys tokens for [`msg::sender`] with `payment`, and returns the number
+    /// of tokens bought, which the calling contract must deliver.
+    ///
+    /// If the sale is paid in ETH, `payment` must be the value of the call.
+    /// Otherwise, `payment` is transferred from the buyer, who must have
+    /// approved the contract first.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the contract's state.
+    /// * `payment` - Amount of the payment token, or of ETH.
+    /// * `proof` - Proof that the buyer is part of the allowlist, if any.
+    ///
+    /// # Errors
+    ///
+    /// If the sale isn't open, then the error [`Error::NotOpen`] is returned.
+    /// If the buyer isn't part of the allowlist, then the error
+    /// [`Error::NotAllowed`] is returned.
+    /// If `payment` doesn't buy any token, then the error
+    /// [`Error::ZeroAmount`] is returned.
+    /// If the purchase would exceed the cap of the sale or of the buyer, then
+    /// the error [`Error::ExceededCap`] or [`Error::ExceededAccountCap`] is
+    /// returned.
+    /// If the sale is paid in ETH and the value of the call isn't `payment`,
+    /// or the sale is paid in tokens and ETH was sent, then the error
+    /// [`Error::InvalidPayment`] is returned.
+    /// If the transfer of the payment fails, then the error
+    /// [`Error::FailedTransfer`] is returned.
+    ///
+    /// # Events
+    ///
+    /// Emits a [`TokensPurchased`] event.
+    pub fn _buy(
+        &mut self,
+        payment: U256,
+        proof: &[B256],
+    ) -> Result<U256, Error> {
+        if !self.is_open() {
+            return Err(Error::NotOpen(TokenSaleNotOpen {
+                opening_time: self.opening_time(),
+                closing_time: self.closing_time(),
+            }));
+        }
+
+        let buyer = msg::sender();
+        if !self.is_allowed(buyer, proof) {
+            return Err(Error::NotAllowed(TokenSaleNotAllowed {
+                account: buyer,
+            }));
+        }
+
+        let amount = self.quote(payment)?;
+        if amount.is_zero() {
+            return Err(Error::ZeroAmount(TokenSaleZeroAmount {}));
+        }
+        let purchased = self._purchased.get(buyer) + amount;
+        let account_cap = self.account_cap();
+        if !account_cap.is_zero() && purchased > account_cap {
+            return Err(Error::ExceededAccountCap(
+                TokenSaleExceededAccountCap {
+                    account: buyer,
+                    cap: account_cap,
+                },
+            ));
+        }
+
+        self.collect(buyer, payment)?;
+
+        self._purchased.insert(buyer, purchased);
+        self._sold.set(self._sold.get() + amount);
+        self._proceeds.set(self._proceeds.get() + payment);
+        evm::log(TokensPurchased { buyer, payment, amount });
+        Ok(amount)
+    }
+
+    /// Sends the proceeds of the sale not withdrawn yet to `to`, and returns
+    /// their amount.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the contract's state.
+    /// * `to` - Account receiving the proceeds.
+    ///
+    /// # Errors
+    ///
+    /// If the transfer of the proceeds fails, then the error
+    /// [`Error::FailedTransfer`] is returned.
+    ///
+    /// # Events
+    ///
+    /// Emits a [`ProceedsWithdrawn`] event.
+    pub fn _withdraw_proceeds(&mut self, to: Address) -> Result<U256, Error> {
+        let amount = self._proceeds.get();
+        self._proceeds.set(U256::ZERO);
+
+        let token = self.payment_token();
+        let from = contract::address();
+        let transferred = if token.is_zero() {
+            transfer_eth(to, amount).is_ok()
+        } else {
+            IERC20::new(token)
+                .transfer(Call::new_in(self), to, amount)
+                .unwrap_or(false)
+        };
+        if !transferred {
+            return Err(Error::FailedTransfer(TokenSaleFailedTransfer {
+                from,
+                to,
+                amount,
+            }));
+        }
+
+        evm::log(ProceedsWithdrawn { to, amount });
+        Ok(amount)
+    }
+
+    /// Returns whether `account` is part of the allowlist, if any.
+    fn is_allowed(&self, account: Address, proof: &[B256]) -> bool {
+        let root = self.allowlist_root();
+        if root.is_zero() {
+            return true;
+        }
+        let leaf = allowlist_leaf(account);
+        let proof: Vec<[u8; 32]> = proof.iter().map(|node| node.0).collect();
+        Verifier::verify(&proof, root.0, leaf.0)
+    }
+
+    /// Transfers `payment` from `buyer` to the contract, or checks that it
+    /// was sent along with the call if the sale is paid in ETH.
+    fn collect(&mut self, buyer: Address, payment: U256) -> Result<(), Error> {
+        let token = self.payment_token();
+        let expected = if token.is_zero() { payment } else { U256::ZERO };
+        let received = msg::value();
+        if received != expected {
+            return Err(Error::InvalidPayment(TokenSaleInvalidPayment {
+                expected,
+                received,
+            }));
+        }
+        if token.is_zero() {
+            return Ok(());
+        }
+
+        let to = contract::address();
+        let transferred = IERC20::new(token)
+            .transfer_from(Call::new_in(self), buyer, to, payment)
+            .unwrap_or(false);
+        if !transferred {
+            return Err(Error::FailedTransfer(TokenSaleFailedTransfer {
+                from: buyer,
+                to,
+                amount: payment,
+            }));
+        }
+        Ok(())
+    }
+}
+
+/// Returns the leaf of `account` in an allowlist, i.e.,
+/// `keccak256(keccak256(abi.encode(account)))`.
+#[must_use]
+pub fn allowlist_leaf(account: Address) -> B256 {
+    keccak(keccak(sol_data::Address::encode_single(&account)))
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use alloy_primitives::{address, uint, Address, B256, U256};
+    use alloy_sol_types::{sol, SolCall};
+    use crypto::merkle::Tree;
+    use motsu::prelude::VM;
+    use stylus_sdk::{contract, msg};
+
+    use super::{allowlist_leaf, Error, TokenSale, TokensPurchased};
+
+    const TOKEN: Address = address!("F4EaCDAbEf3c8f1EdE91b6f2A6840bc2E4DD3526");
+    const ALICE: Address = address!("A11CEacF9aa32246d767FCCD72e02d6bCbcC375d");
+    const BOB: Address = address!("B0B0cB49ec2e96DF5F5fFB081acaE66A2cBBc2e2");
+
+    sol! {
+        function transferFrom(address from, address to, uint256 value) external returns (bool);
+    }
+
+    fn set_up(contract: &mut TokenSale, caps: &[u64], rates: &[u64]) {
+        let caps: Vec<U256> = caps.iter().map(|&cap| U256::from(cap)).collect();
+        let rates: Vec<U256> =
+            rates.iter().map(|&rate| U256::from(rate)).collect();
+        contract._set_tiers(&caps, &rates).expect("should set the tiers");
+        contract._set_window(0, 100).expect("should set the window");
+    }
+
+    fn pay_eth(value: u64) -> U256 {
+        let value = U256::from(value);
+        VM::context().fund(VM::context().msg_sender(), value);
+        VM::context().set_msg_value(value);
+        value
+    }
+
+    #[motsu::test]
+    fn buys_at_fixed_price(contract: TokenSale) {
+        set_up(contract, &[1_000], &[10]);
+        let payment = pay_eth(10);
+
+        let amount = contract._buy(payment, &[]).expect("should buy tokens");
+
+        assert_eq!(amount, uint!(100_U256));
+        assert_eq!(contract.tokens_sold(), amount);
+        assert_eq!(contract.purchased(msg::sender()), amount);
+        assert_eq!(contract.proceeds(), payment);
+        let events = VM::context().emitted_events::<TokensPurchased>();
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].buyer, msg::sender());
+        assert_eq!(events[0].amount, amount);
+    }
+
+    #[motsu::test]
+    fn prices_purchases_across_tiers(contract: TokenSale) {
+        set_up(contract, &[100, 300], &[10, 5]);
+
+        // 10 units buy the 100 tokens of the first tier, and the other 10 buy
+        // 50 tokens of the second.
+        assert_eq!(contract.quote(uint!(20_U256)).unwrap(), uint!(150_U256));
+        assert_eq!(contract.quote(uint!(50_U256)).unwrap(), uint!(300_U256));
+        let err = contract.quote(uint!(51_U256)).unwrap_err();
+        assert!(matches!(err, Error::ExceededCap(_)));
+    }
+
+    #[motsu::test]
+    fn rejects_purchases_over_caps(contract: TokenSale) {
+        set_up(contract, &[1_000], &[10]);
+        contract._set_account_cap(uint!(50_U256));
+        let payment = pay_eth(10);
+
+        let err = contract._buy(payment, &[]).unwrap_err();
+        assert!(matches!(
+            err,
+            Error::ExceededAccountCap(error) if error.cap == uint!(50_U256)
+        ));
+        assert_eq!(contract.tokens_sold(), U256::ZERO);
+    }
+
+    #[motsu::test]
+    fn rejects_purchases_outside_window(contract: TokenSale) {
+        set_up(contract, &[1_000], &[10]);
+        VM::context().set_block_timestamp(101);
+        let payment = pay_eth(10);
+
+        assert!(!contract.is_open());
+        let err = contract._buy(payment, &[]).unwrap_err();
+        assert!(matches!(err, Error::NotOpen(_)));
+    }
+
+    #[motsu::test]
+    fn rejects_mismatched_eth_payments(contract: TokenSale) {
+        set_up(contract, &[1_000], &[10]);
+        pay_eth(9);
+
+        let err = contract._buy(uint!(10_U256), &[]).unwrap_err();
+        assert!(matches!(err, Error::InvalidPayment(_)));
+    }
+
+    #[motsu::test]
+    fn rejects_invalid_configurations(contract: TokenSale) {
+        let one = uint!(1_U256);
+        for (caps, rates) in [
+            (vec![], vec![]),
+            (vec![one], vec![]),
+            (vec![one, one], vec![one, one]),
+            (vec![one], vec![U256::ZERO]),
+        ] {
+            let err = contract._set_tiers(&caps, &rates).unwrap_err();
+            assert!(matches!(err, Error::InvalidTiers(_)));
+        }
+
+        let err = contract._set_window(2, 1).unwrap_err();
+        assert!(matches!(err, Error::InvalidWindow(_)));
+    }
+
+    #[motsu::test]
+    fn restricts_purchases_to_allowlist(contract: TokenSale) {
+        set_up(contract, &[1_000], &[10]);
+        let leaves = [msg::sender(), ALICE, BOB]
+            .map(|account| allowlist_leaf(account).0);
+        let tree = Tree::new(&leaves);
+        contract._set_allowlist_root(tree.root().into());
+        let payment = pay_eth(10);
+
+        let err = contract._buy(payment, &[]).unwrap_err();
+        assert!(matches!(err, Error::NotAllowed(_)));
+
+        let proof: Vec<B256> = tree
+            .proof(leaves[0])
+            .expect("should prove the sender is allowed")
+            .into_iter()
+            .map(B256::from)
+            .collect();
+        assert!(contract._buy(payment, &proof).is_ok());
+    }
+
+    #[motsu::test]
+    fn collects_token_payments(contract: TokenSale) {
+        set_up(contract, &[1_000], &[10]);
+        contract._set_payment_token(TOKEN);
+        VM::context().mock_call(
+            TOKEN,
+            &transferFromCall::SELECTOR,
+            U256::from(1).to_be_bytes_vec(),
+        );
+
+        let amount = contract._buy(uint!(10_U256), &[]).unwrap();
+        assert_eq!(amount, uint!(100_U256));
+
+        VM::context().mock_call(
+            TOKEN,
+            &transferFromCall::SELECTOR,
+            U256::ZERO.to_be_bytes_vec(),
+        );
+        let err = contract._buy(uint!(10_U256), &[]).unwrap_err();
+        assert!(matches!(err, Error::FailedTransfer(_)));
+    }
+
+    #[motsu::test]
+    fn withdraws_eth_proceeds(contract: TokenSale) {
+        set_up(contract, &[1_000], &[10]);
+        let payment = pay_eth(10);
+        contract._buy(payment, &[]).unwrap();
+        assert_eq!(VM::context().balance_of(contract::address()), payment);
+
+        let withdrawn = contract._withdraw_proceeds(ALICE).unwrap();
+
+        assert_eq!(withdrawn, payment);
+        assert_eq!(contract.proceeds(), U256::ZERO);
+        assert_eq!(VM::context().balance_of(ALICE), payment);
+    }
+}
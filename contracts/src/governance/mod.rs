@@ -0,0 +1,2 @@
+//! Utilities to govern contracts on-chain.
+pub mod operation;
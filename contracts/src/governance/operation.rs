@@ -0,0 +1,220 @@
+//! Identifiers of governance operations.
+//!
+//! Timelocks and governors identify the operations they schedule or vote on
+//! by hashing their calls. The functions of this module compute the same
+//! identifiers as the Solidity `TimelockController` and `Governor` of
+//! [OpenZeppelin Contracts], so that an operation can be tracked across both,
+//! e.g., by a Stylus contract checking a proposal queued on a Solidity
+//! timelock:
+//!
+//! ```rust,ignore
+//! let calls = [Operation::new(token, U256::ZERO, transfer_calldata)];
+//! let description_hash = crypto::keccak("Send the grant");
+//! let proposal_id = hash_proposal(&calls, description_hash);
+//! ```
+//!
+//! [OpenZeppelin Contracts]: https://github.com/OpenZeppelin/openzeppelin-contracts
+use alloc::vec::Vec;
+
+use alloy_primitives::{Address, Bytes, B256, U256};
+use alloy_sol_types::{
+    sol_data::{self, Array, FixedBytes, Uint},
+    SolType,
+};
+use stylus_sdk::crypto::keccak;
+
+/// Solidity type of the targets, values and calldata of a batch,
+/// `(address[], uint256[], bytes[])`.
+type BatchType =
+    (Array<sol_data::Address>, Array<Uint<256>>, Array<sol_data::Bytes>);
+
+/// A call made by a governance operation.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Operation {
+    /// Called contract.
+    pub target: Address,
+    /// Value sent with the call, in wei.
+    pub value: U256,
+    /// Calldata of the call.
+    pub data: Bytes,
+}
+
+impl Operation {
+    /// Creates a call to `target` with `data`, sending `value` wei along.
+    #[must_use]
+    pub fn new(target: Address, value: U256, data: impl Into<Bytes>) -> Self {
+        Operation { target, value, data: data.into() }
+    }
+}
+
+/// Returns the targets, values and calldata of `operations`, as the separate
+/// arrays batch functions take, e.g., `executeBatch` or `propose`.
+#[must_use]
+pub fn split(
+    operations: &[Operation],
+) -> (Vec<Address>, Vec<U256>, Vec<Vec<u8>>) {
+    let targets = operations.iter().map(|op| op.target).collect();
+    let values = operations.iter().map(|op| op.value).collect();
+    let data = operations.iter().map(|op| op.data.to_vec()).collect();
+    (targets, values, data)
+}
+
+/// Returns `abi.encode(targets, values, calldatas)` of `operations`.
+#[must_use]
+pub fn encode_batch(operations: &[Operation]) -> Vec<u8> {
+    BatchType::encode_params(&split(operations))
+}
+
+/// Returns the identifier of a timelock operation made of a single call.
+///
+/// Matches `TimelockController.hashOperation`.
+///
+/// # Arguments
+///
+/// * `operation` - Call made by the operation.
+/// * `predecessor` - Identifier of the operation that must be executed before,
+///   or zero if there is none.
+/// * `salt` - Distinguishes operations making the same call.
+#[must_use]
+pub fn hash_operation(
+    operation: &Operation,
+    predecessor: B256,
+    salt: B256,
+) -> B256 {
+    let encoded = <(
+        sol_data::Address,
+        Uint<256>,
+        sol_data::Bytes,
+        FixedBytes<32>,
+        FixedBytes<32>,
+    )>::encode_params(&(
+        operation.target,
+        operation.value,
+        operation.data.to_vec(),
+        predecessor.0,
+        salt.0,
+    ));
+    keccak(encoded)
+}
+
+/// Returns the identifier of a timelock operation made of several calls.
+///
+/// Matches `TimelockController.hashOperationBatch`.
+///
+/// # Arguments
+///
+/// * `operations` - Calls made by the operation, in order.
+/// * `predecessor` - Identifier of the operation that must be executed before,
+///   or zero if there is none.
+/// * `salt` - Distinguishes operations making the same calls.
+#[must_use]
+pub fn hash_operation_batch(
+    operations: &[Operation],
+    predecessor: B256,
+    salt: B256,
+) -> B256 {
+    let (targets, values, data) = split(operations);
+    let encoded = <(
+        Array<sol_data::Address>,
+        Array<Uint<256>>,
+        Array<sol_data::Bytes>,
+        FixedBytes<32>,
+        FixedBytes<32>,
+    )>::encode_params(&(
+        targets,
+        values,
+        data,
+        predecessor.0,
+        salt.0,
+    ));
+    keccak(encoded)
+}
+
+/// Returns the identifier of a governor proposal.
+///
+/// Matches `Governor.hashProposal`.
+///
+/// # Arguments
+///
+/// * `operations` - Calls made by the proposal, in order.
+/// * `description_hash` - `keccak256` hash of the proposal's description.
+#[must_use]
+pub fn hash_proposal(operations: &[Operation], description_hash: B256) -> U256 {
+    let (targets, values, data) = split(operations);
+    let encoded =
+        <(
+            Array<sol_data::Address>,
+            Array<Uint<256>>,
+            Array<sol_data::Bytes>,
+            FixedBytes<32>,
+        )>::encode_params(&(targets, values, data, description_hash.0));
+    U256::from_be_bytes(keccak(encoded).0)
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use alloy_primitives::{address, b256, bytes, uint, Address, B256};
+
+    use super::{
+        encode_batch, hash_operation, hash_operation_batch, hash_proposal,
+        split, Operation,
+    };
+
+    const TARGET: Address =
+        address!("F4EaCDAbEf3c8f1EdE91b6f2A6840bc2E4DD3526");
+
+    fn operations() -> [Operation; 2] {
+        [
+            Operation::new(TARGET, uint!(1_U256), bytes!("12345678")),
+            Operation::new(Address::ZERO, uint!(0_U256), bytes!("")),
+        ]
+    }
+
+    #[test]
+    fn splits_operations() {
+        let (targets, values, data) = split(&operations());
+        assert_eq!(targets, vec![TARGET, Address::ZERO]);
+        assert_eq!(values, vec![uint!(1_U256), uint!(0_U256)]);
+        assert_eq!(data, vec![vec![0x12, 0x34, 0x56, 0x78], vec![]]);
+    }
+
+    #[test]
+    fn encodes_batches() {
+        let encoded = encode_batch(&operations());
+        // Three offsets, then the targets, values and calldata arrays.
+        assert_eq!(encoded.len(), 32 * (3 + 3 + 3 + 6));
+        assert_eq!(encoded[31], 0x60);
+    }
+
+    #[test]
+    fn hashes_operations() {
+        let [operation, _] = operations();
+        let hash = hash_operation(&operation, B256::ZERO, B256::ZERO);
+        assert_eq!(
+            hash,
+            b256!(
+                "ea08285ab2459403fa3ba6497c105b7a36d2dc97673bb369915c12a69c030ccb"
+            )
+        );
+    }
+
+    #[test]
+    fn hashes_operation_batches() {
+        let hash = hash_operation_batch(&operations(), B256::ZERO, B256::ZERO);
+        assert_eq!(
+            hash,
+            b256!(
+                "9166f2e7d835f7a4dd136f073b3bcdcfb7b83891c2bd43bf45e9bda08c7ecd8d"
+            )
+        );
+    }
+
+    #[test]
+    fn hashes_proposals() {
+        let id = hash_proposal(&operations(), B256::ZERO);
+        assert_eq!(
+            id,
+            uint!(113817282543109679540970508559516051050180018109164021655165787329076722805283_U256)
+        );
+    }
+}
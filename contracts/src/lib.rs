@@ -6,8 +6,26 @@ extern crate alloc;
 #[global_allocator]
 static ALLOC: mini_alloc::MiniAlloc = mini_alloc::MiniAlloc::INIT;
 
+pub use openzeppelin_stylus_proc::{
+    abi_interface, define_roles, guard, namespaced_storage, only_role,
+    ErrorDisplay, FromComponentErrors,
+};
+
+#[cfg(feature = "export-abi")]
+pub mod abi;
+#[cfg(feature = "access")]
 pub mod access;
+#[cfg(feature = "arbitrum")]
+pub mod arbitrum;
+#[cfg(feature = "finance")]
+pub mod finance;
+#[cfg(feature = "governance")]
+pub mod governance;
+#[cfg(feature = "oracle")]
+pub mod oracle;
 pub mod token;
+#[cfg(feature = "uniswap")]
+pub mod uniswap;
 pub mod utils;
 
 #[cfg(target_arch = "wasm32")]
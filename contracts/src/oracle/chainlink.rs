@@ -0,0 +1,326 @@
+//! Chainlink price feeds.
+//!
+//! [`PriceFeed`] reads the latest answer of an `AggregatorV3Interface` feed,
+//! and rejects it if it is not positive, comes from an incomplete round or is
+//! older than the feed's heartbeat. [`check_deviation`] additionally bounds
+//! how far a price can move from a reference, e.g., a second feed or a
+//! previously stored price.
+//!
+//! ```rust,ignore
+//! const ETH_USD: Address = address!("639Fe6ab55C921f74e7fac1ee960C0B6293ba612");
+//!
+//! #[external]
+//! impl Vault {
+//!     pub fn eth_price(&self) -> Result<U256, Vec<u8>> {
+//!         let price = PriceFeed::new(ETH_USD, 86_400).latest_price(self)?;
+//!         Ok(price.answer)
+//!     }
+//! }
+//! ```
+// `sol_interface!` doesn't document the items it generates.
+#![allow(missing_docs)]
+use alloc::string::String;
+
+use alloy_primitives::{Address, U256};
+use alloy_sol_types::sol;
+use openzeppelin_stylus_proc::ErrorDisplay;
+use stylus_proc::{sol_interface, SolidityError};
+use stylus_sdk::{block, call::StaticCallContext};
+
+sol_interface! {
+    /// A Chainlink data feed.
+    interface AggregatorV3Interface {
+        function decimals() external view returns (uint8);
+        function description() external view returns (string memory);
+        function version() external view returns (uint256);
+        function getRoundData(uint80 round_id) external view returns (uint80, int256, uint256, uint256, uint80);
+        function latestRoundData() external view returns (uint80, int256, uint256, uint256, uint80);
+    }
+}
+
+sol! {
+    /// The price feed couldn't be read.
+    ///
+    /// * `feed` - Address of the price feed.
+    #[derive(Debug)]
+    #[allow(missing_docs)]
+    error ChainlinkFeedUnavailable(address feed);
+    /// The price feed returned a price that is zero or negative.
+    ///
+    /// * `feed` - Address of the price feed.
+    #[derive(Debug)]
+    #[allow(missing_docs)]
+    error ChainlinkInvalidPrice(address feed);
+    /// The price feed returned a price from a round that is not complete.
+    ///
+    /// * `feed` - Address of the price feed.
+    /// * `round_id` - Round of the price.
+    #[derive(Debug)]
+    #[allow(missing_docs)]
+    error ChainlinkIncompleteRound(address feed, uint80 round_id);
+    /// The price feed returned a price that was last updated more than a
+    /// heartbeat ago.
+    ///
+    /// * `feed` - Address of the price feed.
+    /// * `updated_at` - Timestamp of the last update of the price.
+    #[derive(Debug)]
+    #[allow(missing_docs)]
+    error ChainlinkStalePrice(address feed, uint256 updated_at);
+    /// A price moved from its reference by more than allowed.
+    ///
+    /// * `price` - Price that was checked.
+    /// * `reference` - Price it was compared with.
+    /// * `max_deviation_bps` - Maximum deviation allowed, in basis points.
+    #[derive(Debug)]
+    #[allow(missing_docs)]
+    error ChainlinkPriceDeviation(uint256 price, uint256 reference, uint256 max_deviation_bps);
+}
+
+/// An error that occurred while reading a Chainlink price feed.
+#[derive(SolidityError, Debug, ErrorDisplay)]
+pub enum Error {
+    /// The price feed couldn't be read.
+    FeedUnavailable(ChainlinkFeedUnavailable),
+    /// The price feed returned a price that is zero or negative.
+    InvalidPrice(ChainlinkInvalidPrice),
+    /// The price feed returned a price from a round that is not complete.
+    IncompleteRound(ChainlinkIncompleteRound),
+    /// The price feed returned a price older than its heartbeat.
+    StalePrice(ChainlinkStalePrice),
+    /// A price moved from its reference by more than allowed.
+    PriceDeviation(ChainlinkPriceDeviation),
+}
+
+/// A price returned by a [`PriceFeed`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Price {
+    /// The price, with [`Price::decimals`] decimals.
+    pub answer: U256,
+    /// Number of decimals of [`Price::answer`].
+    pub decimals: u8,
+    /// Timestamp of the last update of the price.
+    pub updated_at: U256,
+}
+
+/// A Chainlink price feed, along with the maximum age of its prices.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PriceFeed {
+    /// Address of the feed.
+    pub address: Address,
+    /// Maximum age of a price, in seconds, usually the heartbeat of the
+    /// feed.
+    pub heartbeat: u64,
+}
+
+impl PriceFeed {
+    /// Creates a price feed reading `address`, whose prices are stale after
+    /// `heartbeat` seconds.
+    #[must_use]
+    pub fn new(address: Address, heartbeat: u64) -> Self {
+        PriceFeed { address, heartbeat }
+    }
+
+    /// Returns the latest price of the feed.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the feed's configuration.
+    /// * `context` - Context of the calls to the feed, e.g., `&*self` from a
+    ///   contract.
+    ///
+    /// # Errors
+    ///
+    /// If the feed can't be read, then the error [`Error::FeedUnavailable`]
+    /// is returned.
+    ///
+    /// If the price is zero or negative, then the error
+    /// [`Error::InvalidPrice`] is returned.
+    ///
+    /// If the price comes from a round that is not complete, then the error
+    /// [`Error::IncompleteRound`] is returned.
+    ///
+    /// If the price was last updated more than [`PriceFeed::heartbeat`]
+    /// seconds ago, then the error [`Error::StalePrice`] is returned.
+    pub fn latest_price(
+        &self,
+        context: impl StaticCallContext + Copy,
+    ) -> Result<Price, Error> {
+        let feed = self.address;
+        let unavailable =
+            |_| Error::FeedUnavailable(ChainlinkFeedUnavailable { feed });
+        let aggregator = AggregatorV3Interface::new(feed);
+
+        let (round_id, answer, _, updated_at, answered_in_round) =
+            aggregator.latest_round_data(context).map_err(unavailable)?;
+        let decimals = aggregator.decimals(context).map_err(unavailable)?;
+
+        if !answer.is_positive() {
+            return Err(Error::InvalidPrice(ChainlinkInvalidPrice { feed }));
+        }
+        if updated_at.is_zero() || answered_in_round < round_id {
+            return Err(Error::IncompleteRound(ChainlinkIncompleteRound {
+                feed,
+                round_id,
+            }));
+        }
+        let now = U256::from(block::timestamp());
+        if now.saturating_sub(updated_at) > U256::from(self.heartbeat) {
+            return Err(Error::StalePrice(ChainlinkStalePrice {
+                feed,
+                updated_at,
+            }));
+        }
+
+        Ok(Price { answer: answer.into_raw(), decimals, updated_at })
+    }
+
+    /// Returns the description of the feed, e.g., `ETH / USD`.
+    ///
+    /// # Errors
+    ///
+    /// If the feed can't be read, then the error [`Error::FeedUnavailable`]
+    /// is returned.
+    pub fn description(
+        &self,
+        context: impl StaticCallContext,
+    ) -> Result<String, Error> {
+        let feed = self.address;
+        AggregatorV3Interface::new(feed).description(context).map_err(|_| {
+            Error::FeedUnavailable(ChainlinkFeedUnavailable { feed })
+        })
+    }
+}
+
+/// Checks that `price` deviates from `reference` by at most
+/// `max_deviation_bps` basis points of `reference`.
+///
+/// Both prices should have the same number of decimals.
+///
+/// # Errors
+///
+/// If `price` deviates by more than `max_deviation_bps`, then the error
+/// [`Error::PriceDeviation`] is returned.
+pub fn check_deviation(
+    price: U256,
+    reference: U256,
+    max_deviation_bps: U256,
+) -> Result<(), Error> {
+    let deviation = price.abs_diff(reference);
+    let max_deviation =
+        reference.saturating_mul(max_deviation_bps) / U256::from(10_000);
+    if deviation > max_deviation {
+        return Err(Error::PriceDeviation(ChainlinkPriceDeviation {
+            price,
+            reference,
+            max_deviation_bps,
+        }));
+    }
+    Ok(())
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use alloy_primitives::{address, uint, Address, U256};
+    use alloy_sol_types::{sol, SolCall};
+    use motsu::prelude::{Caller, VM};
+
+    use super::{check_deviation, Error, Price, PriceFeed};
+
+    const FEED: Address = address!("639Fe6ab55C921f74e7fac1ee960C0B6293ba612");
+    const HEARTBEAT: u64 = 3_600;
+    const NOW: u64 = 1_700_000_000;
+
+    sol! {
+        function decimals() external view returns (uint8);
+        function latestRoundData() external view returns (uint80, int256, uint256, uint256, uint80);
+    }
+
+    /// Mocks the feed to return `answer`, updated at `updated_at`, from round
+    /// `round_id`, answered in round `answered_in_round`.
+    fn mock_round(
+        round_id: u64,
+        answer: U256,
+        updated_at: u64,
+        answered_in_round: u64,
+    ) {
+        let words = [
+            U256::from(round_id),
+            answer,
+            U256::from(updated_at),
+            U256::from(updated_at),
+            U256::from(answered_in_round),
+        ];
+        VM::context().mock_call(
+            FEED,
+            &latestRoundDataCall::SELECTOR,
+            words.iter().flat_map(U256::to_be_bytes::<32>).collect::<Vec<_>>(),
+        );
+        VM::context().mock_call(
+            FEED,
+            &decimalsCall::SELECTOR,
+            U256::from(8).to_be_bytes::<32>(),
+        );
+        VM::context().set_block_timestamp(NOW);
+    }
+
+    #[motsu::test]
+    fn reads_latest_price() {
+        let answer = uint!(2_000_00000000_U256);
+        mock_round(7, answer, NOW - 60, 7);
+
+        let price = PriceFeed::new(FEED, HEARTBEAT).latest_price(&Caller);
+        assert_eq!(
+            price.unwrap(),
+            Price { answer, decimals: 8, updated_at: U256::from(NOW - 60) }
+        );
+    }
+
+    #[motsu::test]
+    fn rejects_unavailable_feed() {
+        VM::context().mock_revert(FEED, &latestRoundDataCall::SELECTOR, vec![]);
+
+        let price = PriceFeed::new(FEED, HEARTBEAT).latest_price(&Caller);
+        assert!(matches!(price, Err(Error::FeedUnavailable(_))));
+    }
+
+    #[motsu::test]
+    fn rejects_negative_price() {
+        mock_round(7, U256::MAX, NOW, 7);
+
+        let price = PriceFeed::new(FEED, HEARTBEAT).latest_price(&Caller);
+        assert!(matches!(price, Err(Error::InvalidPrice(_))));
+    }
+
+    #[motsu::test]
+    fn rejects_incomplete_round() {
+        mock_round(7, uint!(1_U256), NOW, 6);
+
+        let price = PriceFeed::new(FEED, HEARTBEAT).latest_price(&Caller);
+        assert!(matches!(price, Err(Error::IncompleteRound(_))));
+    }
+
+    #[motsu::test]
+    fn rejects_stale_price() {
+        mock_round(7, uint!(1_U256), NOW - HEARTBEAT - 1, 7);
+
+        let price = PriceFeed::new(FEED, HEARTBEAT).latest_price(&Caller);
+        assert!(matches!(price, Err(Error::StalePrice(_))));
+    }
+
+    #[motsu::test]
+    fn checks_deviation() {
+        let reference = uint!(1_000_U256);
+        let max = uint!(100_U256); // 1%.
+
+        assert!(check_deviation(uint!(1_010_U256), reference, max).is_ok());
+        assert!(check_deviation(uint!(990_U256), reference, max).is_ok());
+        assert!(matches!(
+            check_deviation(uint!(1_011_U256), reference, max),
+            Err(Error::PriceDeviation(_))
+        ));
+        assert!(matches!(
+            check_deviation(uint!(989_U256), reference, max),
+            Err(Error::PriceDeviation(_))
+        ));
+    }
+}
@@ -0,0 +1,3 @@
+//! Bindings and helpers to read prices and randomness from on-chain oracles.
+pub mod chainlink;
+pub mod vrf;
@@ -0,0 +1,649 @@
+//! Chainlink VRF v2.5 randomness.
+//!
+//! Random words are requested from the VRF coordinator, and paid for by a
+//! subscription the requesting contract was added to as a consumer. Once the
+//! request is confirmed, the coordinator calls back `rawFulfillRandomWords`
+//! on the contract with the random words and the proof they are random.
+//!
+//! [`VrfCoordinator`] wraps the coordinator's functions. [`RandomnessConsumer`]
+//! keeps the request configuration and tracks pending requests, and
+//! [`IRandomnessConsumer`] is implemented by the contract receiving the words,
+//! e.g., a raffle:
+//!
+//! ```rust,ignore
+//! #[external]
+//! impl Raffle {
+//!     pub fn draw(&mut self) -> Result<U256, Vec<u8>> {
+//!         self.ownable.only_owner()?;
+//!         Ok(self.consumer._request_random_words(1)?)
+//!     }
+//!
+//!     #[selector(name = "rawFulfillRandomWords")]
+//!     pub fn raw_fulfill_random_words(
+//!         &mut self,
+//!         request_id: U256,
+//!         random_words: Vec<U256>,
+//!     ) -> Result<(), Vec<u8>> {
+//!         self.consumer._validate_fulfillment(request_id)?;
+//!         Ok(self.fulfill_random_words(request_id, random_words)?)
+//!     }
+//! }
+//!
+//! impl IRandomnessConsumer for Raffle {
+//!     type Error = Vec<u8>;
+//!
+//!     fn fulfill_random_words(
+//!         &mut self,
+//!         request_id: U256,
+//!         random_words: Vec<U256>,
+//!     ) -> Result<(), Vec<u8>> {
+//!         let winner = random_words[0] % U256::from(self.players.len());
+//!         // ...
+//!     }
+//! }
+//! ```
+//!
+//! For more information see the [Chainlink VRF documentation].
+//!
+//! [Chainlink VRF documentation]: https://docs.chain.link/vrf/v2-5/overview/subscription
+// `sol_interface!` doesn't document the items it generates.
+#![allow(missing_docs)]
+use alloc::vec::Vec;
+
+use alloy_primitives::{Address, B256, U16, U256, U32};
+use alloy_sol_types::{sol, SolCall};
+use openzeppelin_stylus_proc::ErrorDisplay;
+use stylus_proc::{external, sol_interface, sol_storage, SolidityError};
+use stylus_sdk::{
+    call::{self, Call, Error as CallError, StaticCallContext},
+    evm, msg,
+    storage::TopLevelStorage,
+};
+
+use crate::utils::structs::gap::StorageGap;
+
+/// Tag of the extra arguments of a request, i.e.,
+/// `bytes4(keccak256("VRF ExtraArgsV1"))`.
+pub const EXTRA_ARGS_V1_TAG: [u8; 4] = [0x92, 0xfd, 0x13, 0x38];
+
+sol_interface! {
+    /// The Chainlink VRF v2.5 coordinator.
+    interface IVRFCoordinatorV2Plus {
+        function createSubscription() external returns (uint256);
+        function addConsumer(uint256 sub_id, address consumer) external;
+        function removeConsumer(uint256 sub_id, address consumer) external;
+        function fundSubscriptionWithNative(uint256 sub_id) external payable;
+        function pendingRequestExists(uint256 sub_id) external view returns (bool);
+    }
+}
+
+sol! {
+    /// Arguments of a request for random words.
+    #[allow(missing_docs)]
+    struct VRFRandomWordsRequest {
+        bytes32 key_hash;
+        uint256 sub_id;
+        uint16 request_confirmations;
+        uint32 callback_gas_limit;
+        uint32 num_words;
+        bytes extra_args;
+    }
+
+    /// Requests random words from a coordinator, and returns the identifier
+    /// of the request.
+    #[allow(missing_docs)]
+    function requestRandomWords(VRFRandomWordsRequest req) external returns (uint256 request_id);
+}
+
+sol! {
+    /// Emitted when `num_words` random words are requested in the request
+    /// `request_id`.
+    #[allow(missing_docs)]
+    event RandomWordsRequested(uint256 indexed request_id, uint32 num_words);
+    /// Emitted when the request `request_id` is fulfilled.
+    #[allow(missing_docs)]
+    event RandomWordsFulfilled(uint256 indexed request_id);
+}
+
+sol! {
+    /// Random words were delivered by an account that isn't the coordinator.
+    ///
+    /// * `have` - Account delivering the words.
+    /// * `want` - Address of the coordinator.
+    #[derive(Debug)]
+    #[allow(missing_docs)]
+    error VRFConsumerOnlyCoordinatorCanFulfill(address have, address want);
+    /// Random words were delivered for a request that isn't pending.
+    ///
+    /// * `request_id` - Identifier of the request.
+    #[derive(Debug)]
+    #[allow(missing_docs)]
+    error VRFConsumerUnknownRequest(uint256 request_id);
+    /// The coordinator rejected a request for random words.
+    ///
+    /// * `coordinator` - Address of the coordinator.
+    #[derive(Debug)]
+    #[allow(missing_docs)]
+    error VRFConsumerFailedRequest(address coordinator);
+}
+
+/// A [`RandomnessConsumer`] error.
+#[derive(SolidityError, Debug, ErrorDisplay)]
+pub enum Error {
+    /// Random words were delivered by an account that isn't the
+    /// coordinator.
+    OnlyCoordinatorCanFulfill(VRFConsumerOnlyCoordinatorCanFulfill),
+    /// Random words were delivered for a request that isn't pending.
+    UnknownRequest(VRFConsumerUnknownRequest),
+    /// The coordinator rejected a request for random words.
+    FailedRequest(VRFConsumerFailedRequest),
+}
+
+/// Returns the extra arguments of a request, i.e.,
+/// `abi.encodeWithSelector(EXTRA_ARGS_V1_TAG, ExtraArgsV1(native_payment))`.
+///
+/// # Arguments
+///
+/// * `native_payment` - Whether the request is paid for in ETH instead of LINK.
+#[must_use]
+pub fn extra_args_v1(native_payment: bool) -> Vec<u8> {
+    let mut encoded = Vec::with_capacity(36);
+    encoded.extend_from_slice(&EXTRA_ARGS_V1_TAG);
+    encoded.extend_from_slice(&U256::from(native_payment).to_be_bytes::<32>());
+    encoded
+}
+
+/// A request for random words to a [`VrfCoordinator`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RandomWordsRequest {
+    /// Gas lane to use, which sets the maximum gas price paid for the
+    /// callback.
+    pub key_hash: B256,
+    /// Subscription paying for the request.
+    pub subscription_id: U256,
+    /// Number of blocks the coordinator waits for before responding.
+    pub request_confirmations: u16,
+    /// Gas limit of the call to `rawFulfillRandomWords`.
+    pub callback_gas_limit: u32,
+    /// Number of random words requested.
+    pub num_words: u32,
+    /// Whether the request is paid for in ETH instead of LINK.
+    pub native_payment: bool,
+}
+
+/// A Chainlink VRF v2.5 coordinator.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct VrfCoordinator {
+    /// Address of the coordinator.
+    pub address: Address,
+}
+
+impl VrfCoordinator {
+    /// Creates a handle to the coordinator at `address`.
+    #[must_use]
+    pub fn new(address: Address) -> Self {
+        VrfCoordinator { address }
+    }
+
+    /// Requests random words, and returns the identifier of the request.
+    ///
+    /// The calling contract must be a consumer of the subscription.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the coordinator configuration.
+    /// * `storage` - The calling contract.
+    /// * `request` - The request.
+    ///
+    /// # Errors
+    ///
+    /// If the coordinator rejects the request, then the error of the call is
+    /// returned.
+    pub fn request_random_words(
+        &self,
+        storage: &mut impl TopLevelStorage,
+        request: &RandomWordsRequest,
+    ) -> Result<U256, CallError> {
+        // `sol_interface!` encodes `bytes32` arguments as `bytes[32]` in
+        // selectors, so that the call is made by hand.
+        let call = requestRandomWordsCall {
+            req: VRFRandomWordsRequest {
+                key_hash: request.key_hash.0,
+                sub_id: request.subscription_id,
+                request_confirmations: request.request_confirmations,
+                callback_gas_limit: request.callback_gas_limit,
+                num_words: request.num_words,
+                extra_args: extra_args_v1(request.native_payment),
+            },
+        };
+        let returned =
+            call::call(Call::new_in(storage), self.address, &call.encode())?;
+        Ok(requestRandomWordsCall::decode_returns(&returned, true)?.request_id)
+    }
+
+    /// Creates a subscription owned by the calling contract, and returns its
+    /// identifier.
+    ///
+    /// # Errors
+    ///
+    /// If the creation fails, then the error of the call is returned.
+    pub fn create_subscription(
+        &self,
+        storage: &mut impl TopLevelStorage,
+    ) -> Result<U256, CallError> {
+        IVRFCoordinatorV2Plus::new(self.address).create_subscription(storage)
+    }
+
+    /// Adds `consumer` to the subscription `subscription_id`, owned by the
+    /// calling contract.
+    ///
+    /// # Errors
+    ///
+    /// If the addition fails, then the error of the call is returned.
+    pub fn add_consumer(
+        &self,
+        storage: &mut impl TopLevelStorage,
+        subscription_id: U256,
+        consumer: Address,
+    ) -> Result<(), CallError> {
+        IVRFCoordinatorV2Plus::new(self.address).add_consumer(
+            storage,
+            subscription_id,
+            consumer,
+        )
+    }
+
+    /// Removes `consumer` from the subscription `subscription_id`, owned by
+    /// the calling contract.
+    ///
+    /// # Errors
+    ///
+    /// If the removal fails, then the error of the call is returned.
+    pub fn remove_consumer(
+        &self,
+        storage: &mut impl TopLevelStorage,
+        subscription_id: U256,
+        consumer: Address,
+    ) -> Result<(), CallError> {
+        IVRFCoordinatorV2Plus::new(self.address).remove_consumer(
+            storage,
+            subscription_id,
+            consumer,
+        )
+    }
+
+    /// Funds the subscription `subscription_id` with `amount` ETH of the
+    /// calling contract, to pay for requests with
+    /// [`RandomWordsRequest::native_payment`].
+    ///
+    /// # Errors
+    ///
+    /// If the funding fails, then the error of the call is returned.
+    pub fn fund_subscription_with_native(
+        &self,
+        storage: &mut impl TopLevelStorage,
+        subscription_id: U256,
+        amount: U256,
+    ) -> Result<(), CallError> {
+        IVRFCoordinatorV2Plus::new(self.address).fund_subscription_with_native(
+            Call::new_in(storage).value(amount),
+            subscription_id,
+        )
+    }
+
+    /// Returns whether the subscription `subscription_id` has requests that
+    /// weren't fulfilled yet.
+    ///
+    /// # Errors
+    ///
+    /// If the coordinator can't be read, then the error of the call is
+    /// returned.
+    pub fn pending_request_exists(
+        &self,
+        context: impl StaticCallContext,
+        subscription_id: U256,
+    ) -> Result<bool, CallError> {
+        IVRFCoordinatorV2Plus::new(self.address)
+            .pending_request_exists(context, subscription_id)
+    }
+}
+
+/// Interface of the contracts receiving random words from a
+/// [`VrfCoordinator`].
+///
+/// Implementations are called from a `rawFulfillRandomWords(uint256,
+/// uint256[])` function, once [`RandomnessConsumer::_validate_fulfillment`]
+/// checked the words come from the coordinator.
+pub trait IRandomnessConsumer {
+    /// The error type associated to this trait implementation.
+    type Error: Into<Vec<u8>>;
+
+    /// Handles the random words of the request `request_id`.
+    ///
+    /// The coordinator doesn't retry failed callbacks, so implementations
+    /// shouldn't revert, and should fit in the callback gas limit of the
+    /// request.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the contract's state.
+    /// * `request_id` - Identifier of the request.
+    /// * `random_words` - The random words.
+    ///
+    /// # Errors
+    ///
+    /// May return an error, which reverts the callback.
+    fn fulfill_random_words(
+        &mut self,
+        request_id: U256,
+        random_words: Vec<U256>,
+    ) -> Result<(), Self::Error>;
+}
+
+sol_storage! {
+    /// State of a [`RandomnessConsumer`] contract.
+    #[cfg_attr(all(test, feature = "std"), derive(motsu::DefaultStorageLayout))]
+    pub struct RandomnessConsumer {
+        /// Address of the coordinator.
+        address _coordinator;
+        /// Subscription paying for requests.
+        uint256 _subscription_id;
+        /// Whether requests are paid for in ETH instead of LINK.
+        bool _native_payment;
+        /// Gas lane of requests.
+        bytes32 _key_hash;
+        /// Number of blocks the coordinator waits for before responding.
+        uint16 _request_confirmations;
+        /// Gas limit of the callbacks.
+        uint32 _callback_gas_limit;
+        /// Request identifier -> Whether the request is pending.
+        mapping(uint256 => bool) _pending;
+        /// Reserved storage slots, see [`StorageGap`].
+        StorageGap<43> __gap;
+    }
+}
+
+/// NOTE: Implementation of [`TopLevelStorage`] to be able use `&mut self` when
+/// calling other contracts and not `&mut (impl TopLevelStorage +
+/// BorrowMut<Self>)`. Should be fixed in the future by the Stylus team.
+unsafe impl TopLevelStorage for RandomnessConsumer {}
+
+#[external]
+impl RandomnessConsumer {
+    /// Returns the address of the coordinator.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the contract's state.
+    pub fn coordinator(&self) -> Address {
+        self._coordinator.get()
+    }
+
+    /// Returns the subscription paying for requests.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the contract's state.
+    pub fn subscription_id(&self) -> U256 {
+        self._subscription_id.get()
+    }
+
+    /// Returns the gas lane of requests.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the contract's state.
+    pub fn key_hash(&self) -> B256 {
+        self._key_hash.get()
+    }
+
+    /// Returns whether the request `request_id` is waiting for its random
+    /// words.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the contract's state.
+    /// * `request_id` - Identifier of the request.
+    pub fn is_request_pending(&self, request_id: U256) -> bool {
+        self._pending.get(request_id)
+    }
+}
+
+impl RandomnessConsumer {
+    /// Sets the coordinator.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the contract's state.
+    /// * `coordinator` - Address of the coordinator.
+    pub fn _set_coordinator(&mut self, coordinator: Address) {
+        self._coordinator.set(coordinator);
+    }
+
+    /// Sets the subscription paying for requests.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the contract's state.
+    /// * `subscription_id` - Identifier of the subscription.
+    /// * `native_payment` - Whether requests are paid for in ETH instead of
+    ///   LINK.
+    pub fn _set_subscription(
+        &mut self,
+        subscription_id: U256,
+        native_payment: bool,
+    ) {
+        self._subscription_id.set(subscription_id);
+        self._native_payment.set(native_payment);
+    }
+
+    /// Sets how requests are fulfilled.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the contract's state.
+    /// * `key_hash` - Gas lane of requests.
+    /// * `request_confirmations` - Number of blocks the coordinator waits for
+    ///   before responding.
+    /// * `callback_gas_limit` - Gas limit of the callbacks.
+    pub fn _set_request_config(
+        &mut self,
+        key_hash: B256,
+        request_confirmations: u16,
+        callback_gas_limit: u32,
+    ) {
+        self._key_hash.set(key_hash);
+        self._request_confirmations.set(U16::from(request_confirmations));
+        self._callback_gas_limit.set(U32::from(callback_gas_limit));
+    }
+
+    /// Requests `num_words` random words with the configured subscription,
+    /// and returns the identifier of the request.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the contract's state.
+    /// * `num_words` - Number of random words requested.
+    ///
+    /// # Errors
+    ///
+    /// If the coordinator rejects the request, then the error
+    /// [`Error::FailedRequest`] is returned.
+    ///
+    /// # Events
+    ///
+    /// Emits a [`RandomWordsRequested`] event.
+    pub fn _request_random_words(
+        &mut self,
+        num_words: u32,
+    ) -> Result<U256, Error> {
+        let coordinator = self.coordinator();
+        let request = RandomWordsRequest {
+            key_hash: self.key_hash(),
+            subscription_id: self.subscription_id(),
+            request_confirmations: self
+                ._request_confirmations
+                .get()
+                .to::<u16>(),
+            callback_gas_limit: self._callback_gas_limit.get().to::<u32>(),
+            num_words,
+            native_payment: self._native_payment.get(),
+        };
+        let request_id = VrfCoordinator::new(coordinator)
+            .request_random_words(self, &request)
+            .map_err(|_| {
+                Error::FailedRequest(VRFConsumerFailedRequest { coordinator })
+            })?;
+
+        self._pending.setter(request_id).set(true);
+        evm::log(RandomWordsRequested { request_id, num_words });
+        Ok(request_id)
+    }
+
+    /// Checks that the random words of the request `request_id` are
+    /// delivered by the coordinator, and marks the request as fulfilled.
+    ///
+    /// Must be called by `rawFulfillRandomWords` before handing the words to
+    /// [`IRandomnessConsumer::fulfill_random_words`].
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the contract's state.
+    /// * `request_id` - Identifier of the request.
+    ///
+    /// # Errors
+    ///
+    /// If the caller is not the coordinator, then the error
+    /// [`Error::OnlyCoordinatorCanFulfill`] is returned.
+    /// If the request isn't pending, then the error
+    /// [`Error::UnknownRequest`] is returned.
+    ///
+    /// # Events
+    ///
+    /// Emits a [`RandomWordsFulfilled`] event.
+    pub fn _validate_fulfillment(
+        &mut self,
+        request_id: U256,
+    ) -> Result<(), Error> {
+        let have = msg::sender();
+        let want = self.coordinator();
+        if have != want {
+            return Err(Error::OnlyCoordinatorCanFulfill(
+                VRFConsumerOnlyCoordinatorCanFulfill { have, want },
+            ));
+        }
+        if !self.is_request_pending(request_id) {
+            return Err(Error::UnknownRequest(VRFConsumerUnknownRequest {
+                request_id,
+            }));
+        }
+
+        self._pending.setter(request_id).set(false);
+        evm::log(RandomWordsFulfilled { request_id });
+        Ok(())
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use alloy_primitives::{address, b256, keccak256, uint, Address, U256};
+    use alloy_sol_types::SolCall;
+    use motsu::prelude::VM;
+
+    use super::{
+        extra_args_v1, requestRandomWordsCall, Error, RandomWordsFulfilled,
+        RandomWordsRequested, RandomnessConsumer, EXTRA_ARGS_V1_TAG,
+    };
+
+    const COORDINATOR: Address =
+        address!("d5D517aBE5cF79B7e95eC98dB0f0277788aFF634");
+    const ALICE: Address = address!("A11CEacF9aa32246d767FCCD72e02d6bCbcC375d");
+    const REQUEST_ID: U256 = uint!(42_U256);
+
+    fn configure(contract: &mut RandomnessConsumer) {
+        contract._set_coordinator(COORDINATOR);
+        contract._set_subscription(uint!(7_U256), true);
+        contract._set_request_config(
+            b256!(
+                "787d74caea10b2b357790d5b5247c2f63d1d91572a9846f780606e4d953677ae"
+            ),
+            3,
+            100_000,
+        );
+    }
+
+    fn mock_request() {
+        VM::context().mock_call(
+            COORDINATOR,
+            &requestRandomWordsCall::SELECTOR,
+            REQUEST_ID.to_be_bytes::<32>(),
+        );
+    }
+
+    #[motsu::test]
+    fn encodes_extra_args() {
+        assert_eq!(EXTRA_ARGS_V1_TAG, keccak256("VRF ExtraArgsV1")[..4]);
+
+        let extra_args = extra_args_v1(true);
+        assert_eq!(extra_args.len(), 36);
+        assert_eq!(extra_args[..4], EXTRA_ARGS_V1_TAG);
+        assert_eq!(extra_args[35], 1);
+        assert_eq!(extra_args_v1(false)[4..], [0; 32]);
+    }
+
+    #[motsu::test]
+    fn requests_random_words(contract: RandomnessConsumer) {
+        configure(contract);
+        mock_request();
+
+        let request_id = contract._request_random_words(2).unwrap();
+        assert_eq!(request_id, REQUEST_ID);
+        assert!(contract.is_request_pending(REQUEST_ID));
+        let events = VM::context().emitted_events::<RandomWordsRequested>();
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].request_id, REQUEST_ID);
+        assert_eq!(events[0].num_words, 2);
+    }
+
+    #[motsu::test]
+    fn rejects_failed_request(contract: RandomnessConsumer) {
+        configure(contract);
+        VM::context().mock_revert(
+            COORDINATOR,
+            &requestRandomWordsCall::SELECTOR,
+            vec![],
+        );
+
+        let err = contract._request_random_words(2).unwrap_err();
+        assert!(matches!(err, Error::FailedRequest(_)));
+    }
+
+    #[motsu::test]
+    fn fulfills_pending_request(contract: RandomnessConsumer) {
+        VM::context().set_msg_sender(COORDINATOR);
+        configure(contract);
+        mock_request();
+        contract._request_random_words(2).unwrap();
+
+        contract._validate_fulfillment(REQUEST_ID).unwrap();
+        assert!(!contract.is_request_pending(REQUEST_ID));
+        let events = VM::context().emitted_events::<RandomWordsFulfilled>();
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].request_id, REQUEST_ID);
+
+        let err = contract._validate_fulfillment(REQUEST_ID).unwrap_err();
+        assert!(matches!(err, Error::UnknownRequest(_)));
+    }
+
+    #[motsu::test]
+    fn rejects_fulfillment_from_others(contract: RandomnessConsumer) {
+        VM::context().set_msg_sender(ALICE);
+        configure(contract);
+        mock_request();
+        contract._request_random_words(2).unwrap();
+
+        let err = contract._validate_fulfillment(REQUEST_ID).unwrap_err();
+        assert!(matches!(err, Error::OnlyCoordinatorCanFulfill(_)));
+        assert!(contract.is_request_pending(REQUEST_ID));
+    }
+}
@@ -7,8 +7,11 @@
 
 use alloy_primitives::U256;
 use alloy_sol_types::sol;
+use openzeppelin_stylus_proc::ErrorDisplay;
 use stylus_proc::{external, sol_storage, SolidityError};
 
+use crate::utils::structs::gap::StorageGap;
+
 sol! {
     /// Indicates an error related to the operation that failed
     /// because `total_supply` exceeded the `_cap`.
@@ -24,7 +27,7 @@ sol! {
 }
 
 /// A Capped error.
-#[derive(SolidityError, Debug)]
+#[derive(SolidityError, Debug, ErrorDisplay)]
 pub enum Error {
     /// Indicates an error related to the operation that failed
     /// because `total_supply` exceeded the `_cap`.
@@ -41,6 +44,8 @@ sol_storage! {
     pub struct Capped {
         /// A cap to the supply of tokens.
         uint256 _cap;
+        /// Reserved storage slots, see [`StorageGap`].
+        StorageGap<49> __gap;
     }
 }
 
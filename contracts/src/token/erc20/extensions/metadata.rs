@@ -2,12 +2,13 @@
 
 use alloc::string::String;
 
+use alloy_primitives::{fixed_bytes, FixedBytes};
 use stylus_proc::{external, sol_storage};
 
 /// Number of decimals used by default on implementors of [`Metadata`].
 pub const DEFAULT_DECIMALS: u8 = 18;
 
-use crate::utils::Metadata;
+use crate::utils::{structs::gap::StorageGap, Metadata};
 
 sol_storage! {
     /// Metadata of the [`Erc20`] token.
@@ -15,12 +16,18 @@ sol_storage! {
     /// It has hardcoded `decimals` to [`DEFAULT_DECIMALS`].
     pub struct Erc20Metadata {
         /// Common Metadata.
-        Metadata _metadata
+        Metadata _metadata;
+        /// Reserved storage slots, see [`StorageGap`].
+        StorageGap<50> __gap;
     }
 }
 
 /// Interface for the optional metadata functions from the ERC-20 standard.
 pub trait IErc20Metadata {
+    /// ERC-165 interface id of [`IErc20Metadata`], the XOR of the selectors of
+    /// its functions.
+    const INTERFACE_ID: FixedBytes<4> = fixed_bytes!("a219a025");
+
     /// Returns the name of the token.
     ///
     /// # Arguments
@@ -75,3 +82,15 @@ impl IErc20Metadata for Erc20Metadata {
         DEFAULT_DECIMALS
     }
 }
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::{Erc20Metadata, IErc20Metadata};
+    use crate::token::tests::interface_id_of;
+
+    #[test]
+    fn interface_id() {
+        let id = interface_id_of(&["name()", "symbol()", "decimals()"]);
+        assert_eq!(<Erc20Metadata as IErc20Metadata>::INTERFACE_ID, id);
+    }
+}
@@ -4,15 +4,19 @@
 //! revert instead of returning `false` on failure. This behavior is
 //! nonetheless conventional and does not conflict with the expectations of
 //! [`Erc20`] applications.
-use alloy_primitives::{Address, U256};
+use alloy_primitives::{fixed_bytes, Address, FixedBytes, U256};
 use alloy_sol_types::sol;
+use openzeppelin_stylus_proc::ErrorDisplay;
 use stylus_proc::SolidityError;
 use stylus_sdk::{
     evm, msg,
     stylus_proc::{external, sol_storage},
 };
 
+use crate::utils::structs::gap::StorageGap;
+
 pub mod extensions;
+pub mod utils;
 
 sol! {
     /// Emitted when `value` tokens are moved from one account (`from`) to
@@ -74,7 +78,7 @@ sol! {
 /// An [`Erc20`] error defined as described in [ERC-6093].
 ///
 /// [ERC-6093]: https://eips.ethereum.org/EIPS/eip-6093
-#[derive(SolidityError, Debug)]
+#[derive(SolidityError, Debug, ErrorDisplay)]
 pub enum Error {
     /// Indicates an error related to the current balance of `sender`. Used in
     /// transfers.
@@ -101,11 +105,17 @@ sol_storage! {
         mapping(address => mapping(address => uint256)) _allowances;
         /// The total supply of the token.
         uint256 _total_supply;
+        /// Reserved storage slots, see [`StorageGap`].
+        StorageGap<47> __gap;
     }
 }
 
 /// Required interface of an [`Erc20`] compliant contract.
 pub trait IErc20 {
+    /// ERC-165 interface id of [`IErc20`], the XOR of the selectors of
+    /// its functions.
+    const INTERFACE_ID: FixedBytes<4> = fixed_bytes!("36372b07");
+
     /// Returns the number of tokens in existence.
     ///
     /// # Arguments
@@ -387,7 +397,10 @@ impl Erc20 {
                 .expect("should not exceed `U256::MAX` for `_total_supply`");
             self._total_supply.set(total_supply);
         } else {
-            let from_balance = self._balances.get(from);
+            // A single handle per balance, so that its slot is only derived
+            // once.
+            let mut balance_from = self._balances.setter(from);
+            let from_balance = balance_from.get();
             if from_balance < value {
                 return Err(Error::InsufficientBalance(
                     ERC20InsufficientBalance {
@@ -399,7 +412,7 @@ impl Erc20 {
             }
             // Overflow not possible:
             // `value` <= `from_balance` <= `_total_supply`.
-            self._balances.setter(from).set(from_balance - value);
+            balance_from.set(from_balance - value);
         }
 
         if to.is_zero() {
@@ -409,11 +422,12 @@ impl Erc20 {
             // `value` <= `from_balance` <= `_total_supply`.
             self._total_supply.set(total_supply - value);
         } else {
-            let balance_to = self._balances.get(to);
+            let mut balance_to = self._balances.setter(to);
+            let to_balance = balance_to.get();
             // Overflow not possible:
-            // `balance_to` + `value` is at most `total_supply`,
+            // `to_balance` + `value` is at most `total_supply`,
             // which fits into a `U256`.
-            self._balances.setter(to).set(balance_to + value);
+            balance_to.set(to_balance + value);
         }
 
         evm::log(Transfer { from, to, value });
@@ -475,7 +489,9 @@ impl Erc20 {
         spender: Address,
         value: U256,
     ) -> Result<(), Error> {
-        let current_allowance = self._allowances.get(owner).get(spender);
+        let mut allowances = self._allowances.setter(owner);
+        let mut allowance = allowances.setter(spender);
+        let current_allowance = allowance.get();
         if current_allowance != U256::MAX {
             if current_allowance < value {
                 return Err(Error::InsufficientAllowance(
@@ -487,9 +503,7 @@ impl Erc20 {
                 ));
             }
 
-            self._allowances
-                .setter(owner)
-                .insert(spender, current_allowance - value);
+            allowance.set(current_allowance - value);
         }
 
         Ok(())
@@ -499,9 +513,14 @@ impl Erc20 {
 #[cfg(all(test, feature = "std"))]
 mod tests {
     use alloy_primitives::{address, uint, Address, U256};
+    use motsu::prelude::{ExpectRevert, VM};
     use stylus_sdk::msg;
 
-    use super::{Erc20, Error, IErc20};
+    use super::{
+        Approval, ERC20InsufficientAllowance, ERC20InsufficientBalance, Erc20,
+        Error, IErc20, Transfer,
+    };
+    use crate::token::tests::interface_id_of;
 
     #[motsu::test]
     fn reads_balance(contract: Erc20) {
@@ -515,6 +534,18 @@ mod tests {
         assert_eq!(one, balance);
     }
 
+    #[motsu::test]
+    fn reads_dealt_balance(contract: Erc20) {
+        let alice = address!("A11CEacF9aa32246d767FCCD72e02d6bCbcC375d");
+        let one = uint!(1_U256);
+
+        // `_balances` is the first field of `Erc20`.
+        VM::context().deal(U256::ZERO, alice, one);
+
+        assert_eq!(one, contract.balance_of(alice));
+        assert_eq!(U256::ZERO, contract.total_supply());
+    }
+
     #[motsu::test]
     fn update_mint(contract: Erc20) {
         let alice = address!("A11CEacF9aa32246d767FCCD72e02d6bCbcC375d");
@@ -533,6 +564,19 @@ mod tests {
         assert_eq!(initial_supply + one, contract.total_supply());
     }
 
+    #[motsu::test]
+    fn update_mint_adds_to_balance_and_supply(
+        contract: Erc20,
+        to: Address,
+        amount: U256,
+    ) {
+        let result = contract._update(Address::ZERO, to, amount);
+        assert!(result.is_ok());
+
+        assert_eq!(amount, contract.balance_of(to));
+        assert_eq!(amount, contract.total_supply());
+    }
+
     #[motsu::test]
     #[should_panic = "should not exceed `U256::MAX` for `_total_supply`"]
     fn update_mint_errors_arithmetic_overflow(contract: Erc20) {
@@ -752,6 +796,75 @@ mod tests {
         assert_eq!(U256::ZERO, contract.allowance(alice, sender));
     }
 
+    #[motsu::test]
+    fn transfers_from_emits_events(contract: Erc20) {
+        let alice = address!("A11CEacF9aa32246d767FCCD72e02d6bCbcC375d");
+        let bob = address!("B0B0cB49ec2e96DF5F5fFB081acaE66A2cBBc2e2");
+        let sender = msg::sender();
+        let one = uint!(1_U256);
+
+        contract._allowances.setter(alice).setter(sender).set(one);
+        contract._update(Address::ZERO, alice, one).unwrap();
+        assert_eq!(1, VM::context().emitted_events::<Transfer>().len());
+
+        let (result, logs) = VM::context()
+            .capture_logs(|| contract.transfer_from(alice, bob, one));
+        assert!(result.is_ok());
+
+        // Spending the allowance doesn't emit an `Approval` event.
+        assert_eq!(1, logs.len());
+        assert!(logs[0].decode::<Approval>().is_none());
+        let transfer = logs[0].decode::<Transfer>().unwrap();
+        assert_eq!(alice, transfer.from);
+        assert_eq!(bob, transfer.to);
+        assert_eq!(one, transfer.value);
+    }
+
+    #[motsu::test]
+    fn transfer_from_accesses_each_slot_once(contract: Erc20) {
+        let alice = address!("A11CEacF9aa32246d767FCCD72e02d6bCbcC375d");
+        let bob = address!("B0B0cB49ec2e96DF5F5fFB081acaE66A2cBBc2e2");
+        let sender = msg::sender();
+        let one = uint!(1_U256);
+
+        contract._allowances.setter(alice).setter(sender).set(one);
+        contract._update(Address::ZERO, alice, one).unwrap();
+
+        let (result, counts) = VM::context().measure_hostios(|| {
+            VM::context().meter("transfer_from", || {
+                contract.transfer_from(alice, bob, one)
+            })
+        });
+        assert!(result.is_ok());
+
+        // The allowance and both balances.
+        assert_eq!(counts.storage_loads, 3);
+        assert_eq!(counts.storage_stores, 3);
+        assert_eq!(U256::ZERO, contract.allowance(alice, sender));
+    }
+
+    #[motsu::test]
+    fn transfer_from_keeps_infinite_allowance(contract: Erc20) {
+        let alice = address!("A11CEacF9aa32246d767FCCD72e02d6bCbcC375d");
+        let bob = address!("B0B0cB49ec2e96DF5F5fFB081acaE66A2cBBc2e2");
+        let sender = msg::sender();
+        let one = uint!(1_U256);
+
+        contract._allowances.setter(alice).setter(sender).set(U256::MAX);
+        contract._update(Address::ZERO, alice, one).unwrap();
+
+        let (result, counts) = VM::context().measure_hostios(|| {
+            VM::context().meter("transfer_from_infinite_allowance", || {
+                contract.transfer_from(alice, bob, one)
+            })
+        });
+        assert!(result.is_ok());
+
+        // Only the balances are written.
+        assert_eq!(counts.storage_stores, 2);
+        assert_eq!(U256::MAX, contract.allowance(alice, sender));
+    }
+
     #[motsu::test]
     fn transfer_from_errors_when_insufficient_balance(contract: Erc20) {
         let alice = address!("A11CEacF9aa32246d767FCCD72e02d6bCbcC375d");
@@ -763,8 +876,12 @@ mod tests {
         assert_eq!(U256::ZERO, contract.balance_of(alice));
 
         let one = uint!(1_U256);
-        let result = contract.transfer_from(alice, bob, one);
-        assert!(matches!(result, Err(Error::InsufficientBalance(_))));
+        let err = contract
+            .transfer_from(alice, bob, one)
+            .motsu_expect_revert::<ERC20InsufficientBalance>();
+        assert_eq!(err.sender, alice);
+        assert_eq!(err.balance, U256::ZERO);
+        assert_eq!(err.needed, one);
     }
 
     #[motsu::test]
@@ -799,8 +916,13 @@ mod tests {
         contract._update(Address::ZERO, alice, one).unwrap();
         assert_eq!(one, contract.balance_of(alice));
 
-        let result = contract.transfer_from(alice, bob, one);
-        assert!(matches!(result, Err(Error::InsufficientAllowance(_))));
+        contract.transfer_from(alice, bob, one).motsu_expect_revert_with(
+            |err: &ERC20InsufficientAllowance| {
+                err.spender == msg::sender()
+                    && err.allowance == U256::ZERO
+                    && err.needed == one
+            },
+        );
     }
 
     #[motsu::test]
@@ -834,4 +956,17 @@ mod tests {
         let result = contract.approve(Address::ZERO, one);
         assert!(matches!(result, Err(Error::InvalidSpender(_))));
     }
+
+    #[test]
+    fn interface_id() {
+        let id = interface_id_of(&[
+            "totalSupply()",
+            "balanceOf(address)",
+            "transfer(address,uint256)",
+            "allowance(address,address)",
+            "approve(address,uint256)",
+            "transferFrom(address,address,uint256)",
+        ]);
+        assert_eq!(<Erc20 as IErc20>::INTERFACE_ID, id);
+    }
 }
@@ -0,0 +1,2 @@
+//! Utilities to interact with ERC-20 token contracts.
+pub mod permit;
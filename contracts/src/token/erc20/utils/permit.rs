@@ -0,0 +1,196 @@
+//! A griefing-resistant way of using [EIP-2612] permits of third-party
+//! tokens.
+//!
+//! Anyone can submit a permit signature seen in the mempool before the
+//! transaction using it, which then reverts since the nonce of the permit is
+//! already used. Routers calling `permit` then spending the allowance in the
+//! same transaction can thus be griefed. Tokens without `permit`, or with a
+//! fallback function accepting any call, are other sources of surprises.
+//!
+//! [`try_permit`] ignores the outcome of the `permit` call, and checks the
+//! allowance instead: the transaction goes on as long as the spender may
+//! spend the requested value, whoever set the allowance.
+//!
+//! ```rust,ignore
+//! permit::try_permit(self, token, &permit)?;
+//! // Spend the allowance with `transferFrom`.
+//! ```
+//!
+//! [EIP-2612]: https://eips.ethereum.org/EIPS/eip-2612
+// `sol!` doesn't document the items it generates.
+#![allow(missing_docs)]
+use alloy_primitives::{Address, B256, U256};
+use alloy_sol_types::{sol, SolCall};
+use openzeppelin_stylus_proc::ErrorDisplay;
+use stylus_proc::SolidityError;
+use stylus_sdk::{
+    call::{self, Call},
+    prelude::*,
+};
+
+sol! {
+    /// After calling `permit` on `token`, `spender` still can't spend `value`
+    /// tokens of `owner`.
+    #[derive(Debug)]
+    #[allow(missing_docs)]
+    error Erc20FailedPermit(address token, address owner, address spender, uint256 value);
+}
+
+sol! {
+    /// The ERC-20 functions [`try_permit`] calls.
+    interface IERC20Permit {
+        function permit(address owner, address spender, uint256 value, uint256 deadline, uint8 v, bytes32 r, bytes32 s) external;
+        function allowance(address owner, address spender) external view returns (uint256);
+    }
+}
+
+/// An error that occurred while using a permit.
+#[derive(SolidityError, Debug, ErrorDisplay)]
+pub enum Error {
+    /// The spender can't spend the permitted value.
+    FailedPermit(Erc20FailedPermit),
+}
+
+/// A signed [EIP-2612] permit.
+///
+/// [EIP-2612]: https://eips.ethereum.org/EIPS/eip-2612
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Permit {
+    /// Account whose tokens can be spent.
+    pub owner: Address,
+    /// Account allowed to spend the tokens.
+    pub spender: Address,
+    /// Number of tokens the spender may spend.
+    pub value: U256,
+    /// Timestamp after which the signature is invalid.
+    pub deadline: U256,
+    /// Recovery id of the signature.
+    pub v: u8,
+    /// `r` value of the signature.
+    pub r: B256,
+    /// `s` value of the signature.
+    pub s: B256,
+}
+
+/// Calls `permit` on `token`, ignoring whether it succeeds, then checks that
+/// `permit.spender` may spend `permit.value` tokens of `permit.owner`.
+///
+/// # Arguments
+///
+/// * `storage` - The calling contract.
+/// * `token` - Address of the token.
+/// * `permit` - The signed permit.
+///
+/// # Errors
+///
+/// If the allowance of the spender is lower than the permitted value after
+/// the call, or can't be read, then the error [`Error::FailedPermit`] is
+/// returned.
+pub fn try_permit(
+    storage: &mut impl TopLevelStorage,
+    token: Address,
+    permit: &Permit,
+) -> Result<(), Error> {
+    let Permit { owner, spender, value, deadline, v, r, s } = *permit;
+
+    let calldata = IERC20Permit::permitCall {
+        owner,
+        spender,
+        value,
+        deadline,
+        v,
+        r: r.0,
+        s: s.0,
+    }
+    .encode();
+    // A front-run permit reverts, but leaves the expected allowance.
+    let _ = call::call(Call::new_in(storage), token, &calldata);
+
+    let calldata = IERC20Permit::allowanceCall { owner, spender }.encode();
+    let allowance = call::static_call(Call::new_in(storage), token, &calldata)
+        .ok()
+        .and_then(|data| {
+            IERC20Permit::allowanceCall::decode_returns(&data, true).ok()
+        })
+        .map_or(U256::ZERO, |returns| returns._0);
+
+    if allowance < value {
+        return Err(Error::FailedPermit(Erc20FailedPermit {
+            token,
+            owner,
+            spender,
+            value,
+        }));
+    }
+    Ok(())
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use alloy_primitives::{address, uint, Address, B256, U256};
+    use alloy_sol_types::{sol_data, SolCall, SolType};
+    use motsu::prelude::{Caller, VM};
+
+    use super::{try_permit, Error, IERC20Permit, Permit};
+
+    const TOKEN: Address = address!("F4EaCDAbEf3c8f1EdE91b6f2A6840bc2E4DD3526");
+    const ALICE: Address = address!("A11CEacF9aa32246d767FCCD72e02d6bCbcC375d");
+    const ROUTER: Address =
+        address!("B0B0cB49ec2e96DF5F5fFB081acaE66A2cBBc2e2");
+    const VALUE: U256 = uint!(100_U256);
+
+    fn permit() -> Permit {
+        Permit {
+            owner: ALICE,
+            spender: ROUTER,
+            value: VALUE,
+            deadline: U256::MAX,
+            v: 27,
+            r: B256::repeat_byte(1),
+            s: B256::repeat_byte(2),
+        }
+    }
+
+    fn mock_allowance(allowance: U256) {
+        VM::context().mock_call(
+            TOKEN,
+            &IERC20Permit::allowanceCall::SELECTOR,
+            <sol_data::Uint<256> as SolType>::encode_single(&allowance),
+        );
+    }
+
+    #[motsu::test]
+    fn accepts_permits() {
+        mock_allowance(VALUE);
+        assert!(try_permit(&mut Caller, TOKEN, &permit()).is_ok());
+    }
+
+    #[motsu::test]
+    fn accepts_front_run_permits() {
+        VM::context().mock_revert(
+            TOKEN,
+            &IERC20Permit::permitCall::SELECTOR,
+            vec![],
+        );
+        mock_allowance(VALUE + uint!(1_U256));
+        assert!(try_permit(&mut Caller, TOKEN, &permit()).is_ok());
+    }
+
+    #[motsu::test]
+    fn rejects_permits_without_allowance() {
+        mock_allowance(VALUE - uint!(1_U256));
+        let err = try_permit(&mut Caller, TOKEN, &permit()).unwrap_err();
+        assert!(matches!(
+            err,
+            Error::FailedPermit(error)
+                if error.token == TOKEN && error.value == VALUE
+        ));
+    }
+
+    #[motsu::test]
+    fn rejects_unreadable_allowances() {
+        // Calls that aren't mocked return no data.
+        let err = try_permit(&mut Caller, TOKEN, &permit()).unwrap_err();
+        assert!(matches!(err, Error::FailedPermit(_)));
+    }
+}
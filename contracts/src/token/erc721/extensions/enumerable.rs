@@ -7,11 +7,12 @@
 //! CAUTION: [`Erc721`] extensions that implement custom
 //! [`Erc721::balance_of`] logic, such as [`Erc721Consecutive`], interfere with
 //! enumerability and should not be used together with [`Erc721Enumerable`].
-use alloy_primitives::{uint, Address, U256};
+use alloy_primitives::{fixed_bytes, uint, Address, FixedBytes, U256};
 use alloy_sol_types::sol;
+use openzeppelin_stylus_proc::ErrorDisplay;
 use stylus_proc::{external, sol_storage, SolidityError};
 
-use crate::token::erc721::IErc721;
+use crate::{token::erc721::IErc721, utils::structs::gap::StorageGap};
 
 sol! {
     /// Indicates an error when an `owner`'s token query
@@ -30,7 +31,7 @@ sol! {
 }
 
 /// An [`Erc721Enumerable`] extension error.
-#[derive(SolidityError, Debug)]
+#[derive(SolidityError, Debug, ErrorDisplay)]
 pub enum Error {
     /// Indicates an error when an `owner`'s token query
     /// was out of bounds for `index`.
@@ -55,6 +56,8 @@ sol_storage! {
         uint256[] _all_tokens;
         /// Maps indices at `_all_tokens` to tokens ids.
         mapping(uint256 => uint256) _all_tokens_index;
+        /// Reserved storage slots, see [`StorageGap`].
+        StorageGap<46> __gap;
     }
 
 }
@@ -64,6 +67,10 @@ sol_storage! {
 pub trait IErc721Enumerable {
     // TODO: fn supports_interface (#33)
 
+    /// ERC-165 interface id of [`IErc721Enumerable`], the XOR of the selectors
+    /// of its functions.
+    const INTERFACE_ID: FixedBytes<4> = fixed_bytes!("780e9d63");
+
     /// Returns a token ID owned by `owner`
     /// at a given `index` of its token list.
     ///
@@ -139,6 +146,9 @@ impl Erc721Enumerable {
     /// Function to add a token to this extension's
     /// ownership-tracking data structures.
     ///
+    /// The balance of `to` is read through `erc721`, so it must already
+    /// account for `token_id`.
+    ///
     /// # Arguments
     ///
     /// * `&mut self` - Write access to the contract's state.
@@ -186,6 +196,9 @@ impl Erc721Enumerable {
     /// this allows for  gas optimizations e.g.
     /// when performing a transfer operation (avoiding double writes).
     ///
+    /// The balance of `from` is read through `erc721`, so it must no longer
+    /// account for `token_id`.
+    ///
     /// This has O(1) time complexity, but alters the order
     /// of the `self._owned_tokens` array.
 
@@ -309,10 +322,14 @@ impl Erc721Enumerable {
 #[cfg(all(test, feature = "std"))]
 mod tests {
     use alloy_primitives::{address, uint, Address, U256};
+    use motsu::prelude::VM;
     use stylus_sdk::msg;
 
     use super::{Erc721Enumerable, Error, IErc721Enumerable};
-    use crate::token::erc721::{tests::random_token_id, Erc721, IErc721};
+    use crate::token::{
+        erc721::{tests::random_token_id, Erc721, IErc721},
+        tests::interface_id_of,
+    };
 
     const BOB: Address = address!("F4EaCDAbEf3c8f1EdE91b6f2A6840bc2E4DD3526");
 
@@ -537,4 +554,88 @@ mod tests {
             contract.token_of_owner_by_index(alice, U256::ZERO).unwrap_err();
         assert!(matches!(err, Error::OutOfBoundsIndex(_)));
     }
+
+    #[motsu::test]
+    fn transfer_with_enumeration_skips_owner_read(contract: Erc721Enumerable) {
+        let alice = msg::sender();
+        let mut erc721 = Erc721::default();
+
+        // Transfers a token newly minted for ALICE to BOB and updates the
+        // enumeration, reading the owner first if `read_owner` is true.
+        let mut transfer = |read_owner: bool| {
+            let token_id = random_token_id();
+            erc721._mint(alice, token_id).expect("should mint a token");
+            contract
+                ._add_token_to_owner_enumeration(alice, token_id, &erc721)
+                .expect("should add the token to ALICE's enumeration");
+
+            let ((), counts) = VM::context().measure_hostios(|| {
+                let previous_owner = if read_owner {
+                    erc721.owner_of(token_id).expect("should read the owner")
+                } else {
+                    alice
+                };
+                erc721
+                    .transfer_from(alice, BOB, token_id)
+                    .expect("should transfer the token from ALICE to BOB");
+                contract
+                    ._remove_token_from_owner_enumeration(
+                        previous_owner,
+                        token_id,
+                        &erc721,
+                    )
+                    .expect("should remove the token from ALICE's enumeration");
+                contract
+                    ._add_token_to_owner_enumeration(BOB, token_id, &erc721)
+                    .expect("should add the token to BOB's enumeration");
+            });
+            counts
+        };
+
+        let with_owner_read = transfer(true);
+        let without_owner_read = transfer(false);
+        assert_eq!(
+            with_owner_read.storage_loads - without_owner_read.storage_loads,
+            1
+        );
+    }
+
+    #[motsu::test]
+    fn owner_enumeration_reads_balances_through_erc721(
+        contract: Erc721Enumerable,
+    ) {
+        let alice = msg::sender();
+        let mut erc721 = Erc721::default();
+        let token_id = random_token_id();
+        erc721._mint(alice, token_id).expect("should mint a token for ALICE");
+        contract
+            ._add_token_to_owner_enumeration(alice, token_id, &erc721)
+            .expect("should add the token to ALICE's enumeration");
+        erc721
+            .transfer_from(alice, BOB, token_id)
+            .expect("should transfer the token from ALICE to BOB");
+
+        // Like in Solidity, the extension doesn't store balances, so both
+        // updates read one through `erc721`. Removing the token also reads its
+        // index, which is the last one, so no token is swapped.
+        let ((), counts) = VM::context().measure_hostios(|| {
+            contract
+                ._remove_token_from_owner_enumeration(alice, token_id, &erc721)
+                .expect("should remove the token from ALICE's enumeration");
+            contract
+                ._add_token_to_owner_enumeration(BOB, token_id, &erc721)
+                .expect("should add the token to BOB's enumeration");
+        });
+        assert_eq!(counts.storage_loads, 3);
+    }
+
+    #[test]
+    fn interface_id() {
+        let id = interface_id_of(&[
+            "totalSupply()",
+            "tokenOfOwnerByIndex(address,uint256)",
+            "tokenByIndex(uint256)",
+        ]);
+        assert_eq!(<Erc721Enumerable as IErc721Enumerable>::INTERFACE_ID, id);
+    }
 }
@@ -2,9 +2,10 @@
 
 use alloc::string::String;
 
+use alloy_primitives::{fixed_bytes, FixedBytes};
 use stylus_proc::{external, sol_storage};
 
-use crate::utils::Metadata;
+use crate::utils::{structs::gap::StorageGap, Metadata};
 
 sol_storage! {
     /// Metadata of the [`Erc721`] token.
@@ -13,11 +14,21 @@ sol_storage! {
         Metadata _metadata;
         /// Base URI for tokens
         string _base_uri;
+        /// Reserved storage slots, see [`StorageGap`].
+        StorageGap<49> __gap;
     }
 }
 
 /// Interface for the optional metadata functions from the ERC-721 standard.
 pub trait IErc721Metadata {
+    /// ERC-165 interface id of the ERC-721 Metadata extension, the XOR of the
+    /// selectors of `name`, `symbol` and `tokenURI`.
+    ///
+    /// NOTE: [`IErc721Metadata`] exposes `base_uri` instead of `tokenURI`, so
+    /// contracts should only report this interface as supported if they also
+    /// expose `tokenURI`, e.g., through [`super::Erc721UriStorage`].
+    const INTERFACE_ID: FixedBytes<4> = fixed_bytes!("5b5e139f");
+
     /// Returns the token collection name.
     ///
     /// # Arguments
@@ -58,3 +69,15 @@ impl IErc721Metadata for Erc721Metadata {
         self._base_uri.get_string()
     }
 }
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::{Erc721Metadata, IErc721Metadata};
+    use crate::token::tests::interface_id_of;
+
+    #[test]
+    fn interface_id_is_erc721_metadata() {
+        let id = interface_id_of(&["name()", "symbol()", "tokenURI(uint256)"]);
+        assert_eq!(<Erc721Metadata as IErc721Metadata>::INTERFACE_ID, id);
+    }
+}
@@ -1,6 +1,9 @@
 //! ERC-721 token with storage-based token URI management.
 //!
 //! It also implements IERC4096, which is an ERC-721 Metadata Update Extension.
+//!
+//! Token URIs can also hold the metadata itself, built on-chain with
+//! [`JsonMetadata`], see [`Erc721UriStorage::set_token_metadata`].
 use alloc::string::String;
 
 use alloy_primitives::U256;
@@ -8,6 +11,8 @@ use alloy_sol_types::sol;
 use stylus_proc::{external, sol_storage};
 use stylus_sdk::evm;
 
+use crate::utils::{metadata::json::JsonMetadata, structs::gap::StorageGap};
+
 sol! {
     /// This event gets emitted when the metadata of a token is changed.
     ///
@@ -29,6 +34,8 @@ sol_storage! {
     pub struct Erc721UriStorage {
         /// Optional mapping for token URIs.
         mapping(uint256 => string) _token_uris;
+        /// Reserved storage slots, see [`StorageGap`].
+        StorageGap<49> __gap;
     }
 }
 
@@ -47,6 +54,28 @@ impl Erc721UriStorage {
         self._token_uris.setter(token_id).set_str(token_uri);
         evm::log(MetadataUpdate { token_id });
     }
+
+    /// Sets the tokenURI of `token_id` to `metadata`, embedded in a
+    /// `data:application/json;base64,...` URI.
+    ///
+    /// NOTE: The URI is complete, so contracts that prefix token URIs with a
+    /// base URI must return `data:` URIs as is.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the contract's state.
+    /// * `token_id` - Id of a token.
+    /// * `metadata` - Metadata of the token.
+    ///
+    /// # Events
+    /// Emits a [`MetadataUpdate`] event.
+    pub fn set_token_metadata(
+        &mut self,
+        token_id: U256,
+        metadata: &JsonMetadata,
+    ) {
+        self.set_token_uri(token_id, metadata.to_data_uri());
+    }
 }
 
 #[external]
@@ -68,6 +97,7 @@ mod tests {
     use alloy_primitives::U256;
 
     use super::Erc721UriStorage;
+    use crate::utils::metadata::json::JsonMetadata;
 
     fn random_token_id() -> U256 {
         let num: u32 = rand::random();
@@ -96,4 +126,17 @@ mod tests {
 
         assert_eq!(token_uri, contract.token_uri(token_id));
     }
+
+    #[motsu::test]
+    fn set_token_metadata_works(contract: Erc721UriStorage) {
+        let token_id = random_token_id();
+
+        let metadata = JsonMetadata::new().name("A");
+        contract.set_token_metadata(token_id, &metadata);
+
+        assert_eq!(
+            "data:application/json;base64,eyJuYW1lIjoiQSJ9",
+            contract.token_uri(token_id)
+        );
+    }
 }
@@ -2,13 +2,18 @@
 use alloc::vec;
 
 use alloy_primitives::{fixed_bytes, uint, Address, FixedBytes, U128, U256};
+use openzeppelin_stylus_proc::ErrorDisplay;
 use stylus_sdk::{
     abi::Bytes, alloy_sol_types::sol, call::Call, evm, msg, prelude::*,
 };
 
-use crate::utils::math::storage::{AddAssignUnchecked, SubAssignUnchecked};
+use crate::utils::{
+    math::storage::{AddAssignUnchecked, SubAssignUnchecked},
+    structs::gap::StorageGap,
+};
 
 pub mod extensions;
+pub mod utils;
 
 sol! {
     /// Emitted when the `token_id` token is transferred from `from` to `to`.
@@ -118,7 +123,7 @@ sol! {
 /// An [`Erc721`] error defined as described in [ERC-6093].
 ///
 /// [ERC-6093]: https://eips.ethereum.org/EIPS/eip-6093
-#[derive(SolidityError, Debug)]
+#[derive(SolidityError, Debug, ErrorDisplay)]
 pub enum Error {
     /// Indicates that an address can't be an owner.
     /// For example, `Address::ZERO` is a forbidden owner in [`Erc721`].
@@ -177,6 +182,8 @@ sol_storage! {
         mapping(uint256 => address) _token_approvals;
         /// Maps owners to a mapping of operator approvals.
         mapping(address => mapping(address => bool)) _operator_approvals;
+        /// Reserved storage slots, see [`StorageGap`].
+        StorageGap<46> __gap;
     }
 }
 
@@ -187,6 +194,10 @@ unsafe impl TopLevelStorage for Erc721 {}
 
 /// Required interface of an [`Erc721`] compliant contract.
 pub trait IErc721 {
+    /// ERC-165 interface id of [`IErc721`], the XOR of the selectors of
+    /// its functions.
+    const INTERFACE_ID: FixedBytes<4> = fixed_bytes!("80ac58cd");
+
     /// Returns the number of tokens in `owner`'s account.
     ///
     /// # Arguments
@@ -1119,6 +1130,7 @@ mod tests {
         ERC721InvalidReceiver, ERC721InvalidSender, ERC721NonexistentToken,
         Erc721, Error, IErc721,
     };
+    use crate::token::tests::interface_id_of;
 
     const BOB: Address = address!("F4EaCDAbEf3c8f1EdE91b6f2A6840bc2E4DD3526");
     const DAVE: Address = address!("0BB78F7e7132d1651B4Fd884B7624394e92156F1");
@@ -2441,6 +2453,22 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn interface_id() {
+        let id = interface_id_of(&[
+            "balanceOf(address)",
+            "ownerOf(uint256)",
+            "safeTransferFrom(address,address,uint256)",
+            "safeTransferFrom(address,address,uint256,bytes)",
+            "transferFrom(address,address,uint256)",
+            "approve(address,uint256)",
+            "setApprovalForAll(address,bool)",
+            "getApproved(uint256)",
+            "isApprovedForAll(address,address)",
+        ]);
+        assert_eq!(<Erc721 as IErc721>::INTERFACE_ID, id);
+    }
+
     // TODO: think about [`Erc721::_update`] tests.
 
     // TODO: think about [`Erc721::_increase_balance`] tests
@@ -0,0 +1,3 @@
+//! Utilities to interact with ERC-721 token contracts.
+pub mod safe_erc721;
+pub use safe_erc721::SafeErc721;
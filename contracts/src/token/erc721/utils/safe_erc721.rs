@@ -0,0 +1,305 @@
+//! Wrappers around transfers of third-party ERC-721 tokens.
+//!
+//! Contracts holding arbitrary NFTs, e.g., marketplaces or escrows, can't
+//! trust the contracts they call to behave: a transfer may succeed without
+//! moving the token, or the "token" may have no code at all, in which case the
+//! call succeeds without doing anything. [`SafeErc721`] checks that the token
+//! changed hands after each transfer, and bubbles up the revert data of
+//! failed ones:
+//!
+//! ```rust,ignore
+//! SafeErc721::new(collection).safe_transfer_from(
+//!     self,
+//!     seller,
+//!     msg::sender(),
+//!     token_id,
+//!     &[],
+//! )?;
+//! ```
+//!
+//! `try_` variants return whether the transfer succeeded instead, e.g., to
+//! skip tokens that can't be moved in a batch.
+// `sol!` doesn't document the items it generates.
+#![allow(missing_docs)]
+use alloc::vec::Vec;
+
+use alloy_primitives::{Address, U256};
+use alloy_sol_types::{sol, SolCall, SolError};
+use openzeppelin_stylus_proc::ErrorDisplay;
+use stylus_sdk::{
+    call::{self, Call},
+    prelude::*,
+};
+
+sol! {
+    /// An operation with the ERC-721 token `token` failed.
+    #[derive(Debug)]
+    #[allow(missing_docs)]
+    error SafeErc721FailedOperation(address token);
+}
+
+sol! {
+    /// The ERC-721 functions [`SafeErc721`] calls.
+    interface IERC721 {
+        function ownerOf(uint256 token_id) external view returns (address);
+        function transferFrom(address from, address to, uint256 token_id) external;
+        function safeTransferFrom(address from, address to, uint256 token_id, bytes data) external;
+    }
+}
+
+/// An error that occurred while interacting with an ERC-721 token.
+///
+/// Unlike other errors of this crate, it can't derive `SolidityError`, since
+/// the revert data of the token is bubbled up as is.
+#[derive(Debug, ErrorDisplay)]
+pub enum Error {
+    /// The token has no code, or the transfer didn't move the token.
+    FailedOperation(SafeErc721FailedOperation),
+    /// The token reverted with this data.
+    Revert(Vec<u8>),
+}
+
+impl From<Error> for Vec<u8> {
+    fn from(error: Error) -> Vec<u8> {
+        match error {
+            Error::FailedOperation(error) => error.encode(),
+            Error::Revert(data) => data,
+        }
+    }
+}
+
+/// An ERC-721 token contract.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SafeErc721 {
+    /// Address of the contract.
+    pub address: Address,
+}
+
+impl SafeErc721 {
+    /// Creates a handle to the ERC-721 contract at `address`.
+    #[must_use]
+    pub fn new(address: Address) -> Self {
+        SafeErc721 { address }
+    }
+
+    /// Transfers `token_id` from `from` to `to` with `transferFrom`, and
+    /// checks that `to` owns it afterwards.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the token configuration.
+    /// * `storage` - The calling contract.
+    /// * `from` - Current owner of the token.
+    /// * `to` - Recipient of the token.
+    /// * `token_id` - Id of the token.
+    ///
+    /// # Errors
+    ///
+    /// If the token contract reverts, then its revert data is returned as
+    /// [`Error::Revert`].
+    /// If the token contract has no code, or `to` doesn't own `token_id` after
+    /// the transfer, then the error [`Error::FailedOperation`] is returned.
+    pub fn transfer_from(
+        &self,
+        storage: &mut impl TopLevelStorage,
+        from: Address,
+        to: Address,
+        token_id: U256,
+    ) -> Result<(), Error> {
+        let calldata =
+            IERC721::transferFromCall { from, to, token_id }.encode();
+        self.transfer(storage, &calldata, to, token_id)
+    }
+
+    /// Transfers `token_id` from `from` to `to` with `safeTransferFrom`,
+    /// passing `data` to the receiver, and checks that `to` owns it
+    /// afterwards.
+    ///
+    /// The token contract checks that `to` accepts the token if it's a
+    /// contract.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the token configuration.
+    /// * `storage` - The calling contract.
+    /// * `from` - Current owner of the token.
+    /// * `to` - Recipient of the token.
+    /// * `token_id` - Id of the token.
+    /// * `data` - Data passed to `onERC721Received`.
+    ///
+    /// # Errors
+    ///
+    /// If the token contract reverts, e.g., because `to` rejected the token,
+    /// then its revert data is returned as [`Error::Revert`].
+    /// If the token contract has no code, or `to` doesn't own `token_id` after
+    /// the transfer, then the error [`Error::FailedOperation`] is returned.
+    pub fn safe_transfer_from(
+        &self,
+        storage: &mut impl TopLevelStorage,
+        from: Address,
+        to: Address,
+        token_id: U256,
+        data: &[u8],
+    ) -> Result<(), Error> {
+        let calldata = IERC721::safeTransferFromCall {
+            from,
+            to,
+            token_id,
+            data: data.to_vec(),
+        }
+        .encode();
+        self.transfer(storage, &calldata, to, token_id)
+    }
+
+    /// Same as [`SafeErc721::transfer_from`], but returns whether the
+    /// transfer succeeded instead of an error.
+    pub fn try_transfer_from(
+        &self,
+        storage: &mut impl TopLevelStorage,
+        from: Address,
+        to: Address,
+        token_id: U256,
+    ) -> bool {
+        self.transfer_from(storage, from, to, token_id).is_ok()
+    }
+
+    /// Same as [`SafeErc721::safe_transfer_from`], but returns whether the
+    /// transfer succeeded instead of an error.
+    pub fn try_safe_transfer_from(
+        &self,
+        storage: &mut impl TopLevelStorage,
+        from: Address,
+        to: Address,
+        token_id: U256,
+        data: &[u8],
+    ) -> bool {
+        self.safe_transfer_from(storage, from, to, token_id, data).is_ok()
+    }
+
+    /// Calls the token contract with `calldata`, and checks that `to` owns
+    /// `token_id` afterwards.
+    fn transfer(
+        &self,
+        storage: &mut impl TopLevelStorage,
+        calldata: &[u8],
+        to: Address,
+        token_id: U256,
+    ) -> Result<(), Error> {
+        let failed = || {
+            Error::FailedOperation(SafeErc721FailedOperation {
+                token: self.address,
+            })
+        };
+
+        if !self.address.has_code() {
+            return Err(failed());
+        }
+
+        call::call(Call::new_in(storage), self.address, calldata).map_err(
+            |error| match error {
+                call::Error::Revert(data) => Error::Revert(data),
+                call::Error::AbiDecodingFailed(_) => failed(),
+            },
+        )?;
+
+        let calldata = IERC721::ownerOfCall { token_id }.encode();
+        let owner =
+            call::static_call(Call::new_in(storage), self.address, &calldata)
+                .ok()
+                .and_then(|data| {
+                    IERC721::ownerOfCall::decode_returns(&data, true).ok()
+                })
+                .map(|returns| returns._0);
+        if owner == Some(to) {
+            Ok(())
+        } else {
+            Err(failed())
+        }
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use alloy_primitives::{address, uint, Address, U256};
+    use alloy_sol_types::{sol_data, SolCall, SolType};
+    use motsu::prelude::{Caller, VM};
+
+    use super::{Error, SafeErc721, IERC721};
+
+    const TOKEN: Address = address!("F4EaCDAbEf3c8f1EdE91b6f2A6840bc2E4DD3526");
+    const ALICE: Address = address!("A11CEacF9aa32246d767FCCD72e02d6bCbcC375d");
+    const BOB: Address = address!("B0B0cB49ec2e96DF5F5fFB081acaE66A2cBBc2e2");
+    const TOKEN_ID: U256 = uint!(1_U256);
+
+    fn mock_owner(owner: Address) {
+        VM::context().mock_call(
+            TOKEN,
+            &IERC721::ownerOfCall::SELECTOR,
+            <sol_data::Address as SolType>::encode_single(&owner),
+        );
+    }
+
+    fn set_up() {
+        VM::context().set_account_code(TOKEN, vec![0xfe]);
+    }
+
+    #[motsu::test]
+    fn transfers() {
+        set_up();
+        mock_owner(BOB);
+
+        let token = SafeErc721::new(TOKEN);
+        assert!(token.transfer_from(&mut Caller, ALICE, BOB, TOKEN_ID).is_ok());
+        assert!(token
+            .safe_transfer_from(&mut Caller, ALICE, BOB, TOKEN_ID, &[1])
+            .is_ok());
+        assert!(token.try_transfer_from(&mut Caller, ALICE, BOB, TOKEN_ID));
+    }
+
+    #[motsu::test]
+    fn bubbles_reverts() {
+        set_up();
+        let reason = vec![1, 2, 3, 4];
+        VM::context().mock_revert(
+            TOKEN,
+            &IERC721::safeTransferFromCall::SELECTOR,
+            reason.clone(),
+        );
+
+        let token = SafeErc721::new(TOKEN);
+        let err = token
+            .safe_transfer_from(&mut Caller, ALICE, BOB, TOKEN_ID, &[])
+            .unwrap_err();
+        assert!(matches!(&err, Error::Revert(data) if *data == reason));
+        assert_eq!(Vec::<u8>::from(err), reason);
+        assert!(!token.try_safe_transfer_from(
+            &mut Caller,
+            ALICE,
+            BOB,
+            TOKEN_ID,
+            &[]
+        ));
+    }
+
+    #[motsu::test]
+    fn rejects_transfers_not_moving_the_token() {
+        set_up();
+        mock_owner(ALICE);
+
+        let token = SafeErc721::new(TOKEN);
+        let err =
+            token.transfer_from(&mut Caller, ALICE, BOB, TOKEN_ID).unwrap_err();
+        assert!(matches!(err, Error::FailedOperation(_)));
+        assert!(!token.try_transfer_from(&mut Caller, ALICE, BOB, TOKEN_ID));
+    }
+
+    #[motsu::test]
+    fn rejects_tokens_without_code() {
+        mock_owner(BOB);
+
+        let token = SafeErc721::new(TOKEN);
+        let err =
+            token.transfer_from(&mut Caller, ALICE, BOB, TOKEN_ID).unwrap_err();
+        assert!(matches!(err, Error::FailedOperation(_)));
+    }
+}
@@ -0,0 +1,479 @@
+//! Factory of token contracts, deployed as clones of an implementation.
+//!
+//! [`TokenFactory`] deploys [EIP-1167] minimal proxies, which delegate every
+//! call to a canonical token implementation, e.g., an [`Erc20`] or [`Erc721`]
+//! contract. Clones are much cheaper to deploy than the implementation
+//! itself, and each has its own state, set up right after its deployment by
+//! calling `initialize(string name, string symbol, address owner)` on it.
+//! Implementations must guard that function so it only runs once, e.g., with
+//! [`Initializable`], as the `erc20-clone` example does.
+//!
+//! The factory only exposes its deployments: creating tokens is left to the
+//! contract using it, which decides who may do so, e.g.:
+//!
+//! ```rust,ignore
+//! pub fn create_token(
+//!     &mut self,
+//!     name: String,
+//!     symbol: String,
+//! ) -> Result<Address, Vec<u8>> {
+//!     Ok(self.factory._create_token(name, symbol, msg::sender())?)
+//! }
+//! ```
+//!
+//! [EIP-1167]: https://eips.ethereum.org/EIPS/eip-1167
+//! [`Erc20`]: crate::token::erc20::Erc20
+//! [`Erc721`]: crate::token::erc721::Erc721
+//! [`Initializable`]: crate::utils::Initializable
+use alloc::{string::String, vec::Vec};
+
+use alloy_primitives::{Address, B256, U256};
+use alloy_sol_types::{sol, SolCall};
+use openzeppelin_stylus_proc::ErrorDisplay;
+use stylus_proc::{external, sol_storage, SolidityError};
+use stylus_sdk::{
+    call::{self, Call},
+    contract,
+    deploy::RawDeploy,
+    evm,
+    prelude::*,
+};
+
+use crate::utils::structs::gap::StorageGap;
+
+sol! {
+    /// Emitted when `token` is deployed as a clone of `implementation` and
+    /// initialized with `name`, `symbol` and `owner`.
+    #[allow(missing_docs)]
+    event TokenCreated(
+        address indexed token,
+        address indexed implementation,
+        address indexed owner,
+        string name,
+        string symbol
+    );
+}
+
+sol! {
+    /// The implementation of the factory isn't a contract.
+    #[derive(Debug)]
+    #[allow(missing_docs)]
+    error TokenFactoryInvalidImplementation(address implementation);
+
+    /// The deployment of a clone failed, e.g., because a clone was already
+    /// deployed with the same salt.
+    #[derive(Debug)]
+    #[allow(missing_docs)]
+    error TokenFactoryFailedDeployment();
+
+    /// The initialization of the clone `token` reverted.
+    #[derive(Debug)]
+    #[allow(missing_docs)]
+    error TokenFactoryFailedInitialization(address token);
+
+    /// There is no token at `index` in the list of deployed tokens.
+    #[derive(Debug)]
+    #[allow(missing_docs)]
+    error TokenFactoryOutOfBoundsIndex(uint256 index);
+}
+
+sol! {
+    /// Initializer of the clones deployed by a [`TokenFactory`].
+    interface ITokenInitializer {
+        #[allow(missing_docs)]
+        function initialize(string name, string symbol, address owner) external;
+    }
+}
+
+/// A [`TokenFactory`] error.
+#[derive(SolidityError, Debug, ErrorDisplay)]
+pub enum Error {
+    /// The implementation of the factory isn't a contract.
+    InvalidImplementation(TokenFactoryInvalidImplementation),
+    /// The deployment of a clone failed.
+    FailedDeployment(TokenFactoryFailedDeployment),
+    /// The initialization of a clone reverted.
+    FailedInitialization(TokenFactoryFailedInitialization),
+    /// There is no token at an index of the list of deployed tokens.
+    OutOfBoundsIndex(TokenFactoryOutOfBoundsIndex),
+}
+
+sol_storage! {
+    /// State of a [`TokenFactory`] contract.
+    #[cfg_attr(all(test, feature = "std"), derive(motsu::DefaultStorageLayout))]
+    pub struct TokenFactory {
+        /// Token contract the clones delegate to.
+        address _implementation;
+        /// Deployed tokens, in order.
+        address[] _tokens;
+        /// Whether an address is a token deployed by the factory.
+        mapping(address => bool) _is_token;
+        /// Reserved storage slots, see [`StorageGap`].
+        StorageGap<47> __gap;
+    }
+}
+
+/// NOTE: Implementation of [`TopLevelStorage`] to be able use `&mut self` when
+/// calling other contracts and not `&mut (impl TopLevelStorage +
+/// BorrowMut<Self>)`. Should be fixed in the future by the Stylus team.
+unsafe impl TopLevelStorage for TokenFactory {}
+
+/// Returns the init code of an [EIP-1167] minimal proxy delegating every call
+/// to `implementation`.
+///
+/// [EIP-1167]: https://eips.ethereum.org/EIPS/eip-1167
+#[must_use]
+pub fn clone_code(implementation: Address) -> Vec<u8> {
+    let mut code = Vec::with_capacity(55);
+    code.extend_from_slice(&[
+        0x3d, 0x60, 0x2d, 0x80, 0x60, 0x0a, 0x3d, 0x39, 0x81, 0xf3, 0x36, 0x3d,
+        0x3d, 0x37, 0x3d, 0x3d, 0x3d, 0x36, 0x3d, 0x73,
+    ]);
+    code.extend_from_slice(implementation.as_slice());
+    code.extend_from_slice(&[
+        0x5a, 0xf4, 0x3d, 0x82, 0x80, 0x3e, 0x90, 0x3d, 0x91, 0x60, 0x2b, 0x57,
+        0xfd, 0x5b, 0xf3,
+    ]);
+    code
+}
+
+#[external]
+impl TokenFactory {
+    /// Returns the token contract the clones delegate to.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the contract's state.
+    pub fn implementation(&self) -> Address {
+        self._implementation.get()
+    }
+
+    /// Returns the number of tokens deployed by the factory.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the contract's state.
+    pub fn tokens_count(&self) -> U256 {
+        U256::from(self._tokens.len())
+    }
+
+    /// Returns the token deployed by the factory at `index`, in deployment
+    /// order.
+    ///
+    /// Use along with [`TokenFactory::tokens_count`] to enumerate all the
+    /// tokens.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the contract's state.
+    /// * `index` - Index of the token.
+    ///
+    /// # Errors
+    ///
+    /// If `index` is greater than or equal to the number of deployed tokens,
+    /// then the error [`Error::OutOfBoundsIndex`] is returned.
+    pub fn token_by_index(&self, index: U256) -> Result<Address, Error> {
+        self._tokens
+            .get(index)
+            .ok_or(TokenFactoryOutOfBoundsIndex { index }.into())
+    }
+
+    /// Returns whether `account` is a token deployed by the factory.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the contract's state.
+    /// * `account` - Account to check.
+    pub fn is_token(&self, account: Address) -> bool {
+        self._is_token.get(account)
+    }
+
+    /// Returns the address of the token the factory deploys with `salt`,
+    /// see [`TokenFactory::_create_token_deterministic`].
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the contract's state.
+    /// * `salt` - Salt of the deployment.
+    pub fn predict_token_address(&self, salt: B256) -> Address {
+        contract::address()
+            .create2_from_code(salt, clone_code(self._implementation.get()))
+    }
+}
+
+impl TokenFactory {
+    /// Sets the token contract the clones delegate to.
+    ///
+    /// Tokens deployed before keep delegating to the previous implementation.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the contract's state.
+    /// * `implementation` - Token contract to clone.
+    pub fn _set_implementation(&mut self, implementation: Address) {
+        self._implementation.set(implementation);
+    }
+
+    /// Deploys a clone of the implementation and initializes it.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the contract's state.
+    /// * `name` - Name of the token.
+    /// * `symbol` - Symbol of the token.
+    /// * `owner` - Owner of the token.
+    ///
+    /// # Errors
+    ///
+    /// If the implementation isn't a contract, then the error
+    /// [`Error::InvalidImplementation`] is returned.
+    /// If the deployment fails, then the error [`Error::FailedDeployment`] is
+    /// returned.
+    /// If the initialization of the clone reverts, then the error
+    /// [`Error::FailedInitialization`] is returned.
+    ///
+    /// # Events
+    ///
+    /// Emits a [`TokenCreated`] event.
+    pub fn _create_token(
+        &mut self,
+        name: String,
+        symbol: String,
+        owner: Address,
+    ) -> Result<Address, Error> {
+        self.deploy(name, symbol, owner, None)
+    }
+
+    /// Deploys a clone of the implementation at an address determined by
+    /// `salt`, and initializes it.
+    ///
+    /// Anyone can deploy a clone with a given salt first, so that the salt
+    /// should be derived from the account creating the token, e.g., its
+    /// address.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the contract's state.
+    /// * `name` - Name of the token.
+    /// * `symbol` - Symbol of the token.
+    /// * `owner` - Owner of the token.
+    /// * `salt` - Salt of the deployment, see
+    ///   [`TokenFactory::predict_token_address`].
+    ///
+    /// # Errors
+    ///
+    /// If the implementation isn't a contract, then the error
+    /// [`Error::InvalidImplementation`] is returned.
+    /// If a clone was already deployed with `salt`, then the error
+    /// [`Error::FailedDeployment`] is returned.
+    /// If the initialization of the clone reverts, then the error
+    /// [`Error::FailedInitialization`] is returned.
+    ///
+    /// # Events
+    ///
+    /// Emits a [`TokenCreated`] event.
+    pub fn _create_token_deterministic(
+        &mut self,
+        name: String,
+        symbol: String,
+        owner: Address,
+        salt: B256,
+    ) -> Result<Address, Error> {
+        self.deploy(name, symbol, owner, Some(salt))
+    }
+
+    /// Deploys a clone of the implementation, with `salt` if any, initializes
+    /// it and records it.
+    fn deploy(
+        &mut self,
+        name: String,
+        symbol: String,
+        owner: Address,
+        salt: Option<B256>,
+    ) -> Result<Address, Error> {
+        let implementation = self._implementation.get();
+        if !implementation.has_code() {
+            return Err(
+                TokenFactoryInvalidImplementation { implementation }.into()
+            );
+        }
+
+        // SAFETY: the factory holds no reference to its storage across the
+        // deployment, which doesn't run code of the implementation.
+        let token = unsafe {
+            RawDeploy::new()
+                .salt_option(salt)
+                .skip_revert_data()
+                .deploy(&clone_code(implementation), U256::ZERO)
+        }
+        .map_err(|_| TokenFactoryFailedDeployment {})?;
+
+        let initialize = ITokenInitializer::initializeCall {
+            name: name.clone(),
+            symbol: symbol.clone(),
+            owner,
+        };
+        call::call(Call::new_in(self), token, &initialize.encode())
+            .map_err(|_| TokenFactoryFailedInitialization { token })?;
+
+        self._tokens.push(token);
+        self._is_token.setter(token).set(true);
+        evm::log(TokenCreated { token, implementation, owner, name, symbol });
+        Ok(token)
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use alloy_primitives::{address, b256, uint, Address, B256};
+    use alloy_sol_types::SolCall;
+    use motsu::prelude::VM;
+
+    use super::{
+        clone_code, Error, ITokenInitializer, TokenCreated, TokenFactory,
+        TokenFactoryFailedDeployment, TokenFactoryOutOfBoundsIndex,
+    };
+
+    const IMPLEMENTATION: Address =
+        address!("F4EaCDAbEf3c8f1EdE91b6f2A6840bc2E4DD3526");
+    const OWNER: Address = address!("0BB78F7e7132d1651B4Fd884B7624394e92156F1");
+    const SALT: B256 = b256!(
+        "0000000000000000000000000000000000000000000000000000000000000001"
+    );
+
+    fn set_up(contract: &mut TokenFactory) {
+        VM::context().set_account_code(IMPLEMENTATION, vec![0xfe]);
+        contract._set_implementation(IMPLEMENTATION);
+    }
+
+    #[test]
+    fn builds_minimal_proxy_code() {
+        let code = clone_code(IMPLEMENTATION);
+        assert_eq!(code.len(), 55);
+        assert_eq!(&code[20..40], IMPLEMENTATION.as_slice());
+    }
+
+    #[motsu::test]
+    fn creates_tokens(contract: TokenFactory) {
+        set_up(contract);
+
+        let token = contract
+            ._create_token("Token".into(), "TKN".into(), OWNER)
+            .unwrap();
+
+        assert_eq!(
+            VM::context().account_code(token),
+            clone_code(IMPLEMENTATION)
+        );
+        assert_eq!(contract.implementation(), IMPLEMENTATION);
+        assert_eq!(contract.tokens_count(), uint!(1_U256));
+        assert_eq!(contract.token_by_index(uint!(0_U256)).unwrap(), token);
+        assert!(contract.is_token(token));
+        assert!(!contract.is_token(IMPLEMENTATION));
+        let events = VM::context().emitted_events::<TokenCreated>();
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].token, token);
+        assert_eq!(events[0].implementation, IMPLEMENTATION);
+        assert_eq!(events[0].owner, OWNER);
+        assert_eq!(events[0].name, "Token");
+        assert_eq!(events[0].symbol, "TKN");
+    }
+
+    #[motsu::test]
+    fn creates_deterministic_tokens(contract: TokenFactory) {
+        set_up(contract);
+
+        let predicted = contract.predict_token_address(SALT);
+        let token = contract
+            ._create_token_deterministic(
+                "Token".into(),
+                "TKN".into(),
+                OWNER,
+                SALT,
+            )
+            .unwrap();
+        assert_eq!(token, predicted);
+
+        let err = contract
+            ._create_token_deterministic(
+                "Token".into(),
+                "TKN".into(),
+                OWNER,
+                SALT,
+            )
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            Error::FailedDeployment(TokenFactoryFailedDeployment {})
+        ));
+        assert_eq!(contract.tokens_count(), uint!(1_U256));
+    }
+
+    #[motsu::test]
+    fn initializes_tokens(contract: TokenFactory) {
+        set_up(contract);
+        let token = contract.predict_token_address(SALT);
+        // Only the expected initialization succeeds.
+        VM::context().mock_revert(
+            token,
+            &ITokenInitializer::initializeCall::SELECTOR,
+            vec![],
+        );
+        let initialize = ITokenInitializer::initializeCall {
+            name: "Token".into(),
+            symbol: "TKN".into(),
+            owner: OWNER,
+        };
+        VM::context().mock_call(token, &initialize.encode(), vec![]);
+
+        let created = contract
+            ._create_token_deterministic(
+                "Token".into(),
+                "TKN".into(),
+                OWNER,
+                SALT,
+            )
+            .unwrap();
+        assert_eq!(created, token);
+    }
+
+    #[motsu::test]
+    fn rejects_failed_initializations(contract: TokenFactory) {
+        set_up(contract);
+        let token = contract.predict_token_address(SALT);
+        VM::context().mock_revert(
+            token,
+            &ITokenInitializer::initializeCall::SELECTOR,
+            vec![],
+        );
+
+        let err = contract
+            ._create_token_deterministic(
+                "Token".into(),
+                "TKN".into(),
+                OWNER,
+                SALT,
+            )
+            .unwrap_err();
+        assert!(matches!(err, Error::FailedInitialization(_)));
+        assert!(!contract.is_token(token));
+    }
+
+    #[motsu::test]
+    fn rejects_missing_implementations(contract: TokenFactory) {
+        let err = contract
+            ._create_token("Token".into(), "TKN".into(), OWNER)
+            .unwrap_err();
+        assert!(matches!(err, Error::InvalidImplementation(_)));
+    }
+
+    #[motsu::test]
+    fn rejects_out_of_bounds_indices(contract: TokenFactory) {
+        let err = contract.token_by_index(uint!(0_U256)).unwrap_err();
+        assert!(matches!(
+            err,
+            Error::OutOfBoundsIndex(TokenFactoryOutOfBoundsIndex { index })
+                if index == uint!(0_U256)
+        ));
+    }
+}
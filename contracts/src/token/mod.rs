@@ -1,3 +1,25 @@
 //! Token standards.
+#[cfg(feature = "erc20")]
 pub mod erc20;
+#[cfg(feature = "erc721")]
 pub mod erc721;
+#[cfg(feature = "factory")]
+pub mod factory;
+#[cfg(feature = "weth")]
+pub mod weth;
+
+#[cfg(all(test, feature = "std"))]
+pub(crate) mod tests {
+    use alloy_primitives::{keccak256, FixedBytes};
+
+    /// Returns the ERC-165 interface id of the functions with `signatures`,
+    /// i.e., the XOR of their selectors.
+    pub(crate) fn interface_id_of(signatures: &[&str]) -> FixedBytes<4> {
+        signatures
+            .iter()
+            .map(|signature| {
+                FixedBytes::<4>::from_slice(&keccak256(signature)[..4])
+            })
+            .fold(FixedBytes::ZERO, |id, selector| id ^ selector)
+    }
+}
@@ -0,0 +1,379 @@
+//! Wrapped Ether.
+//!
+//! [`Weth`] wraps and unwraps ETH through a canonical `WETH9` contract, for
+//! contracts that handle ETH and ERC-20 tokens alike, e.g., vaults or
+//! routers:
+//!
+//! ```rust,ignore
+//! #[external]
+//! impl Router {
+//!     #[payable]
+//!     pub fn deposit(&mut self) -> Result<(), Vec<u8>> {
+//!         let weth = Weth::new(self.weth.get());
+//!         let value = weth.wrap_received(self)?;
+//!         ...
+//!     }
+//!
+//!     pub fn withdraw(&mut self, amount: U256) -> Result<(), Vec<u8>> {
+//!         let weth = Weth::new(self.weth.get());
+//!         weth.unwrap_from(self, msg::sender(), amount, msg::sender())?;
+//!         ...
+//!     }
+//! }
+//! ```
+//!
+//! The Stylus SDK doesn't support `receive` functions yet, so ETH is wrapped
+//! on receipt by calling [`Weth::wrap_received`] from `#[payable]` methods.
+//!
+//! Note that `WETH9` sends the ETH it unwraps with a plain transfer, which the
+//! contract calling [`Weth::unwrap`] must accept.
+// `sol_interface!` doesn't document the items it generates.
+#![allow(missing_docs)]
+use alloy_primitives::{Address, U256};
+use alloy_sol_types::sol;
+use openzeppelin_stylus_proc::ErrorDisplay;
+use stylus_proc::{sol_interface, SolidityError};
+use stylus_sdk::{
+    call::{transfer_eth, Call, StaticCallContext},
+    contract, msg,
+    storage::TopLevelStorage,
+};
+
+sol_interface! {
+    /// The canonical Wrapped Ether contract.
+    interface IWETH9 {
+        function deposit() external payable;
+        function withdraw(uint256 wad) external;
+        function totalSupply() external view returns (uint256);
+        function balanceOf(address account) external view returns (uint256);
+        function allowance(address owner, address spender) external view returns (uint256);
+        function approve(address spender, uint256 wad) external returns (bool);
+        function transfer(address to, uint256 wad) external returns (bool);
+        function transferFrom(address from, address to, uint256 wad) external returns (bool);
+    }
+}
+
+sol! {
+    /// ETH couldn't be wrapped.
+    ///
+    /// * `value` - Amount of ETH to wrap.
+    #[derive(Debug)]
+    #[allow(missing_docs)]
+    error WethFailedDeposit(uint256 value);
+    /// WETH couldn't be unwrapped.
+    ///
+    /// * `amount` - Amount of WETH to unwrap.
+    #[derive(Debug)]
+    #[allow(missing_docs)]
+    error WethFailedWithdrawal(uint256 amount);
+    /// The unwrapped ETH couldn't be sent to `to`.
+    ///
+    /// * `to` - Account the ETH was sent to.
+    /// * `amount` - Amount of ETH sent.
+    #[derive(Debug)]
+    #[allow(missing_docs)]
+    error WethFailedTransfer(address to, uint256 amount);
+    /// WETH couldn't be pulled from `from`, e.g., because it didn't approve
+    /// the contract.
+    ///
+    /// * `from` - Account the WETH was pulled from.
+    /// * `amount` - Amount of WETH pulled.
+    #[derive(Debug)]
+    #[allow(missing_docs)]
+    error WethFailedTransferFrom(address from, uint256 amount);
+}
+
+/// An error that occurred while wrapping or unwrapping ETH.
+#[derive(SolidityError, Debug, ErrorDisplay)]
+pub enum Error {
+    /// ETH couldn't be wrapped.
+    FailedDeposit(WethFailedDeposit),
+    /// WETH couldn't be unwrapped.
+    FailedWithdrawal(WethFailedWithdrawal),
+    /// The unwrapped ETH couldn't be sent.
+    FailedTransfer(WethFailedTransfer),
+    /// WETH couldn't be pulled from an account.
+    FailedTransferFrom(WethFailedTransferFrom),
+}
+
+/// A `WETH9` contract.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Weth {
+    /// Address of the contract.
+    pub address: Address,
+}
+
+impl Weth {
+    /// Creates a handle to the `WETH9` contract at `address`.
+    #[must_use]
+    pub fn new(address: Address) -> Self {
+        Weth { address }
+    }
+
+    /// Wraps `value` wei held by the calling contract into WETH.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the WETH configuration.
+    /// * `storage` - The calling contract.
+    /// * `value` - Amount of ETH to wrap.
+    ///
+    /// # Errors
+    ///
+    /// If the deposit fails, e.g., because the contract holds less than
+    /// `value` wei, then the error [`Error::FailedDeposit`] is returned.
+    pub fn wrap(
+        &self,
+        storage: &mut impl TopLevelStorage,
+        value: U256,
+    ) -> Result<(), Error> {
+        let call = Call::new_in(storage).value(value);
+        IWETH9::new(self.address)
+            .deposit(call)
+            .map_err(|_| Error::FailedDeposit(WethFailedDeposit { value }))
+    }
+
+    /// Wraps the ETH sent along the current call into WETH, and returns the
+    /// amount wrapped.
+    ///
+    /// Meant to be called from `#[payable]` methods, so that the contract
+    /// holds WETH instead of the ETH it receives.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the WETH configuration.
+    /// * `storage` - The calling contract.
+    ///
+    /// # Errors
+    ///
+    /// If the deposit fails, then the error [`Error::FailedDeposit`] is
+    /// returned.
+    pub fn wrap_received(
+        &self,
+        storage: &mut impl TopLevelStorage,
+    ) -> Result<U256, Error> {
+        let value = msg::value();
+        if !value.is_zero() {
+            self.wrap(storage, value)?;
+        }
+        Ok(value)
+    }
+
+    /// Unwraps `amount` WETH held by the calling contract into ETH.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the WETH configuration.
+    /// * `storage` - The calling contract.
+    /// * `amount` - Amount of WETH to unwrap.
+    ///
+    /// # Errors
+    ///
+    /// If the withdrawal fails, e.g., because the contract holds less than
+    /// `amount` WETH, then the error [`Error::FailedWithdrawal`] is returned.
+    pub fn unwrap(
+        &self,
+        storage: &mut impl TopLevelStorage,
+        amount: U256,
+    ) -> Result<(), Error> {
+        IWETH9::new(self.address).withdraw(storage, amount).map_err(|_| {
+            Error::FailedWithdrawal(WethFailedWithdrawal { amount })
+        })
+    }
+
+    /// Unwraps `amount` WETH held by the calling contract and sends the ETH
+    /// to `to`.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the WETH configuration.
+    /// * `storage` - The calling contract.
+    /// * `amount` - Amount of WETH to unwrap.
+    /// * `to` - Account receiving the ETH.
+    ///
+    /// # Errors
+    ///
+    /// If the withdrawal fails, then the error [`Error::FailedWithdrawal`]
+    /// is returned.
+    ///
+    /// If `to` doesn't accept the ETH, then the error
+    /// [`Error::FailedTransfer`] is returned.
+    pub fn unwrap_to(
+        &self,
+        storage: &mut impl TopLevelStorage,
+        amount: U256,
+        to: Address,
+    ) -> Result<(), Error> {
+        self.unwrap(storage, amount)?;
+        transfer_eth(to, amount).map_err(|_| {
+            Error::FailedTransfer(WethFailedTransfer { to, amount })
+        })
+    }
+
+    /// Pulls `amount` WETH from `from`, unwraps it and sends the ETH to `to`.
+    ///
+    /// `from` must have approved the calling contract to spend at least
+    /// `amount` WETH.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the WETH configuration.
+    /// * `storage` - The calling contract.
+    /// * `from` - Account holding the WETH.
+    /// * `amount` - Amount of WETH to unwrap.
+    /// * `to` - Account receiving the ETH.
+    ///
+    /// # Errors
+    ///
+    /// If the WETH can't be pulled from `from`, then the error
+    /// [`Error::FailedTransferFrom`] is returned.
+    ///
+    /// If the withdrawal fails, then the error [`Error::FailedWithdrawal`]
+    /// is returned.
+    ///
+    /// If `to` doesn't accept the ETH, then the error
+    /// [`Error::FailedTransfer`] is returned.
+    pub fn unwrap_from(
+        &self,
+        storage: &mut impl TopLevelStorage,
+        from: Address,
+        amount: U256,
+        to: Address,
+    ) -> Result<(), Error> {
+        let pulled = IWETH9::new(self.address).transfer_from(
+            &mut *storage,
+            from,
+            contract::address(),
+            amount,
+        );
+        if !matches!(pulled, Ok(true)) {
+            return Err(Error::FailedTransferFrom(WethFailedTransferFrom {
+                from,
+                amount,
+            }));
+        }
+        self.unwrap_to(storage, amount, to)
+    }
+
+    /// Returns the WETH balance of the calling contract.
+    ///
+    /// # Errors
+    ///
+    /// If the balance can't be read, then the raw error of the call is
+    /// returned.
+    pub fn balance(
+        &self,
+        context: impl StaticCallContext,
+    ) -> Result<U256, stylus_sdk::call::Error> {
+        IWETH9::new(self.address).balance_of(context, contract::address())
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use alloy_primitives::{address, uint, Address, U256};
+    use alloy_sol_types::{sol, SolCall};
+    use motsu::prelude::{Caller, VM};
+    use stylus_sdk::{contract, msg};
+
+    use super::{Error, Weth};
+
+    sol! {
+        function withdraw(uint256 wad) external;
+        function transferFrom(address from, address to, uint256 wad) external returns (bool);
+    }
+
+    const WETH: Address = address!("82aF49447D8a07e3bd95BD0d56f35241523fBab1");
+    const ALICE: Address = address!("F4EaCDAbEf3c8f1EdE91b6f2A6840bc2E4DD3526");
+
+    #[motsu::test]
+    fn wraps_eth() {
+        let value = uint!(100_U256);
+        VM::context().fund(contract::address(), value);
+
+        Weth::new(WETH).wrap(&mut Caller, value).unwrap();
+        assert_eq!(VM::context().balance_of(WETH), value);
+        assert_eq!(VM::context().balance_of(contract::address()), U256::ZERO);
+    }
+
+    #[motsu::test]
+    fn rejects_wrapping_more_than_balance() {
+        let result = Weth::new(WETH).wrap(&mut Caller, uint!(1_U256));
+        assert!(matches!(result, Err(Error::FailedDeposit(_))));
+    }
+
+    #[motsu::test]
+    fn rejects_failed_withdrawal() {
+        VM::context().mock_revert(WETH, &withdrawCall::SELECTOR, vec![]);
+
+        let result = Weth::new(WETH).unwrap(&mut Caller, uint!(1_U256));
+        assert!(matches!(result, Err(Error::FailedWithdrawal(_))));
+    }
+
+    #[motsu::test]
+    fn unwraps_to_account() {
+        // `WETH9` sends the unwrapped ETH back, which isn't simulated.
+        let amount = uint!(100_U256);
+        VM::context().fund(contract::address(), amount);
+
+        Weth::new(WETH).unwrap_to(&mut Caller, amount, ALICE).unwrap();
+        assert_eq!(VM::context().balance_of(ALICE), amount);
+    }
+
+    #[motsu::test]
+    fn wraps_received_eth() {
+        let value = uint!(100_U256);
+        VM::context().fund(msg::sender(), value);
+        VM::context().set_msg_value(value);
+
+        let wrapped = Weth::new(WETH).wrap_received(&mut Caller).unwrap();
+        assert_eq!(wrapped, value);
+        assert_eq!(VM::context().balance_of(WETH), value);
+    }
+
+    #[motsu::test]
+    fn skips_wrapping_without_value() {
+        let wrapped = Weth::new(WETH).wrap_received(&mut Caller).unwrap();
+        assert_eq!(wrapped, U256::ZERO);
+    }
+
+    #[motsu::test]
+    fn unwraps_from_account() {
+        // `WETH9` sends the unwrapped ETH back, which isn't simulated.
+        let amount = uint!(100_U256);
+        VM::context().fund(contract::address(), amount);
+        let pull = transferFromCall {
+            from: ALICE,
+            to: contract::address(),
+            wad: amount,
+        };
+        VM::context().mock_call(
+            WETH,
+            &pull.encode(),
+            U256::from(1).to_be_bytes_vec(),
+        );
+
+        Weth::new(WETH).unwrap_from(&mut Caller, ALICE, amount, ALICE).unwrap();
+        assert_eq!(VM::context().balance_of(ALICE), amount);
+    }
+
+    #[motsu::test]
+    fn rejects_failed_transfer_from() {
+        VM::context().mock_revert(WETH, &transferFromCall::SELECTOR, vec![]);
+
+        let result = Weth::new(WETH).unwrap_from(
+            &mut Caller,
+            ALICE,
+            uint!(1_U256),
+            ALICE,
+        );
+        assert!(matches!(result, Err(Error::FailedTransferFrom(_))));
+    }
+
+    #[motsu::test]
+    fn rejects_failed_transfer() {
+        let result =
+            Weth::new(WETH).unwrap_to(&mut Caller, uint!(1_U256), ALICE);
+        assert!(matches!(result, Err(Error::FailedTransfer(_))));
+    }
+}
@@ -0,0 +1,3 @@
+//! Bindings and helpers to build on top of Uniswap.
+pub mod v3;
+pub mod v4;
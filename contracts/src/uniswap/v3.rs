@@ -0,0 +1,400 @@
+//! Uniswap v3 time-weighted average prices.
+//!
+//! Every Uniswap v3 pool records the running sum of its tick over time, so
+//! that the average tick over a window is the difference of two sums divided
+//! by the window's length. Since a single block can't move such an average
+//! much, it is harder to manipulate than the current price of the pool.
+//!
+//! [`Twap`] reads the average tick of a pool over a window, and converts it
+//! to a price:
+//!
+//! ```rust,ignore
+//! #[external]
+//! impl Vault {
+//!     pub fn weth_in_usdc(&self, amount: u128) -> Result<U256, Vec<u8>> {
+//!         let twap = Twap::new(self.pool.get(), 1_800);
+//!         Ok(twap.quote(self, amount, self.weth.get(), self.usdc.get())?)
+//!     }
+//! }
+//! ```
+//!
+//! Note that the pool must have recorded observations covering the whole
+//! window, which may require increasing its observation cardinality.
+use alloc::vec;
+
+use alloy_primitives::{uint, Address, U256, U512};
+use alloy_sol_types::{sol, SolCall};
+use openzeppelin_stylus_proc::ErrorDisplay;
+use stylus_proc::SolidityError;
+use stylus_sdk::call::{static_call, StaticCallContext};
+
+/// Lowest tick of a pool.
+pub const MIN_TICK: i32 = -887_272;
+/// Highest tick of a pool.
+pub const MAX_TICK: i32 = 887_272;
+
+/// `sqrt(1.0001^-(2^i)) * 2^128` for every bit `i` of a tick, see
+/// [`sqrt_ratio_at_tick`].
+const SQRT_RATIOS: [U256; 20] = [
+    uint!(0xfffcb933bd6fad37aa2d162d1a594001_U256),
+    uint!(0xfff97272373d413259a46990580e213a_U256),
+    uint!(0xfff2e50f5f656932ef12357cf3c7fdcc_U256),
+    uint!(0xffe5caca7e10e4e61c3624eaa0941cd0_U256),
+    uint!(0xffcb9843d60f6159c9db58835c926644_U256),
+    uint!(0xff973b41fa98c081472e6896dfb254c0_U256),
+    uint!(0xff2ea16466c96a3843ec78b326b52861_U256),
+    uint!(0xfe5dee046a99a2a811c461f1969c3053_U256),
+    uint!(0xfcbe86c7900a88aedcffc83b479aa3a4_U256),
+    uint!(0xf987a7253ac413176f2b074cf7815e54_U256),
+    uint!(0xf3392b0822b70005940c7a398e4b70f3_U256),
+    uint!(0xe7159475a2c29b7443b29c7fa6e889d9_U256),
+    uint!(0xd097f3bdfd2022b8845ad8f792aa5825_U256),
+    uint!(0xa9f746462d870fdf8a65dc1f90e061e5_U256),
+    uint!(0x70d869a156d2a1b890bb3df62baf32f7_U256),
+    uint!(0x31be135f97d08fd981231505542fcfa6_U256),
+    uint!(0x9aa508b5b7a84e1c677de54f3e99bc9_U256),
+    uint!(0x5d6af8dedb81196699c329225ee604_U256),
+    uint!(0x2216e584f5fa1ea926041bedfe98_U256),
+    uint!(0x48a170391f7dc42444e8fa2_U256),
+];
+
+sol! {
+    /// Returns the tick and liquidity cumulatives of a Uniswap v3 pool, as
+    /// of `seconds_agos` ago.
+    #[allow(missing_docs)]
+    function observe(uint32[] seconds_agos) external view returns (int56[] tick_cumulatives, uint160[] seconds_per_liquidity_cumulative_x128s);
+
+    /// The window of a time-weighted average is empty.
+    #[derive(Debug)]
+    #[allow(missing_docs)]
+    error UniswapV3InvalidTwapWindow();
+    /// The observations of the pool couldn't be read, e.g., because they
+    /// don't cover the whole window.
+    ///
+    /// * `pool` - Address of the pool.
+    #[derive(Debug)]
+    #[allow(missing_docs)]
+    error UniswapV3ObservationUnavailable(address pool);
+    /// A tick is outside of [`MIN_TICK`, `MAX_TICK`].
+    ///
+    /// * `tick` - The tick.
+    #[derive(Debug)]
+    #[allow(missing_docs)]
+    error UniswapV3InvalidTick(int64 tick);
+    /// A quote doesn't fit in 256 bits.
+    #[derive(Debug)]
+    #[allow(missing_docs)]
+    error UniswapV3QuoteOverflow();
+}
+
+/// An error that occurred while reading a time-weighted average price.
+#[derive(SolidityError, Debug, ErrorDisplay)]
+pub enum Error {
+    /// The window of the average is empty.
+    InvalidTwapWindow(UniswapV3InvalidTwapWindow),
+    /// The observations of the pool couldn't be read.
+    ObservationUnavailable(UniswapV3ObservationUnavailable),
+    /// A tick is outside of [`MIN_TICK`, `MAX_TICK`].
+    InvalidTick(UniswapV3InvalidTick),
+    /// A quote doesn't fit in 256 bits.
+    QuoteOverflow(UniswapV3QuoteOverflow),
+}
+
+/// The time-weighted average price of a Uniswap v3 pool, over a window
+/// ending at the current block.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Twap {
+    /// Address of the pool.
+    pub pool: Address,
+    /// Length of the window, in seconds.
+    pub window: u32,
+}
+
+impl Twap {
+    /// Creates a time-weighted average of `pool` over the last `window`
+    /// seconds.
+    #[must_use]
+    pub fn new(pool: Address, window: u32) -> Self {
+        Twap { pool, window }
+    }
+
+    /// Returns the average tick of the pool over the window, rounded towards
+    /// negative infinity.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the average's configuration.
+    /// * `context` - Context of the call to the pool, e.g., `&*self` from a
+    ///   contract.
+    ///
+    /// # Errors
+    ///
+    /// If the window is empty, then the error [`Error::InvalidTwapWindow`] is
+    /// returned.
+    ///
+    /// If the observations of the pool can't be read, e.g., because they
+    /// don't go back as far as the window, then the error
+    /// [`Error::ObservationUnavailable`] is returned.
+    ///
+    /// If the pool returns observations averaging to a tick outside of
+    /// [`MIN_TICK`, `MAX_TICK`], then the error [`Error::InvalidTick`] is
+    /// returned.
+    pub fn mean_tick(
+        &self,
+        context: impl StaticCallContext,
+    ) -> Result<i32, Error> {
+        if self.window == 0 {
+            return Err(Error::InvalidTwapWindow(
+                UniswapV3InvalidTwapWindow {},
+            ));
+        }
+
+        let unavailable = || {
+            Error::ObservationUnavailable(UniswapV3ObservationUnavailable {
+                pool: self.pool,
+            })
+        };
+        // `sol_interface!` expects functions returning several dynamic values
+        // to wrap them in a tuple, so that the call is made by hand.
+        let call = observeCall { seconds_agos: vec![self.window, 0] };
+        let returned = static_call(context, self.pool, &call.encode())
+            .map_err(|_| unavailable())?;
+        let cumulatives = observeCall::decode_returns(&returned, true)
+            .map_err(|_| unavailable())?
+            .tick_cumulatives;
+        let [start, end] = cumulatives[..] else {
+            return Err(unavailable());
+        };
+
+        // Tick cumulatives are `int56` values, which wrap around on
+        // overflow, so the difference is taken modulo 2^56 and sign-extended
+        // back.
+        let delta = (end.wrapping_sub(start) << 8) >> 8;
+        let tick = delta.div_euclid(i64::from(self.window));
+        i32::try_from(tick)
+            .ok()
+            .filter(|tick| (MIN_TICK..=MAX_TICK).contains(tick))
+            .ok_or(Error::InvalidTick(UniswapV3InvalidTick { tick }))
+    }
+
+    /// Returns the amount of `quote_token` `base_amount` of `base_token` is
+    /// worth at the average price of the pool over the window.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the average's configuration.
+    /// * `context` - Context of the call to the pool.
+    /// * `base_amount` - Amount of `base_token` to convert.
+    /// * `base_token` - Token being converted, one of the pool's.
+    /// * `quote_token` - Token converted to, the other one of the pool's.
+    ///
+    /// # Errors
+    ///
+    /// If the average tick can't be read, then the error of
+    /// [`Twap::mean_tick`] is returned.
+    ///
+    /// If the quote doesn't fit in 256 bits, then the error
+    /// [`Error::QuoteOverflow`] is returned.
+    pub fn quote(
+        &self,
+        context: impl StaticCallContext,
+        base_amount: u128,
+        base_token: Address,
+        quote_token: Address,
+    ) -> Result<U256, Error> {
+        let tick = self.mean_tick(context)?;
+        quote_at_tick(tick, base_amount, base_token, quote_token)
+    }
+}
+
+/// Returns `sqrt(1.0001^tick) * 2^96`, the square root of the price of a
+/// pool at `tick`, as a Q64.96 number.
+///
+/// Matches `TickMath.getSqrtRatioAtTick` of Uniswap v3.
+///
+/// # Errors
+///
+/// If `tick` is outside of [`MIN_TICK`, `MAX_TICK`], then the error
+/// [`Error::InvalidTick`] is returned.
+pub fn sqrt_ratio_at_tick(tick: i32) -> Result<U256, Error> {
+    if !(MIN_TICK..=MAX_TICK).contains(&tick) {
+        return Err(Error::InvalidTick(UniswapV3InvalidTick {
+            tick: i64::from(tick),
+        }));
+    }
+
+    // `sqrt(1.0001^-|tick|) * 2^128`, multiplying the ratio of every bit set.
+    let abs_tick = tick.unsigned_abs();
+    let mut ratio: U256 = uint!(1_U256) << 128;
+    for (bit, sqrt_ratio) in SQRT_RATIOS.iter().enumerate() {
+        if abs_tick & (1 << bit) != 0 {
+            ratio = (ratio * sqrt_ratio) >> 128;
+        }
+    }
+    if tick > 0 {
+        ratio = U256::MAX / ratio;
+    }
+
+    // Back to 96 bits of precision, rounding up so that the tick of the
+    // result is the original one.
+    let round_up = !(ratio & U256::from(u32::MAX)).is_zero();
+    Ok((ratio >> 32) + U256::from(round_up))
+}
+
+/// Returns the amount of `quote_token` `base_amount` of `base_token` is
+/// worth at `tick`.
+///
+/// Matches `OracleLibrary.getQuoteAtTick` of Uniswap v3, which sorts the
+/// tokens of a pool by address.
+///
+/// # Errors
+///
+/// If `tick` is outside of [`MIN_TICK`, `MAX_TICK`], then the error
+/// [`Error::InvalidTick`] is returned.
+///
+/// If the quote doesn't fit in 256 bits, then the error
+/// [`Error::QuoteOverflow`] is returned.
+pub fn quote_at_tick(
+    tick: i32,
+    base_amount: u128,
+    base_token: Address,
+    quote_token: Address,
+) -> Result<U256, Error> {
+    let sqrt_ratio = sqrt_ratio_at_tick(tick)?;
+    let base_amount = U256::from(base_amount);
+
+    // The squared ratio only fits in 256 bits when the ratio fits in 128, so
+    // that precision is traded off for range otherwise.
+    let overflow = || Error::QuoteOverflow(UniswapV3QuoteOverflow {});
+    let (ratio, one) = if sqrt_ratio <= U256::from(u128::MAX) {
+        (sqrt_ratio * sqrt_ratio, uint!(1_U256) << 192)
+    } else {
+        let ratio = mul_div(sqrt_ratio, sqrt_ratio, uint!(1_U256) << 64)
+            .ok_or_else(overflow)?;
+        (ratio, uint!(1_U256) << 128)
+    };
+
+    let quote = if base_token < quote_token {
+        mul_div(ratio, base_amount, one)
+    } else {
+        mul_div(one, base_amount, ratio)
+    };
+    quote.ok_or_else(overflow)
+}
+
+/// Returns `a * b / denominator` rounded down, with full precision for the
+/// intermediate product, or `None` if the result doesn't fit in 256 bits.
+fn mul_div(a: U256, b: U256, denominator: U256) -> Option<U256> {
+    let result = U512::from(a) * U512::from(b) / U512::from(denominator);
+    let (result, overflow) =
+        U256::overflowing_from_limbs_slice(result.as_limbs());
+    (!overflow).then_some(result)
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use alloy_primitives::{address, uint, Address, U256};
+    use alloy_sol_types::{
+        sol_data::{Array, Int, Uint},
+        SolCall, SolType,
+    };
+    use motsu::prelude::{Caller, VM};
+
+    use super::{
+        observeCall, quote_at_tick, sqrt_ratio_at_tick, Error, Twap, MAX_TICK,
+        MIN_TICK,
+    };
+
+    const POOL: Address = address!("C31E54c7a869B9FcBEcc14363CF510d1c41fa443");
+    const WETH: Address = address!("82aF49447D8a07e3bd95BD0d56f35241523fBab1");
+    const USDC: Address = address!("FF970A61A04b1cA14834A43f5dE4533eBDDB5CC8");
+
+    /// Mocks `observe` of the pool to return `tick_cumulatives`.
+    fn mock_observations(tick_cumulatives: Vec<i64>) {
+        let liquidity_cumulatives = vec![U256::ZERO; tick_cumulatives.len()];
+        VM::context().mock_call(
+            POOL,
+            &observeCall::SELECTOR,
+            <(Array<Int<56>>, Array<Uint<160>>) as SolType>::encode_params(&(
+                tick_cumulatives,
+                liquidity_cumulatives,
+            )),
+        );
+    }
+
+    #[motsu::test]
+    fn computes_sqrt_ratios() {
+        assert_eq!(
+            sqrt_ratio_at_tick(0).unwrap(),
+            uint!(79228162514264337593543950336_U256)
+        );
+        assert_eq!(
+            sqrt_ratio_at_tick(MIN_TICK).unwrap(),
+            uint!(4295128739_U256)
+        );
+        assert_eq!(
+            sqrt_ratio_at_tick(MAX_TICK).unwrap(),
+            uint!(1461446703485210103287273052203988822378723970342_U256)
+        );
+        assert!(matches!(
+            sqrt_ratio_at_tick(MAX_TICK + 1),
+            Err(Error::InvalidTick(_))
+        ));
+    }
+
+    #[motsu::test]
+    fn quotes_at_ticks() {
+        let amount = 10_u128.pow(18);
+        assert_eq!(
+            quote_at_tick(0, amount, WETH, USDC).unwrap(),
+            U256::from(amount)
+        );
+        assert_eq!(
+            quote_at_tick(0, amount, USDC, WETH).unwrap(),
+            U256::from(amount)
+        );
+
+        // 1.0001^6932 is about 2, the price of WETH, which has the lower
+        // address, in USDC.
+        let quote = quote_at_tick(6932, amount, WETH, USDC).unwrap();
+        assert!(quote > uint!(1_999_000_000_000_000_000_U256));
+        assert!(quote < uint!(2_001_000_000_000_000_000_U256));
+        let quote = quote_at_tick(6932, amount, USDC, WETH).unwrap();
+        assert!(quote > uint!(499_000_000_000_000_000_U256));
+        assert!(quote < uint!(501_000_000_000_000_000_U256));
+
+        // Extreme quotes still fit in 256 bits.
+        for tick in [MIN_TICK, MAX_TICK] {
+            assert!(quote_at_tick(tick, u128::MAX, WETH, USDC).is_ok());
+            assert!(quote_at_tick(tick, u128::MAX, USDC, WETH).is_ok());
+        }
+    }
+
+    #[motsu::test]
+    fn reads_mean_tick() {
+        mock_observations(vec![1_000, 1_000 + 60 * 100]);
+        assert_eq!(Twap::new(POOL, 60).mean_tick(&Caller).unwrap(), 100);
+    }
+
+    #[motsu::test]
+    fn rounds_mean_tick_down() {
+        mock_observations(vec![0, -61]);
+        assert_eq!(Twap::new(POOL, 60).mean_tick(&Caller).unwrap(), -2);
+    }
+
+    #[motsu::test]
+    fn reads_mean_tick_across_overflow() {
+        let max = (1_i64 << 55) - 1;
+        mock_observations(vec![max - 59, -max - 1 + 60]);
+        assert_eq!(Twap::new(POOL, 60).mean_tick(&Caller).unwrap(), 2);
+    }
+
+    #[motsu::test]
+    fn rejects_invalid_windows() {
+        let result = Twap::new(POOL, 0).mean_tick(&Caller);
+        assert!(matches!(result, Err(Error::InvalidTwapWindow(_))));
+
+        let result = Twap::new(POOL, 60).mean_tick(&Caller);
+        assert!(matches!(result, Err(Error::ObservationUnavailable(_))));
+    }
+}
@@ -0,0 +1,619 @@
+//! Uniswap v4 periphery helpers.
+//!
+//! All the pools of Uniswap v4 live in a single `PoolManager` contract, which
+//! only accepts operations from a contract that unlocked it. The manager then
+//! calls back `unlockCallback` on that contract, which swaps or modifies
+//! liquidity there, and must settle every balance it owes and take every
+//! balance it is owed before returning, a scheme known as flash accounting.
+//!
+//! [`PoolManager`] wraps the manager's functions, and [`IUnlockCallback`] is
+//! implemented by the contract receiving the callback, e.g., a router:
+//!
+//! ```rust,ignore
+//! #[external]
+//! impl Router {
+//!     pub fn swap(&mut self, key: ..., params: ...) -> Result<(), Vec<u8>> {
+//!         let manager = PoolManager::new(self.pool_manager.get());
+//!         manager.unlock(self, encode(key, params))?;
+//!         Ok(())
+//!     }
+//!
+//!     #[selector(name = "unlockCallback")]
+//!     pub fn unlock_callback(&mut self, data: Bytes) -> Result<Bytes, Vec<u8>> {
+//!         let manager = PoolManager::new(self.pool_manager.get());
+//!         manager.only_pool_manager()?;
+//!         let (key, params) = decode(&data);
+//!         let delta = manager.swap(self, key, params, vec![])?;
+//!         manager.resolve(self, key, delta, msg::sender())?;
+//!         Ok(Bytes::new())
+//!     }
+//! }
+//! ```
+//!
+//! For more information see the [Uniswap v4 documentation].
+//!
+//! [Uniswap v4 documentation]: https://docs.uniswap.org/contracts/v4/overview
+// `sol_interface!` doesn't document the items it generates.
+#![allow(missing_docs)]
+use alloc::vec::Vec;
+
+use alloy_primitives::{aliases::U160, Address, Bytes, B256, I256, U256};
+use alloy_sol_types::{sol, SolCall};
+use openzeppelin_stylus_proc::ErrorDisplay;
+use stylus_proc::{sol_interface, SolidityError};
+use stylus_sdk::{
+    call::{self, Call, Error as CallError},
+    msg,
+    storage::TopLevelStorage,
+};
+
+/// Currency representing native ETH in pools.
+pub const NATIVE: Address = Address::ZERO;
+
+sol_interface! {
+    /// The Uniswap v4 `PoolManager` contract.
+    interface IPoolManager {
+        function unlock(bytes calldata data) external returns (bytes memory);
+        function swap((address, address, uint24, int24, address) calldata key, (bool, int256, uint160) calldata params, bytes calldata hook_data) external returns (int256);
+        function sync(address currency) external;
+        function settle() external payable returns (uint256);
+        function take(address currency, address to, uint256 amount) external;
+    }
+
+    /// The subset of ERC-20 used to pay the `PoolManager`.
+    interface IERC20Minimal {
+        function transfer(address to, uint256 value) external returns (bool);
+    }
+}
+
+sol! {
+    /// A pool key, as the `PoolManager` expects it.
+    #[allow(missing_docs)]
+    struct UniswapV4PoolKey {
+        address currency0;
+        address currency1;
+        uint24 fee;
+        int24 tick_spacing;
+        address hooks;
+    }
+
+    /// Parameters of a liquidity modification, as the `PoolManager` expects
+    /// them.
+    #[allow(missing_docs)]
+    struct UniswapV4ModifyLiquidityParams {
+        int24 tick_lower;
+        int24 tick_upper;
+        int256 liquidity_delta;
+        bytes32 salt;
+    }
+
+    /// Adds or removes liquidity, and returns the balance changes of the
+    /// caller and the fees accrued by the position.
+    #[allow(missing_docs)]
+    function modifyLiquidity(UniswapV4PoolKey key, UniswapV4ModifyLiquidityParams params, bytes hook_data) external returns (int256 caller_delta, int256 fees_accrued);
+}
+
+sol! {
+    /// The unlock callback was called by an account other than the
+    /// `PoolManager`.
+    ///
+    /// * `caller` - Account that called the callback.
+    #[derive(Debug)]
+    #[allow(missing_docs)]
+    error UniswapV4NotPoolManager(address caller);
+}
+
+/// An error that occurred while handling an unlock callback.
+#[derive(SolidityError, Debug, ErrorDisplay)]
+pub enum Error {
+    /// The callback was called by an account other than the `PoolManager`.
+    NotPoolManager(UniswapV4NotPoolManager),
+}
+
+/// Identifies a pool.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PoolKey {
+    /// The lower currency of the pool, sorted numerically.
+    pub currency0: Address,
+    /// The higher currency of the pool, sorted numerically.
+    pub currency1: Address,
+    /// Fee of the pool, in hundredths of a bip.
+    pub fee: u32,
+    /// Spacing between the ticks liquidity can be provided at.
+    pub tick_spacing: i32,
+    /// Hooks contract of the pool.
+    pub hooks: Address,
+}
+
+impl PoolKey {
+    /// Returns the key as the tuple the `PoolManager` expects.
+    fn to_tuple(self) -> (Address, Address, u32, i32, Address) {
+        (
+            self.currency0,
+            self.currency1,
+            self.fee,
+            self.tick_spacing,
+            self.hooks,
+        )
+    }
+}
+
+/// Parameters of a swap.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SwapParams {
+    /// Whether `currency0` is swapped for `currency1`, or the other way
+    /// around.
+    pub zero_for_one: bool,
+    /// Amount to swap, which is an exact input when negative and an exact
+    /// output when positive.
+    pub amount_specified: I256,
+    /// Price the swap stops at, as a Q64.96 square root.
+    pub sqrt_price_limit_x96: U160,
+}
+
+/// Parameters of a liquidity modification.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ModifyLiquidityParams {
+    /// Lower tick of the position.
+    pub tick_lower: i32,
+    /// Upper tick of the position.
+    pub tick_upper: i32,
+    /// Liquidity added when positive, or removed when negative.
+    pub liquidity_delta: I256,
+    /// Distinguishes positions of the same owner over the same range.
+    pub salt: B256,
+}
+
+/// Balance changes of both currencies of a pool, packed as the `PoolManager`
+/// returns them.
+///
+/// Negative amounts are owed to the `PoolManager`, and positive ones are
+/// owed by it.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct BalanceDelta(pub I256);
+
+impl BalanceDelta {
+    /// Packs `amount0` and `amount1` into a delta.
+    #[must_use]
+    pub fn new(amount0: i128, amount1: i128) -> Self {
+        let mut bytes = [0; 32];
+        bytes[..16].copy_from_slice(&amount0.to_be_bytes());
+        bytes[16..].copy_from_slice(&amount1.to_be_bytes());
+        BalanceDelta(I256::from_be_bytes(bytes))
+    }
+
+    /// Returns the change of `currency0`.
+    #[must_use]
+    pub fn amount0(self) -> i128 {
+        let mut amount = [0; 16];
+        amount.copy_from_slice(&self.0.to_be_bytes::<32>()[..16]);
+        i128::from_be_bytes(amount)
+    }
+
+    /// Returns the change of `currency1`.
+    #[must_use]
+    pub fn amount1(self) -> i128 {
+        let mut amount = [0; 16];
+        amount.copy_from_slice(&self.0.to_be_bytes::<32>()[16..]);
+        i128::from_be_bytes(amount)
+    }
+}
+
+/// Interface of the contracts unlocking the `PoolManager`.
+///
+/// Implementations must be exposed as `unlockCallback(bytes)`, which the
+/// `PoolManager` calls back within [`PoolManager::unlock`].
+pub trait IUnlockCallback {
+    /// The error type associated to this trait implementation.
+    type Error: Into<Vec<u8>>;
+
+    /// Operates on the unlocked `PoolManager`.
+    ///
+    /// Every balance owed to or by the caller must be resolved by the time it
+    /// returns, e.g., with [`PoolManager::resolve`], or the whole unlock
+    /// reverts.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the contract's state.
+    /// * `data` - Data passed to [`PoolManager::unlock`].
+    ///
+    /// # Errors
+    ///
+    /// Implementations should check the callback is called by the
+    /// `PoolManager`, see [`PoolManager::only_pool_manager`].
+    fn unlock_callback(&mut self, data: Bytes) -> Result<Bytes, Self::Error>;
+}
+
+/// A Uniswap v4 `PoolManager` contract.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PoolManager {
+    /// Address of the contract.
+    pub address: Address,
+}
+
+impl PoolManager {
+    /// Creates a handle to the `PoolManager` contract at `address`.
+    #[must_use]
+    pub fn new(address: Address) -> Self {
+        PoolManager { address }
+    }
+
+    /// Checks that the function is called by the `PoolManager`, as unlock
+    /// callbacks must be.
+    ///
+    /// # Errors
+    ///
+    /// If the caller is not the `PoolManager`, then the error
+    /// [`Error::NotPoolManager`] is returned.
+    pub fn only_pool_manager(&self) -> Result<(), Error> {
+        let caller = msg::sender();
+        if caller != self.address {
+            return Err(Error::NotPoolManager(UniswapV4NotPoolManager {
+                caller,
+            }));
+        }
+        Ok(())
+    }
+
+    /// Unlocks the `PoolManager`, which calls back the calling contract's
+    /// [`IUnlockCallback::unlock_callback`] with `data`, and returns what the
+    /// callback returned.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the `PoolManager` configuration.
+    /// * `storage` - The calling contract.
+    /// * `data` - Data passed along to the callback.
+    ///
+    /// # Errors
+    ///
+    /// If the callback reverts, or leaves balances unresolved, then the
+    /// error of the call is returned.
+    pub fn unlock(
+        &self,
+        storage: &mut impl TopLevelStorage,
+        data: Vec<u8>,
+    ) -> Result<Vec<u8>, CallError> {
+        IPoolManager::new(self.address).unlock(storage, data)
+    }
+
+    /// Swaps within the pool identified by `key`, and returns the balance
+    /// changes of the calling contract.
+    ///
+    /// Must be called while unlocked, from the unlock callback.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the `PoolManager` configuration.
+    /// * `storage` - The calling contract.
+    /// * `key` - Pool to swap in.
+    /// * `params` - Parameters of the swap.
+    /// * `hook_data` - Data passed along to the hooks of the pool.
+    ///
+    /// # Errors
+    ///
+    /// If the swap fails, then the error of the call is returned.
+    pub fn swap(
+        &self,
+        storage: &mut impl TopLevelStorage,
+        key: PoolKey,
+        params: SwapParams,
+        hook_data: Vec<u8>,
+    ) -> Result<BalanceDelta, CallError> {
+        let params = (
+            params.zero_for_one,
+            params.amount_specified,
+            U256::from(params.sqrt_price_limit_x96),
+        );
+        IPoolManager::new(self.address)
+            .swap(storage, key.to_tuple(), params, hook_data)
+            .map(BalanceDelta)
+    }
+
+    /// Adds or removes liquidity of the calling contract's position in the
+    /// pool identified by `key`.
+    ///
+    /// Returns the balance changes of the calling contract, fees included,
+    /// and the fees accrued by the position.
+    ///
+    /// Must be called while unlocked, from the unlock callback.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the `PoolManager` configuration.
+    /// * `storage` - The calling contract.
+    /// * `key` - Pool of the position.
+    /// * `params` - Parameters of the modification.
+    /// * `hook_data` - Data passed along to the hooks of the pool.
+    ///
+    /// # Errors
+    ///
+    /// If the modification fails, then the error of the call is returned.
+    pub fn modify_liquidity(
+        &self,
+        storage: &mut impl TopLevelStorage,
+        key: PoolKey,
+        params: ModifyLiquidityParams,
+        hook_data: Vec<u8>,
+    ) -> Result<(BalanceDelta, BalanceDelta), CallError> {
+        // `sol_interface!` encodes `bytes32` arguments as `bytes[32]` in
+        // selectors, so that the call is made by hand.
+        let call = modifyLiquidityCall {
+            key: UniswapV4PoolKey {
+                currency0: key.currency0,
+                currency1: key.currency1,
+                fee: key.fee,
+                tick_spacing: key.tick_spacing,
+                hooks: key.hooks,
+            },
+            params: UniswapV4ModifyLiquidityParams {
+                tick_lower: params.tick_lower,
+                tick_upper: params.tick_upper,
+                liquidity_delta: params.liquidity_delta,
+                salt: params.salt.0,
+            },
+            hook_data,
+        };
+        let returned =
+            call::call(Call::new_in(storage), self.address, &call.encode())?;
+        let returns = modifyLiquidityCall::decode_returns(&returned, true)?;
+        Ok((
+            BalanceDelta(returns.caller_delta),
+            BalanceDelta(returns.fees_accrued),
+        ))
+    }
+
+    /// Pays `amount` of `currency` owed by the calling contract to the
+    /// `PoolManager`.
+    ///
+    /// ETH is sent along with the settlement, while tokens are first synced,
+    /// then transferred to the `PoolManager`.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the `PoolManager` configuration.
+    /// * `storage` - The calling contract.
+    /// * `currency` - Currency to pay, [`NATIVE`] for ETH.
+    /// * `amount` - Amount to pay.
+    ///
+    /// # Errors
+    ///
+    /// If one of the calls fails, e.g., because the calling contract holds
+    /// less than `amount`, then the error of the call is returned.
+    pub fn settle(
+        &self,
+        storage: &mut impl TopLevelStorage,
+        currency: Address,
+        amount: U256,
+    ) -> Result<(), CallError> {
+        let manager = IPoolManager::new(self.address);
+        if currency == NATIVE {
+            manager.settle(Call::new_in(storage).value(amount))?;
+            return Ok(());
+        }
+
+        manager.sync(&mut *storage, currency)?;
+        // Tokens not returning a value are not supported by the binding, and
+        // a transfer returning `false` leaves the balance unsettled, so that
+        // the unlock reverts anyway.
+        IERC20Minimal::new(currency).transfer(
+            &mut *storage,
+            self.address,
+            amount,
+        )?;
+        manager.settle(Call::new_in(storage))?;
+        Ok(())
+    }
+
+    /// Withdraws `amount` of `currency` owed by the `PoolManager` to `to`.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the `PoolManager` configuration.
+    /// * `storage` - The calling contract.
+    /// * `currency` - Currency to withdraw, [`NATIVE`] for ETH.
+    /// * `to` - Account receiving the currency.
+    /// * `amount` - Amount to withdraw.
+    ///
+    /// # Errors
+    ///
+    /// If the withdrawal fails, then the error of the call is returned.
+    pub fn take(
+        &self,
+        storage: &mut impl TopLevelStorage,
+        currency: Address,
+        to: Address,
+        amount: U256,
+    ) -> Result<(), CallError> {
+        IPoolManager::new(self.address).take(storage, currency, to, amount)
+    }
+
+    /// Resolves `delta` of the pool identified by `key`: amounts owed to the
+    /// `PoolManager` are settled from the calling contract, and amounts owed
+    /// by it are taken to `recipient`.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the `PoolManager` configuration.
+    /// * `storage` - The calling contract.
+    /// * `key` - Pool the delta was returned for.
+    /// * `delta` - Balance changes to resolve.
+    /// * `recipient` - Account receiving the amounts owed by the `PoolManager`.
+    ///
+    /// # Errors
+    ///
+    /// If settling or taking one of the currencies fails, then the error of
+    /// the call is returned.
+    pub fn resolve(
+        &self,
+        storage: &mut impl TopLevelStorage,
+        key: PoolKey,
+        delta: BalanceDelta,
+        recipient: Address,
+    ) -> Result<(), CallError> {
+        for (currency, amount) in
+            [(key.currency0, delta.amount0()), (key.currency1, delta.amount1())]
+        {
+            let value = U256::from(amount.unsigned_abs());
+            if amount < 0 {
+                self.settle(storage, currency, value)?;
+            } else if amount > 0 {
+                self.take(storage, currency, recipient, value)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use alloy_primitives::{address, hex, uint, Address, B256, I256, U256};
+    use alloy_sol_types::{sol, SolCall};
+    use motsu::prelude::{Caller, VM};
+    use stylus_sdk::contract;
+
+    use super::{
+        modifyLiquidityCall, BalanceDelta, Error, ModifyLiquidityParams,
+        PoolKey, PoolManager, SwapParams, NATIVE,
+    };
+
+    const MANAGER: Address =
+        address!("000000000004444c5dc75cB358380D2e3dE08A90");
+    const TOKEN: Address = address!("F4EaCDAbEf3c8f1EdE91b6f2A6840bc2E4DD3526");
+    const ALICE: Address = address!("0BB78F7e7132d1651B4Fd884B7624394e92156F1");
+
+    const KEY: PoolKey = PoolKey {
+        currency0: NATIVE,
+        currency1: TOKEN,
+        fee: 3000,
+        tick_spacing: 60,
+        hooks: Address::ZERO,
+    };
+
+    sol! {
+        function swap((address, address, uint24, int24, address) key, (bool, int256, uint160) params, bytes hook_data) external returns (int256);
+        function sync(address currency) external;
+        function settle() external payable returns (uint256);
+        function take(address currency, address to, uint256 amount) external;
+        function transfer(address to, uint256 value) external returns (bool);
+    }
+
+    #[motsu::test]
+    fn packs_balance_deltas() {
+        let delta = BalanceDelta::new(-5, 7);
+        assert_eq!(delta.amount0(), -5);
+        assert_eq!(delta.amount1(), 7);
+
+        let delta = BalanceDelta::new(i128::MAX, i128::MIN);
+        assert_eq!(delta.amount0(), i128::MAX);
+        assert_eq!(delta.amount1(), i128::MIN);
+        assert_eq!(BalanceDelta::default(), BalanceDelta::new(0, 0));
+    }
+
+    #[motsu::test]
+    fn swaps() {
+        let expected = BalanceDelta::new(-100, 95);
+        VM::context().mock_call(
+            MANAGER,
+            &swapCall::SELECTOR,
+            expected.0.to_be_bytes::<32>(),
+        );
+
+        let params = SwapParams {
+            zero_for_one: true,
+            amount_specified: I256::try_from(-100).unwrap(),
+            sqrt_price_limit_x96: uint!(4295128740_U160),
+        };
+        let delta = PoolManager::new(MANAGER)
+            .swap(&mut Caller, KEY, params, vec![])
+            .unwrap();
+        assert_eq!(delta, expected);
+    }
+
+    #[motsu::test]
+    fn modifies_liquidity() {
+        assert_eq!(modifyLiquidityCall::SELECTOR, hex!("5a6bcfda"));
+
+        let salt = B256::repeat_byte(7);
+        let mut calldata = hex!("5a6bcfda").to_vec();
+        for word in [
+            U256::ZERO,
+            U256::from_be_slice(TOKEN.as_slice()),
+            U256::from(3000),
+            U256::from(60),
+            U256::ZERO,
+            I256::try_from(-120).unwrap().into_raw(),
+            U256::from(120),
+            U256::from(1_000),
+            U256::from_be_bytes(salt.0),
+            // Offset and length of the hook data.
+            U256::from(0x140),
+            U256::from(1),
+        ] {
+            calldata.extend_from_slice(&word.to_be_bytes::<32>());
+        }
+        calldata.push(0xab);
+        calldata.extend_from_slice(&[0; 31]);
+
+        let delta = BalanceDelta::new(-1_000, -1_000);
+        let fees = BalanceDelta::new(3, 0);
+        let mut returned = delta.0.to_be_bytes::<32>().to_vec();
+        returned.extend_from_slice(&fees.0.to_be_bytes::<32>());
+        VM::context().mock_call(MANAGER, &calldata, returned);
+
+        let params = ModifyLiquidityParams {
+            tick_lower: -120,
+            tick_upper: 120,
+            liquidity_delta: I256::try_from(1_000).unwrap(),
+            salt,
+        };
+        let result = PoolManager::new(MANAGER)
+            .modify_liquidity(&mut Caller, KEY, params, vec![0xab])
+            .unwrap();
+        assert_eq!(result, (delta, fees));
+    }
+
+    #[motsu::test]
+    fn resolves_deltas() {
+        VM::context().fund(contract::address(), uint!(100_U256));
+        VM::context().mock_call(MANAGER, &settleCall::SELECTOR, [0; 32]);
+        VM::context().mock_call(MANAGER, &takeCall::SELECTOR, vec![]);
+
+        let delta = BalanceDelta::new(-100, 95);
+        PoolManager::new(MANAGER)
+            .resolve(&mut Caller, KEY, delta, ALICE)
+            .unwrap();
+        assert_eq!(VM::context().balance_of(MANAGER), uint!(100_U256));
+    }
+
+    #[motsu::test]
+    fn settles_tokens() {
+        let manager = PoolManager::new(MANAGER);
+        assert!(manager.settle(&mut Caller, TOKEN, U256::from(1)).is_err());
+
+        VM::context().mock_call(MANAGER, &syncCall::SELECTOR, vec![]);
+        VM::context().mock_call(
+            TOKEN,
+            &transferCall::SELECTOR,
+            U256::from(1).to_be_bytes::<32>(),
+        );
+        VM::context().mock_call(MANAGER, &settleCall::SELECTOR, [0; 32]);
+        assert!(manager.settle(&mut Caller, TOKEN, U256::from(1)).is_ok());
+    }
+
+    #[motsu::test]
+    fn allows_callbacks_from_pool_manager() {
+        VM::context().set_msg_sender(MANAGER);
+        assert!(PoolManager::new(MANAGER).only_pool_manager().is_ok());
+    }
+
+    #[motsu::test]
+    fn rejects_callbacks_from_others() {
+        VM::context().set_msg_sender(ALICE);
+        let result = PoolManager::new(MANAGER).only_pool_manager();
+        assert!(
+            matches!(result, Err(Error::NotPoolManager(e)) if e.caller == ALICE)
+        );
+    }
+}
@@ -0,0 +1,123 @@
+//! Low-level calls to other accounts.
+//!
+//! Once a call returns, the caller pays for the memory it copies the return
+//! data to. A callee that returns, or reverts with, a huge payload can thus
+//! make its caller run out of gas, even when the call itself is allowed to
+//! fail. [`excessively_safe_call`] guards against these return bombs by
+//! bounding both the gas forwarded to the callee and the bytes copied back:
+//!
+//! ```rust,ignore
+//! let data = IERC721Receiver::onERC721ReceivedCall { .. }.abi_encode();
+//! match excessively_safe_call(to, 30_000, U256::ZERO, 32, &data) {
+//!     Ok(returned) => check_selector(&returned),
+//!     Err(_) => revert_with_receiver_error(),
+//! }
+//! ```
+use alloc::vec::Vec;
+
+use alloy_primitives::{Address, U256};
+use stylus_sdk::call::RawCall;
+
+/// Calls `target` with `data`, sending `value` wei along, and copies at most
+/// `max_copy` bytes of the data it returns.
+///
+/// Returns the copied return data if the call succeeds, or the copied revert
+/// data if it fails.
+///
+/// # Arguments
+///
+/// * `target` - Called account.
+/// * `gas` - Maximum gas forwarded to `target`, clipped to the gas remaining
+///   under the 63/64 rule.
+/// * `value` - Value sent with the call, in wei.
+/// * `max_copy` - Maximum number of bytes of return or revert data copied.
+/// * `data` - Calldata of the call.
+///
+/// # Errors
+///
+/// If the call to `target` reverts, or the contract can't afford `value`, then
+/// the first `max_copy` bytes of the revert data are returned.
+pub fn excessively_safe_call(
+    target: Address,
+    gas: u64,
+    value: U256,
+    max_copy: usize,
+    data: &[u8],
+) -> Result<Vec<u8>, Vec<u8>> {
+    RawCall::new_with_value(value)
+        .gas(gas)
+        .limit_return_data(0, max_copy)
+        .call(target, data)
+}
+
+/// Static calls `target` with `data`, and copies at most `max_copy` bytes of
+/// the data it returns.
+///
+/// See [`excessively_safe_call`] for details.
+///
+/// # Errors
+///
+/// If the call to `target` reverts, then the first `max_copy` bytes of the
+/// revert data are returned.
+pub fn excessively_safe_static_call(
+    target: Address,
+    gas: u64,
+    max_copy: usize,
+    data: &[u8],
+) -> Result<Vec<u8>, Vec<u8>> {
+    RawCall::new_static()
+        .gas(gas)
+        .limit_return_data(0, max_copy)
+        .call(target, data)
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use alloy_primitives::{address, uint, Address, U256};
+    use motsu::prelude::VM;
+    use stylus_sdk::contract;
+
+    use super::{excessively_safe_call, excessively_safe_static_call};
+
+    const TARGET: Address =
+        address!("F4EaCDAbEf3c8f1EdE91b6f2A6840bc2E4DD3526");
+
+    #[motsu::test]
+    fn copies_bounded_return_data() {
+        VM::context().mock_call(TARGET, &[1], vec![7; 10_000]);
+
+        let returned =
+            excessively_safe_call(TARGET, 30_000, U256::ZERO, 32, &[1, 2]);
+        assert_eq!(returned, Ok(vec![7; 32]));
+
+        let returned = excessively_safe_static_call(TARGET, 30_000, 0, &[1]);
+        assert_eq!(returned, Ok(vec![]));
+    }
+
+    #[motsu::test]
+    fn copies_short_return_data() {
+        VM::context().mock_call(TARGET, &[1], vec![7; 4]);
+
+        let returned = excessively_safe_static_call(TARGET, 30_000, 32, &[1]);
+        assert_eq!(returned, Ok(vec![7; 4]));
+    }
+
+    #[motsu::test]
+    fn copies_bounded_revert_data() {
+        VM::context().mock_revert(TARGET, &[1], vec![9; 10_000]);
+
+        let returned =
+            excessively_safe_call(TARGET, 30_000, U256::ZERO, 4, &[1]);
+        assert_eq!(returned, Err(vec![9; 4]));
+    }
+
+    #[motsu::test]
+    fn sends_value() {
+        let value = uint!(10_U256);
+        VM::context().fund(contract::address(), value);
+
+        assert!(excessively_safe_call(TARGET, 30_000, value, 0, &[]).is_ok());
+        assert_eq!(VM::context().balance_of(TARGET), value);
+        assert!(excessively_safe_call(TARGET, 30_000, value, 0, &[]).is_err());
+    }
+}
@@ -0,0 +1,59 @@
+//! Base64 encoding, as defined in [RFC 4648].
+//!
+//! Mostly used to embed data in `data:` URIs, e.g., the on-chain metadata of
+//! a token, see [`crate::utils::metadata::json`].
+//!
+//! [RFC 4648]: https://datatracker.ietf.org/doc/html/rfc4648#section-4
+use alloc::string::String;
+
+/// Characters of the standard Base64 alphabet, indexed by their value.
+const ALPHABET: &[u8; 64] =
+    b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+/// Returns the Base64 encoding of `data`, padded with `=`.
+///
+/// Matches `Base64.encode` of [OpenZeppelin Contracts].
+///
+/// [OpenZeppelin Contracts]: https://github.com/OpenZeppelin/openzeppelin-contracts/blob/v5.0.0/contracts/utils/Base64.sol
+#[must_use]
+pub fn encode(data: &[u8]) -> String {
+    let mut encoded = String::with_capacity(data.len().div_ceil(3) * 4);
+    for chunk in data.chunks(3) {
+        let b0 = chunk[0];
+        let b1 = chunk.get(1).copied().unwrap_or_default();
+        let b2 = chunk.get(2).copied().unwrap_or_default();
+        let sextets = [b0 >> 2, (b0 << 4 | b1 >> 4), (b1 << 2 | b2 >> 6), b2];
+
+        // A chunk of `n` bytes is encoded by `n + 1` characters.
+        for (i, sextet) in sextets.into_iter().enumerate() {
+            if i <= chunk.len() {
+                encoded.push(char::from(ALPHABET[usize::from(sextet & 0x3f)]));
+            } else {
+                encoded.push('=');
+            }
+        }
+    }
+    encoded
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::encode;
+
+    #[test]
+    fn encodes_rfc_vectors() {
+        assert_eq!(encode(b""), "");
+        assert_eq!(encode(b"f"), "Zg==");
+        assert_eq!(encode(b"fo"), "Zm8=");
+        assert_eq!(encode(b"foo"), "Zm9v");
+        assert_eq!(encode(b"foob"), "Zm9vYg==");
+        assert_eq!(encode(b"fooba"), "Zm9vYmE=");
+        assert_eq!(encode(b"foobar"), "Zm9vYmFy");
+    }
+
+    #[test]
+    fn encodes_whole_alphabet() {
+        assert_eq!(encode(&[0xfb, 0xff, 0xbf]), "+/+/");
+        assert_eq!(encode(&[0x00, 0x10, 0x83]), "ABCD");
+    }
+}
@@ -0,0 +1,169 @@
+//! Elliptic Curve Digital Signature Algorithm (ECDSA) operations.
+//!
+//! These functions can be used to verify that a message was signed by the
+//! holder of the private key of a given address. Signatures are recovered
+//! with the `ecrecover` precompile.
+use alloc::vec::Vec;
+
+use alloy_primitives::{address, uint, Address, B256, U256};
+use alloy_sol_types::sol;
+use openzeppelin_stylus_proc::ErrorDisplay;
+use stylus_proc::SolidityError;
+use stylus_sdk::call::RawCall;
+
+/// Address of the `ecrecover` precompile.
+const ECRECOVER: Address = address!("0000000000000000000000000000000000000001");
+
+/// Half of the order of the `secp256k1` curve. Signatures with a higher `s`
+/// value are malleable, and rejected.
+pub const SECP256K1N_HALF: U256 = uint!(
+    0x7FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFF5D576E7357A4501DDFE92F46681B20A0_U256
+);
+
+sol! {
+    /// The signature derives the `Address::ZERO`.
+    #[derive(Debug)]
+    #[allow(missing_docs)]
+    error ECDSAInvalidSignature();
+    /// The signature has an invalid length.
+    ///
+    /// * `length` - Length of the signature.
+    #[derive(Debug)]
+    #[allow(missing_docs)]
+    error ECDSAInvalidSignatureLength(uint256 length);
+    /// The signature has an `s` value in the upper half order.
+    ///
+    /// * `s` - The `s` value of the signature.
+    #[derive(Debug)]
+    #[allow(missing_docs)]
+    error ECDSAInvalidSignatureS(bytes32 s);
+}
+
+/// An error that occurred while recovering a signer.
+#[derive(SolidityError, Debug, ErrorDisplay)]
+pub enum Error {
+    /// The signature derives the `Address::ZERO`.
+    InvalidSignature(ECDSAInvalidSignature),
+    /// The signature has an invalid length.
+    InvalidSignatureLength(ECDSAInvalidSignatureLength),
+    /// The signature has an `s` value in the upper half order.
+    InvalidSignatureS(ECDSAInvalidSignatureS),
+}
+
+/// Returns the address that signed `hash` with `signature`, the 65-byte
+/// concatenation of its `r`, `s` and `v` values.
+///
+/// # Arguments
+///
+/// * `hash` - Hash of the signed message.
+/// * `signature` - The signature, as `r ++ s ++ v`.
+///
+/// # Errors
+///
+/// If `signature` isn't 65 bytes long, then the error
+/// [`Error::InvalidSignatureLength`] is returned.
+/// Otherwise, see [`recover_vrs`].
+pub fn recover(hash: B256, signature: &[u8]) -> Result<Address, Error> {
+    if signature.len() != 65 {
+        return Err(Error::InvalidSignatureLength(
+            ECDSAInvalidSignatureLength { length: U256::from(signature.len()) },
+        ));
+    }
+    let r = B256::from_slice(&signature[..32]);
+    let s = B256::from_slice(&signature[32..64]);
+    recover_vrs(hash, signature[64], r, s)
+}
+
+/// Returns the address that signed `hash` with the signature `(v, r, s)`.
+///
+/// # Arguments
+///
+/// * `hash` - Hash of the signed message.
+/// * `v` - Recovery id of the signature, `27` or `28`.
+/// * `r` - `r` value of the signature.
+/// * `s` - `s` value of the signature.
+///
+/// # Errors
+///
+/// If `s` is in the upper half of the curve order, then the error
+/// [`Error::InvalidSignatureS`] is returned.
+/// If no address can be recovered from the signature, then the error
+/// [`Error::InvalidSignature`] is returned.
+pub fn recover_vrs(
+    hash: B256,
+    v: u8,
+    r: B256,
+    s: B256,
+) -> Result<Address, Error> {
+    if U256::from_be_bytes(s.0) > SECP256K1N_HALF {
+        return Err(Error::InvalidSignatureS(ECDSAInvalidSignatureS {
+            s: s.0,
+        }));
+    }
+
+    let mut input = Vec::with_capacity(128);
+    input.extend_from_slice(hash.as_slice());
+    input.extend_from_slice(&U256::from(v).to_be_bytes::<32>());
+    input.extend_from_slice(r.as_slice());
+    input.extend_from_slice(s.as_slice());
+
+    let signer = RawCall::new_static()
+        .call(ECRECOVER, &input)
+        .ok()
+        .filter(|output| output.len() == 32)
+        .map_or(Address::ZERO, |output| Address::from_slice(&output[12..]));
+    if signer.is_zero() {
+        return Err(Error::InvalidSignature(ECDSAInvalidSignature {}));
+    }
+    Ok(signer)
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use alloy_primitives::{address, b256, hex, Address, B256};
+
+    use super::{recover, recover_vrs, Error};
+
+    const HASH: B256 = b256!(
+        "18c547e4f7b0f325ad1e56f57e26c745b09a3e503d86e00e5255ff7f715d3d1c"
+    );
+    const SIGNER: Address =
+        address!("a94f5374fce5edbc8e2a8697c15331677e6ebf0b");
+
+    fn signature() -> Vec<u8> {
+        hex!(
+            "73b1693892219d736caba55bdb67216e485557ea6b6af75f37096c9aa6a5a75f"
+            "1146bf4e2fc4de1c94f1b81868960f68bc842490f1b6bc98387ba6b57599fbf8"
+            "1b"
+        )
+        .to_vec()
+    }
+
+    #[motsu::test]
+    fn recovers_signer() {
+        assert_eq!(recover(HASH, &signature()).unwrap(), SIGNER);
+    }
+
+    #[motsu::test]
+    fn rejects_invalid_signatures() {
+        let mut signature = signature();
+        signature[64] = 0;
+        let err = recover(HASH, &signature).unwrap_err();
+        assert!(matches!(err, Error::InvalidSignature(_)));
+
+        let err = recover(HASH, &signature[..64]).unwrap_err();
+        assert!(matches!(err, Error::InvalidSignatureLength(_)));
+    }
+
+    #[motsu::test]
+    fn rejects_high_s_signatures() {
+        let err = recover_vrs(
+            HASH,
+            27,
+            B256::repeat_byte(1),
+            B256::repeat_byte(0xff),
+        )
+        .unwrap_err();
+        assert!(matches!(err, Error::InvalidSignatureS(_)));
+    }
+}
@@ -0,0 +1,113 @@
+//! Hashing of [EIP-712] typed structured data.
+//!
+//! Signatures of typed data are only valid for the contract and chain of the
+//! domain they were made for. A contract verifying them hashes the structured
+//! data it receives with [`hash_typed_data`], then recovers the signer of the
+//! digest, e.g., with [`crate::utils::cryptography::ecdsa::recover`]:
+//!
+//! ```rust,ignore
+//! let domain = eip712::domain_separator("MyContract", "1");
+//! let digest = eip712::hash_typed_data(domain, struct_hash);
+//! let signer = ecdsa::recover(digest, &signature)?;
+//! ```
+//!
+//! [EIP-712]: https://eips.ethereum.org/EIPS/eip-712
+use alloc::vec::Vec;
+
+use alloy_primitives::{fixed_bytes, B256, U256};
+use alloy_sol_types::{
+    sol_data::{self, FixedBytes, Uint},
+    SolType,
+};
+use stylus_sdk::{block, contract, crypto::keccak};
+
+/// Type hash of the domain, i.e., `keccak256("EIP712Domain(string name,string
+/// version,uint256 chainId,address verifyingContract)")`.
+pub const TYPE_HASH: B256 = fixed_bytes!(
+    "8b73c3c69bb8fe3d512ecc4cf759cc79239f7b179b0ffacaa9a75d522b39400f"
+);
+
+/// Returns the domain separator of the calling contract on the current chain.
+///
+/// # Arguments
+///
+/// * `name` - Name of the signing domain, e.g., of the contract.
+/// * `version` - Current major version of the signing domain.
+#[must_use]
+pub fn domain_separator(name: &str, version: &str) -> B256 {
+    let encoded = <(
+        FixedBytes<32>,
+        FixedBytes<32>,
+        FixedBytes<32>,
+        Uint<256>,
+        sol_data::Address,
+    )>::encode_params(&(
+        TYPE_HASH.0,
+        keccak(name).0,
+        keccak(version).0,
+        U256::from(block::chainid()),
+        contract::address(),
+    ));
+    keccak(encoded)
+}
+
+/// Returns the digest to sign for the structured data hashed to
+/// `struct_hash`, in the domain of `domain_separator`.
+///
+/// # Arguments
+///
+/// * `domain_separator` - Domain separator, see [`domain_separator`].
+/// * `struct_hash` - The `hashStruct` of the data.
+#[must_use]
+pub fn hash_typed_data(domain_separator: B256, struct_hash: B256) -> B256 {
+    let mut encoded = Vec::with_capacity(66);
+    encoded.extend_from_slice(b"\x19\x01");
+    encoded.extend_from_slice(domain_separator.as_slice());
+    encoded.extend_from_slice(struct_hash.as_slice());
+    keccak(encoded)
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use alloy_primitives::{address, fixed_bytes, keccak256};
+    use motsu::prelude::VM;
+
+    use super::{domain_separator, hash_typed_data, TYPE_HASH};
+
+    #[test]
+    fn type_hash_matches_definition() {
+        assert_eq!(
+            TYPE_HASH,
+            keccak256(
+                "EIP712Domain(string name,string version,uint256 chainId,\
+                 address verifyingContract)"
+            )
+        );
+    }
+
+    #[motsu::test]
+    fn hashes_typed_data() {
+        VM::context().set_chain_id(1);
+        VM::context().set_contract_address(address!(
+            "CcCCccccCCCCcCCCCCCcCcCccCcCCCcCcccccccC"
+        ));
+
+        let domain = domain_separator("Ether Mail", "1");
+        assert_eq!(
+            domain,
+            fixed_bytes!(
+                "f2cee375fa42b42143804025fc449deafd50cc031ca257e0b194a650a912090f"
+            )
+        );
+        // `hashStruct` of the `Mail` of the example of EIP-712.
+        let struct_hash = fixed_bytes!(
+            "c52c0ee5d84264471806290a3f2c4cecfc5490626bf912d01f240d7a274b371e"
+        );
+        assert_eq!(
+            hash_typed_data(domain, struct_hash),
+            fixed_bytes!(
+                "be609aee343fb3c4b28e1df9e632fca64fcfaede20f02e86244efddf30957bd2"
+            )
+        );
+    }
+}
@@ -0,0 +1,142 @@
+//! Standard hashing algorithms that are not natively available in Stylus.
+//!
+//! Both `SHA2-256` and `RIPEMD-160` are exposed by the EVM as precompiled
+//! contracts, which is what [`Hashes`] calls into by default. Enabling the
+//! `pure-rust-hashes` feature computes the digests inside the contract instead,
+//! trading a larger binary for not having to perform an external call.
+use alloy_primitives::{FixedBytes, B256};
+
+/// Address of the `SHA2-256` precompile.
+#[cfg(not(feature = "pure-rust-hashes"))]
+const SHA256_PRECOMPILE: alloy_primitives::Address =
+    alloy_primitives::address!("0000000000000000000000000000000000000002");
+/// Address of the `RIPEMD-160` precompile.
+#[cfg(not(feature = "pure-rust-hashes"))]
+const RIPEMD160_PRECOMPILE: alloy_primitives::Address =
+    alloy_primitives::address!("0000000000000000000000000000000000000003");
+
+/// Hashing algorithms commonly used when interacting with other chains, e.g.,
+/// when verifying Bitcoin SPV proofs or hash time-locked contracts.
+pub struct Hashes;
+
+impl Hashes {
+    /// Returns the `SHA2-256` digest of `data`.
+    ///
+    /// # Arguments
+    ///
+    /// * `data` - The bytes to hash.
+    ///
+    /// # Panics
+    ///
+    /// If the call to the `SHA2-256` precompile fails, which only happens when
+    /// the call runs out of gas.
+    #[must_use]
+    pub fn sha256(data: &[u8]) -> B256 {
+        #[cfg(feature = "pure-rust-hashes")]
+        {
+            use sha2::Digest;
+            B256::from_slice(&sha2::Sha256::digest(data))
+        }
+        #[cfg(not(feature = "pure-rust-hashes"))]
+        {
+            let output = precompile(SHA256_PRECOMPILE, data);
+            B256::from_slice(&output)
+        }
+    }
+
+    /// Returns the `RIPEMD-160` digest of `data`.
+    ///
+    /// # Arguments
+    ///
+    /// * `data` - The bytes to hash.
+    ///
+    /// # Panics
+    ///
+    /// If the call to the `RIPEMD-160` precompile fails, which only happens
+    /// when the call runs out of gas.
+    #[must_use]
+    pub fn ripemd160(data: &[u8]) -> FixedBytes<20> {
+        #[cfg(feature = "pure-rust-hashes")]
+        {
+            use ripemd::Digest;
+            FixedBytes::from_slice(&ripemd::Ripemd160::digest(data))
+        }
+        #[cfg(not(feature = "pure-rust-hashes"))]
+        {
+            // The precompile returns the digest left-padded to 32 bytes.
+            let output = precompile(RIPEMD160_PRECOMPILE, data);
+            FixedBytes::from_slice(&output[12..])
+        }
+    }
+}
+
+/// Performs a static call to the hashing precompile at `address` and returns
+/// its 32-byte output.
+#[cfg(not(feature = "pure-rust-hashes"))]
+fn precompile(address: alloy_primitives::Address, data: &[u8]) -> [u8; 32] {
+    let output = stylus_sdk::call::RawCall::new_static()
+        .call(address, data)
+        .expect("hashing precompile call should succeed");
+    output.try_into().expect("hashing precompile should return 32 bytes")
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use alloy_primitives::{b256, fixed_bytes};
+
+    use super::Hashes;
+
+    #[motsu::test]
+    fn sha256_matches_known_digests() {
+        assert_eq!(
+            Hashes::sha256(b""),
+            b256!(
+                "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855"
+            )
+        );
+        assert_eq!(
+            Hashes::sha256(b"abc"),
+            b256!(
+                "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+            )
+        );
+    }
+
+    #[motsu::test]
+    fn ripemd160_matches_known_digests() {
+        assert_eq!(
+            Hashes::ripemd160(b""),
+            fixed_bytes!("9c1185a5c5e9fc54612808977ee8f548b2258d31")
+        );
+        assert_eq!(
+            Hashes::ripemd160(b"abc"),
+            fixed_bytes!("8eb208f7e05d987a9b044a8e98c6b087f15a0bfc")
+        );
+    }
+
+    #[cfg(not(feature = "pure-rust-hashes"))]
+    #[motsu::test]
+    fn calls_precompiles() {
+        use motsu::prelude::VM;
+
+        use super::{RIPEMD160_PRECOMPILE, SHA256_PRECOMPILE};
+
+        VM::context().mock_call(SHA256_PRECOMPILE, b"abc", vec![0x11; 32]);
+        VM::context().mock_call(RIPEMD160_PRECOMPILE, b"abc", vec![0x22; 32]);
+
+        assert_eq!(Hashes::sha256(b"abc"), [0x11; 32]);
+        assert_eq!(Hashes::ripemd160(b"abc"), [0x22; 20]);
+    }
+
+    #[cfg(not(feature = "pure-rust-hashes"))]
+    #[motsu::test]
+    #[should_panic = "hashing precompile should return 32 bytes"]
+    fn panics_on_malformed_precompile_output() {
+        use motsu::prelude::VM;
+
+        use super::SHA256_PRECOMPILE;
+
+        VM::context().mock_call(SHA256_PRECOMPILE, &[], vec![0x11; 20]);
+        _ = Hashes::sha256(b"abc");
+    }
+}
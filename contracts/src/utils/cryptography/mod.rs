@@ -0,0 +1,6 @@
+//! Cryptographic utilities.
+pub mod ecdsa;
+pub mod eip712;
+pub mod hashes;
+
+pub use hashes::Hashes;
@@ -0,0 +1,111 @@
+//! Initializable Contract.
+//!
+//! Contracts deployed as clones or behind proxies don't run a constructor, so
+//! that their state is set up by an initializer function called right after
+//! their deployment, e.g., by the `TokenFactory` that cloned them.
+//! [`Initializable`] makes sure the initializer only runs once, so that
+//! nobody can initialize the contract again and, e.g., take its ownership:
+//!
+//! ```rust,ignore
+//! pub fn initialize(&mut self, owner: Address) -> Result<(), Vec<u8>> {
+//!     self.initializable._initialize()?;
+//!     self.ownable._transfer_ownership(owner);
+//!     Ok(())
+//! }
+//! ```
+use alloy_sol_types::sol;
+use openzeppelin_stylus_proc::ErrorDisplay;
+use stylus_proc::{sol_storage, SolidityError};
+use stylus_sdk::evm;
+
+use crate::utils::structs::gap::StorageGap;
+
+sol! {
+    /// Emitted when the contract is initialized.
+    ///
+    /// * `version` - Version the contract was initialized to, always `1`.
+    #[allow(missing_docs)]
+    event Initialized(uint64 version);
+}
+
+sol! {
+    /// The contract was already initialized.
+    #[derive(Debug)]
+    #[allow(missing_docs)]
+    error InvalidInitialization();
+}
+
+/// An [`Initializable`] error.
+#[derive(SolidityError, Debug, ErrorDisplay)]
+pub enum Error {
+    /// The contract was already initialized.
+    InvalidInitialization(InvalidInitialization),
+}
+
+sol_storage! {
+    /// State of an Initializable Contract.
+    #[cfg_attr(all(test, feature = "std"), derive(motsu::DefaultStorageLayout))]
+    pub struct Initializable {
+        /// Indicates whether the contract was initialized.
+        bool _initialized;
+        /// Reserved storage slots, see [`StorageGap`].
+        StorageGap<49> __gap;
+    }
+}
+
+impl Initializable {
+    /// Returns true if the contract was initialized, and false otherwise.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the contract's state.
+    #[must_use]
+    pub fn is_initialized(&self) -> bool {
+        self._initialized.get()
+    }
+
+    /// Marks the contract as initialized. Must be called first by the
+    /// initializer of the contract.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the contract's state.
+    ///
+    /// # Errors
+    ///
+    /// If the contract was already initialized, then the error
+    /// [`Error::InvalidInitialization`] is returned.
+    ///
+    /// # Events
+    ///
+    /// Emits an [`Initialized`] event.
+    pub fn _initialize(&mut self) -> Result<(), Error> {
+        if self.is_initialized() {
+            return Err(Error::InvalidInitialization(InvalidInitialization {}));
+        }
+        self._initialized.set(true);
+        evm::log(Initialized { version: 1 });
+        Ok(())
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use motsu::prelude::VM;
+
+    use super::{Error, Initializable, Initialized};
+
+    #[motsu::test]
+    fn initializes_once(contract: Initializable) {
+        assert!(!contract.is_initialized());
+
+        contract._initialize().expect("should initialize");
+        assert!(contract.is_initialized());
+        let events = VM::context().emitted_events::<Initialized>();
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].version, 1);
+
+        let err = contract._initialize().unwrap_err();
+        assert!(matches!(err, Error::InvalidInitialization(_)));
+    }
+}
@@ -1,3 +1,4 @@
 //! Math helpers for `alloy` and Solidity storage types.
 pub mod alloy;
+#[cfg(feature = "erc721")]
 pub mod storage;
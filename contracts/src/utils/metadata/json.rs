@@ -0,0 +1,275 @@
+//! On-chain JSON metadata of tokens.
+//!
+//! [`JsonMetadata`] assembles the metadata of an ERC-721 or ERC-1155 token,
+//! following the metadata JSON schemas of these standards and the common
+//! `attributes` extension, and returns it as a `data:` URI. Token URIs can
+//! then be served without any off-chain storage:
+//!
+//! ```rust,ignore
+//! let uri = JsonMetadata::new()
+//!     .name("Sword #1")
+//!     .description("A fully on-chain sword.")
+//!     .svg_image(r#"<svg xmlns="http://www.w3.org/2000/svg"/>"#)
+//!     .attribute("Rarity", "Legendary")
+//!     .numeric_attribute("Level", U256::from(5))
+//!     .to_data_uri();
+//! ```
+use alloc::{
+    string::{String, ToString},
+    vec::Vec,
+};
+use core::fmt::Write;
+
+use alloy_primitives::U256;
+
+use crate::utils::base64;
+
+/// Value of an attribute of a token.
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum AttributeValue {
+    /// Rendered as a JSON string.
+    String(String),
+    /// Rendered as a JSON number.
+    Number(U256),
+}
+
+/// Attribute of a token, as displayed by marketplaces.
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct Attribute {
+    /// Name of the trait.
+    trait_type: String,
+    /// Value of the trait.
+    value: AttributeValue,
+}
+
+/// Builder of the JSON metadata of a token.
+///
+/// Fields left unset are omitted from the JSON document.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct JsonMetadata {
+    /// Name of the token.
+    name: Option<String>,
+    /// Description of the token.
+    description: Option<String>,
+    /// URI of the image of the token.
+    image: Option<String>,
+    /// URI of a page describing the token.
+    external_url: Option<String>,
+    /// Attributes of the token, in order.
+    attributes: Vec<Attribute>,
+}
+
+impl JsonMetadata {
+    /// Creates empty metadata.
+    #[must_use]
+    pub fn new() -> Self {
+        JsonMetadata::default()
+    }
+
+    /// Sets the name of the token.
+    #[must_use]
+    pub fn name(mut self, name: impl Into<String>) -> Self {
+        self.name = Some(name.into());
+        self
+    }
+
+    /// Sets the description of the token.
+    #[must_use]
+    pub fn description(mut self, description: impl Into<String>) -> Self {
+        self.description = Some(description.into());
+        self
+    }
+
+    /// Sets the URI of the image of the token.
+    #[must_use]
+    pub fn image(mut self, uri: impl Into<String>) -> Self {
+        self.image = Some(uri.into());
+        self
+    }
+
+    /// Sets the image of the token to the SVG document `svg`, embedded in a
+    /// `data:image/svg+xml;base64,...` URI.
+    #[must_use]
+    pub fn svg_image(self, svg: &str) -> Self {
+        self.image(data_uri("image/svg+xml", svg.as_bytes()))
+    }
+
+    /// Sets the URI of a page describing the token.
+    #[must_use]
+    pub fn external_url(mut self, uri: impl Into<String>) -> Self {
+        self.external_url = Some(uri.into());
+        self
+    }
+
+    /// Adds an attribute with a text value.
+    #[must_use]
+    pub fn attribute(
+        mut self,
+        trait_type: impl Into<String>,
+        value: impl Into<String>,
+    ) -> Self {
+        self.attributes.push(Attribute {
+            trait_type: trait_type.into(),
+            value: AttributeValue::String(value.into()),
+        });
+        self
+    }
+
+    /// Adds an attribute with a numeric value, e.g., a level or a stat that
+    /// marketplaces can sort and filter by.
+    #[must_use]
+    pub fn numeric_attribute(
+        mut self,
+        trait_type: impl Into<String>,
+        value: U256,
+    ) -> Self {
+        self.attributes.push(Attribute {
+            trait_type: trait_type.into(),
+            value: AttributeValue::Number(value),
+        });
+        self
+    }
+
+    /// Returns the metadata as a JSON document.
+    #[must_use]
+    pub fn to_json(&self) -> String {
+        let fields = [
+            ("name", &self.name),
+            ("description", &self.description),
+            ("image", &self.image),
+            ("external_url", &self.external_url),
+        ];
+
+        let mut json = String::from("{");
+        for (key, value) in fields {
+            if let Some(value) = value {
+                push_separator(&mut json);
+                push_string(&mut json, key);
+                json.push(':');
+                push_string(&mut json, value);
+            }
+        }
+
+        if !self.attributes.is_empty() {
+            push_separator(&mut json);
+            json.push_str("\"attributes\":[");
+            for (i, attribute) in self.attributes.iter().enumerate() {
+                if i > 0 {
+                    json.push(',');
+                }
+                json.push_str("{\"trait_type\":");
+                push_string(&mut json, &attribute.trait_type);
+                json.push_str(",\"value\":");
+                match &attribute.value {
+                    AttributeValue::String(value) => {
+                        push_string(&mut json, value);
+                    }
+                    AttributeValue::Number(value) => {
+                        json.push_str(&value.to_string());
+                    }
+                }
+                json.push('}');
+            }
+            json.push(']');
+        }
+
+        json.push('}');
+        json
+    }
+
+    /// Returns the metadata as a `data:application/json;base64,...` URI,
+    /// which can be returned as is by `tokenURI` or `uri`.
+    #[must_use]
+    pub fn to_data_uri(&self) -> String {
+        data_uri("application/json", self.to_json().as_bytes())
+    }
+}
+
+/// Returns a `data:` URI embedding `data` of type `media_type`.
+fn data_uri(media_type: &str, data: &[u8]) -> String {
+    let mut uri = String::from("data:");
+    uri.push_str(media_type);
+    uri.push_str(";base64,");
+    uri.push_str(&base64::encode(data));
+    uri
+}
+
+/// Separates a new member of the JSON object being built from the previous
+/// one, if any.
+fn push_separator(json: &mut String) {
+    if !json.ends_with('{') {
+        json.push(',');
+    }
+}
+
+/// Appends `value` to `json` as a JSON string, escaping the characters that
+/// can't appear as is.
+fn push_string(json: &mut String, value: &str) {
+    json.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => json.push_str("\\\""),
+            '\\' => json.push_str("\\\\"),
+            '\n' => json.push_str("\\n"),
+            '\r' => json.push_str("\\r"),
+            '\t' => json.push_str("\\t"),
+            c if c.is_control() => {
+                // Writing to a `String` never fails.
+                let _ = write!(json, "\\u{:04x}", u32::from(c));
+            }
+            c => json.push(c),
+        }
+    }
+    json.push('"');
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use alloy_primitives::uint;
+
+    use super::JsonMetadata;
+
+    #[test]
+    fn builds_empty_metadata() {
+        assert_eq!(JsonMetadata::new().to_json(), "{}");
+    }
+
+    #[test]
+    fn builds_metadata() {
+        let json = JsonMetadata::new()
+            .name("Sword #1")
+            .description("A sword.")
+            .image("ipfs://sword")
+            .external_url("https://example.com/1")
+            .attribute("Rarity", "Legendary")
+            .numeric_attribute("Level", uint!(5_U256))
+            .to_json();
+        assert_eq!(
+            json,
+            r#"{"name":"Sword #1","description":"A sword.","image":"ipfs://sword","external_url":"https://example.com/1","attributes":[{"trait_type":"Rarity","value":"Legendary"},{"trait_type":"Level","value":5}]}"#
+        );
+    }
+
+    #[test]
+    fn escapes_strings() {
+        let json = JsonMetadata::new()
+            .description("\"Quoted\" \\ line\nbreak\u{1}")
+            .to_json();
+        assert_eq!(
+            json,
+            r#"{"description":"\"Quoted\" \\ line\nbreak\u0001"}"#
+        );
+    }
+
+    #[test]
+    fn builds_data_uris() {
+        let uri = JsonMetadata::new().name("A").to_data_uri();
+        assert_eq!(uri, "data:application/json;base64,eyJuYW1lIjoiQSJ9");
+
+        let metadata = JsonMetadata::new().svg_image("<svg/>");
+        assert_eq!(
+            metadata,
+            JsonMetadata::new().image("data:image/svg+xml;base64,PHN2Zy8+")
+        );
+    }
+}
@@ -1,8 +1,14 @@
 //! Common Metadata Smart Contract.
+//!
+//! See [`json`] to build token metadata stored on-chain.
 use alloc::string::String;
 
 use stylus_proc::{external, sol_storage};
 
+use crate::utils::structs::gap::StorageGap;
+
+pub mod json;
+
 sol_storage! {
     /// Metadata of the token.
     pub struct Metadata {
@@ -10,6 +16,8 @@ sol_storage! {
         string _name;
         /// Token symbol.
         string _symbol;
+        /// Reserved storage slots, see [`StorageGap`].
+        StorageGap<48> __gap;
     }
 }
 
@@ -1,8 +1,15 @@
 //! Common Smart Contracts utilities.
+pub mod address;
+pub mod base64;
+pub mod cryptography;
+pub mod initializable;
 pub mod math;
 pub mod metadata;
+#[cfg(feature = "multicall")]
+pub mod multicall;
 pub mod pausable;
 pub mod structs;
 
+pub use initializable::Initializable;
 pub use metadata::Metadata;
 pub use pausable::Pausable;
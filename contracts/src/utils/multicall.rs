@@ -0,0 +1,292 @@
+//! Batching calls to other contracts through Multicall3.
+//!
+//! [`Multicall`] assembles a batch of calls, which are then sent in a single
+//! call to the canonical [Multicall3] contract, deployed at [`MULTICALL3`] on
+//! most chains:
+//!
+//! ```rust,ignore
+//! let results = Multicall::new()
+//!     .add(token, IErc20::balanceOfCall { account: alice }.abi_encode())
+//!     .add_allow_failure(token, IErc20::balanceOfCall { account: bob }.abi_encode())
+//!     .call(self)?;
+//! ```
+//!
+//! Note that the calls are made by the Multicall3 contract, so that
+//! `msg::sender()` is its address rather than the calling contract's.
+//!
+//! [Multicall3]: https://github.com/mds1/multicall
+// `sol_interface!` doesn't document the items it generates.
+#![allow(missing_docs)]
+use alloc::vec::Vec;
+
+use alloy_primitives::{address, Address, U256};
+use stylus_proc::sol_interface;
+use stylus_sdk::{
+    call::{Call, Error},
+    storage::TopLevelStorage,
+};
+
+/// Address of the canonical Multicall3 contract.
+pub const MULTICALL3: Address =
+    address!("cA11bde05977b3631167028862bE2a173976CA11");
+
+sol_interface! {
+    /// The canonical Multicall3 contract.
+    interface IMulticall3 {
+        function aggregate3((address, bool, bytes)[] calldata calls) external payable returns ((bool, bytes)[] memory);
+        function aggregate3Value((address, bool, uint256, bytes)[] calldata calls) external payable returns ((bool, bytes)[] memory);
+        function tryAggregate(bool require_success, (address, bytes)[] calldata calls) external payable returns ((bool, bytes)[] memory);
+    }
+}
+
+/// A call in a [`Multicall`] batch.
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct Call3 {
+    /// Called contract.
+    target: Address,
+    /// Whether the batch still succeeds if this call fails.
+    allow_failure: bool,
+    /// Value sent with the call, in wei.
+    value: U256,
+    /// Calldata of the call.
+    calldata: Vec<u8>,
+}
+
+/// Result of a call in a [`Multicall`] batch.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CallResult {
+    /// Whether the call succeeded.
+    pub success: bool,
+    /// Data returned by the call, or revert data if it failed.
+    pub return_data: Vec<u8>,
+}
+
+/// A batch of calls, sent through Multicall3.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Multicall {
+    /// Address of the Multicall3 contract.
+    multicall: Option<Address>,
+    /// Calls of the batch, in order.
+    calls: Vec<Call3>,
+}
+
+impl Multicall {
+    /// Creates an empty batch, sent through [`MULTICALL3`].
+    #[must_use]
+    pub fn new() -> Self {
+        Multicall::default()
+    }
+
+    /// Sends the batch through the Multicall3 contract at `address`, rather
+    /// than through [`MULTICALL3`].
+    #[must_use]
+    pub fn at(mut self, address: Address) -> Self {
+        self.multicall = Some(address);
+        self
+    }
+
+    /// Adds a call to `target` with `calldata`, which must succeed for the
+    /// batch to succeed.
+    #[must_use]
+    pub fn add(self, target: Address, calldata: impl Into<Vec<u8>>) -> Self {
+        self.push(target, false, U256::ZERO, calldata.into())
+    }
+
+    /// Adds a call to `target` with `calldata`, which may fail without
+    /// failing the batch.
+    #[must_use]
+    pub fn add_allow_failure(
+        self,
+        target: Address,
+        calldata: impl Into<Vec<u8>>,
+    ) -> Self {
+        self.push(target, true, U256::ZERO, calldata.into())
+    }
+
+    /// Adds a call to `target` with `calldata`, sending `value` wei along,
+    /// which must succeed for the batch to succeed.
+    #[must_use]
+    pub fn add_with_value(
+        self,
+        target: Address,
+        calldata: impl Into<Vec<u8>>,
+        value: U256,
+    ) -> Self {
+        self.push(target, false, value, calldata.into())
+    }
+
+    /// Returns the number of calls in the batch.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.calls.len()
+    }
+
+    /// Returns whether the batch has no calls.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.calls.is_empty()
+    }
+
+    /// Returns the total value sent with the calls of the batch, in wei.
+    #[must_use]
+    pub fn value(&self) -> U256 {
+        self.calls.iter().map(|call| call.value).sum()
+    }
+
+    /// Sends the batch, returning the result of every call, in order.
+    ///
+    /// Batches sending value use `aggregate3Value`, so that the calling
+    /// contract must hold [`Multicall::value`] wei, and the others use
+    /// `aggregate3`.
+    ///
+    /// # Arguments
+    ///
+    /// * `self` - The batch to send.
+    /// * `storage` - The calling contract.
+    ///
+    /// # Errors
+    ///
+    /// If a call that doesn't allow failure fails, or if the Multicall3
+    /// contract can't be called, then the error of the call is returned.
+    pub fn call(
+        self,
+        storage: &mut impl TopLevelStorage,
+    ) -> Result<Vec<CallResult>, Error> {
+        let multicall = IMulticall3::new(self.multicall.unwrap_or(MULTICALL3));
+        let value = self.value();
+
+        let results = if value.is_zero() {
+            let calls = self
+                .calls
+                .into_iter()
+                .map(|call| (call.target, call.allow_failure, call.calldata))
+                .collect();
+            multicall.aggregate_3(storage, calls)?
+        } else {
+            let calls = self
+                .calls
+                .into_iter()
+                .map(|call| {
+                    (call.target, call.allow_failure, call.value, call.calldata)
+                })
+                .collect();
+            let call = Call::new_in(storage).value(value);
+            multicall.aggregate_3_value(call, calls)?
+        };
+
+        Ok(results
+            .into_iter()
+            .map(|(success, return_data)| CallResult { success, return_data })
+            .collect())
+    }
+
+    /// Adds a call to the batch.
+    fn push(
+        mut self,
+        target: Address,
+        allow_failure: bool,
+        value: U256,
+        calldata: Vec<u8>,
+    ) -> Self {
+        self.calls.push(Call3 { target, allow_failure, value, calldata });
+        self
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use alloy_primitives::{address, uint, Address};
+    use alloy_sol_types::{
+        sol,
+        sol_data::{Array, Bool, Bytes},
+        SolCall, SolType,
+    };
+    use motsu::prelude::{Caller, VM};
+    use stylus_sdk::contract;
+
+    use super::{CallResult, Multicall, MULTICALL3};
+
+    const TOKEN: Address = address!("F4EaCDAbEf3c8f1EdE91b6f2A6840bc2E4DD3526");
+
+    sol! {
+        function aggregate3((address, bool, bytes)[] calls) external payable returns ((bool, bytes)[]);
+        function aggregate3Value((address, bool, uint256, bytes)[] calls) external payable returns ((bool, bytes)[]);
+    }
+
+    /// Mocks the function of Multicall3 with `selector` to return `results`.
+    fn mock_results(selector: [u8; 4], results: Vec<(bool, Vec<u8>)>) {
+        VM::context().mock_call(
+            MULTICALL3,
+            &selector,
+            <(Array<(Bool, Bytes)>,) as SolType>::encode(&(results,)),
+        );
+    }
+
+    #[motsu::test]
+    fn builds_batches() {
+        let batch = Multicall::new()
+            .add(TOKEN, vec![1])
+            .add_allow_failure(TOKEN, vec![2])
+            .add_with_value(TOKEN, vec![3], uint!(5_U256))
+            .add_with_value(TOKEN, vec![4], uint!(7_U256));
+
+        assert_eq!(batch.len(), 4);
+        assert!(!batch.is_empty());
+        assert_eq!(batch.value(), uint!(12_U256));
+        assert!(Multicall::new().is_empty());
+    }
+
+    #[motsu::test]
+    fn aggregates_calls() {
+        mock_results(
+            aggregate3Call::SELECTOR,
+            vec![(true, vec![1]), (false, vec![])],
+        );
+
+        let results = Multicall::new()
+            .add(TOKEN, vec![1])
+            .add_allow_failure(TOKEN, vec![2])
+            .call(&mut Caller)
+            .unwrap();
+        assert_eq!(
+            results,
+            vec![
+                CallResult { success: true, return_data: vec![1] },
+                CallResult { success: false, return_data: vec![] },
+            ]
+        );
+    }
+
+    #[motsu::test]
+    fn aggregates_calls_with_value() {
+        let value = uint!(10_U256);
+        VM::context().fund(contract::address(), value);
+        mock_results(aggregate3ValueCall::SELECTOR, vec![(true, vec![])]);
+
+        let results = Multicall::new()
+            .add_with_value(TOKEN, vec![], value)
+            .call(&mut Caller)
+            .unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(VM::context().balance_of(MULTICALL3), value);
+    }
+
+    #[motsu::test]
+    fn sends_through_custom_multicall() {
+        let multicall = address!("0BB78F7e7132d1651B4Fd884B7624394e92156F1");
+        VM::context().mock_call(
+            multicall,
+            &aggregate3Call::SELECTOR,
+            <(Array<(Bool, Bytes)>,) as SolType>::encode(&(vec![],)),
+        );
+        VM::context().mock_revert(
+            MULTICALL3,
+            &aggregate3Call::SELECTOR,
+            vec![],
+        );
+
+        let result = Multicall::new().at(multicall).call(&mut Caller);
+        assert_eq!(result.unwrap(), vec![]);
+        assert!(Multicall::new().call(&mut Caller).is_err());
+    }
+}
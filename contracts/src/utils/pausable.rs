@@ -11,9 +11,12 @@
 //! only once the modifiers are put in place.
 
 use alloy_sol_types::sol;
+use openzeppelin_stylus_proc::ErrorDisplay;
 use stylus_proc::{external, sol_storage, SolidityError};
 use stylus_sdk::{evm, msg};
 
+use crate::utils::structs::gap::StorageGap;
+
 sol! {
     /// Emitted when pause is triggered by `account`.
     #[allow(missing_docs)]
@@ -39,7 +42,7 @@ sol! {
 }
 
 /// A Pausable error.
-#[derive(SolidityError, Debug)]
+#[derive(SolidityError, Debug, ErrorDisplay)]
 pub enum Error {
     /// Indicates an error related to the operation that failed
     /// because the contract had been in `Paused` state.
@@ -55,6 +58,8 @@ sol_storage! {
     pub struct Pausable {
         /// Indicates whether the contract is `Paused`.
         bool _paused;
+        /// Reserved storage slots, see [`StorageGap`].
+        StorageGap<49> __gap;
     }
 }
 
@@ -0,0 +1,122 @@
+//! Slots reserved for future versions of a contract.
+//!
+//! Contracts behind a proxy lay out their components one after the other, so
+//! that a component gaining a field in a new version would move the state of
+//! every component declared after it. Instead, components reserve a number
+//! of slots with a [`StorageGap`] as their last field, named `__gap`, and new
+//! fields take slots from it:
+//!
+//! ```rust,ignore
+//! sol_storage! {
+//!     pub struct Erc20 {
+//!         mapping(address => uint256) _balances;
+//!         mapping(address => mapping(address => uint256)) _allowances;
+//!         uint256 _total_supply;
+//!         StorageGap<47> __gap;
+//!     }
+//! }
+//! ```
+//!
+//! By convention, the fields and the gap of a component add up to 50 slots,
+//! and the gap shrinks by as many slots as the fields added before it take.
+//! `motsu::assert_layout_compatible!` recognizes fields named `__gap`,
+//! and checks that the fields added in a new version fit in the gap of the
+//! old one.
+use alloy_primitives::U256;
+use stylus_sdk::storage::{StorageGuard, StorageGuardMut, StorageType};
+
+/// `N` storage slots reserved for fields added in future versions of a
+/// contract.
+///
+/// It can't be read nor written, and only moves the fields declared after it.
+#[derive(Debug)]
+pub struct StorageGap<const N: usize>;
+
+impl<const N: usize> StorageType for StorageGap<N> {
+    type Wraps<'a>
+        = StorageGuard<'a, Self>
+    where
+        Self: 'a;
+    type WrapsMut<'a>
+        = StorageGuardMut<'a, Self>
+    where
+        Self: 'a;
+
+    const REQUIRED_SLOTS: usize = N;
+
+    unsafe fn new(_slot: U256, _offset: u8) -> Self {
+        StorageGap
+    }
+
+    fn load<'s>(self) -> Self::Wraps<'s> {
+        StorageGuard::new(self)
+    }
+
+    fn load_mut<'s>(self) -> Self::WrapsMut<'s> {
+        StorageGuardMut::new(self)
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use alloy_primitives::uint;
+    use motsu::layout::StorageLayout;
+    use stylus_proc::sol_storage;
+
+    use super::StorageGap;
+
+    sol_storage! {
+        #[derive(motsu::DefaultStorageLayout)]
+        pub struct Component {
+            uint256 value;
+            StorageGap<49> __gap;
+        }
+
+        #[derive(motsu::DefaultStorageLayout)]
+        pub struct ComponentV2 {
+            uint256 value;
+            address owner;
+            bool paused;
+            StorageGap<48> __gap;
+        }
+
+        #[derive(motsu::DefaultStorageLayout)]
+        pub struct ComponentV3 {
+            uint256 value;
+            address owner;
+            bool paused;
+            uint256 cap;
+            StorageGap<46> __gap;
+        }
+
+        #[derive(motsu::DefaultStorageLayout)]
+        pub struct Contract {
+            Component component;
+            uint256 next;
+        }
+    }
+
+    #[test]
+    fn reserves_slots() {
+        let layout = Component::storage_layout();
+        assert_eq!(layout[1].slot, uint!(1_U256));
+        assert_eq!(layout[1].slots, 49);
+
+        // The Stylus SDK doesn't count the last slot of the fields preceding
+        // a nested struct or array in the size of a struct, so that the next
+        // field starts in the last slot of the gap, which is never written.
+        let layout = Contract::storage_layout();
+        assert_eq!(layout[1].slot, uint!(49_U256));
+    }
+
+    #[test]
+    fn new_fields_take_gap_slots() {
+        motsu::assert_layout_compatible!(Component, ComponentV2);
+    }
+
+    #[test]
+    #[should_panic = "gap `__gap` at slot 0x3 ends at slot 0x31, instead of 0x32"]
+    fn gaps_must_shrink_by_new_fields() {
+        motsu::assert_layout_compatible!(ComponentV2, ComponentV3);
+    }
+}
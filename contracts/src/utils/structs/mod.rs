@@ -1,2 +1,3 @@
 //! Solidity storage types used by other contracts.
 pub mod bitmap;
+pub mod gap;
@@ -0,0 +1,5 @@
+// SPDX-License-Identifier: MIT
+pragma solidity ^0.8.21;
+
+// Clones don't run a constructor, and are set up with `initialize` instead.
+contract Erc20CloneExample {}
@@ -0,0 +1,62 @@
+#![cfg_attr(not(test), no_std, no_main)]
+extern crate alloc;
+
+use alloc::{string::String, vec::Vec};
+
+use alloy_primitives::{Address, U256};
+use openzeppelin_stylus::{
+    access::ownable::{self, Ownable, OwnableInvalidOwner},
+    token::erc20::{extensions::Erc20Metadata, Erc20},
+    utils::Initializable,
+};
+use stylus_sdk::prelude::{entrypoint, external, sol_storage};
+
+sol_storage! {
+    /// Implementation of the tokens deployed by a `TokenFactory`, whose
+    /// clones are set up by `initialize` rather than by a constructor.
+    #[entrypoint]
+    struct Erc20CloneExample {
+        #[borrow]
+        Erc20 erc20;
+        #[borrow]
+        Erc20Metadata metadata;
+        #[borrow]
+        Ownable ownable;
+        Initializable initializable;
+    }
+}
+
+#[external]
+#[inherit(Erc20, Erc20Metadata, Ownable)]
+impl Erc20CloneExample {
+    /// Sets the name, symbol and owner of the token. Can only be called once,
+    /// which the `TokenFactory` deploying the clone does right away.
+    pub fn initialize(
+        &mut self,
+        name: String,
+        symbol: String,
+        owner: Address,
+    ) -> Result<(), Vec<u8>> {
+        self.initializable._initialize()?;
+        if owner.is_zero() {
+            return Err(ownable::Error::InvalidOwner(OwnableInvalidOwner {
+                owner,
+            })
+            .into());
+        }
+        self.metadata._metadata._name.set_str(name);
+        self.metadata._metadata._symbol.set_str(symbol);
+        self.ownable._transfer_ownership(owner);
+        Ok(())
+    }
+
+    pub fn mint(
+        &mut self,
+        account: Address,
+        value: U256,
+    ) -> Result<(), Vec<u8>> {
+        self.ownable.only_owner()?;
+        self.erc20._mint(account, value)?;
+        Ok(())
+    }
+}
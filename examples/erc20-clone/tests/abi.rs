@@ -0,0 +1,22 @@
+#![allow(dead_code)]
+use alloy::sol;
+
+sol!(
+    #[sol(rpc)]
+    contract Erc20Clone {
+        function name() external view returns (string name);
+        function symbol() external view returns (string symbol);
+        function owner() external view returns (address owner);
+        function balanceOf(address account) external view returns (uint256 balance);
+
+        function initialize(string name, string symbol, address owner) external;
+        function mint(address account, uint256 amount) external;
+
+        error InvalidInitialization();
+        error OwnableInvalidOwner(address owner);
+        error OwnableUnauthorizedAccount(address account);
+
+        #[derive(Debug, PartialEq)]
+        event Initialized(uint64 version);
+    }
+);
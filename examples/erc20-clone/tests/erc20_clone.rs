@@ -0,0 +1,94 @@
+#![cfg(feature = "e2e")]
+
+use abi::Erc20Clone;
+use alloy::primitives::{Address, U256};
+use e2e::{receipt, send, Account, EventExt, Revert};
+use eyre::Result;
+
+mod abi;
+
+const TOKEN_NAME: &str = "Test Token";
+const TOKEN_SYMBOL: &str = "TTK";
+
+async fn deploy(account: &Account) -> eyre::Result<Address> {
+    e2e::deploy(account.url(), &account.pk(), None).await
+}
+
+// ============================================================================
+// Integration Tests: ERC-20 Clone
+// ============================================================================
+
+#[e2e::test]
+async fn initializes(alice: Account) -> Result<()> {
+    let contract_addr = deploy(&alice).await?;
+    let contract = Erc20Clone::new(contract_addr, &alice.wallet);
+    let alice_addr = alice.address();
+
+    let receipt = receipt!(contract.initialize(
+        TOKEN_NAME.to_owned(),
+        TOKEN_SYMBOL.to_owned(),
+        alice_addr
+    ))?;
+    receipt.emits(Erc20Clone::Initialized { version: 1 });
+
+    let Erc20Clone::nameReturn { name } = contract.name().call().await?;
+    let Erc20Clone::symbolReturn { symbol } = contract.symbol().call().await?;
+    let Erc20Clone::ownerReturn { owner } = contract.owner().call().await?;
+    assert_eq!(name, TOKEN_NAME);
+    assert_eq!(symbol, TOKEN_SYMBOL);
+    assert_eq!(owner, alice_addr);
+
+    let one = U256::from(1);
+    let _ = receipt!(contract.mint(alice_addr, one))?;
+    let Erc20Clone::balanceOfReturn { balance } =
+        contract.balanceOf(alice_addr).call().await?;
+    assert_eq!(balance, one);
+
+    Ok(())
+}
+
+#[e2e::test]
+async fn rejects_second_initialization(
+    alice: Account,
+    bob: Account,
+) -> Result<()> {
+    let contract_addr = deploy(&alice).await?;
+    let contract = Erc20Clone::new(contract_addr, &bob.wallet);
+
+    let _ = receipt!(contract.initialize(
+        TOKEN_NAME.to_owned(),
+        TOKEN_SYMBOL.to_owned(),
+        alice.address()
+    ))?;
+
+    let err = send!(contract.initialize(
+        TOKEN_NAME.to_owned(),
+        TOKEN_SYMBOL.to_owned(),
+        bob.address()
+    ))
+    .expect_err("should not initialize twice");
+    assert!(err.reverted_with(Erc20Clone::InvalidInitialization {}));
+
+    let Erc20Clone::ownerReturn { owner } = contract.owner().call().await?;
+    assert_eq!(owner, alice.address());
+
+    Ok(())
+}
+
+#[e2e::test]
+async fn rejects_zero_address_owner(alice: Account) -> Result<()> {
+    let contract_addr = deploy(&alice).await?;
+    let contract = Erc20Clone::new(contract_addr, &alice.wallet);
+
+    let err = send!(contract.initialize(
+        TOKEN_NAME.to_owned(),
+        TOKEN_SYMBOL.to_owned(),
+        Address::ZERO
+    ))
+    .expect_err("should not initialize with a zero address owner");
+    assert!(err.reverted_with(Erc20Clone::OwnableInvalidOwner {
+        owner: Address::ZERO
+    }));
+
+    Ok(())
+}
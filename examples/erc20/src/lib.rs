@@ -1,23 +1,31 @@
-#![cfg_attr(not(test), no_main, no_std)]
+#![cfg_attr(not(any(test, feature = "export-abi")), no_main, no_std)]
 extern crate alloc;
 
-use alloc::vec::Vec;
-
 use alloy_primitives::{Address, U256};
 use openzeppelin_stylus::{
+    guard,
     token::erc20::{
+        self,
         extensions::{capped, Capped, Erc20Metadata, IErc20Burnable},
         Erc20, IErc20,
     },
-    utils::Pausable,
+    utils::{pausable, Pausable},
+    ErrorDisplay, FromComponentErrors,
 };
 use stylus_sdk::prelude::{entrypoint, external, sol_storage};
 
 const DECIMALS: u8 = 10;
 
+#[derive(FromComponentErrors, Debug, ErrorDisplay)]
+pub enum Error {
+    Erc20(erc20::Error),
+    Capped(capped::Error),
+    Pausable(pausable::Error),
+}
+
 sol_storage! {
     #[entrypoint]
-    struct Erc20Example {
+    pub struct Erc20Example {
         #[borrow]
         Erc20 erc20;
         #[borrow]
@@ -40,18 +48,18 @@ impl Erc20Example {
         DECIMALS
     }
 
-    pub fn burn(&mut self, value: U256) -> Result<(), Vec<u8>> {
-        self.pausable.when_not_paused()?;
-        self.erc20.burn(value).map_err(|e| e.into())
+    #[guard(self.pausable.when_not_paused())]
+    pub fn burn(&mut self, value: U256) -> Result<(), Error> {
+        Ok(self.erc20.burn(value)?)
     }
 
+    #[guard(self.pausable.when_not_paused())]
     pub fn burn_from(
         &mut self,
         account: Address,
         value: U256,
-    ) -> Result<(), Vec<u8>> {
-        self.pausable.when_not_paused()?;
-        self.erc20.burn_from(account, value).map_err(|e| e.into())
+    ) -> Result<(), Error> {
+        Ok(self.erc20.burn_from(account, value)?)
     }
 
     // Add token minting feature.
@@ -59,12 +67,8 @@ impl Erc20Example {
     // Make sure to handle `Capped` properly. You should not call
     // [`Erc20::_update`] to mint tokens -- it will the break `Capped`
     // mechanism.
-    pub fn mint(
-        &mut self,
-        account: Address,
-        value: U256,
-    ) -> Result<(), Vec<u8>> {
-        self.pausable.when_not_paused()?;
+    #[guard(self.pausable.when_not_paused())]
+    pub fn mint(&mut self, account: Address, value: U256) -> Result<(), Error> {
         let max_supply = self.capped.cap();
 
         // Overflow check required.
@@ -87,22 +91,22 @@ impl Erc20Example {
         Ok(())
     }
 
+    #[guard(self.pausable.when_not_paused())]
     pub fn transfer(
         &mut self,
         to: Address,
         value: U256,
-    ) -> Result<bool, Vec<u8>> {
-        self.pausable.when_not_paused()?;
-        self.erc20.transfer(to, value).map_err(|e| e.into())
+    ) -> Result<bool, Error> {
+        Ok(self.erc20.transfer(to, value)?)
     }
 
+    #[guard(self.pausable.when_not_paused())]
     pub fn transfer_from(
         &mut self,
         from: Address,
         to: Address,
         value: U256,
-    ) -> Result<bool, Vec<u8>> {
-        self.pausable.when_not_paused()?;
-        self.erc20.transfer_from(from, to, value).map_err(|e| e.into())
+    ) -> Result<bool, Error> {
+        Ok(self.erc20.transfer_from(from, to, value)?)
     }
 }
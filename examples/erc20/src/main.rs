@@ -0,0 +1,25 @@
+//! Prints the ABI of the contract, as a Solidity interface, or as JSON when
+//! passed `--json`:
+//!
+//! ```sh
+//! cargo run -p erc20-example --features export-abi -- --json
+//! ```
+use erc20_example::Erc20Example;
+use openzeppelin_stylus::{
+    abi::{event, print_json_abi},
+    token::erc20::{Approval, Transfer},
+    utils::pausable::{Paused, Unpaused},
+};
+
+fn main() {
+    if std::env::args().any(|arg| arg == "--json") {
+        print_json_abi::<Erc20Example>(&[
+            event::<Transfer>(),
+            event::<Approval>(),
+            event::<Paused>(),
+            event::<Unpaused>(),
+        ]);
+    } else {
+        erc20_example::print_abi("MIT", "pragma solidity ^0.8.23;");
+    }
+}
@@ -3,7 +3,7 @@
 use alloy::{
     primitives::{Address, U256},
     sol,
-    sol_types::{SolConstructor, SolError},
+    sol_types::SolError,
 };
 use alloy_primitives::uint;
 use e2e::{receipt, send, watch, Account, EventExt, Panic, PanicCode, Revert};
@@ -29,8 +29,7 @@ async fn deploy(
         symbol_: TOKEN_SYMBOL.to_owned(),
         cap_: cap.unwrap_or(CAP),
     };
-    let args = alloy::hex::encode(args.abi_encode());
-    e2e::deploy(rpc_url, private_key, Some(args)).await
+    e2e::deploy_with_constructor(rpc_url, private_key, &args).await
 }
 
 // ============================================================================
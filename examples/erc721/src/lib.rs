@@ -71,14 +71,12 @@ impl Erc721Example {
     ) -> Result<(), Vec<u8>> {
         self.pausable.when_not_paused()?;
 
-        // Retrieve the previous owner.
-        let previous_owner = self.erc721.owner_of(token_id)?;
-
         self.erc721.safe_transfer_from(from, to, token_id)?;
 
-        // Update the extension's state.
+        // Update the extension's state. The transfer fails unless `from`
+        // owned the token, so it is the previous owner.
         self.enumerable._remove_token_from_owner_enumeration(
-            previous_owner,
+            from,
             token_id,
             &self.erc721,
         )?;
@@ -101,14 +99,12 @@ impl Erc721Example {
     ) -> Result<(), Vec<u8>> {
         self.pausable.when_not_paused()?;
 
-        // Retrieve the previous owner.
-        let previous_owner = self.erc721.owner_of(token_id)?;
-
         self.erc721.safe_transfer_from_with_data(from, to, token_id, data)?;
 
-        // Update the extension's state.
+        // Update the extension's state. The transfer fails unless `from`
+        // owned the token, so it is the previous owner.
         self.enumerable._remove_token_from_owner_enumeration(
-            previous_owner,
+            from,
             token_id,
             &self.erc721,
         )?;
@@ -129,14 +125,12 @@ impl Erc721Example {
     ) -> Result<(), Vec<u8>> {
         self.pausable.when_not_paused()?;
 
-        // Retrieve the previous owner.
-        let previous_owner = self.erc721.owner_of(token_id)?;
-
         self.erc721.transfer_from(from, to, token_id)?;
 
-        // Update the extension's state.
+        // Update the extension's state. The transfer fails unless `from`
+        // owned the token, so it is the previous owner.
         self.enumerable._remove_token_from_owner_enumeration(
-            previous_owner,
+            from,
             token_id,
             &self.erc721,
         )?;
@@ -155,8 +149,9 @@ impl Erc721Example {
         let base = self.metadata.base_uri();
         let token_uri = self.uri_storage.token_uri(token_id);
 
-        // If there is no base URI, return the token URI.
-        if base.is_empty() {
+        // If there is no base URI, or the token URI embeds the metadata,
+        // return the token URI.
+        if base.is_empty() || token_uri.starts_with("data:") {
             return token_uri;
         }
 
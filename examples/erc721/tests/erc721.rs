@@ -3,7 +3,6 @@
 use alloy::{
     primitives::{Address, U256},
     sol,
-    sol_types::SolConstructor,
 };
 use alloy_primitives::uint;
 use e2e::{receipt, send, watch, Account, EventExt, Revert};
@@ -27,8 +26,7 @@ async fn deploy(rpc_url: &str, private_key: &str) -> eyre::Result<Address> {
         name_: TOKEN_NAME.to_owned(),
         symbol_: TOKEN_SYMBOL.to_owned(),
     };
-    let args = alloy::hex::encode(args.abi_encode());
-    e2e::deploy(rpc_url, private_key, Some(args)).await
+    e2e::deploy_with_constructor(rpc_url, private_key, &args).await
 }
 
 #[e2e::test]
@@ -6,6 +6,7 @@ use alloc::vec::Vec;
 use alloy_primitives::B256;
 use crypto::{
     merkle::{self, Verifier},
+    poseidon2::Poseidon2Builder,
     KeccakBuilder,
 };
 use stylus_proc::SolidityError;
@@ -68,6 +69,16 @@ impl VerifierContract {
         Verifier::<KeccakBuilder>::verify(&proof, *root, *leaf)
     }
 
+    pub fn verify_poseidon(
+        &self,
+        proof: Vec<B256>,
+        root: B256,
+        leaf: B256,
+    ) -> bool {
+        let proof: Vec<[u8; 32]> = proof.into_iter().map(|m| *m).collect();
+        Verifier::verify_with_builder(&proof, *root, *leaf, &Poseidon2Builder)
+    }
+
     pub fn verify_multi_proof(
         &self,
         proof: Vec<B256>,
@@ -6,7 +6,7 @@ use alloy::{
     providers::Provider,
     rpc::types::{BlockNumberOrTag, Filter},
     sol,
-    sol_types::{SolConstructor, SolError, SolEvent},
+    sol_types::{SolError, SolEvent},
 };
 use e2e::{receipt, send, Account, EventExt, Revert};
 use eyre::Result;
@@ -19,8 +19,7 @@ sol!("src/constructor.sol");
 
 async fn deploy(account: &Account, owner: Address) -> eyre::Result<Address> {
     let args = OwnableExample::constructorCall { initialOwner: owner };
-    let args = alloy::hex::encode(args.abi_encode());
-    e2e::deploy(account.url(), &account.pk(), Some(args)).await
+    e2e::deploy_with_constructor(account.url(), &account.pk(), &args).await
 }
 
 // ============================================================================
@@ -0,0 +1,103 @@
+//! Native benchmarks of the hashing and Merkle proof verification routines.
+// `criterion_group!` and `criterion_main!` declare undocumented functions.
+#![allow(missing_docs)]
+use criterion::{
+    black_box, criterion_group, criterion_main, BenchmarkId, Criterion,
+};
+use crypto::{
+    hash::{commutative_hash_pair, BuildHasher},
+    keccak::KeccakBuilder,
+    merkle::{Tree, Verifier},
+    poseidon2::Poseidon2Builder,
+};
+use rand::{thread_rng, RngCore};
+
+/// Number of leaves of the trees benchmarked.
+const SIZES: [usize; 3] = [16, 1_024, 65_536];
+
+/// Returns `n` random leaves.
+fn leaves(n: usize) -> Vec<[u8; 32]> {
+    let mut rng = thread_rng();
+    (0..n)
+        .map(|_| {
+            let mut leaf = [0u8; 32];
+            rng.fill_bytes(&mut leaf);
+            leaf
+        })
+        .collect()
+}
+
+fn hashing(c: &mut Criterion) {
+    let [a, b] = [[1u8; 32], [2u8; 32]];
+    c.bench_function("commutative_hash_pair", |bench| {
+        bench.iter(|| {
+            commutative_hash_pair(
+                black_box(a),
+                black_box(b),
+                KeccakBuilder.build_hasher(),
+            )
+        });
+    });
+    c.bench_function("commutative_hash_pair_poseidon2", |bench| {
+        bench.iter(|| {
+            commutative_hash_pair(
+                black_box(a),
+                black_box(b),
+                Poseidon2Builder.build_hasher(),
+            )
+        });
+    });
+}
+
+fn verify(c: &mut Criterion) {
+    let mut group = c.benchmark_group("verify");
+    for size in SIZES {
+        let leaves = leaves(size);
+        let tree = Tree::new(&leaves);
+        let leaf = leaves[size / 2];
+        let proof = tree.proof(leaf).expect("should prove a leaf of the tree");
+        group.bench_with_input(
+            BenchmarkId::from_parameter(size),
+            &proof,
+            |bench, proof| {
+                bench.iter(|| {
+                    Verifier::verify(
+                        black_box(proof),
+                        tree.root(),
+                        black_box(leaf),
+                    )
+                });
+            },
+        );
+    }
+    group.finish();
+}
+
+fn verify_multi_proof(c: &mut Criterion) {
+    let mut group = c.benchmark_group("verify_multi_proof");
+    for size in SIZES {
+        let leaves = leaves(size);
+        let tree = Tree::new(&leaves);
+        let proven = &leaves[..8];
+        let multi_proof =
+            tree.multi_proof(proven).expect("should prove leaves of the tree");
+        group.bench_with_input(
+            BenchmarkId::from_parameter(size),
+            &multi_proof,
+            |bench, multi_proof| {
+                bench.iter(|| {
+                    Verifier::verify_multi_proof(
+                        black_box(&multi_proof.proof),
+                        black_box(&multi_proof.proof_flags),
+                        tree.root(),
+                        black_box(&multi_proof.leaves),
+                    )
+                });
+            },
+        );
+    }
+    group.finish();
+}
+
+criterion_group!(benches, hashing, verify, verify_multi_proof);
+criterion_main!(benches);
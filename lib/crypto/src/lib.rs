@@ -7,3 +7,5 @@ pub mod merkle;
 
 pub mod keccak;
 pub use keccak::KeccakBuilder;
+
+pub mod poseidon2;
@@ -317,9 +317,6 @@ where
 }
 
 /// An error that occurred while verifying a multi-proof.
-///
-/// TODO: Once <https://github.com/rust-lang/rust/issues/103765> is resolved,
-/// we should derive `core::error::Error`.
 #[derive(core::fmt::Debug)]
 pub enum MultiProofError {
     /// The proof length does not match the flags.
@@ -353,6 +350,232 @@ impl core::fmt::Display for MultiProofError {
     }
 }
 
+impl core::error::Error for MultiProofError {}
+
+/// A Merkle tree built from a set of leaves, used to generate proofs that can
+/// be checked with [`Verifier`].
+///
+/// The tree is laid out exactly like `OpenZeppelin`'s `StandardMerkleTree`:
+/// leaves are sorted, stored in reverse order at the end of a flat array, and
+/// inner nodes are computed with [`commutative_hash_pair`]. This means roots
+/// and proofs generated here are interchangeable with the ones generated by
+/// the [JavaScript library].
+///
+/// Leaves are expected to be already hashed. Note that `StandardMerkleTree`
+/// double-hashes the abi-encoded values (i.e.,
+/// `keccak256(keccak256(abi.encode(values)))`).
+///
+/// [JavaScript library]: https://github.com/OpenZeppelin/merkle-tree
+#[cfg(feature = "std")]
+pub struct Tree {
+    /// All the nodes of the tree, with the root at index `0`.
+    nodes: Vec<Bytes32>,
+    /// Number of leaves in the tree.
+    leaves: usize,
+}
+
+/// A multi-proof generated by [`Tree::multi_proof`].
+///
+/// Its members can be passed directly to [`Verifier::verify_multi_proof`].
+#[cfg(feature = "std")]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MultiProof {
+    /// The leaves being proven, in the order expected by the verifier.
+    pub leaves: Vec<Bytes32>,
+    /// Sibling hashes needed to rebuild the root.
+    pub proof: Vec<Bytes32>,
+    /// Whether each step consumes a node from `leaves`/inner nodes (`true`)
+    /// or from `proof` (`false`).
+    pub proof_flags: Vec<bool>,
+}
+
+#[cfg(feature = "std")]
+impl Tree {
+    /// Build a Merkle tree from `leaves` using the default `keccak256`
+    /// hashing algorithm.
+    ///
+    /// # Arguments
+    ///
+    /// * `leaves` - The hashed leaves of the tree, in any order.
+    ///
+    /// # Panics
+    ///
+    /// If `leaves` is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use crypto::merkle::{Tree, Verifier};
+    ///
+    /// let leaves = [[1u8; 32], [2u8; 32], [3u8; 32]];
+    /// let tree = Tree::new(&leaves);
+    ///
+    /// let proof = tree.proof(leaves[0]).unwrap();
+    /// assert!(Verifier::verify(&proof, tree.root(), leaves[0]));
+    /// ```
+    #[must_use]
+    pub fn new(leaves: &[Bytes32]) -> Self {
+        Tree::with_builder(leaves, &KeccakBuilder)
+    }
+
+    /// Build a Merkle tree from `leaves` using a custom hashing algorithm
+    /// defined by `builder`.
+    ///
+    /// # Arguments
+    ///
+    /// * `leaves` - The hashed leaves of the tree, in any order.
+    /// * `builder` - A [`BuildHasher`] that represents a hashing algorithm.
+    ///
+    /// # Panics
+    ///
+    /// If `leaves` is empty.
+    pub fn with_builder<B>(leaves: &[Bytes32], builder: &B) -> Self
+    where
+        B: BuildHasher,
+        B::Hasher: Hasher<Output = Bytes32>,
+    {
+        assert!(!leaves.is_empty(), "expected a non-zero number of leaves");
+
+        let mut sorted = leaves.to_vec();
+        sorted.sort_unstable();
+
+        let len = 2 * sorted.len() - 1;
+        let mut nodes = vec![[0u8; 32]; len];
+        for (i, leaf) in sorted.iter().enumerate() {
+            nodes[len - 1 - i] = *leaf;
+        }
+        for i in (0..len - sorted.len()).rev() {
+            nodes[i] = commutative_hash_pair(
+                nodes[2 * i + 1],
+                nodes[2 * i + 2],
+                builder.build_hasher(),
+            );
+        }
+
+        Tree { nodes, leaves: sorted.len() }
+    }
+
+    /// Returns the root of the tree.
+    #[must_use]
+    pub fn root(&self) -> Bytes32 {
+        self.nodes[0]
+    }
+
+    /// Generate a proof that `leaf` is part of the tree.
+    ///
+    /// Returns `None` if `leaf` is not part of the tree.
+    ///
+    /// # Arguments
+    ///
+    /// * `leaf` - The hashed leaf to prove.
+    #[must_use]
+    pub fn proof(&self, leaf: Bytes32) -> Option<Vec<Bytes32>> {
+        let mut index = self.node_index(leaf)?;
+        let mut proof = Vec::new();
+        while index > 0 {
+            proof.push(self.nodes[sibling(index)]);
+            index = parent(index);
+        }
+
+        Some(proof)
+    }
+
+    /// Generate a multi-proof that all `leaves` are part of the tree.
+    ///
+    /// Returns `None` if any of `leaves` is not part of the tree, or if
+    /// `leaves` contains duplicates.
+    ///
+    /// # Arguments
+    ///
+    /// * `leaves` - The hashed leaves to prove, in any order.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use crypto::merkle::{Tree, Verifier};
+    ///
+    /// let leaves = [[1u8; 32], [2u8; 32], [3u8; 32], [4u8; 32]];
+    /// let tree = Tree::new(&leaves);
+    ///
+    /// let multi_proof = tree.multi_proof(&leaves[1..]).unwrap();
+    /// let verification = Verifier::verify_multi_proof(
+    ///     &multi_proof.proof,
+    ///     &multi_proof.proof_flags,
+    ///     tree.root(),
+    ///     &multi_proof.leaves,
+    /// );
+    /// assert!(verification.unwrap());
+    /// ```
+    #[must_use]
+    pub fn multi_proof(&self, leaves: &[Bytes32]) -> Option<MultiProof> {
+        let mut indices = leaves
+            .iter()
+            .map(|&leaf| self.node_index(leaf))
+            .collect::<Option<Vec<_>>>()?;
+        // The verifier expects leaves from right to left, deepest first.
+        indices.sort_unstable_by(|a, b| b.cmp(a));
+        if indices.windows(2).any(|w| w[0] == w[1]) {
+            return None;
+        }
+
+        let mut stack: std::collections::VecDeque<_> =
+            indices.iter().copied().collect();
+        let mut proof = Vec::new();
+        let mut proof_flags = Vec::new();
+        while let Some(&j) = stack.front() {
+            if j == 0 {
+                break;
+            }
+            stack.pop_front();
+
+            let s = sibling(j);
+            if stack.front() == Some(&s) {
+                proof_flags.push(true);
+                stack.pop_front();
+            } else {
+                proof_flags.push(false);
+                proof.push(self.nodes[s]);
+            }
+            stack.push_back(parent(j));
+        }
+
+        if indices.is_empty() {
+            proof.push(self.root());
+        }
+
+        Some(MultiProof {
+            leaves: indices.iter().map(|&i| self.nodes[i]).collect(),
+            proof,
+            proof_flags,
+        })
+    }
+
+    /// Returns the position of `leaf` in the flat node array.
+    fn node_index(&self, leaf: Bytes32) -> Option<usize> {
+        let leaves = &self.nodes[self.nodes.len() - self.leaves..];
+        leaves
+            .iter()
+            .position(|&node| node == leaf)
+            .map(|i| self.nodes.len() - self.leaves + i)
+    }
+}
+
+/// Index of the sibling of the node at `index`.
+#[cfg(feature = "std")]
+fn sibling(index: usize) -> usize {
+    if index & 1 == 1 {
+        index + 1
+    } else {
+        index - 1
+    }
+}
+
+/// Index of the parent of the node at `index`.
+#[cfg(feature = "std")]
+fn parent(index: usize) -> usize {
+    (index - 1) / 2
+}
+
 #[cfg(all(test, feature = "std"))]
 mod tests {
     //! NOTE: The values used as input for these tests were all generated using
@@ -360,8 +583,8 @@ mod tests {
     use hex_literal::hex;
     use rand::{thread_rng, RngCore};
 
-    use super::{Bytes32, KeccakBuilder, Verifier};
-    use crate::hash::{commutative_hash_pair, BuildHasher};
+    use super::{Bytes32, KeccakBuilder, Tree, Verifier};
+    use crate::hash::{commutative_hash_pair, BuildHasher, Hasher};
 
     /// Shorthand for declaring variables converted from a hex literal to a
     /// fixed 32-byte slice.
@@ -663,4 +886,119 @@ mod tests {
         );
         assert!(verification.is_err());
     }
+
+    /// Computes the leaf hash of a `StandardMerkleTree` with a single
+    /// `string` value, i.e., `keccak256(keccak256(abi.encode(value)))`.
+    fn standard_leaf(value: &str) -> Bytes32 {
+        let len = value.len();
+        let mut encoded = vec![0u8; 64 + len.div_ceil(32) * 32];
+        encoded[31] = 0x20;
+        encoded[56..64].copy_from_slice(&(len as u64).to_be_bytes());
+        encoded[64..64 + len].copy_from_slice(value.as_bytes());
+
+        let mut hasher = KeccakBuilder.build_hasher();
+        hasher.update(&encoded);
+        let hash = hasher.finalize();
+
+        let mut hasher = KeccakBuilder.build_hasher();
+        hasher.update(hash);
+        hasher.finalize()
+    }
+
+    fn standard_leaves(values: &str) -> Vec<Bytes32> {
+        values.chars().map(|c| standard_leaf(&c.to_string())).collect()
+    }
+
+    #[test]
+    fn generates_standard_tree_proofs() {
+        // ```js
+        // const merkleTree = StandardMerkleTree.of(toElements('abc'), ['string']);
+        //
+        // const root = merkleTree.root;
+        // const proof = merkleTree.getProof(['a']);
+        // ```
+        bytes! {
+            root = "f2129b5a697531ef818f644564a6552b35c549722385bc52aa7fe46c0b5f46b1";
+            leaf = "9c15a6a0eaeed500fd9eed4cbeab71f797cefcc67bfd46683e4d2e6ff7f06d1c";
+        };
+        let expected = bytes_array! {
+            "19ba6c6333e0e9a15bf67523e0676e2f23eb8e574092552d5e888c64a4bb3681",
+            "9cf5a63718145ba968a01c1d557020181c5b252f665cf7386d370eddb176517b",
+        };
+
+        let leaves = standard_leaves("abc");
+        assert_eq!(leaves[0], leaf);
+
+        let tree = Tree::new(&leaves);
+        assert_eq!(tree.root(), root);
+        assert_eq!(tree.proof(leaf).unwrap(), expected);
+    }
+
+    #[test]
+    fn generates_standard_tree_multi_proofs() {
+        // ```js
+        // const merkleTree = StandardMerkleTree.of(toElements('abcdef'), ['string']);
+        //
+        // const root = merkleTree.root;
+        // const { proof, proofFlags, leaves } = merkleTree.getMultiProof(toElements('bdf'));
+        // const hashes = leaves.map(e => merkleTree.leafHash(e));
+        // ```
+        bytes! {
+            root = "6deb52b5da8fd108f79fab00341f38d2587896634c646ee52e49f845680a70c8";
+        };
+        let leaves = bytes_array! {
+            "19ba6c6333e0e9a15bf67523e0676e2f23eb8e574092552d5e888c64a4bb3681",
+            "c62a8cfa41edc0ef6f6ae27a2985b7d39c7fea770787d7e104696c6e81f64848",
+            "eba909cf4bb90c6922771d7f126ad0fd11dfde93f3937a196274e1ac20fd2f5b",
+        };
+        let proof = bytes_array! {
+            "9a4f64e953595df82d1b4f570d34c4f4f0cfaf729a61e9d60e83e579e1aa283e",
+            "8076923e76cf01a7c048400a2304c9a9c23bbbdac3a98ea3946340fdafbba34f",
+        };
+
+        let tree = Tree::new(&standard_leaves("abcdef"));
+        assert_eq!(tree.root(), root);
+
+        let multi_proof = tree.multi_proof(&standard_leaves("bdf")).unwrap();
+        assert_eq!(multi_proof.leaves, leaves);
+        assert_eq!(multi_proof.proof, proof);
+        assert_eq!(multi_proof.proof_flags, [false, true, false, true]);
+    }
+
+    #[test]
+    fn generated_proofs_are_verified() {
+        let mut leaves = vec![[0u8; 32]; 11];
+        for leaf in &mut leaves {
+            thread_rng().fill_bytes(leaf);
+        }
+        let tree = Tree::new(&leaves);
+        let root = tree.root();
+
+        for &leaf in &leaves {
+            let proof = tree.proof(leaf).unwrap();
+            assert!(Verifier::verify(&proof, root, leaf));
+        }
+
+        for subset in [&leaves[..0], &leaves[..1], &leaves[3..7], &leaves[..]] {
+            let multi_proof = tree.multi_proof(subset).unwrap();
+            let verification = Verifier::verify_multi_proof(
+                &multi_proof.proof,
+                &multi_proof.proof_flags,
+                root,
+                &multi_proof.leaves,
+            );
+            assert!(verification.unwrap());
+        }
+    }
+
+    #[test]
+    fn rejects_proofs_for_unknown_leaves() {
+        let leaves = standard_leaves("abcd");
+        let tree = Tree::new(&leaves);
+        let unknown = standard_leaf("e");
+
+        assert!(tree.proof(unknown).is_none());
+        assert!(tree.multi_proof(&[leaves[0], unknown]).is_none());
+        assert!(tree.multi_proof(&[leaves[0], leaves[0]]).is_none());
+    }
 }
@@ -0,0 +1,227 @@
+//! Arithmetic in the scalar field of the BN254 curve, the field Poseidon2 is
+//! instantiated over.
+use core::ops::{Add, Mul};
+
+/// Modulus of the field, as little-endian 64-bit limbs.
+const MODULUS: [u64; 4] = [
+    0x43e1_f593_f000_0001,
+    0x2833_e848_79b9_7091,
+    0xb850_45b6_8181_585d,
+    0x3064_4e72_e131_a029,
+];
+
+/// `R^2 mod MODULUS`, with `R = 2^256`, used to convert to Montgomery form.
+const R2: [u64; 4] = [
+    0x1bb8_e645_ae21_6da7,
+    0x53fe_3ab1_e35c_59e3,
+    0x8c49_833d_53bb_8085,
+    0x0216_d0b1_7f4e_44a5,
+];
+
+/// `-MODULUS^-1 mod 2^64`.
+const INV: u64 = 0xc2e1_f593_efff_ffff;
+
+/// An element of the field, kept in Montgomery form.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) struct Fr([u64; 4]);
+
+impl Fr {
+    /// The additive identity.
+    pub(crate) const ZERO: Fr = Fr([0; 4]);
+
+    /// Parses a canonical element from 64 big-endian hexadecimal digits.
+    ///
+    /// # Panics
+    ///
+    /// If `hex` isn't made of 64 hexadecimal digits. Only meant to be used
+    /// for constants, so that it panics at compile time.
+    pub(crate) const fn from_hex(hex: &str) -> Fr {
+        let hex = hex.as_bytes();
+        assert!(hex.len() == 64, "should be 64 hexadecimal digits");
+
+        let mut limbs = [0u64; 4];
+        let mut i = 0;
+        while i < 64 {
+            let digit = match hex[i] {
+                b'0'..=b'9' => hex[i] - b'0',
+                b'a'..=b'f' => hex[i] - b'a' + 10,
+                _ => panic!("should be lowercase hexadecimal digits"),
+            };
+            let limb = 3 - i / 16;
+            limbs[limb] = limbs[limb] << 4 | digit as u64;
+            i += 1;
+        }
+        Fr(mont_mul(&limbs, &R2))
+    }
+
+    /// Interprets `bytes` as a big-endian integer, reduced modulo the field
+    /// modulus.
+    pub(crate) fn from_be_bytes(bytes: &[u8; 32]) -> Fr {
+        let mut limbs = [0u64; 4];
+        for (limb, chunk) in limbs.iter_mut().rev().zip(bytes.chunks_exact(8)) {
+            let mut word = [0u8; 8];
+            word.copy_from_slice(chunk);
+            *limb = u64::from_be_bytes(word);
+        }
+        // Multiplying any 256-bit integer by `R2` yields a reduced element.
+        Fr(mont_mul(&limbs, &R2))
+    }
+
+    /// Returns the canonical big-endian encoding of the element.
+    pub(crate) fn to_be_bytes(self) -> [u8; 32] {
+        let limbs = mont_mul(&self.0, &[1, 0, 0, 0]);
+        let mut bytes = [0u8; 32];
+        for (chunk, limb) in bytes.chunks_exact_mut(8).zip(limbs.iter().rev()) {
+            chunk.copy_from_slice(&limb.to_be_bytes());
+        }
+        bytes
+    }
+
+    /// Returns `self^5`, the S-box of Poseidon2 over this field.
+    pub(crate) fn pow5(self) -> Fr {
+        let square = self * self;
+        square * square * self
+    }
+}
+
+impl Add for Fr {
+    type Output = Fr;
+
+    fn add(self, rhs: Fr) -> Fr {
+        let mut sum = [0u64; 4];
+        let mut carry = 0;
+        for ((limb, &a), &b) in sum.iter_mut().zip(&self.0).zip(&rhs.0) {
+            (*limb, carry) = adc(a, b, carry);
+        }
+        // Both operands are reduced and the modulus is below `2^254`, so the
+        // sum doesn't overflow.
+        Fr(reduce(sum))
+    }
+}
+
+impl Mul for Fr {
+    type Output = Fr;
+
+    fn mul(self, rhs: Fr) -> Fr {
+        Fr(mont_mul(&self.0, &rhs.0))
+    }
+}
+
+/// Returns `a + b + carry`, along with the carry out.
+#[allow(clippy::cast_possible_truncation)]
+const fn adc(a: u64, b: u64, carry: u64) -> (u64, u64) {
+    let sum = a as u128 + b as u128 + carry as u128;
+    (sum as u64, (sum >> 64) as u64)
+}
+
+/// Returns `a + b * c + carry`, along with the carry out.
+#[allow(clippy::cast_possible_truncation)]
+const fn mac(a: u64, b: u64, c: u64, carry: u64) -> (u64, u64) {
+    let sum = a as u128 + b as u128 * c as u128 + carry as u128;
+    (sum as u64, (sum >> 64) as u64)
+}
+
+/// Returns `a mod MODULUS`, assuming `a < 2 * MODULUS`.
+const fn reduce(a: [u64; 4]) -> [u64; 4] {
+    let mut difference = [0u64; 4];
+    let mut borrow = false;
+    let mut i = 0;
+    while i < 4 {
+        let (d, b1) = a[i].overflowing_sub(MODULUS[i]);
+        let (d, b2) = d.overflowing_sub(borrow as u64);
+        difference[i] = d;
+        borrow = b1 || b2;
+        i += 1;
+    }
+    if borrow {
+        a
+    } else {
+        difference
+    }
+}
+
+/// Returns `a * b * R^-1 mod MODULUS`, with the CIOS method.
+///
+/// The result is reduced as long as `a * b < R * MODULUS`, e.g., if `b` is
+/// reduced.
+const fn mont_mul(a: &[u64; 4], b: &[u64; 4]) -> [u64; 4] {
+    let mut product = [0u64; 6];
+    let mut i = 0;
+    while i < 4 {
+        let mut carry = 0;
+        let mut j = 0;
+        while j < 4 {
+            (product[j], carry) = mac(product[j], a[j], b[i], carry);
+            j += 1;
+        }
+        (product[4], product[5]) = adc(product[4], carry, 0);
+
+        let factor = product[0].wrapping_mul(INV);
+        (_, carry) = mac(product[0], factor, MODULUS[0], 0);
+        j = 1;
+        while j < 4 {
+            (product[j - 1], carry) =
+                mac(product[j], factor, MODULUS[j], carry);
+            j += 1;
+        }
+        (product[3], carry) = adc(product[4], carry, 0);
+        product[4] = product[5] + carry;
+        i += 1;
+    }
+    // The result is below `2 * MODULUS < 2^255`, so `product[4]` is zero.
+    reduce([product[0], product[1], product[2], product[3]])
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use hex_literal::hex;
+
+    use super::Fr;
+
+    const MODULUS_BYTES: [u8; 32] = hex!(
+        "30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000001"
+    );
+
+    fn fr(n: u8) -> Fr {
+        let mut bytes = [0u8; 32];
+        bytes[31] = n;
+        Fr::from_be_bytes(&bytes)
+    }
+
+    #[test]
+    fn round_trips_bytes() {
+        let bytes = hex!(
+            "1d066a255517b7fd8bddd3a93f7804ef7f8fcde48bb4c37a59a09a1a97052816"
+        );
+        assert_eq!(Fr::from_be_bytes(&bytes).to_be_bytes(), bytes);
+        assert_eq!(
+            Fr::from_hex(
+                "1d066a255517b7fd8bddd3a93f7804ef7f8fcde48bb4c37a59a09a1a97052816"
+            ),
+            Fr::from_be_bytes(&bytes)
+        );
+    }
+
+    #[test]
+    fn reduces_bytes() {
+        assert_eq!(Fr::from_be_bytes(&MODULUS_BYTES), Fr::ZERO);
+        let reduced = hex!(
+            "0e0a77c19a07df2f666ea36f7879462e36fc76959f60cd29ac96341c4ffffffa"
+        );
+        assert_eq!(Fr::from_be_bytes(&[0xff; 32]).to_be_bytes(), reduced);
+    }
+
+    #[test]
+    fn adds_and_multiplies() {
+        let minus_one = {
+            let mut bytes = MODULUS_BYTES;
+            bytes[31] -= 1;
+            Fr::from_be_bytes(&bytes)
+        };
+        assert_eq!(minus_one + fr(1), Fr::ZERO);
+        assert_eq!(minus_one + fr(3), fr(2));
+        assert_eq!(fr(6) * fr(7), fr(42));
+        assert_eq!(minus_one * minus_one, fr(1));
+        assert_eq!(fr(3).pow5(), fr(243));
+    }
+}
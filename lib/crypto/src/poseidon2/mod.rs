@@ -0,0 +1,260 @@
+//! An instance of the [Poseidon2] hash function, for Merkle trees built for
+//! zero-knowledge proofs.
+//!
+//! Poseidon2 operates on field elements rather than bytes, which makes it far
+//! cheaper than `keccak256` to prove in a circuit. [`Poseidon2Builder`] plugs
+//! it into the [`merkle`][crate::merkle] verifier, so that a contract can
+//! check proofs of the same trees as the circuits of a zk airdrop:
+//!
+//! ```rust
+//! use crypto::{merkle::Verifier, poseidon2::Poseidon2Builder};
+//! # let (proof, root, leaf) = ([[0u8; 32]], [0u8; 32], [0u8; 32]);
+//!
+//! let verified =
+//!     Verifier::verify_with_builder(&proof, root, leaf, &Poseidon2Builder);
+//! # assert!(!verified);
+//! ```
+//!
+//! The permutation is instantiated over the scalar field of BN254, with a
+//! state of 3 elements, and matches the reference implementation. Pairs of
+//! nodes are compressed as in the Merkle trees of the reference
+//! implementation, by permuting `[left, right, 0]` and keeping the first
+//! element.
+//!
+//! [`Poseidon2`] is only this compression function, not a general purpose
+//! hash: it hashes exactly two 32-byte nodes, and panics on any other input.
+//! Without length padding, shorter inputs would collide with some pair of
+//! nodes, e.g., the single node `x` with the pair `(x, 0)`.
+//!
+//! WARNING: Nodes are 32-byte big-endian encodings of field elements, and are
+//! reduced modulo the field order before being hashed. Two leaves congruent
+//! modulo the order, e.g., `x` and `x + p`, are thus part of the same trees.
+//! Contracts that track used leaves, e.g., to prevent claiming an airdrop
+//! twice, should compute leaves from their own data, or only accept
+//! canonical ones.
+//!
+//! [Poseidon2]: https://eprint.iacr.org/2023/323
+use crate::hash::{BuildHasher, Hasher};
+
+mod constants;
+mod field;
+
+use constants::{FULL_ROUNDS, PARTIAL_ROUNDS};
+use field::Fr;
+
+/// Length of the input of the compression function, in bytes.
+const INPUT_LEN: usize = 64;
+
+/// Diagonal of the internal matrix, minus the identity.
+const INTERNAL_DIAGONAL: [u8; 3] = [1, 1, 2];
+
+/// A [`Hasher`] builder for [`Poseidon2`].
+#[allow(clippy::module_name_repetitions)]
+pub struct Poseidon2Builder;
+
+impl BuildHasher for Poseidon2Builder {
+    type Hasher = Poseidon2;
+
+    #[inline]
+    fn build_hasher(&self) -> Self::Hasher {
+        Poseidon2 { input: [0; INPUT_LEN], len: 0 }
+    }
+}
+
+/// The Poseidon2 compression function of Merkle trees, over the BN254 scalar
+/// field.
+///
+/// Hashes exactly two nodes, given as 32-byte big-endian field elements, by
+/// permuting `[left, right, 0]` and keeping the first element.
+pub struct Poseidon2 {
+    /// Bytes of the two nodes being hashed.
+    input: [u8; INPUT_LEN],
+    /// Number of bytes in `input`.
+    len: usize,
+}
+
+impl Hasher for Poseidon2 {
+    type Output = [u8; 32];
+
+    /// Absorbs part of the two nodes being hashed.
+    ///
+    /// # Panics
+    ///
+    /// If more than two 32-byte nodes are hashed.
+    fn update(&mut self, input: impl AsRef<[u8]>) {
+        let input = input.as_ref();
+        let end = self.len + input.len();
+        assert!(end <= INPUT_LEN, "Poseidon2 hashes exactly two 32-byte nodes");
+        self.input[self.len..end].copy_from_slice(input);
+        self.len = end;
+    }
+
+    /// Compresses the two nodes.
+    ///
+    /// # Panics
+    ///
+    /// If less than two 32-byte nodes were hashed.
+    fn finalize(self) -> Self::Output {
+        assert_eq!(
+            self.len, INPUT_LEN,
+            "Poseidon2 hashes exactly two 32-byte nodes"
+        );
+        let (left, right) = self.input.split_at(32);
+        let mut state = [
+            Fr::from_be_bytes(left.try_into().unwrap()),
+            Fr::from_be_bytes(right.try_into().unwrap()),
+            Fr::ZERO,
+        ];
+        permute(&mut state);
+        state[0].to_be_bytes()
+    }
+}
+
+/// Applies the Poseidon2 permutation to `state`.
+fn permute(state: &mut [Fr; 3]) {
+    let (first_full_rounds, last_full_rounds) =
+        FULL_ROUNDS.split_at(FULL_ROUNDS.len() / 2);
+
+    external_linear_layer(state);
+    for constants in first_full_rounds {
+        full_round(state, constants);
+    }
+    for &constant in &PARTIAL_ROUNDS {
+        state[0] = (state[0] + constant).pow5();
+        internal_linear_layer(state);
+    }
+    for constants in last_full_rounds {
+        full_round(state, constants);
+    }
+}
+
+/// Adds `constants` to `state`, applies the S-box to every element and mixes
+/// them.
+fn full_round(state: &mut [Fr; 3], constants: &[Fr; 3]) {
+    for (element, &constant) in state.iter_mut().zip(constants) {
+        *element = (*element + constant).pow5();
+    }
+    external_linear_layer(state);
+}
+
+/// Multiplies `state` by the circulant matrix `circ(2, 1, 1)`.
+fn external_linear_layer(state: &mut [Fr; 3]) {
+    let sum = state[0] + state[1] + state[2];
+    for element in state.iter_mut() {
+        *element = *element + sum;
+    }
+}
+
+/// Multiplies `state` by the matrix `diag(INTERNAL_DIAGONAL) + 1`.
+fn internal_linear_layer(state: &mut [Fr; 3]) {
+    let sum = state[0] + state[1] + state[2];
+    for (element, &diagonal) in state.iter_mut().zip(&INTERNAL_DIAGONAL) {
+        if diagonal == 2 {
+            *element = *element + *element;
+        }
+        *element = *element + sum;
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use hex_literal::hex;
+
+    use super::{field::Fr, permute, Poseidon2Builder};
+    use crate::{
+        hash::{commutative_hash_pair, BuildHasher, Hasher},
+        merkle::Verifier,
+    };
+
+    fn element(n: u8) -> [u8; 32] {
+        let mut bytes = [0u8; 32];
+        bytes[31] = n;
+        bytes
+    }
+
+    fn hash(a: [u8; 32], b: [u8; 32]) -> [u8; 32] {
+        commutative_hash_pair(a, b, Poseidon2Builder.build_hasher())
+    }
+
+    #[test]
+    fn matches_reference_permutation() {
+        let mut state = [0, 1, 2].map(|n| Fr::from_be_bytes(&element(n)));
+        permute(&mut state);
+        assert_eq!(
+            state.map(Fr::to_be_bytes),
+            [
+                hex!("0bb61d24daca55eebcb1929a82650f328134334da98ea4f847f760054f4a3033"),
+                hex!("303b6f7c86d043bfcbcc80214f26a30277a15d3f74ca654992defe7ff8d03570"),
+                hex!("1ed25194542b12eef8617361c3ba7c52e660b145994427cc86296242cf766ec8"),
+            ]
+        );
+    }
+
+    #[test]
+    fn compresses_pairs() {
+        assert_eq!(
+            hash(element(1), element(2)),
+            hex!("2afac3bdc3663b71eefeecdf21b147d0ba7dd7a169a7757c05ed6bfb065bffd2")
+        );
+        assert_eq!(hash(element(2), element(1)), hash(element(1), element(2)));
+    }
+
+    #[test]
+    fn hashes_nodes_in_pieces() {
+        let mut hasher = Poseidon2Builder.build_hasher();
+        let input = [element(1), element(2)].concat();
+        hasher.update(&input[..20]);
+        hasher.update(&input[20..]);
+        assert_eq!(hasher.finalize(), hash(element(1), element(2)));
+    }
+
+    #[test]
+    #[should_panic = "Poseidon2 hashes exactly two 32-byte nodes"]
+    fn rejects_partial_nodes() {
+        let mut hasher = Poseidon2Builder.build_hasher();
+        hasher.update(element(1));
+        hasher.update([0u8]);
+        _ = hasher.finalize();
+    }
+
+    #[test]
+    #[should_panic = "Poseidon2 hashes exactly two 32-byte nodes"]
+    fn rejects_single_node() {
+        // Would collide with the pair `(0, 0)` otherwise.
+        _ = Poseidon2Builder.hash_one(element(0));
+    }
+
+    #[test]
+    #[should_panic = "Poseidon2 hashes exactly two 32-byte nodes"]
+    fn rejects_more_than_two_nodes() {
+        let mut hasher = Poseidon2Builder.build_hasher();
+        hasher.update([element(1), element(2), element(3)].concat());
+    }
+
+    #[test]
+    fn verifies_proofs() {
+        let leaves = [1, 2, 3, 4].map(element);
+        let right = hash(leaves[2], leaves[3]);
+        assert_eq!(
+            right,
+            hex!("0f2021db8d04204e74cec23e5bd3fe4562e2cac46ab33fe7310325c5b0d0b1eb")
+        );
+        let root = hex!(
+            "08ba4bdcfe965b5cb148e2f1b09293ca119b0f1fe50e940285058fecc9ec058a"
+        );
+
+        let proof = [leaves[1], right];
+        assert!(Verifier::verify_with_builder(
+            &proof,
+            root,
+            leaves[0],
+            &Poseidon2Builder
+        ));
+        assert!(!Verifier::verify_with_builder(
+            &proof,
+            root,
+            leaves[2],
+            &Poseidon2Builder
+        ));
+    }
+}
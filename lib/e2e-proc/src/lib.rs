@@ -15,7 +15,18 @@ mod test;
 ///     // ...
 /// }
 /// ```
+///
+/// Accounts start with 10 ETH as balance. Use `fund` to set a different
+/// balance, and `accounts` to get that many accounts through a `Vec<Account>`
+/// parameter:
+///
+/// ```rust,ignore
+/// #[e2e::test(fund = "1000 ether", accounts = 5)]
+/// async fn foo(alice: Account, users: Vec<Account>) -> eyre::Result<()> {
+///     // ...
+/// }
+/// ```
 #[proc_macro_attribute]
 pub fn test(attr: TokenStream, input: TokenStream) -> TokenStream {
-    test::test(&attr, input)
+    test::test(attr, input)
 }
@@ -1,6 +1,9 @@
 use proc_macro::TokenStream;
 use quote::quote;
-use syn::{parse_macro_input, FnArg};
+use syn::{
+    parse::Parser, parse_macro_input, punctuated::Punctuated, Expr, FnArg, Lit,
+    MetaNameValue, Token, Type,
+};
 
 /// Shorthand to print nice errors.
 macro_rules! error {
@@ -13,11 +16,56 @@ macro_rules! error {
     }};
 }
 
+/// Arguments of the `#[e2e::test(..)]` attribute.
+#[derive(Default)]
+struct Args {
+    /// Balance of the accounts in ETH, e.g., `"10"`.
+    fund: Option<String>,
+    /// Number of accounts to set up for a `Vec<Account>` parameter.
+    accounts: Option<usize>,
+}
+
+impl Args {
+    /// Parses `fund = "<amount> ether"` and `accounts = <count>` arguments.
+    fn parse(attr: TokenStream) -> syn::Result<Self> {
+        let parser = Punctuated::<MetaNameValue, Token![,]>::parse_terminated;
+        let mut args = Args::default();
+        for arg in parser.parse(attr)? {
+            let Expr::Lit(lit) = &arg.value else {
+                error!(@ arg.value, "expected a literal");
+            };
+
+            if arg.path.is_ident("fund") {
+                let Lit::Str(amount) = &lit.lit else {
+                    error!(@ lit, "expected an amount like \"10 ether\"");
+                };
+                let amount = amount.value();
+                let Some(ether) = amount.strip_suffix("ether") else {
+                    error!(@ lit, "expected an amount like \"10 ether\"");
+                };
+                args.fund = Some(ether.trim().to_owned());
+            } else if arg.path.is_ident("accounts") {
+                let Lit::Int(count) = &lit.lit else {
+                    error!(@ lit, "expected a number of accounts");
+                };
+                args.accounts = Some(count.base10_parse()?);
+            } else {
+                error!(@ arg.path, "unexpected argument, expected `fund` or `accounts`");
+            }
+        }
+        Ok(args)
+    }
+}
+
 /// Defines an end-to-end test that injects test accounts through parameters.
 ///
 /// For more information see [`crate::test`].
-pub(crate) fn test(_attr: &TokenStream, input: TokenStream) -> TokenStream {
+pub(crate) fn test(attr: TokenStream, input: TokenStream) -> TokenStream {
     let item_fn = parse_macro_input!(input as syn::ItemFn);
+    let args = match Args::parse(attr) {
+        Ok(args) => args,
+        Err(error) => return error.to_compile_error().into(),
+    };
     let attrs = &item_fn.attrs;
     let sig = &item_fn.sig;
     let fn_name = &sig.ident;
@@ -25,16 +73,44 @@ pub(crate) fn test(_attr: &TokenStream, input: TokenStream) -> TokenStream {
     let fn_stmts = &item_fn.block.stmts;
     let fn_args = &sig.inputs;
 
-    let account_declarations = fn_args.into_iter().map(|arg| {
+    let new_account = |account_ty: &Type| {
+        if let Some(ether) = &args.fund {
+            quote! { #account_ty::with_balance(#ether).await? }
+        } else {
+            quote! { #account_ty::new().await? }
+        }
+    };
+
+    let mut account_declarations = Vec::new();
+    for arg in fn_args {
         let FnArg::Typed(arg) = arg else {
             error!(arg, "unexpected receiver argument in test signature");
         };
         let account_arg_binding = &arg.pat;
-        let account_ty = &arg.ty;
-        quote! {
-            let #account_arg_binding = #account_ty::new().await?;
-        }
-    });
+
+        let declaration = if let Some(account_ty) = vec_item_type(&arg.ty) {
+            let Some(count) = args.accounts else {
+                error!(
+                    arg.ty,
+                    "`Vec` parameters require the `accounts` argument"
+                );
+            };
+            let new_account = new_account(account_ty);
+            quote! {
+                let mut #account_arg_binding = Vec::with_capacity(#count);
+                for _ in 0..#count {
+                    #account_arg_binding.push(#new_account);
+                }
+            }
+        } else {
+            let new_account = new_account(&arg.ty);
+            quote! {
+                let #account_arg_binding = #new_account;
+            }
+        };
+        account_declarations.push(declaration);
+    }
+
     quote! {
         #( #attrs )*
         #[tokio::test]
@@ -45,3 +121,22 @@ pub(crate) fn test(_attr: &TokenStream, input: TokenStream) -> TokenStream {
     }
     .into()
 }
+
+/// Returns `T` if `ty` is `Vec<T>`.
+fn vec_item_type(ty: &Type) -> Option<&Type> {
+    let Type::Path(path) = ty else {
+        return None;
+    };
+    let segment = path.path.segments.last()?;
+    if segment.ident != "Vec" {
+        return None;
+    }
+    let syn::PathArguments::AngleBracketed(generics) = &segment.arguments
+    else {
+        return None;
+    };
+    match generics.args.first()? {
+        syn::GenericArgument::Type(ty) => Some(ty),
+        _ => None,
+    }
+}
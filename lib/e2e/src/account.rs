@@ -1,16 +1,23 @@
+use std::sync::OnceLock;
+
 use alloy::{
-    network::EthereumWallet,
-    primitives::Address,
+    network::{EthereumWallet, TransactionBuilder},
+    primitives::{
+        utils::{format_ether, parse_ether},
+        Address, U256,
+    },
     providers::{Provider, ProviderBuilder},
+    rpc::types::eth::TransactionRequest,
     signers::local::PrivateKeySigner,
 };
-use eyre::{bail, Result};
+use eyre::{bail, Context, Result};
 use once_cell::sync::Lazy;
 use tokio::sync::{Mutex, MutexGuard};
 
 use crate::{
     environment::get_node_path,
-    system::{Wallet, RPC_URL_ENV_VAR_NAME},
+    node,
+    system::{self, chain_rpc_url, Wallet},
 };
 
 /// Type that corresponds to a test account.
@@ -29,7 +36,16 @@ impl Account {
     ///
     /// May fail if funding the newly created account fails.
     pub async fn new() -> Result<Self> {
-        AccountFactory::create().await
+        AccountFactory::create("10").await
+    }
+
+    /// Create a new account funded with `ether` ETH, e.g., `"1000"`.
+    ///
+    /// # Errors
+    ///
+    /// May fail if funding the newly created account fails.
+    pub async fn with_balance(ether: &str) -> Result<Self> {
+        AccountFactory::create(ether).await
     }
 
     /// Get a hex-encoded String representing this account's private key.
@@ -49,36 +65,194 @@ impl Account {
     pub fn url(&self) -> &str {
         self.wallet.client().transport().url()
     }
+
+    /// Impersonates `address` on the node tests run against, so that tests can
+    /// act as accounts they don't have the private key of, e.g., token
+    /// whales or protocol admins on a fork.
+    ///
+    /// Uses `anvil_impersonateAccount`, so this only works against nodes that
+    /// support it, like `anvil` and `hardhat` forks.
+    ///
+    /// # Errors
+    ///
+    /// May fail if the node doesn't support `anvil_impersonateAccount`.
+    pub async fn impersonate(address: Address) -> Result<Impersonated> {
+        let provider = system::provider();
+        provider
+            .raw_request::<_, ()>("anvil_impersonateAccount".into(), (address,))
+            .await
+            .wrap_err(format!("failed to impersonate {address}"))?;
+        Ok(Impersonated { address, provider })
+    }
+
+    /// Returns this account connected to the rpc endpoint of the chain named
+    /// `chain`, defined by the `RPC_URL_<CHAIN>` environment variable, e.g.,
+    /// `RPC_URL_L1` for `"l1"`.
+    ///
+    /// The returned account has the same signer, so it can be used to exercise
+    /// cross-chain features from both sides. Note that accounts are only
+    /// funded on the chain behind `RPC_URL`.
+    ///
+    /// # Errors
+    ///
+    /// May fail if the `RPC_URL_<CHAIN>` environment variable is not set or is
+    /// not a valid URL.
+    pub fn on(&self, chain: &str) -> Result<Account> {
+        let rpc_url = chain_rpc_url(chain)?
+            .parse()
+            .wrap_err(format!("failed to parse the {chain} rpc url"))?;
+        let wallet = ProviderBuilder::new()
+            .with_recommended_fillers()
+            .wallet(EthereumWallet::from(self.signer.clone()))
+            .on_http(rpc_url);
+        Ok(Account { signer: self.signer.clone(), wallet })
+    }
 }
 
-/// A unit struct used as a synchronization mechanism in
-/// [`SYNC_ACCOUNT_FACTORY`].
-struct AccountFactory;
+/// An account the node sends transactions for without its private key, on
+/// nodes that support impersonation, like `anvil` forks.
+///
+/// Transactions are sent with `eth_sendTransaction`, so they need their
+/// sender set explicitly, e.g., `contract.transfer(to, value).from(whale)`.
+#[derive(Clone, Debug)]
+pub struct Impersonated {
+    /// The impersonated address.
+    pub address: Address,
+    /// An `alloy` provider without a `WalletFiller`, connected to the node
+    /// impersonating `address`.
+    pub provider: system::Provider,
+}
+
+impl Impersonated {
+    /// Stops impersonating this account.
+    ///
+    /// # Errors
+    ///
+    /// May fail if the node doesn't support `anvil_stopImpersonatingAccount`.
+    pub async fn stop(self) -> Result<()> {
+        self.provider
+            .raw_request::<_, ()>(
+                "anvil_stopImpersonatingAccount".into(),
+                (self.address,),
+            )
+            .await
+            .wrap_err(format!("failed to stop impersonating {}", self.address))
+    }
+}
+
+/// Balance the root account is topped up with on top of the requested amount,
+/// in ETH.
+const ROOT_FUNDING: &str = "1000";
+
+/// Balance of the root account kept aside to pay for funding transactions, in
+/// ETH.
+const ROOT_GAS_RESERVE: &str = "1";
+
+/// Bookkeeping of the root account new accounts get funded from, used as a
+/// synchronization mechanism in [`SYNC_ACCOUNT_FACTORY`].
+///
+/// Funding an account through the nitro test node is slow and has to be
+/// synchronized, since it sends from a single "god" wallet. Instead, a root
+/// account is funded once with enough ETH for many accounts, and new accounts
+/// are funded with regular transfers from it. The factory hands out the
+/// nonces of these transfers, so that they can be mined concurrently.
+struct AccountFactory {
+    /// ETH the root account can still hand out before it needs a top-up.
+    allowance: U256,
+    /// Nonce of the next transfer from the root account, if known.
+    nonce: Option<u64>,
+}
 
 impl AccountFactory {
     /// Get access to the factory in a synchronized manner.
     async fn lock() -> MutexGuard<'static, Self> {
-        /// Since after wallet generation accounts get funded in the nitro test
-        /// node from a single "god" wallet, we must synchronize account
-        /// creation (otherwise the nonce will be too low).
         static SYNC_ACCOUNT_FACTORY: Lazy<Mutex<AccountFactory>> =
-            Lazy::new(|| Mutex::new(AccountFactory));
+            Lazy::new(|| {
+                Mutex::new(AccountFactory {
+                    allowance: U256::ZERO,
+                    nonce: None,
+                })
+            });
 
         SYNC_ACCOUNT_FACTORY.lock().await
     }
 
-    /// Create new account and fund it via nitro test node access.
+    /// Create new account and fund it with `ether` ETH from the root account.
     ///
     /// # Errors
     ///
-    /// May fail if unable to find the path to the node or if funding the newly
-    /// created account fails.
-    async fn create() -> eyre::Result<Account> {
-        let _lock = AccountFactory::lock().await;
+    /// May fail if unable to start the node, to find the path to the node or if
+    /// funding the newly created account fails.
+    async fn create(ether: &str) -> eyre::Result<Account> {
+        static ROOT: OnceLock<PrivateKeySigner> = OnceLock::new();
+
+        node::ensure_running().await?;
 
-        let signer = PrivateKeySigner::random();
-        let addr = signer.address();
+        // Providers are tied to the runtime of the test that created them, so
+        // every test connects the root account anew.
+        let root = AccountFactory::connect(
+            ROOT.get_or_init(PrivateKeySigner::random).clone(),
+        );
+        let amount = parse_ether(ether)?;
+        let account = AccountFactory::connect(PrivateKeySigner::random());
 
+        // Transfers are broadcast while holding the lock, so that reading and
+        // incrementing the nonce is a single step, and every broadcast
+        // transfer is pending by the time the nonce is refetched. Only waiting
+        // for them to be mined happens concurrently.
+        let pending = {
+            let mut factory = AccountFactory::lock().await;
+            if factory.allowance < amount {
+                let top_up = amount + parse_ether(ROOT_FUNDING)?;
+                AccountFactory::fund(root.address(), &format_ether(top_up))?;
+                factory.allowance += top_up - parse_ether(ROOT_GAS_RESERVE)?;
+            }
+
+            let nonce = match factory.nonce {
+                Some(nonce) => nonce,
+                None => {
+                    root.wallet
+                        .get_transaction_count(root.address())
+                        .pending()
+                        .await?
+                }
+            };
+            let tx = TransactionRequest::default()
+                .with_to(account.address())
+                .with_value(amount)
+                .with_nonce(nonce);
+            match root.wallet.send_transaction(tx).await {
+                Ok(pending) => {
+                    factory.allowance -= amount;
+                    factory.nonce = Some(nonce + 1);
+                    pending
+                }
+                Err(e) => {
+                    // The nonce might be stale, so refetch it next time.
+                    factory.nonce = None;
+                    return Err(e).wrap_err(format!(
+                        "account's wallet wasn't funded - address is {}",
+                        account.address()
+                    ));
+                }
+            }
+        };
+
+        pending.watch().await.wrap_err(format!(
+            "account's wallet wasn't funded - address is {}",
+            account.address()
+        ))?;
+
+        Ok(account)
+    }
+
+    /// Funds `addr` with `ether` ETH via nitro test node access.
+    ///
+    /// # Errors
+    ///
+    /// May fail if unable to find the path to the node or if funding `addr`
+    /// fails.
+    fn fund(addr: Address, ether: &str) -> eyre::Result<()> {
         // ./test-node.bash script send-l2 --to
         // address_0x01fA6bf4Ee48B6C95900BCcf9BEA172EF5DBd478 --ethamount 10
         let node_script = get_node_path()?.join("test-node.bash");
@@ -88,23 +262,27 @@ impl AccountFactory {
             .arg("--to")
             .arg(format!("address_{addr}"))
             .arg("--ethamount")
-            .arg("10")
+            .arg(ether)
             .output()?;
 
-        let rpc_url = std::env::var(RPC_URL_ENV_VAR_NAME)
-            .expect("failed to load RPC_URL var from env")
+        if output.status.success() {
+            Ok(())
+        } else {
+            let err = String::from_utf8_lossy(&output.stderr);
+            bail!("account's wallet wasn't funded - address is {addr}:\n{err}")
+        }
+    }
+
+    /// Returns an account for `signer` connected to the rpc endpoint tests
+    /// run against.
+    fn connect(signer: PrivateKeySigner) -> Account {
+        let rpc_url = node::rpc_url()
             .parse()
             .expect("failed to parse RPC_URL string into a URL");
         let wallet = ProviderBuilder::new()
             .with_recommended_fillers()
             .wallet(EthereumWallet::from(signer.clone()))
             .on_http(rpc_url);
-
-        if output.status.success() {
-            Ok(Account { signer, wallet })
-        } else {
-            let err = String::from_utf8_lossy(&output.stderr);
-            bail!("account's wallet wasn't funded - address is {addr}:\n{err}")
-        }
+        Account { signer, wallet }
     }
 }
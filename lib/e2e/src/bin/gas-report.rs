@@ -0,0 +1,56 @@
+//! Prints the gas report recorded by an end-to-end test suite, along with the
+//! sizes of the contracts deployed, as markdown tables.
+//!
+//! Usage: `gas-report <path> [--compare <baseline>] [--threshold <percent>]`,
+//! where `<path>` is the file the `GAS_REPORT` environment variable pointed to
+//! while running the suite, or the `MOTSU_GAS_REPORT` one for unit tests.
+//!
+//! With `--compare`, the average gas used by every function is compared with
+//! the report at `<baseline>`, usually a copy of a previous `GAS_REPORT` file,
+//! and this fails if some function got more expensive by more than
+//! `<percent>` percent, `0` by default.
+use e2e::GasReport;
+use eyre::{bail, ContextCompat, WrapErr};
+
+const USAGE: &str =
+    "usage: gas-report <path> [--compare <baseline>] [--threshold <percent>]";
+
+fn main() -> eyre::Result<()> {
+    let mut args = std::env::args().skip(1);
+    let path = args.next().wrap_err(USAGE)?;
+
+    let mut baseline = None;
+    let mut threshold = 0.0;
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--compare" => baseline = Some(args.next().wrap_err(USAGE)?),
+            "--threshold" => {
+                threshold = args
+                    .next()
+                    .wrap_err(USAGE)?
+                    .parse()
+                    .wrap_err("invalid threshold")?;
+            }
+            _ => bail!("unexpected argument `{arg}`\n{USAGE}"),
+        }
+    }
+
+    let report = GasReport::read(path)?;
+    print!("{report}");
+
+    let Some(baseline) = baseline else {
+        return Ok(());
+    };
+    let diff = report.compare(&GasReport::read(baseline)?);
+    println!();
+    print!("{diff}");
+
+    let regressions = diff.regressions(threshold);
+    if !regressions.is_empty() {
+        bail!(
+            "gas usage grew by more than {threshold}% for: {}",
+            regressions.join(", ")
+        );
+    }
+    Ok(())
+}
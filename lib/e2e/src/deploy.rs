@@ -1,17 +1,31 @@
-use alloy::primitives::Address;
+use std::{
+    collections::BTreeMap,
+    sync::{Arc, Mutex},
+};
+
+use alloy::{
+    primitives::{keccak256, Address, B256},
+    sol_types::SolConstructor,
+};
+use eyre::Context;
 use koba::config::Deploy;
+use tokio::sync::OnceCell;
 
-use crate::project::Crate;
+use crate::{project::Crate, wasm};
 
 /// Deploy and activate the contract implemented as `#[entrypoint]` in the
 /// current crate using `rpc_url`, `private_key` and the ABI-encoded constructor
 /// `args`.
 ///
+/// The sizes of the wasm binary are recorded along with gas measurements, see
+/// [`crate::record_gas`].
+///
 /// # Errors
 ///
 /// May error if:
 ///
 /// - Unable to collect information about the crate required for deployment.
+/// - Unable to read or compress the wasm binary.
 /// - `koba::deploy` errors.
 pub async fn deploy(
     rpc_url: &str,
@@ -21,6 +35,7 @@ pub async fn deploy(
     let pkg = Crate::new()?;
     let sol_path = pkg.manifest_dir.join("src/constructor.sol");
     let wasm_path = pkg.wasm;
+    wasm::measure(&wasm_path)?;
 
     let config = Deploy {
         generate_config: koba::config::Generate {
@@ -42,3 +57,78 @@ pub async fn deploy(
     let address = koba::deploy(&config).await?;
     Ok(address)
 }
+
+/// Deploy and activate the contract implemented as `#[entrypoint]` in the
+/// current crate using `rpc_url`, `private_key` and the typed `constructor`
+/// call.
+///
+/// `constructor` is usually the `constructorCall` struct generated by a `sol!`
+/// invocation with the path to the crate's `src/constructor.sol`, so that the
+/// constructor arguments are type-checked at compile time against it.
+///
+/// # Errors
+///
+/// May error if:
+///
+/// - Unable to collect information about the crate required for deployment.
+/// - `koba::deploy` errors.
+pub async fn deploy_with_constructor<C: SolConstructor>(
+    rpc_url: &str,
+    private_key: &str,
+    constructor: &C,
+) -> eyre::Result<Address> {
+    let args = alloy::hex::encode(constructor.abi_encode());
+    deploy(rpc_url, private_key, Some(args)).await
+}
+
+/// Deploys the contract implemented as `#[entrypoint]` in the current crate
+/// once per test binary, and returns the address of that deployment to every
+/// caller.
+///
+/// Deployments are keyed by `rpc_url`, the hash of the compiled wasm binary
+/// and the ABI-encoded constructor `args`, so that tests sharing a deployment
+/// all see a contract built from the same code with the same arguments.
+/// Concurrent callers wait for the first deployment to finish.
+///
+/// Since deploying dominates the running time of most tests, prefer this over
+/// [`deploy`] for tests that only read from the contract, or that only
+/// interact with it through their own accounts. Note that the contract is
+/// deployed by whichever test gets there first, so tests that depend on who
+/// the deployer is, or on state no other test changes, should use [`deploy`].
+///
+/// # Errors
+///
+/// May error if:
+///
+/// - Unable to collect information about the crate required for deployment.
+/// - Unable to read the compiled wasm binary.
+/// - `koba::deploy` errors.
+///
+/// # Panics
+///
+/// May panic if a previous deployment panicked.
+pub async fn deploy_shared(
+    rpc_url: &str,
+    private_key: &str,
+    args: Option<String>,
+) -> eyre::Result<Address> {
+    type Key = (String, B256, Option<String>);
+    static DEPLOYMENTS: Mutex<BTreeMap<Key, Arc<OnceCell<Address>>>> =
+        Mutex::new(BTreeMap::new());
+
+    let pkg = Crate::new()?;
+    let wasm = std::fs::read(&pkg.wasm)
+        .wrap_err(format!("failed to read {}", pkg.wasm.display()))?;
+    let key = (rpc_url.to_owned(), keccak256(wasm), args.clone());
+
+    let deployment = DEPLOYMENTS
+        .lock()
+        .expect("should lock shared deployments")
+        .entry(key)
+        .or_default()
+        .clone();
+    let address = deployment
+        .get_or_try_init(|| deploy(rpc_url, private_key, args))
+        .await?;
+    Ok(*address)
+}
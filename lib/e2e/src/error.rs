@@ -55,6 +55,30 @@ pub trait Revert<E> {
     /// Checks that `Self` corresponds to the typed abi-encoded error
     /// `expected`.
     fn reverted_with(&self, expected: E) -> bool;
+
+    /// Asserts that `Self` corresponds to the typed abi-encoded error
+    /// `expected`, field values included.
+    ///
+    /// Unlike `assert!(err.reverted_with(expected))`, the panic message tells
+    /// apart a different error from the same error with different field
+    /// values, and points at the fields that differ.
+    ///
+    /// # Panics
+    ///
+    /// If `Self` is not a revert, or if it reverted with a different error or
+    /// with different field values.
+    #[track_caller]
+    fn assert_reverted_with(&self, expected: E);
+}
+
+/// Returns the raw data `error` reverted with, if any.
+fn revert_data(error: &alloy::contract::Error) -> Option<Vec<u8>> {
+    let alloy::contract::Error::TransportError(e) = error else {
+        return None;
+    };
+
+    let raw_value = e.as_error_resp()?.data.clone()?;
+    alloy::hex::decode(raw_value.get().trim_matches('"')).ok()
 }
 
 impl Panic for alloy::contract::Error {
@@ -91,4 +115,42 @@ impl<E: SolError> Revert<E> for alloy::contract::Error {
         let expected = alloy::hex::encode(expected.abi_encode());
         expected == actual
     }
+
+    fn assert_reverted_with(&self, expected: E) {
+        let signature = E::SIGNATURE;
+        let Some(actual) = revert_data(self) else {
+            panic!("expected a revert with `{signature}`, got: {self:?}");
+        };
+
+        let expected = expected.abi_encode();
+        let (actual_selector, actual_fields) =
+            actual.split_at(actual.len().min(4));
+        let (expected_selector, expected_fields) = expected.split_at(4);
+        assert!(
+            actual_selector == expected_selector,
+            "expected a revert with `{signature}`, got error with selector \
+             0x{}",
+            alloy::hex::encode(actual_selector)
+        );
+
+        let mismatches: Vec<String> = expected_fields
+            .chunks(32)
+            .zip(actual_fields.chunks(32))
+            .enumerate()
+            .filter(|(_, (expected, actual))| expected != actual)
+            .map(|(i, (expected, actual))| {
+                format!(
+                    "  word {i}: expected 0x{}, got 0x{}",
+                    alloy::hex::encode(expected),
+                    alloy::hex::encode(actual)
+                )
+            })
+            .collect();
+        assert!(
+            mismatches.is_empty()
+                && expected_fields.len() == actual_fields.len(),
+            "reverted with `{signature}`, but with different field values:\n{}",
+            mismatches.join("\n")
+        );
+    }
 }
@@ -1,9 +1,22 @@
-use alloy::{rpc::types::eth::TransactionReceipt, sol_types::SolEvent};
+use alloy::{
+    rpc::types::eth::{Log, TransactionReceipt},
+    sol_types::SolEvent,
+};
 
 /// Extension trait for asserting an event gets emitted.
 pub trait EventExt<E> {
     /// Asserts the contract emitted the `expected` event.
     fn emits(&self, expected: E);
+
+    /// Asserts the contract emitted an event of type `E` for which
+    /// `predicate` holds, e.g., a `Transfer` from Alice of any positive value:
+    ///
+    /// ```rust,ignore
+    /// receipt.emits_matching(|e: &Erc20::Transfer| {
+    ///     e.from == alice_addr && e.value > U256::ZERO
+    /// });
+    /// ```
+    fn emits_matching<F: Fn(&E) -> bool>(&self, predicate: F);
 }
 
 impl<E> EventExt<E> for TransactionReceipt
@@ -24,4 +37,77 @@ where
 
         assert!(emitted, "Event {expected:?} not emitted");
     }
+
+    fn emits_matching<F: Fn(&E) -> bool>(&self, predicate: F) {
+        let events: Vec<E> = self
+            .inner
+            .logs()
+            .iter()
+            .filter_map(|log| log.log_decode().ok())
+            .map(|log| log.inner.data)
+            .collect();
+
+        assert!(
+            events.iter().any(predicate),
+            "no {} event matches, emitted: {events:?}",
+            E::SIGNATURE
+        );
+    }
+}
+
+/// Matches logs decoding to an event of a given type, see [`event`].
+pub struct EventMatcher {
+    /// Signature of the event matched, e.g., `Transfer(address,address,
+    /// uint256)`.
+    signature: &'static str,
+    /// Whether a log matches.
+    matches: Box<dyn Fn(&Log) -> bool>,
+}
+
+/// Returns a matcher for events of type `E` for which `predicate` holds, to
+/// be used with [`EventSequenceExt::emits_in_order`].
+///
+/// Use `|_| true` to match any event of type `E`.
+pub fn event<E, F>(predicate: F) -> EventMatcher
+where
+    E: SolEvent,
+    F: Fn(&E) -> bool + 'static,
+{
+    EventMatcher {
+        signature: E::SIGNATURE,
+        matches: Box::new(move |log| {
+            log.log_decode::<E>().is_ok_and(|log| predicate(&log.inner.data))
+        }),
+    }
+}
+
+/// Extension trait for asserting the order events get emitted in.
+pub trait EventSequenceExt {
+    /// Asserts the contract emitted events matching `expected`, in that
+    /// order.
+    ///
+    /// Other events may be emitted before, after or in between the expected
+    /// ones.
+    ///
+    /// ```rust,ignore
+    /// receipt.emits_in_order(&[
+    ///     event(|e: &Erc20::Approval| e.owner == alice_addr),
+    ///     event(|e: &Erc20::Transfer| e.from == alice_addr),
+    /// ]);
+    /// ```
+    fn emits_in_order(&self, expected: &[EventMatcher]);
+}
+
+impl EventSequenceExt for TransactionReceipt {
+    fn emits_in_order(&self, expected: &[EventMatcher]) {
+        let mut logs = self.inner.logs().iter();
+        for (i, matcher) in expected.iter().enumerate() {
+            assert!(
+                logs.any(|log| (matcher.matches)(log)),
+                "expected event #{i}, a matching {}, was not emitted after \
+                 the previous ones",
+                matcher.signature
+            );
+        }
+    }
 }
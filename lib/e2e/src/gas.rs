@@ -0,0 +1,369 @@
+use std::{
+    collections::BTreeMap,
+    fmt,
+    fs::{self, OpenOptions},
+    io::Write,
+    path::Path,
+};
+
+use alloy::{contract::SolCallBuilder, network::Network, sol_types::SolCall};
+use eyre::{bail, Context, ContextCompat};
+use serde_json::{json, Value};
+
+use crate::wasm::WasmSize;
+
+/// Name of the environment variable holding the path of the file gas
+/// measurements are appended to.
+pub(crate) const GAS_REPORT_ENV_VAR_NAME: &str = "GAS_REPORT";
+
+/// Extension trait to get the name of the function a call calls.
+pub trait FunctionName {
+    /// Returns the signature of the function called, e.g.,
+    /// `transfer(address,uint256)`.
+    fn function_name(&self) -> &'static str;
+}
+
+impl<T, P, C: SolCall, N: Network> FunctionName for SolCallBuilder<T, P, C, N> {
+    fn function_name(&self) -> &'static str {
+        C::SIGNATURE
+    }
+}
+
+/// Records that a call to `function` used `gas_used` gas.
+///
+/// If the `GAS_REPORT` environment variable is set to a path, the measurement
+/// is appended to that file as a JSON line, e.g.,
+/// `{"function":"transfer(address,uint256)","gas":52000}`, so that a
+/// [`GasReport`] can be built once the whole suite ran. Otherwise, this is a
+/// no-op.
+///
+/// Gas measurements of unit tests, written by `motsu` to its
+/// `MOTSU_GAS_REPORT` file, use the same format.
+///
+/// # Panics
+///
+/// May panic if unable to write to the `GAS_REPORT` file.
+pub fn record_gas(function: &str, gas_used: u128) {
+    append(&json!({ "function": function, "gas": gas_used }));
+}
+
+/// Records the sizes of the wasm binary named `name`.
+///
+/// Like [`record_gas`], this appends a JSON line to the `GAS_REPORT` file if
+/// set, e.g., `{"wasm":"erc20_example","uncompressed":60000,"compressed":
+/// 20000}`.
+///
+/// # Panics
+///
+/// May panic if unable to write to the `GAS_REPORT` file.
+pub(crate) fn record_wasm_size(name: &str, size: WasmSize) {
+    let WasmSize { uncompressed, compressed } = size;
+    append(&json!({
+        "wasm": name,
+        "uncompressed": uncompressed,
+        "compressed": compressed,
+    }));
+}
+
+/// Appends `measurement` to the `GAS_REPORT` file, if set, as a JSON line.
+fn append(measurement: &Value) {
+    let Some(path) = std::env::var_os(GAS_REPORT_ENV_VAR_NAME) else {
+        return;
+    };
+    let line = format!("{measurement}\n");
+
+    // A single write per line, so that tests running concurrently don't
+    // interleave their measurements.
+    OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .and_then(|mut file| file.write_all(line.as_bytes()))
+        .expect("should write to the gas report");
+}
+
+/// Asserts that `gas_used` is within `tolerance` gas of `expected`.
+///
+/// # Panics
+///
+/// If `gas_used` differs from `expected` by more than `tolerance`.
+#[track_caller]
+pub fn assert_gas(gas_used: u128, expected: u128, tolerance: u128) {
+    let diff = gas_used.abs_diff(expected);
+    assert!(
+        diff <= tolerance,
+        "gas used {gas_used} is not within {tolerance} of {expected} \
+         (off by {diff})"
+    );
+}
+
+/// Gas used by every function called in an end-to-end test suite, along with
+/// the sizes of the wasm binaries deployed.
+///
+/// Its [`fmt::Display`] implementation renders it as markdown tables.
+#[derive(Debug, Default)]
+pub struct GasReport {
+    /// Gas used by every call, by function.
+    calls: BTreeMap<String, Vec<u128>>,
+    /// Sizes of the wasm binaries, by name.
+    sizes: BTreeMap<String, WasmSize>,
+}
+
+impl GasReport {
+    /// Reads the measurements recorded to the file at `path`.
+    ///
+    /// # Errors
+    ///
+    /// May error if:
+    ///
+    /// - Unable to read the file at `path`.
+    /// - A line of the file is not a measurement written by [`record_gas`],
+    ///   when deploying, or by `motsu`.
+    pub fn read<P: AsRef<Path>>(path: P) -> eyre::Result<Self> {
+        let path = path.as_ref();
+        let content = fs::read_to_string(path)
+            .wrap_err(format!("failed to read {}", path.display()))?;
+
+        let mut report = GasReport::default();
+        for line in content.lines() {
+            let measurement: Value = serde_json::from_str(line)
+                .wrap_err(format!("invalid measurement: {line}"))?;
+
+            if let Some(name) = measurement["wasm"].as_str() {
+                let size = |key: &str| {
+                    measurement[key]
+                        .as_u64()
+                        .and_then(|size| usize::try_from(size).ok())
+                        .wrap_err(format!("invalid wasm size: {line}"))
+                };
+                let size = WasmSize {
+                    uncompressed: size("uncompressed")?,
+                    compressed: size("compressed")?,
+                };
+                report.sizes.insert(name.to_owned(), size);
+                continue;
+            }
+
+            let (Some(function), Some(gas)) =
+                (measurement["function"].as_str(), measurement["gas"].as_u64())
+            else {
+                bail!("invalid gas measurement: {line}");
+            };
+            report
+                .calls
+                .entry(function.to_owned())
+                .or_default()
+                .push(gas.into());
+        }
+        Ok(report)
+    }
+
+    /// Compares the average gas used by every function with `baseline`.
+    #[must_use]
+    pub fn compare(&self, baseline: &GasReport) -> GasDiff {
+        let mut functions: BTreeMap<String, (Option<u128>, Option<u128>)> =
+            BTreeMap::new();
+        for (function, calls) in &baseline.calls {
+            functions.entry(function.clone()).or_default().0 =
+                Some(average(calls));
+        }
+        for (function, calls) in &self.calls {
+            functions.entry(function.clone()).or_default().1 =
+                Some(average(calls));
+        }
+        GasDiff { functions }
+    }
+}
+
+/// Returns the average of `calls`, rounded down.
+fn average(calls: &[u128]) -> u128 {
+    calls.iter().sum::<u128>() / calls.len().max(1) as u128
+}
+
+/// Average gas used by every function in a [`GasReport`], compared with a
+/// baseline, see [`GasReport::compare`].
+///
+/// Its [`fmt::Display`] implementation renders it as a markdown table.
+#[derive(Debug)]
+pub struct GasDiff {
+    /// Average gas used by function, in the baseline and currently, if the
+    /// function was called.
+    functions: BTreeMap<String, (Option<u128>, Option<u128>)>,
+}
+
+impl GasDiff {
+    /// Returns the functions whose average gas usage grew by more than
+    /// `threshold` percent of the baseline.
+    ///
+    /// Functions missing from either side are not considered regressions.
+    #[must_use]
+    pub fn regressions(&self, threshold: f64) -> Vec<&str> {
+        self.functions
+            .iter()
+            .filter_map(|(function, delta)| match delta {
+                (Some(baseline), Some(current)) if current > baseline => {
+                    let percent = percent(*baseline, *current);
+                    (percent > threshold).then_some(function.as_str())
+                }
+                _ => None,
+            })
+            .collect()
+    }
+}
+
+/// Returns how much `current` differs from `baseline`, in percent of the
+/// latter.
+#[allow(clippy::cast_precision_loss)]
+fn percent(baseline: u128, current: u128) -> f64 {
+    if baseline == 0 {
+        return if current == 0 { 0.0 } else { f64::INFINITY };
+    }
+    (current as f64 - baseline as f64) / baseline as f64 * 100.0
+}
+
+impl fmt::Display for GasDiff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "| Function | Baseline | Current | Delta |")?;
+        writeln!(f, "| -------- | -------- | ------- | ----- |")?;
+        for (function, delta) in &self.functions {
+            match *delta {
+                (Some(baseline), Some(current)) => {
+                    let diff = if current >= baseline {
+                        format!("+{}", current - baseline)
+                    } else {
+                        format!("-{}", baseline - current)
+                    };
+                    let percent = percent(baseline, current);
+                    writeln!(
+                        f,
+                        "| {function} | {baseline} | {current} | {diff} \
+                         ({percent:+.2}%) |"
+                    )?;
+                }
+                (Some(baseline), None) => {
+                    writeln!(f, "| {function} | {baseline} | - | removed |")?;
+                }
+                (None, Some(current)) => {
+                    writeln!(f, "| {function} | - | {current} | new |")?;
+                }
+                (None, None) => {}
+            }
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for GasReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "| Function | Calls | Min | Avg | Max |")?;
+        writeln!(f, "| -------- | ----- | --- | --- | --- |")?;
+        for (function, calls) in &self.calls {
+            let min = calls.iter().min().copied().unwrap_or_default();
+            let max = calls.iter().max().copied().unwrap_or_default();
+            let avg = average(calls);
+            writeln!(
+                f,
+                "| {function} | {} | {min} | {avg} | {max} |",
+                calls.len()
+            )?;
+        }
+
+        if !self.sizes.is_empty() {
+            writeln!(f)?;
+            writeln!(f, "| Contract | Uncompressed | Compressed |")?;
+            writeln!(f, "| -------- | ------------ | ---------- |")?;
+            for (name, size) in &self.sizes {
+                writeln!(
+                    f,
+                    "| {name} | {} | {} |",
+                    size.uncompressed, size.compressed
+                )?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::GasReport;
+    use crate::wasm::WasmSize;
+
+    fn report(calls: &[(&str, &[u128])]) -> GasReport {
+        let calls = calls
+            .iter()
+            .map(|(function, gas)| ((*function).to_owned(), gas.to_vec()))
+            .collect();
+        GasReport { calls, ..GasReport::default() }
+    }
+
+    #[test]
+    fn reads_measurements() {
+        let path = std::env::temp_dir()
+            .join(format!("e2e-gas-report-{}.jsonl", std::process::id()));
+        std::fs::write(
+            &path,
+            "{\"function\":\"transfer(address,uint256)\",\"gas\":100}\n\
+             {\"gas\":300,\"function\":\"transfer(address,uint256)\"}\n\
+             {\"wasm\":\"erc20_example\",\"uncompressed\":60,\"compressed\":20}\n",
+        )
+        .unwrap();
+        let report = GasReport::read(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(report.calls["transfer(address,uint256)"], [100, 300]);
+        assert_eq!(
+            report.sizes["erc20_example"],
+            WasmSize { uncompressed: 60, compressed: 20 }
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_measurements() {
+        let path = std::env::temp_dir().join(format!(
+            "e2e-invalid-gas-report-{}.jsonl",
+            std::process::id()
+        ));
+        std::fs::write(&path, "{\"function\":\"transfer()\"}\n").unwrap();
+        let report = GasReport::read(&path);
+        std::fs::remove_file(&path).unwrap();
+        assert!(report.is_err());
+    }
+
+    #[test]
+    fn compares_with_baseline() {
+        let baseline = report(&[
+            ("approve(address,uint256)", &[100]),
+            ("burn(uint256)", &[100]),
+            ("transfer(address,uint256)", &[100, 200]),
+        ]);
+        let current = report(&[
+            ("approve(address,uint256)", &[90]),
+            ("mint(address,uint256)", &[300]),
+            ("transfer(address,uint256)", &[160, 170]),
+        ]);
+
+        let diff = current.compare(&baseline);
+        assert_eq!(
+            diff.to_string(),
+            "| Function | Baseline | Current | Delta |\n\
+             | -------- | -------- | ------- | ----- |\n\
+             | approve(address,uint256) | 100 | 90 | -10 (-10.00%) |\n\
+             | burn(uint256) | 100 | - | removed |\n\
+             | mint(address,uint256) | - | 300 | new |\n\
+             | transfer(address,uint256) | 150 | 165 | +15 (+10.00%) |\n"
+        );
+        assert_eq!(diff.regressions(0.0), ["transfer(address,uint256)"]);
+        assert_eq!(diff.regressions(9.5), ["transfer(address,uint256)"]);
+        assert!(diff.regressions(10.0).is_empty());
+    }
+
+    #[test]
+    fn compares_with_free_baseline() {
+        let baseline = report(&[("free()", &[0]), ("paid()", &[0])]);
+        let current = report(&[("free()", &[0]), ("paid()", &[1])]);
+
+        let diff = current.compare(&baseline);
+        assert_eq!(diff.regressions(1_000.0), ["paid()"]);
+    }
+}
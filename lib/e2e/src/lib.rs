@@ -4,15 +4,23 @@ mod deploy;
 mod environment;
 mod error;
 mod event;
+mod gas;
+pub mod mining;
+mod node;
 mod project;
 mod system;
+mod verify;
+mod wasm;
 
-pub use account::Account;
-pub use deploy::deploy;
+pub use account::{Account, Impersonated};
+pub use deploy::{deploy, deploy_shared, deploy_with_constructor};
 pub use e2e_proc::test;
 pub use error::{Panic, PanicCode, Revert};
-pub use event::EventExt;
+pub use event::{event, EventExt, EventMatcher, EventSequenceExt};
+pub use gas::{assert_gas, record_gas, FunctionName, GasDiff, GasReport};
 pub use system::{provider, Provider, Wallet};
+pub use verify::verify;
+pub use wasm::{wasm_size, WasmSize};
 
 /// This macro provides a shorthand for broadcasting the transaction to the
 /// network.
@@ -83,9 +91,69 @@ macro_rules! watch {
 ///     let receipt = receipt!(contract.mint(alice_addr, token_id))?;
 ///     // ...
 /// }
+///
+/// The gas used by the transaction is recorded with [`record_gas`] under the
+/// name of the function called.
 #[macro_export]
 macro_rules! receipt {
-    ($e:expr) => {
-        send!($e)?.get_receipt().await
-    };
+    ($e:expr) => {{
+        let call = $e;
+        let function = $crate::FunctionName::function_name(&call);
+        send!(call)?.get_receipt().await.map(|receipt| {
+            $crate::record_gas(function, receipt.gas_used);
+            receipt
+        })
+    }};
+}
+
+/// This macro provides a shorthand for broadcasting the transaction to the
+/// network, fetching the transaction receipt, and asserting that the gas used
+/// is within `tolerance` of `expected`, to catch gas regressions.
+///
+/// # Examples
+///
+/// ```rust,ignore
+/// #[e2e::test]
+/// async fn transfer_gas(alice: Account) -> eyre::Result<()> {
+///     let contract_addr = deploy(alice.url(), &alice.pk()).await?;
+///     let contract = Erc20::new(contract_addr, &alice.wallet);
+///
+///     let receipt =
+///         assert_gas_within!(contract.transfer(bob_addr, one), 52_000, 500);
+///     // ...
+/// }
+#[macro_export]
+macro_rules! assert_gas_within {
+    ($e:expr, $expected:expr, $tolerance:expr) => {{
+        let receipt = receipt!($e)?;
+        $crate::assert_gas(receipt.gas_used, $expected, $tolerance);
+        receipt
+    }};
+}
+
+/// This macro asserts that the compiled wasm binary of the crate `example`
+/// compresses to less than `bytes` bytes, so that contract size regressions
+/// are caught before deploying fails.
+///
+/// # Examples
+///
+/// ```rust,ignore
+/// #[test]
+/// fn fits_size_budget() {
+///     assert_wasm_size_under!("erc20-example", 20 * 1024);
+/// }
+/// ```
+#[macro_export]
+macro_rules! assert_wasm_size_under {
+    ($example:expr, $bytes:expr) => {{
+        let example = $example;
+        let bytes = $bytes;
+        let size = $crate::wasm_size(example)
+            .expect("should read the compiled wasm binary");
+        assert!(
+            size.compressed < bytes,
+            "{example} compresses to {} bytes, over the budget of {bytes} bytes",
+            size.compressed
+        );
+    }};
 }
@@ -0,0 +1,110 @@
+//! Block mining and time control, for nodes that support it, like `anvil`
+//! and `hardhat` in dev mode.
+//!
+//! These let tests of timelocks, vesting schedules or auctions skip ahead
+//! instead of sleeping in real time.
+use std::time::Duration;
+
+use alloy::{
+    primitives::U64,
+    providers::Provider,
+    transports::{Transport, TransportResult},
+};
+
+/// Mines `blocks` blocks right away.
+///
+/// # Errors
+///
+/// May fail if the node doesn't support `anvil_mine`.
+pub async fn mine<P, T>(provider: &P, blocks: u64) -> TransportResult<()>
+where
+    P: Provider<T>,
+    T: Transport + Clone,
+{
+    provider.raw_request("anvil_mine".into(), (U64::from(blocks),)).await
+}
+
+/// Enables or disables mining a block for every transaction sent.
+///
+/// With automine disabled, transactions stay pending until a block gets
+/// mined, e.g., with [`mine`].
+///
+/// # Errors
+///
+/// May fail if the node doesn't support `evm_setAutomine`.
+pub async fn set_automine<P, T>(
+    provider: &P,
+    enabled: bool,
+) -> TransportResult<()>
+where
+    P: Provider<T>,
+    T: Transport + Clone,
+{
+    provider.raw_request("evm_setAutomine".into(), (enabled,)).await
+}
+
+/// Mines a block every `interval`, or disables interval mining if `interval`
+/// is zero.
+///
+/// # Errors
+///
+/// May fail if the node doesn't support `evm_setIntervalMining`.
+pub async fn set_interval_mining<P, T>(
+    provider: &P,
+    interval: Duration,
+) -> TransportResult<()>
+where
+    P: Provider<T>,
+    T: Transport + Clone,
+{
+    provider
+        .raw_request("evm_setIntervalMining".into(), (interval.as_secs(),))
+        .await
+}
+
+/// Moves the node's clock forward by `duration`.
+///
+/// This takes effect on the next block, so [`mine`] one for calls to see the
+/// new `block.timestamp`.
+///
+/// # Errors
+///
+/// May fail if the node doesn't support `evm_increaseTime`.
+pub async fn increase_time<P, T>(
+    provider: &P,
+    duration: Duration,
+) -> TransportResult<()>
+where
+    P: Provider<T>,
+    T: Transport + Clone,
+{
+    provider
+        .raw_request::<_, i64>(
+            "evm_increaseTime".into(),
+            (U64::from(duration.as_secs()),),
+        )
+        .await?;
+    Ok(())
+}
+
+/// Sets the timestamp of the next block to `timestamp`, in seconds since the
+/// Unix epoch.
+///
+/// # Errors
+///
+/// May fail if the node doesn't support `evm_setNextBlockTimestamp`.
+pub async fn set_next_block_timestamp<P, T>(
+    provider: &P,
+    timestamp: u64,
+) -> TransportResult<()>
+where
+    P: Provider<T>,
+    T: Transport + Clone,
+{
+    provider
+        .raw_request(
+            "evm_setNextBlockTimestamp".into(),
+            (U64::from(timestamp),),
+        )
+        .await
+}
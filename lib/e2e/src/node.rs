@@ -0,0 +1,118 @@
+use std::{
+    process::Stdio,
+    time::{Duration, Instant},
+};
+
+use alloy::providers::{Provider, ProviderBuilder};
+use eyre::{bail, Context};
+use tokio::{process::Command, sync::OnceCell};
+
+use crate::{environment::get_workspace_root, system::RPC_URL_ENV_VAR_NAME};
+
+/// RPC endpoint of a local nitro test node.
+const LOCAL_RPC_URL: &str = "http://localhost:8547";
+
+/// How long to wait for a freshly started node to produce blocks.
+// `Duration::from_mins` isn't available on older toolchains.
+#[allow(clippy::duration_suboptimal_units)]
+const READINESS_TIMEOUT: Duration = Duration::from_secs(2 * 60);
+
+/// How often to poll a starting node for readiness.
+const READINESS_POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Returns the rpc endpoint tests run against: the `RPC_URL` environment
+/// variable if set, otherwise the endpoint of the local nitro test node.
+pub(crate) fn rpc_url() -> String {
+    std::env::var(RPC_URL_ENV_VAR_NAME)
+        .unwrap_or_else(|_| LOCAL_RPC_URL.to_owned())
+}
+
+/// Makes sure a node is available to run tests against.
+///
+/// When `RPC_URL` is set, the node behind it is assumed to be managed by the
+/// caller and this is a no-op. Otherwise, the local nitro test node is
+/// started, unless it is already running, and this waits until it is ready.
+/// A node started this way is shut down once the test process exits.
+///
+/// The node is only started once per test process, however many tests call
+/// this concurrently.
+///
+/// # Errors
+///
+/// May error if:
+///
+/// - Unable to run `scripts/nitro-testnode.sh`.
+/// - The node doesn't become ready in time.
+pub(crate) async fn ensure_running() -> eyre::Result<()> {
+    static NODE: OnceCell<()> = OnceCell::const_new();
+
+    if std::env::var_os(RPC_URL_ENV_VAR_NAME).is_some() {
+        return Ok(());
+    }
+
+    NODE.get_or_try_init(start).await.copied()
+}
+
+/// Starts the local nitro test node in detached mode, initializing it first
+/// if needed, and waits until it is ready.
+async fn start() -> eyre::Result<()> {
+    if is_ready().await {
+        // Someone else is running the node, so they are in charge of it.
+        return Ok(());
+    }
+
+    let script = get_workspace_root()?.join("scripts/nitro-testnode.sh");
+    let output = Command::new(&script)
+        .arg("--detach")
+        .output()
+        .await
+        .wrap_err("should run `scripts/nitro-testnode.sh`")?;
+    if !output.status.success() {
+        let err = String::from_utf8_lossy(&output.stderr);
+        bail!("failed to start the nitro test node:\n{err}");
+    }
+
+    shutdown_on_exit(&script.to_string_lossy())?;
+
+    let start = Instant::now();
+    while !is_ready().await {
+        if start.elapsed() > READINESS_TIMEOUT {
+            bail!(
+                "nitro test node not ready after {}s",
+                READINESS_TIMEOUT.as_secs()
+            );
+        }
+        tokio::time::sleep(READINESS_POLL_INTERVAL).await;
+    }
+
+    Ok(())
+}
+
+/// Whether the local node answers rpc requests and has produced a block.
+async fn is_ready() -> bool {
+    let url = LOCAL_RPC_URL.parse().expect("should parse local rpc url");
+    let provider = ProviderBuilder::new().on_http(url);
+    matches!(provider.get_block_number().await, Ok(number) if number > 0)
+}
+
+/// Spawns a watcher that shuts down the nitro test node once the current
+/// process exits.
+///
+/// Statics are never dropped, so there is no hook to run teardown from the
+/// test process itself.
+fn shutdown_on_exit(script: &str) -> eyre::Result<()> {
+    let pid = std::process::id();
+    let watcher = format!(
+        "while kill -0 {pid} 2>/dev/null; do sleep 1; done; \
+         '{script}' --shutdown"
+    );
+    let _watcher = std::process::Command::new("sh")
+        .arg("-c")
+        .arg(watcher)
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()
+        .wrap_err("should spawn the nitro test node watcher")?;
+    Ok(())
+}
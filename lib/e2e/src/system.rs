@@ -11,8 +11,24 @@ use alloy::{
 };
 use eyre::Context;
 
+use crate::node;
+
 pub(crate) const RPC_URL_ENV_VAR_NAME: &str = "RPC_URL";
 
+/// Returns the rpc endpoint of the chain named `chain`, defined by the
+/// `RPC_URL_<CHAIN>` environment variable, e.g., `RPC_URL_L1` for `"l1"`.
+///
+/// # Errors
+///
+/// May error if the environment variable is not set.
+pub(crate) fn chain_rpc_url(chain: &str) -> eyre::Result<String> {
+    let name = format!(
+        "{RPC_URL_ENV_VAR_NAME}_{}",
+        chain.to_uppercase().replace('-', "_")
+    );
+    std::env::var(&name).wrap_err(format!("failed to load {name}"))
+}
+
 /// Convenience type alias that represents an Ethereum wallet.
 pub type Wallet = FillProvider<
     JoinFill<
@@ -38,20 +54,15 @@ pub type Provider = FillProvider<
     Ethereum,
 >;
 
-/// Load the `name` environment variable.
-fn env(name: &str) -> eyre::Result<String> {
-    std::env::var(name).wrap_err(format!("failed to load {name}"))
-}
-
-/// Returns an alloy provider connected to the `RPC_URL` rpc endpoint.
+/// Returns an alloy provider connected to the `RPC_URL` rpc endpoint, or to
+/// the local nitro test node if `RPC_URL` is unset.
 ///
 /// # Panics
 ///
-/// May panic if unable to load the `RPC_URL` environment variable.
+/// May panic if unable to parse the `RPC_URL` environment variable.
 #[must_use]
 pub fn provider() -> Provider {
-    let rpc_url = env(RPC_URL_ENV_VAR_NAME)
-        .expect("failed to load RPC_URL var from env")
+    let rpc_url = node::rpc_url()
         .parse()
         .expect("failed to parse RPC_URL string into a URL");
     ProviderBuilder::new().with_recommended_fillers().on_http(rpc_url)
@@ -0,0 +1,60 @@
+use std::fs;
+
+use alloy::{
+    primitives::Address,
+    providers::{Provider, ProviderBuilder},
+};
+use eyre::{bail, Context};
+use tokio::process::Command;
+
+use crate::{project::Crate, wasm::compress};
+
+/// Verifies that the contract deployed at `address` was built from the
+/// current crate.
+///
+/// Runs `cargo stylus check` on the compiled wasm binary of the current crate
+/// against `rpc_url`, and checks that the code at `address` is exactly that
+/// binary, compressed the way [`crate::deploy`] compresses it.
+///
+/// # Errors
+///
+/// May error if:
+///
+/// - Unable to collect information about the crate.
+/// - Unable to read or compress the compiled wasm binary.
+/// - `cargo stylus check` fails.
+/// - Unable to fetch the code at `address`.
+/// - The code at `address` doesn't match the compiled wasm binary.
+pub async fn verify(rpc_url: &str, address: Address) -> eyre::Result<()> {
+    let pkg = Crate::new()?;
+
+    let output = Command::new("cargo")
+        .arg("stylus")
+        .arg("check")
+        .arg("--wasm-file")
+        .arg(&pkg.wasm)
+        .arg("--endpoint")
+        .arg(rpc_url)
+        .output()
+        .await
+        .wrap_err("should run `cargo stylus check`")?;
+    if !output.status.success() {
+        let err = String::from_utf8_lossy(&output.stderr);
+        bail!("`cargo stylus check` failed for {}:\n{err}", pkg.wasm.display());
+    }
+
+    let wasm = fs::read(&pkg.wasm)
+        .wrap_err(format!("failed to read {}", pkg.wasm.display()))?;
+    let expected = compress(&wasm)?;
+
+    let provider = ProviderBuilder::new().on_http(rpc_url.parse()?);
+    let code = provider.get_code_at(address).await?;
+    if code.as_ref() != expected.as_slice() {
+        bail!(
+            "code deployed at {address} doesn't match {}",
+            pkg.wasm.display()
+        );
+    }
+
+    Ok(())
+}
@@ -0,0 +1,105 @@
+use std::{collections::BTreeMap, fs, io::Read, path::Path, sync::Mutex};
+
+use alloy::primitives::{keccak256, B256};
+use brotli2::read::BrotliEncoder;
+use eyre::Context;
+
+use crate::{gas::record_wasm_size, project::get_wasm};
+
+/// Brotli compression level used when deploying wasm binaries.
+const COMPRESSION_LEVEL: u32 = 11;
+
+/// Prefix of the code of deployed Stylus contracts.
+const STYLUS_PREFIX: [u8; 4] = [0xEF, 0xF0, 0x00, 0x00];
+
+/// Code deployed from the wasm binaries compressed so far, by the hash of the
+/// binary.
+static CODES: Mutex<BTreeMap<B256, Vec<u8>>> = Mutex::new(BTreeMap::new());
+
+/// Sizes of a compiled wasm binary, in bytes.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct WasmSize {
+    /// Size of the binary as compiled.
+    pub uncompressed: usize,
+    /// Size of the code deployed from the binary, which is what the
+    /// contract size limit applies to.
+    pub compressed: usize,
+}
+
+/// Returns the code deployed from the wasm binary `wasm`: the binary
+/// compressed with brotli, prefixed with the Stylus discriminant.
+///
+/// Compressing at the highest level takes a while, so the code is cached by
+/// the hash of `wasm`, and each binary is only compressed once per test
+/// binary, however many times it's deployed.
+///
+/// # Errors
+///
+/// May error if unable to compress `wasm`.
+///
+/// # Panics
+///
+/// May panic if a previous compression panicked.
+pub(crate) fn compress(wasm: &[u8]) -> eyre::Result<Vec<u8>> {
+    // The lock is held while compressing, so that concurrent deployments of
+    // the same binary wait for the first compression instead of repeating it.
+    let mut codes = CODES.lock().expect("should lock compressed codes");
+    let hash = keccak256(wasm);
+    if let Some(code) = codes.get(&hash) {
+        return Ok(code.clone());
+    }
+
+    let mut code = STYLUS_PREFIX.to_vec();
+    BrotliEncoder::new(wasm, COMPRESSION_LEVEL)
+        .read_to_end(&mut code)
+        .wrap_err("failed to compress wasm bytes")?;
+    codes.insert(hash, code.clone());
+    Ok(code)
+}
+
+/// Returns the sizes of the wasm binary at `path`, and records them under its
+/// name when the `GAS_REPORT` environment variable is set.
+///
+/// # Errors
+///
+/// May error if unable to read or compress the wasm binary.
+pub(crate) fn measure(path: &Path) -> eyre::Result<WasmSize> {
+    let wasm = fs::read(path)
+        .wrap_err(format!("failed to read {}", path.display()))?;
+    let uncompressed = wasm.len();
+    let compressed = compress(&wasm)?.len();
+    let size = WasmSize { uncompressed, compressed };
+
+    let name = path.file_stem().unwrap_or_default().to_string_lossy();
+    record_wasm_size(&name, size);
+    Ok(size)
+}
+
+/// Returns the sizes of the compiled wasm binary of the crate named
+/// `example`, e.g., `"erc20-example"`.
+///
+/// # Errors
+///
+/// May error if unable to find, read or compress the wasm binary.
+pub fn wasm_size(example: &str) -> eyre::Result<WasmSize> {
+    measure(&get_wasm(example)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use alloy::primitives::keccak256;
+
+    use super::{compress, CODES, STYLUS_PREFIX};
+
+    #[test]
+    fn caches_compressed_code() {
+        let wasm = b"\0asm\x01\0\0\0".repeat(64);
+        let code = compress(&wasm).unwrap();
+        assert!(code.starts_with(&STYLUS_PREFIX));
+        assert!(code.len() < wasm.len());
+
+        let hash = keccak256(&wasm);
+        assert_eq!(CODES.lock().unwrap().get(&hash), Some(&code));
+        assert_eq!(compress(&wasm).unwrap(), code);
+    }
+}
@@ -0,0 +1,33 @@
+#![cfg(feature = "e2e")]
+//! Tests for funding test accounts.
+
+use alloy::{primitives::utils::parse_ether, providers::Provider};
+use e2e::Account;
+use eyre::Result;
+
+async fn assert_funded(account: &Account, ether: &str) -> Result<()> {
+    let balance = account.wallet.get_balance(account.address()).await?;
+    assert_eq!(balance, parse_ether(ether)?);
+    Ok(())
+}
+
+#[e2e::test]
+async fn funds_accounts_created_back_to_back() -> Result<()> {
+    let alice = Account::new().await?;
+    let bob = Account::with_balance("1").await?;
+
+    assert_funded(&alice, "10").await?;
+    assert_funded(&bob, "1").await?;
+    Ok(())
+}
+
+#[e2e::test]
+async fn funds_accounts_created_concurrently() -> Result<()> {
+    let (alice, bob, charlie) =
+        tokio::try_join!(Account::new(), Account::new(), Account::new())?;
+
+    for account in [&alice, &bob, &charlie] {
+        assert_funded(account, "10").await?;
+    }
+    Ok(())
+}
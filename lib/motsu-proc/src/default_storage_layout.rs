@@ -3,8 +3,6 @@ use proc_macro::TokenStream;
 use quote::quote;
 use syn::{Data, DeriveInput};
 
-const STORAGE_WORD_BYTES: u8 = 32;
-
 pub(crate) fn impl_default_storage_layout(ast: &DeriveInput) -> TokenStream {
     let name = &ast.ident;
     let (impl_generics, ty_generics, where_clause) =
@@ -21,38 +19,58 @@ pub(crate) fn impl_default_storage_layout(ast: &DeriveInput) -> TokenStream {
     };
 
     let mut field_initializations = Vec::new();
+    let mut field_layouts = Vec::new();
     for field in fields {
         let field_name = &field.ident;
         let field_type = &field.ty;
 
         let ty = quote! { <#field_type as stylus_sdk::storage::StorageType> };
-        let field_init = quote! {
-            {
-                if offset + #ty::SLOT_BYTES as u8 > #STORAGE_WORD_BYTES {
-                    next_slot += 1;
-                    offset = 0;
-                }
-                let instance = unsafe { #ty::new(alloy_primitives::U256::from(next_slot), offset) };
-                offset += #ty::SLOT_BYTES as u8;
-                instance
-            }
+        let place = quote! {
+            cursor.place(#ty::SLOT_BYTES, #ty::REQUIRED_SLOTS)
         };
 
         field_initializations.push(quote! {
-            #field_name: #field_init
+            #field_name: {
+                let (slot, offset) = #place;
+                unsafe { #ty::new(slot, offset) }
+            }
+        });
+
+        let name =
+            field_name.as_ref().map(ToString::to_string).unwrap_or_default();
+        field_layouts.push(quote! {
+            {
+                let (slot, offset) = #place;
+                layout.push(motsu::layout::Field {
+                    name: #name,
+                    ty: core::any::type_name::<#field_type>(),
+                    slot,
+                    offset,
+                    bytes: #ty::SLOT_BYTES as u8,
+                    slots: #ty::REQUIRED_SLOTS,
+                });
+            }
         });
     }
 
     quote! {
         impl #impl_generics Default for #name #ty_generics #where_clause{
             fn default() -> Self {
-                let mut next_slot: i32 = 0;
-                let mut offset: u8 = 0;
+                let mut cursor = motsu::layout::Cursor::new();
                 #name {
                     #(#field_initializations),*
                 }
             }
         }
+
+        impl #impl_generics motsu::layout::StorageLayout for #name #ty_generics #where_clause {
+            fn storage_layout() -> Vec<motsu::layout::Field> {
+                let mut cursor = motsu::layout::Cursor::new();
+                let mut layout = Vec::new();
+                #(#field_layouts)*
+                layout
+            }
+        }
     }
     .into()
 }
@@ -44,6 +44,23 @@ mod test;
 /// }
 /// ```
 ///
+/// Parameters following the contract are generated with `proptest`, which
+/// turns the test into a property test that runs for many inputs, each
+/// against a fresh contract. Values are generated from the `Arbitrary`
+/// implementation of each parameter, unless a strategy is passed with the
+/// `#[strategy(..)]` attribute. See `motsu::fuzz` for more information.
+///
+/// ```rust,ignore
+/// #[cfg(test)]
+/// mod tests {
+///     #[motsu::test]
+///     fn mints(contract: Erc20, to: Address, amount: U256) {
+///         contract._mint(to, amount).unwrap();
+///         assert_eq!(amount, contract.balance_of(to));
+///     }
+/// }
+/// ```
+///
 /// ```rust,ignore
 /// #[cfg(test)]
 /// mod tests {
@@ -58,19 +75,24 @@ pub fn test(attr: TokenStream, input: TokenStream) -> TokenStream {
     test::test(&attr, input)
 }
 
-/// Automatically implements the `Default` trait for a struct that uses
-/// `sol_storage!`.
+/// Automatically implements the `Default` trait for a storage struct, declared
+/// either with `sol_storage!` or with `#[solidity_storage]`.
 ///
 /// This macro initializes the struct fields based on how they are laid out in
 /// the EVM state trie. It is intended to be a helper for tests to avoid having
 /// to implement `Default` for each contract.
 ///
+/// It also implements `motsu::layout::StorageLayout`, which describes that
+/// layout, so that upgrades can be checked not to move existing fields with
+/// `motsu::assert_layout_compatible!`.
+///
 /// # Usage
 ///
 /// To use this macro, simply add `#[derive(motsu::DefaultStorageLayout)]` to
-/// your `sol_storage!` struct. Make sure all the fields in your struct are
-/// compatible with Stylus' storage, that means they implement the `StorageType`
-/// trait.
+/// your storage struct. Make sure all the fields in your struct are compatible
+/// with Stylus' storage, that means they implement the `StorageType` trait.
+/// Nested components take as many slots as they require, like they do
+/// on-chain.
 ///
 /// # Examples
 ///
@@ -86,14 +108,14 @@ pub fn test(attr: TokenStream, input: TokenStream) -> TokenStream {
 ///        uint256 _total_supply;
 ///    }
 /// }
-/// ```
 ///
-/// ## Notice
-///
-/// For now this macro only works with structs that use the
-/// `sol_storage!` macro, which allows you to use the solidity syntax.
-/// If you want to write your contracts using `#[solidity_storage]`
-/// and the Rust syntax, you will have to implement `Default` yourself.
+/// #[solidity_storage]
+/// #[derive(motsu::DefaultStorageLayout)]
+/// pub struct Token {
+///     erc20: Erc20,
+///     holders: StorageVec<StorageAddress>,
+/// }
+/// ```
 ///
 /// ## See Also
 ///
@@ -1,7 +1,7 @@
 //! Defines the `#[motsu::test]` procedural macro.
 use proc_macro::TokenStream;
 use quote::quote;
-use syn::{parse_macro_input, FnArg};
+use syn::{parse_macro_input, Attribute, Expr, FnArg};
 
 /// Defines a unit test that provides access to Stylus' execution context.
 ///
@@ -21,32 +21,95 @@ pub(crate) fn test(_attr: &TokenStream, input: TokenStream) -> TokenStream {
         return quote! {
             #( #attrs )*
             #[test]
-            fn #fn_name() #fn_return_type {
-                let _lock = ::motsu::prelude::acquire_storage();
-                let res = #fn_block;
-                ::motsu::prelude::reset_storage();
-                res
+            fn #fn_name() {
+                ::motsu::prelude::run_isolated(
+                    concat!(module_path!(), "::", stringify!(#fn_name)),
+                    || #fn_return_type {
+                        let _lock = ::motsu::prelude::acquire_storage();
+                        let res = #fn_block;
+                        ::motsu::prelude::reset_storage();
+                        res
+                    },
+                );
             }
         }
         .into();
     }
 
-    // We can unwrap because we handle the empty case above. We don't support
-    // more than one parameter for now, so we skip them.
+    // We can unwrap because we handle the empty case above.
     let arg = fn_args.first().unwrap();
     let FnArg::Typed(arg) = arg else {
         error!(arg, "unexpected receiver argument in test signature");
     };
     let contract_arg_binding = &arg.pat;
     let contract_ty = &arg.ty;
+
+    // Parameters following the contract are fuzzed, either with the strategy
+    // passed in their `#[strategy(..)]` attribute, or with their `Arbitrary`
+    // implementation.
+    let mut fuzzed_pats = Vec::new();
+    let mut strategies = Vec::new();
+    for arg in fn_args.iter().skip(1) {
+        let FnArg::Typed(arg) = arg else {
+            error!(arg, "unexpected receiver argument in test signature");
+        };
+        let ty = &arg.ty;
+        let strategy = match strategy_attr(&arg.attrs) {
+            Ok(Some(strategy)) => quote! { #strategy },
+            Ok(None) => quote! { ::motsu::fuzz::any::<#ty>() },
+            Err(err) => return err.to_compile_error().into(),
+        };
+        fuzzed_pats.push(&arg.pat);
+        strategies.push(strategy);
+    }
+
+    if fuzzed_pats.is_empty() {
+        return quote! {
+            #( #attrs )*
+            #[test]
+            fn #fn_name() {
+                ::motsu::prelude::run_isolated(
+                    concat!(module_path!(), "::", stringify!(#fn_name)),
+                    || ::motsu::prelude::with_context::<#contract_ty>(
+                        | #contract_arg_binding | #fn_block
+                    ),
+                );
+            }
+        }
+        .into();
+    }
+
     quote! {
         #( #attrs )*
         #[test]
-        fn #fn_name() #fn_return_type {
-            ::motsu::prelude::with_context::<#contract_ty>(| #contract_arg_binding |
-                #fn_block
-            )
+        fn #fn_name() {
+            ::motsu::prelude::run_isolated(
+                concat!(module_path!(), "::", stringify!(#fn_name)),
+                || ::motsu::fuzz::run(
+                    ( #( #strategies, )* ),
+                    | ( #( #fuzzed_pats, )* ) | {
+                        ::motsu::prelude::with_context::<#contract_ty>(
+                            | #contract_arg_binding | #fn_block
+                        )
+                    }
+                ),
+            );
         }
     }
     .into()
 }
+
+/// Returns the strategy expression of a `#[strategy(..)]` attribute, if any.
+fn strategy_attr(attrs: &[Attribute]) -> syn::Result<Option<Expr>> {
+    let mut strategy = None;
+    for attr in attrs {
+        if !attr.path().is_ident("strategy") {
+            error!(@ attr, "unexpected attribute on test parameter");
+        }
+        if strategy.is_some() {
+            error!(@ attr, "duplicate `strategy` attribute");
+        }
+        strategy = Some(attr.parse_args::<Expr>()?);
+    }
+    Ok(strategy)
+}
@@ -1,5 +1,9 @@
 use alloy_primitives::{address, uint, Address, U128, U16, U256, U32, U64, U8};
-use stylus_sdk::stylus_proc::sol_storage;
+use motsu::layout::StorageLayout;
+use stylus_sdk::{
+    storage::{StorageMap, StorageType, StorageU256},
+    stylus_proc::{sol_storage, solidity_storage},
+};
 
 sol_storage! {
     #[derive(motsu_proc::DefaultStorageLayout)]
@@ -128,3 +132,126 @@ fn address_updates(contract: AddressContract) {
     assert_eq!(contract.lender.get(), lender);
     assert_eq!(contract.borrower.get(), borrower);
 }
+
+#[motsu::test]
+fn fuzzes_parameters(contract: Erc20, key: Address, value: U256) {
+    contract._balances.insert(key, value);
+    assert_eq!(contract._balances.get(key), value);
+    assert_eq!(contract._total_supply.get(), U256::ZERO);
+}
+
+#[motsu::test]
+#[should_panic = "minimal failing input"]
+fn shrinks_failing_inputs(contract: Erc20, value: U256) {
+    contract._total_supply.set(value);
+    assert!(contract._total_supply.get() < uint!(256_U256));
+}
+
+#[motsu::test]
+fn fuzzes_parameters_with_strategies(
+    contract: Erc20,
+    #[strategy(motsu::fuzz::address())] key: Address,
+    #[strategy(0..10u64)] value: u64,
+) {
+    contract._balances.insert(key, U256::from(value));
+    assert!(contract._balances.get(key) < uint!(10_U256));
+}
+
+sol_storage! {
+    #[derive(motsu_proc::DefaultStorageLayout)]
+    pub struct Erc20Capped {
+        mapping(address => uint256) balances;
+        mapping(address => mapping(address => uint256)) allowances;
+        uint256 total_supply;
+        uint256 cap;
+    }
+}
+
+#[motsu::test]
+fn upgrade_preserves_storage(contract: Erc20) {
+    let key = address!("a935CEC3c5Ef99D7F1016674DEFd455Ef06776C5");
+    let value = uint!(100_U256);
+    contract._balances.insert(key, value);
+    contract._total_supply.set(value);
+
+    // Contracts share the storage of the test, so instantiating the new
+    // implementation is enough to simulate an upgrade.
+    let mut upgraded = Erc20Capped::default();
+    upgraded.cap.set(uint!(1000_U256));
+    assert_eq!(upgraded.balances.get(key), value);
+    assert_eq!(upgraded.total_supply.get(), value);
+    assert_eq!(upgraded.cap.get(), uint!(1000_U256));
+}
+
+#[test]
+fn upgrade_keeps_layout() {
+    motsu::assert_layout_compatible!(Erc20, Erc20Capped);
+}
+
+#[test]
+fn packed_fields_share_slots() {
+    let layout = UintSorted::storage_layout();
+    let slots: Vec<_> =
+        layout.iter().map(|field| (field.slot, field.offset)).collect();
+    assert_eq!(
+        slots,
+        [
+            (U256::ZERO, 0),
+            (uint!(1_U256), 16),
+            (uint!(1_U256), 8),
+            (uint!(1_U256), 4),
+            (uint!(1_U256), 2),
+            (uint!(1_U256), 1),
+            (uint!(1_U256), 0),
+        ]
+    );
+}
+
+#[motsu::test]
+fn matches_on_chain_layout(contract: UintUnsorted) {
+    // Laid out by `#[solidity_storage]`, as on-chain.
+    let mut on_chain =
+        unsafe { <UintUnsorted as StorageType>::new(U256::ZERO, 0) };
+    on_chain.b.set(uint!(5_U16));
+    on_chain.c.set(uint!(3_U64));
+    on_chain.d.set(uint!(6_U8));
+    on_chain.e.set(uint!(4_U32));
+    on_chain.f.set(uint!(2_U128));
+    on_chain.g.set(uint!(7_U8));
+
+    assert_eq!(contract.b.get(), uint!(5_U16));
+    assert_eq!(contract.c.get(), uint!(3_U64));
+    assert_eq!(contract.d.get(), uint!(6_U8));
+    assert_eq!(contract.e.get(), uint!(4_U32));
+    assert_eq!(contract.f.get(), uint!(2_U128));
+    assert_eq!(contract.g.get(), uint!(7_U8));
+}
+
+/// [`Erc20`], written with the storage types `sol_storage!` expands to.
+#[solidity_storage]
+#[derive(motsu_proc::DefaultStorageLayout)]
+pub struct Erc20Aliased {
+    _balances: StorageMap<Address, StorageU256>,
+    _allowances: StorageMap<Address, StorageMap<Address, StorageU256>>,
+    _total_supply: StorageU256,
+}
+
+#[test]
+fn compares_resolved_types() {
+    motsu::assert_layout_compatible!(Erc20, Erc20Aliased);
+}
+
+#[test]
+#[should_panic = "storage layout of `UintSorted` is incompatible with `UintUnsorted`"]
+fn reordering_fields_breaks_layout() {
+    motsu::assert_layout_compatible!(UintUnsorted, UintSorted);
+}
+
+#[test]
+fn serializes_layout() {
+    let json = motsu::layout::to_json::<Erc20>();
+    assert!(json.ends_with(
+        "{\"name\":\"_total_supply\",\"type\":\"stylus_sdk::storage::\
+         StorageUint<256, 4>\",\"slot\":\"0x2\",\"offset\":0,\"bytes\":32}]"
+    ));
+}
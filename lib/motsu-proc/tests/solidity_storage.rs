@@ -0,0 +1,108 @@
+//! Tests for deriving `DefaultStorageLayout` on `#[solidity_storage]` structs.
+use alloy_primitives::{address, uint, Address, U256, U8};
+use motsu::layout::StorageLayout;
+use stylus_sdk::{
+    storage::{
+        StorageAddress, StorageBool, StorageMap, StorageType, StorageU256,
+        StorageU8, StorageVec,
+    },
+    stylus_proc::solidity_storage,
+};
+
+/// An ERC-20 component.
+#[solidity_storage]
+#[derive(motsu_proc::DefaultStorageLayout)]
+pub struct Erc20 {
+    balances: StorageMap<Address, StorageU256>,
+    _allowances: StorageMap<Address, StorageMap<Address, StorageU256>>,
+    total_supply: StorageU256,
+}
+
+/// A contract nesting the component.
+#[solidity_storage]
+#[derive(motsu_proc::DefaultStorageLayout)]
+pub struct Token {
+    erc20: Erc20,
+    holders: StorageVec<StorageAddress>,
+    owner: StorageAddress,
+    decimals: StorageU8,
+}
+
+#[motsu::test]
+fn initializes_maps_and_vecs(contract: Token) {
+    let alice = address!("a935CEC3c5Ef99D7F1016674DEFd455Ef06776C5");
+    let value = uint!(100_U256);
+
+    contract.erc20.balances.setter(alice).set(value);
+    contract.holders.push(alice);
+    contract.decimals.set(U8::from(18));
+
+    assert_eq!(contract.erc20.balances.get(alice), value);
+    assert_eq!(contract.holders.get(0), Some(alice));
+    assert_eq!(contract.decimals.get(), U8::from(18));
+}
+
+#[motsu::test]
+fn nested_components_dont_overlap(contract: Token) {
+    let alice = address!("a935CEC3c5Ef99D7F1016674DEFd455Ef06776C5");
+    contract.erc20.total_supply.set(uint!(1_U256));
+    contract.owner.set(alice);
+
+    // Reads from storage rather than from the cached values.
+    let fresh = Token::default();
+    assert_eq!(fresh.erc20.total_supply.get(), uint!(1_U256));
+    assert_eq!(fresh.owner.get(), alice);
+}
+
+/// A contract nesting the component after a partially used slot.
+#[solidity_storage]
+#[derive(motsu_proc::DefaultStorageLayout)]
+pub struct PausableToken {
+    paused: StorageBool,
+    erc20: Erc20,
+    decimals: StorageU8,
+}
+
+#[motsu::test]
+fn nests_components_after_packed_fields(contract: PausableToken) {
+    // Laid out by `#[solidity_storage]`, as on-chain.
+    let mut on_chain =
+        unsafe { <PausableToken as StorageType>::new(U256::ZERO, 0) };
+    on_chain.paused.set(true);
+    on_chain.erc20.total_supply.set(uint!(1_U256));
+    on_chain.decimals.set(U8::from(18));
+
+    assert!(contract.paused.get());
+    assert_eq!(contract.erc20.total_supply.get(), uint!(1_U256));
+    assert_eq!(contract.decimals.get(), U8::from(18));
+
+    let slots: Vec<(&str, U256, u8)> = PausableToken::storage_layout()
+        .iter()
+        .map(|field| (field.name, field.slot, field.offset))
+        .collect();
+    assert_eq!(
+        slots,
+        vec![
+            ("paused", U256::ZERO, 31),
+            ("erc20", uint!(1_U256), 0),
+            ("decimals", uint!(4_U256), 31),
+        ]
+    );
+}
+
+#[test]
+fn lays_out_nested_components() {
+    let slots: Vec<(&str, U256)> = Token::storage_layout()
+        .iter()
+        .map(|field| (field.name, field.slot))
+        .collect();
+    assert_eq!(
+        slots,
+        vec![
+            ("erc20", U256::ZERO),
+            ("holders", uint!(3_U256)),
+            ("owner", uint!(4_U256)),
+            ("decimals", uint!(4_U256)),
+        ]
+    );
+}
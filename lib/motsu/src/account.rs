@@ -0,0 +1,131 @@
+//! Deterministic accounts for tests.
+use core::fmt;
+
+use alloy_primitives::{Address, U256};
+
+use crate::{prelude::VM, shims::keccak256};
+
+/// An account with a stable address derived from its name.
+///
+/// Using named accounts instead of hardcoded addresses makes tests easier to
+/// read, and failure output reproducible across runs, since accounts are
+/// displayed with their name.
+///
+/// # Examples
+///
+/// ```rust,ignore
+/// #[motsu::test]
+/// fn transfers(contract: Erc20) {
+///     let alice = Account::named("alice");
+///     let bob = Account::builder("bob").balance(one_eth).build();
+///     contract.transfer(alice.address(), one).unwrap();
+/// }
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Account {
+    name: &'static str,
+    address: Address,
+}
+
+impl Account {
+    /// Returns the account named `name`.
+    ///
+    /// Its address is the last 20 bytes of the `keccak256` hash of `name`, so
+    /// it is the same across runs.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - Name of the account.
+    #[must_use]
+    pub fn named(name: &'static str) -> Self {
+        let hash = keccak256(name.as_bytes());
+        Account { name, address: Address::from_slice(&hash[12..]) }
+    }
+
+    /// Returns a builder for the account named `name`, which allows
+    /// configuring its state in the VM.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - Name of the account.
+    pub fn builder(name: &'static str) -> AccountBuilder {
+        AccountBuilder {
+            account: Account::named(name),
+            balance: None,
+            code: None,
+        }
+    }
+
+    /// Returns the name of the account.
+    #[must_use]
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+
+    /// Returns the address of the account.
+    #[must_use]
+    pub fn address(&self) -> Address {
+        self.address
+    }
+}
+
+impl From<Account> for Address {
+    fn from(account: Account) -> Self {
+        account.address
+    }
+}
+
+impl fmt::Display for Account {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} ({})", self.name, self.address)
+    }
+}
+
+/// Configures the state of an [`Account`] in the VM.
+///
+/// Created with [`Account::builder`].
+#[must_use]
+pub struct AccountBuilder {
+    account: Account,
+    balance: Option<U256>,
+    code: Option<Vec<u8>>,
+}
+
+impl AccountBuilder {
+    /// Sets the ETH balance of the account, in wei.
+    ///
+    /// # Arguments
+    ///
+    /// * `self` - The builder.
+    /// * `balance` - ETH balance of the account.
+    pub fn balance(mut self, balance: U256) -> Self {
+        self.balance = Some(balance);
+        self
+    }
+
+    /// Deploys `code` at the address of the account, so that it's treated as
+    /// a contract, e.g., by `has_code()`.
+    ///
+    /// # Arguments
+    ///
+    /// * `self` - The builder.
+    /// * `code` - Bytecode of the account.
+    pub fn code(mut self, code: impl Into<Vec<u8>>) -> Self {
+        self.code = Some(code.into());
+        self
+    }
+
+    /// Applies the configuration to the VM and returns the account.
+    #[must_use]
+    pub fn build(self) -> Account {
+        let context = VM::context();
+        let address = self.account.address();
+        if let Some(balance) = self.balance {
+            context.set_balance(address, balance);
+        }
+        if let Some(code) = self.code {
+            context.set_account_code(address, code);
+        }
+        self.account
+    }
+}
@@ -0,0 +1,24 @@
+//! A stand-in for the contract calling into a library under test.
+use stylus_sdk::storage::TopLevelStorage;
+
+/// A contract without state, calling into the library under test.
+///
+/// Libraries that call other contracts take the calling contract as
+/// `&mut impl TopLevelStorage` or as a call context. `Caller` can be passed
+/// instead of declaring a contract in every test module.
+///
+/// # Examples
+///
+/// ```rust,ignore
+/// #[motsu::test]
+/// fn wraps_eth() {
+///     VM::context().fund(contract::address(), value);
+///     Weth::new(WETH).wrap(&mut Caller, value).unwrap();
+/// }
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Caller;
+
+/// NOTE: Implementation of [`TopLevelStorage`] to be able to pass `Caller`
+/// where libraries expect the contract making the call.
+unsafe impl TopLevelStorage for Caller {}
@@ -1,7 +1,25 @@
 //! Unit-testing context for Stylus contracts.
-use std::sync::{Mutex, MutexGuard};
+use std::{
+    borrow::BorrowMut,
+    collections::{HashMap, HashSet},
+    env, fmt,
+    fs::OpenOptions,
+    io::{Read, Seek, SeekFrom, Write},
+    process::{ExitCode, Termination},
+    sync::{Mutex, MutexGuard},
+    time::Duration,
+};
 
-use crate::storage::reset_storage;
+use alloy_primitives::{Address, B256, U256};
+use alloy_sol_types::SolEvent;
+use once_cell::sync::Lazy;
+use stylus_sdk::{abi::Router, storage::TopLevelStorage};
+
+use crate::{
+    shims::{Bytes32, CONTRACT_ADDRESS, MSG_SENDER},
+    slot,
+    storage::{reset_storage, STORAGE},
+};
 
 /// A global static mutex.
 ///
@@ -12,7 +30,11 @@ use crate::storage::reset_storage;
 /// See [`with_context`].
 pub(crate) static STORAGE_MUTEX: Mutex<()> = Mutex::new(());
 
-/// Acquires access to storage.
+/// Acquires exclusive access to storage.
+///
+/// Tests annotated with [`#[motsu::test]`][crate::test] run in their own
+/// process, so the lock is never contended and tests run in parallel. It only
+/// serializes tests that use [`with_context`] without [`run_isolated`].
 pub fn acquire_storage() -> MutexGuard<'static, ()> {
     STORAGE_MUTEX.lock().unwrap_or_else(|e| {
         reset_storage();
@@ -27,3 +49,1217 @@ pub fn with_context<C: Default>(closure: impl FnOnce(&mut C)) {
     closure(&mut contract);
     reset_storage();
 }
+
+/// Runs `test` in a new process that only executes the test named
+/// `test_path`.
+///
+/// The Stylus SDK caches values like `block::timestamp()` or `msg::sender()`
+/// in process-wide statics the first time they are read, which is sound
+/// on-chain, where every call gets a fresh `wasm` instance, but means tests
+/// sharing a process would observe each other's [`Context`]. Running every
+/// test in its own process gives it a pristine VM.
+///
+/// `test_path` should be `concat!(module_path!(), "::", <test name>)`, as
+/// generated by [`#[motsu::test]`][crate::test].
+///
+/// # Panics
+///
+/// If the test fails in the child process, in which case the panic message
+/// contains the child's output. This makes `#[should_panic(expected = ..)]`
+/// work as usual.
+pub fn run_isolated<T: Termination>(test_path: &str, test: impl FnOnce() -> T) {
+    // Test names don't include the name of the crate.
+    let test_name = test_path.split_once("::").map_or(test_path, |(_, n)| n);
+    rusty_fork::fork(
+        test_name,
+        test_name,
+        |_| {},
+        |child, output| {
+            let status = child.wait().expect("should wait for test process");
+            if status.success() {
+                return;
+            }
+
+            let mut captured = String::new();
+            let _ = output.seek(SeekFrom::Start(0));
+            let _ = output.read_to_string(&mut captured);
+            // Avoid printing the output twice.
+            let _ = output.set_len(0);
+            panic!("test process exited with {status}:\n{captured}");
+        },
+        || {
+            if test().report() != ExitCode::SUCCESS {
+                std::process::exit(1);
+            }
+        },
+    )
+    .expect("should spawn test process");
+}
+
+/// Mocked state of the VM that can be altered by the running test.
+pub(crate) struct VmState {
+    /// Returned by the `block_timestamp` hostio.
+    pub(crate) block_timestamp: u64,
+    /// Returned by the `block_number` hostio.
+    pub(crate) block_number: u64,
+    /// Returned by the `block_basefee` hostio.
+    pub(crate) block_basefee: U256,
+    /// Returned by the `block_coinbase` hostio.
+    pub(crate) block_coinbase: Address,
+    /// Returned by the `chainid` hostio.
+    pub(crate) chain_id: u64,
+    /// Returned by the `msg_sender` hostio.
+    pub(crate) msg_sender: Address,
+    /// Returned by the `msg_value` hostio.
+    pub(crate) msg_value: U256,
+    /// Account that paid `msg_value` to the contract.
+    pub(crate) msg_value_payer: Address,
+    /// Returned by the `tx_origin` hostio.
+    pub(crate) tx_origin: Address,
+    /// Returned by the `contract_address` hostio.
+    pub(crate) contract_address: Address,
+    /// ETH balance of each account, in wei.
+    pub(crate) balances: HashMap<Address, U256>,
+    /// Code deployed at each account. Accounts without code are EOAs.
+    pub(crate) codes: HashMap<Address, Vec<u8>>,
+    /// Number of contracts deployed by each account.
+    pub(crate) nonces: HashMap<Address, u64>,
+    /// Gas charged by the hostios called so far.
+    pub(crate) gas_used: u64,
+    /// Storage slots accessed so far, which are cheaper to access again.
+    pub(crate) accessed_slots: HashSet<Bytes32>,
+    /// Calls to other contracts stubbed by the running test.
+    pub(crate) mocked_calls: Vec<MockedCall>,
+    /// Result of the last call to another contract.
+    pub(crate) return_data: Vec<u8>,
+    /// Logs emitted so far, in order.
+    pub(crate) logs: Vec<Log>,
+    /// Gas measurements recorded so far, in order.
+    pub(crate) gas_report: Vec<GasMeasurement>,
+    /// Number of times the hostios that touch EVM state were called so far.
+    pub(crate) hostio_counts: HostioCounts,
+}
+
+/// An EVM log emitted by the contract under test.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Log {
+    /// Indexed topics of the log. For non-anonymous events, the first topic
+    /// is the hash of the event signature.
+    pub topics: Vec<B256>,
+    /// ABI-encoded non-indexed fields of the log.
+    pub data: Vec<u8>,
+}
+
+impl Log {
+    /// Decodes the log as an `E` event, returning `None` if it isn't one.
+    #[must_use]
+    pub fn decode<E: SolEvent>(&self) -> Option<E> {
+        if !E::ANONYMOUS && self.topics.first() != Some(&E::SIGNATURE_HASH) {
+            return None;
+        }
+        E::decode_log(self.topics.iter().copied(), &self.data, true).ok()
+    }
+}
+
+/// Error returned by [`Context::with_gas_limit`] when the call runs out of gas.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct OutOfGas {
+    /// Gas the call was limited to.
+    pub gas_limit: u64,
+}
+/// Environment variable holding the path of the file gas measurements are
+/// appended to.
+const GAS_REPORT_VAR: &str = "MOTSU_GAS_REPORT";
+
+/// Gas used by a call, recorded with [`Context::meter`].
+///
+/// It is displayed as a JSON object, e.g.,
+/// `{"function":"transfer","gas":26000}`, in the format of the `GAS_REPORT`
+/// of end-to-end tests, so that both can be printed and compared with the
+/// `gas-report` binary of the `e2e` crate.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct GasMeasurement {
+    /// Name the call was metered under.
+    pub name: String,
+    /// Gas charged by the hostios the call used.
+    pub gas: u64,
+}
+
+impl fmt::Display for GasMeasurement {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let json =
+            serde_json::json!({ "function": self.name, "gas": self.gas });
+        write!(f, "{json}")
+    }
+}
+
+/// Number of times a call used each hostio that touches EVM state, see
+/// [`Context::measure_hostios`].
+///
+/// It is displayed as a JSON object, e.g., `{"calls":0,"logs":1,
+/// "storage_loads":3,"storage_stores":2}`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct HostioCounts {
+    /// Storage slots read, like the `SLOAD` opcode.
+    pub storage_loads: u64,
+    /// Storage slots written, like the `SSTORE` opcode.
+    pub storage_stores: u64,
+    /// Calls to other contracts, including static and delegate calls.
+    pub calls: u64,
+    /// Logs emitted.
+    pub logs: u64,
+}
+
+impl core::ops::Sub for HostioCounts {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self {
+        HostioCounts {
+            storage_loads: self.storage_loads - rhs.storage_loads,
+            storage_stores: self.storage_stores - rhs.storage_stores,
+            calls: self.calls - rhs.calls,
+            logs: self.logs - rhs.logs,
+        }
+    }
+}
+
+impl fmt::Display for HostioCounts {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let json = serde_json::json!({
+            "storage_loads": self.storage_loads,
+            "storage_stores": self.storage_stores,
+            "calls": self.calls,
+            "logs": self.logs,
+        });
+        write!(f, "{json}")
+    }
+}
+
+/// The storage of the contract under test at some point of a test, taken with
+/// [`Context::snapshot`].
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Snapshot(HashMap<Bytes32, Bytes32>);
+
+impl Snapshot {
+    /// Returns the value of `slot` when the snapshot was taken.
+    #[must_use]
+    pub fn get(&self, slot: B256) -> B256 {
+        self.0.get(&slot.0).copied().unwrap_or_default().into()
+    }
+}
+
+/// A storage slot whose value differs between two [`Snapshot`]s.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SlotChange {
+    /// Storage slot that changed.
+    pub slot: B256,
+    /// Value of the slot in the first snapshot.
+    pub before: B256,
+    /// Value of the slot in the second snapshot.
+    pub after: B256,
+}
+
+/// A call to another contract stubbed with [`Context::mock_call`] or
+/// [`Context::mock_revert`].
+pub(crate) struct MockedCall {
+    /// Address of the called contract.
+    pub(crate) target: Address,
+    /// Prefix of the calldata that this mock matches.
+    pub(crate) calldata: Vec<u8>,
+    /// Whether the call reverts.
+    pub(crate) reverts: bool,
+    /// Data returned by the call, or revert data if it reverts.
+    pub(crate) return_data: Vec<u8>,
+}
+
+impl Default for VmState {
+    fn default() -> Self {
+        VmState {
+            block_timestamp: 0,
+            block_number: 0,
+            block_basefee: U256::ZERO,
+            block_coinbase: Address::ZERO,
+            chain_id: 0,
+            msg_sender: default_sender(),
+            msg_value: U256::ZERO,
+            msg_value_payer: default_sender(),
+            tx_origin: default_sender(),
+            contract_address: CONTRACT_ADDRESS,
+            balances: HashMap::new(),
+            codes: HashMap::new(),
+            nonces: HashMap::new(),
+            gas_used: 0,
+            accessed_slots: HashSet::new(),
+            mocked_calls: Vec::new(),
+            return_data: Vec::new(),
+            logs: Vec::new(),
+            gas_report: Vec::new(),
+            hostio_counts: HostioCounts::default(),
+        }
+    }
+}
+
+/// Returns [`MSG_SENDER`] as an address.
+fn default_sender() -> Address {
+    const_hex::const_decode_to_array::<20>(MSG_SENDER)
+        .map(Address::from)
+        .expect("should parse MSG_SENDER as an address")
+}
+
+impl VmState {
+    /// Returns the ETH balance of `account`.
+    pub(crate) fn balance(&self, account: Address) -> U256 {
+        self.balances.get(&account).copied().unwrap_or_default()
+    }
+
+    /// Moves `value` wei from `from` to `to`.
+    ///
+    /// Returns `false` if `from` doesn't have enough balance, in which case
+    /// no balance is modified.
+    pub(crate) fn transfer(
+        &mut self,
+        from: Address,
+        to: Address,
+        value: U256,
+    ) -> bool {
+        if from == to {
+            return self.balance(from) >= value;
+        }
+        let Some(from_balance) = self.balance(from).checked_sub(value) else {
+            return false;
+        };
+        let Some(to_balance) = self.balance(to).checked_add(value) else {
+            return false;
+        };
+        self.balances.insert(from, from_balance);
+        self.balances.insert(to, to_balance);
+        true
+    }
+
+    /// Charges `gas` to the running test.
+    pub(crate) fn charge(&mut self, gas: u64) {
+        self.gas_used = self.gas_used.saturating_add(gas);
+    }
+
+    /// Marks `key` as accessed, returning whether it was accessed before.
+    pub(crate) fn access_slot(&mut self, key: Bytes32) -> bool {
+        !self.accessed_slots.insert(key)
+    }
+
+    /// Returns the mock that matches a call to `target` with `calldata`.
+    ///
+    /// If several mocks match, the one with the longest calldata wins, so
+    /// that specific arguments take precedence over a bare selector.
+    pub(crate) fn mocked_call(
+        &self,
+        target: Address,
+        calldata: &[u8],
+    ) -> Option<&MockedCall> {
+        self.mocked_calls
+            .iter()
+            .filter(|mock| {
+                mock.target == target && calldata.starts_with(&mock.calldata)
+            })
+            .max_by_key(|mock| mock.calldata.len())
+    }
+
+    /// Registers a mock, replacing any previous one for the same call.
+    fn mock(&mut self, mock: MockedCall) {
+        self.mocked_calls
+            .retain(|m| m.target != mock.target || m.calldata != mock.calldata);
+        self.mocked_calls.push(mock);
+    }
+}
+
+/// State of the VM for the running test.
+pub(crate) static VM_STATE: Lazy<Mutex<VmState>> =
+    Lazy::new(|| Mutex::new(VmState::default()));
+
+/// Returns the state of the VM for the running test.
+pub(crate) fn vm_state() -> MutexGuard<'static, VmState> {
+    VM_STATE.lock().unwrap_or_else(std::sync::PoisonError::into_inner)
+}
+
+/// Resets the state of the VM to its defaults.
+pub(crate) fn reset_vm() {
+    *vm_state() = VmState::default();
+}
+
+/// Entry point to the mocked Stylus VM running the current test.
+///
+/// # Examples
+///
+/// ```rust,ignore
+/// #[motsu::test]
+/// fn releases_after_deadline(contract: Vesting) {
+///     VM::context().set_block_timestamp(1_000);
+///     assert_eq!(block::timestamp(), 1_000);
+/// }
+/// ```
+pub struct VM;
+
+impl VM {
+    /// Returns the execution [`Context`] of the running test.
+    #[must_use]
+    pub fn context() -> Context {
+        Context
+    }
+}
+
+/// Execution context of the running test.
+///
+/// It configures the values the VM returns for hostios that depend on the
+/// block, transaction or message being processed.
+///
+/// It also keeps track of the ETH balance of every account, which is updated
+/// when value is sent to or from the contract.
+///
+/// Note that the Stylus SDK caches most of these values after they are read
+/// for the first time, so they should be configured before calling into the
+/// contract.
+pub struct Context;
+
+impl Context {
+    /// Returns the timestamp of the current block, in seconds since the UNIX
+    /// epoch.
+    #[must_use]
+    pub fn block_timestamp(&self) -> u64 {
+        vm_state().block_timestamp
+    }
+
+    /// Sets the timestamp of the current block, in seconds since the UNIX
+    /// epoch.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the context.
+    /// * `timestamp` - Value returned by `block::timestamp()`.
+    pub fn set_block_timestamp(&self, timestamp: u64) {
+        vm_state().block_timestamp = timestamp;
+    }
+
+    /// Returns the number of the current block.
+    #[must_use]
+    pub fn block_number(&self) -> u64 {
+        vm_state().block_number
+    }
+
+    /// Sets the number of the current block.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the context.
+    /// * `number` - Value returned by `block::number()`.
+    pub fn set_block_number(&self, number: u64) {
+        vm_state().block_number = number;
+    }
+
+    /// Moves the timestamp of the current block `duration` into the future,
+    /// truncated to whole seconds.
+    ///
+    /// Like the other values of the context, the timestamp is cached by the
+    /// Stylus SDK the first time it is read, so the contract observes it only
+    /// if it hasn't read the timestamp yet. Scenarios at different points in
+    /// time, e.g., before and after a deadline, should be separate tests.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the context.
+    /// * `duration` - Time to move forward.
+    ///
+    /// # Panics
+    ///
+    /// If the timestamp overflows.
+    pub fn advance_time(&self, duration: Duration) {
+        let mut state = vm_state();
+        state.block_timestamp = state
+            .block_timestamp
+            .checked_add(duration.as_secs())
+            .expect("block timestamp should not overflow");
+    }
+
+    /// Moves the number of the current block `blocks` blocks forward.
+    ///
+    /// See [`Context::advance_time`] for when the contract observes the new
+    /// block number.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the context.
+    /// * `blocks` - Number of blocks to move forward.
+    ///
+    /// # Panics
+    ///
+    /// If the block number overflows.
+    pub fn advance_blocks(&self, blocks: u64) {
+        let mut state = vm_state();
+        state.block_number = state
+            .block_number
+            .checked_add(blocks)
+            .expect("block number should not overflow");
+    }
+
+    /// Returns the base fee of the current block, in wei.
+    #[must_use]
+    pub fn block_basefee(&self) -> U256 {
+        vm_state().block_basefee
+    }
+
+    /// Sets the base fee of the current block, in wei.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the context.
+    /// * `basefee` - Value returned by `block::basefee()`.
+    pub fn set_block_basefee(&self, basefee: U256) {
+        vm_state().block_basefee = basefee;
+    }
+
+    /// Returns the address of the current block's coinbase.
+    #[must_use]
+    pub fn block_coinbase(&self) -> Address {
+        vm_state().block_coinbase
+    }
+
+    /// Sets the address of the current block's coinbase.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the context.
+    /// * `coinbase` - Value returned by `block::coinbase()`.
+    pub fn set_block_coinbase(&self, coinbase: Address) {
+        vm_state().block_coinbase = coinbase;
+    }
+
+    /// Returns the id of the chain.
+    #[must_use]
+    pub fn chain_id(&self) -> u64 {
+        vm_state().chain_id
+    }
+
+    /// Sets the id of the chain, e.g., to compute EIP-712 domain separators.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the context.
+    /// * `chain_id` - Value returned by `block::chainid()`.
+    pub fn set_chain_id(&self, chain_id: u64) {
+        vm_state().chain_id = chain_id;
+    }
+
+    /// Returns the address of the account calling the contract.
+    #[must_use]
+    pub fn msg_sender(&self) -> Address {
+        vm_state().msg_sender
+    }
+
+    /// Sets the address of the account calling the contract, which defaults
+    /// to [`MSG_SENDER`].
+    ///
+    /// This is how a test impersonates another account, e.g., to check that
+    /// a function is restricted to its owner. Since `msg::sender()` is cached
+    /// by the Stylus SDK once the contract reads it, the sender can't be
+    /// changed halfway through a test, so every account has to be tested in
+    /// its own test.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the context.
+    /// * `sender` - Address returned by `msg::sender()`.
+    pub fn set_msg_sender(&self, sender: Address) {
+        vm_state().msg_sender = sender;
+    }
+
+    /// Returns the address of the account that sent the transaction.
+    #[must_use]
+    pub fn tx_origin(&self) -> Address {
+        vm_state().tx_origin
+    }
+
+    /// Sets the address of the account that sent the transaction, which
+    /// defaults to [`MSG_SENDER`].
+    ///
+    /// Setting it apart from [`Context::set_msg_sender`] simulates a call
+    /// made through another contract, so that `msg::sender() ==
+    /// tx::origin()` checks can be tested. Like the sender, it is cached by
+    /// the Stylus SDK once the contract reads it.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the context.
+    /// * `origin` - Address returned by `tx::origin()`.
+    pub fn set_tx_origin(&self, origin: Address) {
+        vm_state().tx_origin = origin;
+    }
+
+    /// Returns the ETH value in wei sent to the contract.
+    #[must_use]
+    pub fn msg_value(&self) -> U256 {
+        vm_state().msg_value
+    }
+
+    /// Sets the ETH value in wei sent to the contract, so that `#[payable]`
+    /// functions can be tested.
+    ///
+    /// Like on-chain, `value` is transferred from `msg::sender()` to the
+    /// contract, so the sender has to be [funded][Context::fund] first.
+    /// Setting the value again replaces the previous one, which is refunded
+    /// to whoever paid it, so that the sender only pays for the value of the
+    /// call under test.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the context.
+    /// * `value` - Value returned by `msg::value()`.
+    ///
+    /// # Panics
+    ///
+    /// * If the contract already spent the previous value.
+    /// * If `msg::sender()` doesn't have enough balance to pay for `value`.
+    pub fn set_msg_value(&self, value: U256) {
+        let mut state = vm_state();
+        let (sender, contract) = (state.msg_sender, state.contract_address);
+        let (payer, previous) = (state.msg_value_payer, state.msg_value);
+        assert!(
+            state.transfer(contract, payer, previous),
+            "contract already spent the previous msg value of {previous} wei"
+        );
+        assert!(
+            state.transfer(sender, contract, value),
+            "msg sender has insufficient balance to send {value} wei"
+        );
+        state.msg_value = value;
+        state.msg_value_payer = sender;
+    }
+
+    /// Returns the address of the contract under test.
+    #[must_use]
+    pub fn contract_address(&self) -> Address {
+        vm_state().contract_address
+    }
+
+    /// Sets the address of the contract under test.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the context.
+    /// * `address` - Value returned by `contract::address()`.
+    pub fn set_contract_address(&self, address: Address) {
+        vm_state().contract_address = address;
+    }
+
+    /// Returns the ETH balance of `account`, in wei.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the context.
+    /// * `account` - Account to read the balance of.
+    #[must_use]
+    pub fn balance_of(&self, account: Address) -> U256 {
+        vm_state().balance(account)
+    }
+
+    /// Adds `amount` wei to the ETH balance of `account`.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the context.
+    /// * `account` - Account to fund.
+    /// * `amount` - Amount of wei to add to the balance.
+    ///
+    /// # Panics
+    ///
+    /// If the balance of `account` overflows.
+    pub fn fund(&self, account: Address, amount: U256) {
+        let mut state = vm_state();
+        let balance = state
+            .balance(account)
+            .checked_add(amount)
+            .expect("balance should not overflow");
+        state.balances.insert(account, balance);
+    }
+
+    /// Sets the ETH balance of `account` to `balance` wei.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the context.
+    /// * `account` - Account to set the balance of.
+    /// * `balance` - New balance of `account`.
+    pub fn set_balance(&self, account: Address, balance: U256) {
+        vm_state().balances.insert(account, balance);
+    }
+
+    /// Returns the code deployed at `account`, which is empty for EOAs.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the context.
+    /// * `account` - Account to read the code of.
+    #[must_use]
+    pub fn account_code(&self, account: Address) -> Vec<u8> {
+        vm_state().codes.get(&account).cloned().unwrap_or_default()
+    }
+
+    /// Deploys `code` at `account`, so that it's treated as a contract.
+    /// Passing empty `code` turns `account` back into an EOA.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the context.
+    /// * `account` - Account to deploy the code at.
+    /// * `code` - Bytecode of the account.
+    pub fn set_account_code(&self, account: Address, code: impl Into<Vec<u8>>) {
+        let code = code.into();
+        let mut state = vm_state();
+        if code.is_empty() {
+            state.codes.remove(&account);
+        } else {
+            state.codes.insert(account, code);
+        }
+    }
+
+    /// Returns the gas charged by the hostios called so far in the test.
+    ///
+    /// Only the cost of accessing EVM state (storage, logs, calls and
+    /// hashing) is accounted for, following the EVM gas schedule. The cost
+    /// of executing the `wasm` program itself is not, so this is a lower
+    /// bound of the gas an actual transaction would use.
+    #[must_use]
+    pub fn gas_used(&self) -> u64 {
+        vm_state().gas_used
+    }
+
+    /// Runs `call` and returns its result along with the gas charged by the
+    /// hostios it called.
+    ///
+    /// See [`Context::gas_used`] for details on how gas is estimated.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the context.
+    /// * `call` - Closure calling into the contract.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,ignore
+    /// #[motsu::test]
+    /// fn transfer_gas(contract: Erc20) {
+    ///     contract._mint(msg::sender(), one).unwrap();
+    ///     let (result, gas) =
+    ///         VM::context().measure_gas(|| contract.transfer(alice, one));
+    ///     assert!(result.is_ok());
+    ///     assert!(gas < 30_000);
+    /// }
+    /// ```
+    pub fn measure_gas<R>(&self, call: impl FnOnce() -> R) -> (R, u64) {
+        let before = self.gas_used();
+        let result = call();
+        (result, self.gas_used() - before)
+    }
+
+    /// Runs `call`, recording the gas charged by the hostios it called under
+    /// `name`, and returns its result.
+    ///
+    /// Measurements can be read with [`Context::gas_report`]. If the
+    /// `MOTSU_GAS_REPORT` environment variable is set to a path, they're also
+    /// appended to that file as JSON lines, e.g., `{"function":"transfer",
+    /// "gas":26000}`, so that a table of the gas used by every function can
+    /// be built from a whole test run with the `gas-report` binary of the
+    /// `e2e` crate, and compared across revisions.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the context.
+    /// * `name` - Name of the measurement, usually the function called.
+    /// * `call` - Closure calling into the contract.
+    ///
+    /// # Panics
+    ///
+    /// If `MOTSU_GAS_REPORT` is set but the report can't be written.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,ignore
+    /// #[motsu::test]
+    /// fn meters_transfer(contract: Erc20) {
+    ///     contract._mint(msg::sender(), one).unwrap();
+    ///     VM::context().meter("transfer", || contract.transfer(alice, one));
+    /// }
+    /// ```
+    pub fn meter<R>(&self, name: &str, call: impl FnOnce() -> R) -> R {
+        let (result, gas) = self.measure_gas(call);
+        let measurement = GasMeasurement { name: name.to_owned(), gas };
+
+        if let Some(path) = env::var_os(GAS_REPORT_VAR) {
+            // A single write per line, so that concurrent tests don't
+            // interleave their measurements.
+            let line = format!("{measurement}\n");
+            OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(path)
+                .and_then(|mut file| file.write_all(line.as_bytes()))
+                .expect("should write gas report");
+        }
+
+        vm_state().gas_report.push(measurement);
+        result
+    }
+
+    /// Returns the gas measurements recorded with [`Context::meter`], in
+    /// order.
+    #[must_use]
+    pub fn gas_report(&self) -> Vec<GasMeasurement> {
+        vm_state().gas_report.clone()
+    }
+
+    /// Returns how many times the hostios that touch EVM state were called
+    /// so far in the test.
+    #[must_use]
+    pub fn hostio_counts(&self) -> HostioCounts {
+        vm_state().hostio_counts
+    }
+
+    /// Runs `call` and returns its result along with the number of times it
+    /// used each hostio that touches EVM state.
+    ///
+    /// Gas alone doesn't tell which operations a function spends it on, so
+    /// this helps finding redundant storage reads or writes, e.g., a value
+    /// loaded twice instead of being kept in a local.
+    ///
+    /// Note that the Stylus SDK caches values read through a storage handle,
+    /// so only the accesses that reach the VM are counted, as on-chain.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the context.
+    /// * `call` - Closure calling into the contract.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,ignore
+    /// #[motsu::test]
+    /// fn transfer_hostios(contract: Erc20) {
+    ///     contract._mint(msg::sender(), one).unwrap();
+    ///     let (_, counts) =
+    ///         VM::context().measure_hostios(|| contract.transfer(alice, one));
+    ///     assert_eq!(counts.storage_stores, 2);
+    ///     assert_eq!(counts.logs, 1);
+    /// }
+    /// ```
+    pub fn measure_hostios<R>(
+        &self,
+        call: impl FnOnce() -> R,
+    ) -> (R, HostioCounts) {
+        let before = self.hostio_counts();
+        let result = call();
+        (result, self.hostio_counts() - before)
+    }
+
+    /// Runs `call` with `gas_limit` gas, reverting it if it uses more.
+    ///
+    /// Like on-chain, running out of gas reverts the state `call` changed:
+    /// storage, ETH balances (including value sent along calls and
+    /// deployments), deployed code, deployment nonces and emitted logs.
+    ///
+    /// The gas `call` used is still charged, and neither the slots it warmed
+    /// up nor its hostio counts and gas measurements are reset. Since the VM
+    /// can't interrupt the contract, `call` runs to completion before being
+    /// reverted, and values read by storage handles in the meantime stay
+    /// cached in them.
+    ///
+    /// Calls can be nested, in which case the gas used by the inner call
+    /// counts towards the limit of the outer one.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the context.
+    /// * `gas_limit` - Gas `call` can use.
+    /// * `call` - Closure calling into the contract.
+    ///
+    /// # Errors
+    ///
+    /// If `call` uses more than `gas_limit` gas, then the error
+    /// [`OutOfGas`] is returned.
+    ///
+    /// # Panics
+    ///
+    /// May panic if unable to lock `STORAGE`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,ignore
+    /// #[motsu::test]
+    /// fn bounds_loop(contract: Enumerable) {
+    ///     let result =
+    ///         VM::context().with_gas_limit(100_000, || contract.clear());
+    ///     assert!(result.is_err());
+    /// }
+    /// ```
+    pub fn with_gas_limit<R>(
+        &self,
+        gas_limit: u64,
+        call: impl FnOnce() -> R,
+    ) -> Result<R, OutOfGas> {
+        let snapshot = self.snapshot();
+        let (balances, codes, nonces, logs) = {
+            let state = vm_state();
+            (
+                state.balances.clone(),
+                state.codes.clone(),
+                state.nonces.clone(),
+                state.logs.len(),
+            )
+        };
+
+        let (result, gas) = self.measure_gas(call);
+        if gas <= gas_limit {
+            return Ok(result);
+        }
+
+        *STORAGE.lock().unwrap() = snapshot.0;
+        let mut state = vm_state();
+        state.balances = balances;
+        state.codes = codes;
+        state.nonces = nonces;
+        state.logs.truncate(logs);
+        Err(OutOfGas { gas_limit })
+    }
+
+    /// Calls `contract` with raw `calldata`, dispatching it to the external
+    /// function matching its selector like the contract's entrypoint does.
+    ///
+    /// This allows testing how a contract handles calldata that doesn't go
+    /// through its Rust API, e.g., unknown selectors or malformed arguments.
+    /// Like on-chain, calls with an unknown selector revert with empty data.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the context.
+    /// * `contract` - Contract to call.
+    /// * `calldata` - Selector of the function followed by its ABI-encoded
+    ///   arguments.
+    ///
+    /// # Errors
+    ///
+    /// If the call reverts, then the revert data is returned.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,ignore
+    /// #[motsu::test]
+    /// fn rejects_unknown_selectors(contract: Erc721) {
+    ///     let calldata = [0xde, 0xad, 0xbe, 0xef];
+    ///     let result = VM::context().call_raw(contract, &calldata);
+    ///     assert_eq!(result, Err(vec![]));
+    /// }
+    /// ```
+    pub fn call_raw<C>(
+        &self,
+        contract: &mut C,
+        calldata: &[u8],
+    ) -> Result<Vec<u8>, Vec<u8>>
+    where
+        C: TopLevelStorage + BorrowMut<<C as Router<C>>::Storage> + Router<C>,
+    {
+        let Some((selector, input)) = calldata.split_first_chunk::<4>() else {
+            return Err(Vec::new());
+        };
+        let selector = u32::from_be_bytes(*selector);
+        C::route(contract, selector, input).unwrap_or(Err(Vec::new()))
+    }
+
+    /// Makes calls to `target` whose calldata starts with `calldata` succeed
+    /// and return `return_data`.
+    ///
+    /// Passing just a function selector as `calldata` stubs every call to
+    /// that function, while passing the full calldata stubs only calls with
+    /// those arguments. When several mocks match a call, the most specific
+    /// one is used.
+    ///
+    /// Calls that don't match any mock succeed with empty return data.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the context.
+    /// * `target` - Address of the called contract.
+    /// * `calldata` - Prefix of the calldata to match.
+    /// * `return_data` - ABI-encoded data returned by the call.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,ignore
+    /// let selector = IERC20::balanceOfCall::SELECTOR;
+    /// VM::context().mock_call(token, &selector, U256::from(1).abi_encode());
+    /// ```
+    pub fn mock_call(
+        &self,
+        target: Address,
+        calldata: &[u8],
+        return_data: impl Into<Vec<u8>>,
+    ) {
+        vm_state().mock(MockedCall {
+            target,
+            calldata: calldata.to_vec(),
+            reverts: false,
+            return_data: return_data.into(),
+        });
+    }
+
+    /// Makes calls to `target` whose calldata starts with `calldata` revert
+    /// with `revert_data`.
+    ///
+    /// See [`Context::mock_call`] for details on how calls are matched.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the context.
+    /// * `target` - Address of the called contract.
+    /// * `calldata` - Prefix of the calldata to match.
+    /// * `revert_data` - ABI-encoded error the call reverts with.
+    pub fn mock_revert(
+        &self,
+        target: Address,
+        calldata: &[u8],
+        revert_data: impl Into<Vec<u8>>,
+    ) {
+        vm_state().mock(MockedCall {
+            target,
+            calldata: calldata.to_vec(),
+            reverts: true,
+            return_data: revert_data.into(),
+        });
+    }
+
+    /// Removes all the mocks registered with [`Context::mock_call`] and
+    /// [`Context::mock_revert`].
+    pub fn clear_mocked_calls(&self) {
+        vm_state().mocked_calls.clear();
+    }
+
+    /// Returns the logs emitted so far in the test, in order.
+    #[must_use]
+    pub fn logs(&self) -> Vec<Log> {
+        vm_state().logs.clone()
+    }
+
+    /// Returns the `E` events emitted so far in the test, in order.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,ignore
+    /// contract.transfer(alice, one).unwrap();
+    /// let events = VM::context().emitted_events::<Transfer>();
+    /// assert_eq!(events.len(), 1);
+    /// assert_eq!(events[0].to, alice);
+    /// ```
+    #[must_use]
+    pub fn emitted_events<E: SolEvent>(&self) -> Vec<E> {
+        vm_state().logs.iter().filter_map(Log::decode).collect()
+    }
+
+    /// Removes the logs emitted so far in the test.
+    pub fn clear_logs(&self) {
+        vm_state().logs.clear();
+    }
+
+    /// Runs `call` and returns its result along with the logs it emitted, in
+    /// order.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the context.
+    /// * `call` - Closure calling into the contract.
+    pub fn capture_logs<R>(&self, call: impl FnOnce() -> R) -> (R, Vec<Log>) {
+        let before = vm_state().logs.len();
+        let result = call();
+        let logs = vm_state().logs[before..].to_vec();
+        (result, logs)
+    }
+
+    /// Returns the raw value of the storage slot `slot`.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the context.
+    /// * `slot` - Storage slot to read.
+    ///
+    /// # Panics
+    ///
+    /// May panic if unable to lock `STORAGE`.
+    #[must_use]
+    pub fn load(&self, slot: B256) -> B256 {
+        STORAGE.lock().unwrap().get(&slot.0).copied().unwrap_or_default().into()
+    }
+
+    /// Overwrites the raw value of the storage slot `slot`.
+    ///
+    /// Storage handles cache the values they read, so `store` should be
+    /// called before the contract reads `slot`.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the context.
+    /// * `slot` - Storage slot to write.
+    /// * `value` - Value to write.
+    ///
+    /// # Panics
+    ///
+    /// May panic if unable to lock `STORAGE`.
+    pub fn store(&self, slot: B256, value: B256) {
+        STORAGE.lock().unwrap().insert(slot.0, value.0);
+    }
+
+    /// Returns a snapshot of the storage of the contract under test.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the context.
+    ///
+    /// # Panics
+    ///
+    /// May panic if unable to lock `STORAGE`.
+    #[must_use]
+    pub fn snapshot(&self) -> Snapshot {
+        Snapshot(STORAGE.lock().unwrap().clone())
+    }
+
+    /// Returns the slots whose value differs between `before` and `after`,
+    /// sorted by slot.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the context.
+    /// * `before` - Snapshot taken first.
+    /// * `after` - Snapshot taken last.
+    #[must_use]
+    pub fn storage_diff(
+        &self,
+        before: &Snapshot,
+        after: &Snapshot,
+    ) -> Vec<SlotChange> {
+        let slots: HashSet<_> = before.0.keys().chain(after.0.keys()).collect();
+        let mut changes: Vec<_> = slots
+            .into_iter()
+            .map(|slot| B256::from(*slot))
+            .map(|slot| SlotChange {
+                slot,
+                before: before.get(slot),
+                after: after.get(slot),
+            })
+            .filter(|change| change.before != change.after)
+            .collect();
+        changes.sort_by_key(|change| change.slot);
+        changes
+    }
+
+    /// Runs `call` and asserts that it only changed the storage slots in
+    /// `slots`, returning its result.
+    ///
+    /// This is useful to check that a call has no unintended side effects,
+    /// e.g., that an upgrade doesn't overwrite unrelated state.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the context.
+    /// * `slots` - Storage slots `call` is allowed to change.
+    /// * `call` - Closure calling into the contract.
+    ///
+    /// # Panics
+    ///
+    /// If `call` changed a slot not in `slots`, with a panic message listing
+    /// the unexpected changes.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,ignore
+    /// #[motsu::test]
+    /// fn mint_only_changes_balance_and_supply(contract: Erc20) {
+    ///     let total_supply = B256::with_last_byte(2);
+    ///     VM::context().assert_changes_only(&[balance, total_supply], || {
+    ///         contract._mint(alice, one).unwrap();
+    ///     });
+    /// }
+    /// ```
+    #[track_caller]
+    pub fn assert_changes_only<R>(
+        &self,
+        slots: &[B256],
+        call: impl FnOnce() -> R,
+    ) -> R {
+        let before = self.snapshot();
+        let result = call();
+        let after = self.snapshot();
+
+        let unexpected: Vec<_> = self
+            .storage_diff(&before, &after)
+            .into_iter()
+            .filter(|change| !slots.contains(&change.slot))
+            .collect();
+        assert!(
+            unexpected.is_empty(),
+            "call changed unexpected storage slots: {unexpected:#?}"
+        );
+        result
+    }
+
+    /// Sets the token balance of `account` to `amount`, without minting
+    /// tokens.
+    ///
+    /// This is useful to establish balances of tokens that don't expose a
+    /// way to mint them. `balances_slot` is the storage slot of the
+    /// `mapping(address => uint256)` holding the balances, e.g., `0` for
+    /// `Erc20` when laid out by [`DefaultStorageLayout`].
+    ///
+    /// Note that the total supply is not updated. If needed, it can be set
+    /// with [`Context::store`].
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the context.
+    /// * `balances_slot` - Storage slot of the balances mapping.
+    /// * `account` - Account to set the balance of.
+    /// * `amount` - New balance of `account`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,ignore
+    /// #[motsu::test]
+    /// fn transfers(contract: Erc20) {
+    ///     VM::context().deal(U256::ZERO, msg::sender(), one);
+    ///     contract.transfer(alice, one).unwrap();
+    /// }
+    /// ```
+    ///
+    /// [`DefaultStorageLayout`]: crate::DefaultStorageLayout
+    pub fn deal(&self, balances_slot: U256, account: Address, amount: U256) {
+        let slot = slot::mapping(balances_slot, account);
+        self.store(slot.into(), amount.into());
+    }
+
+    /// Asserts that the raw value of the storage slot `slot` is `expected`.
+    ///
+    /// Combined with the functions in [`crate::slot`], this checks that
+    /// values are stored where a Solidity reference layout expects them.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the context.
+    /// * `slot` - Storage slot to check.
+    /// * `expected` - Expected value of the slot.
+    ///
+    /// # Panics
+    ///
+    /// If the value of `slot` isn't `expected`.
+    #[track_caller]
+    pub fn assert_slot(
+        &self,
+        slot: impl Into<B256>,
+        expected: impl Into<B256>,
+    ) {
+        let (slot, expected) = (slot.into(), expected.into());
+        let value = self.load(slot);
+        assert_eq!(value, expected, "unexpected value at storage slot {slot}");
+    }
+}
@@ -0,0 +1,162 @@
+//! Property-based testing for Stylus contracts.
+//!
+//! Parameters of a [`#[motsu::test]`][crate::test] function following the
+//! contract are generated with [`proptest`], so tests written for a single
+//! example can be turned into property tests by taking the values they use
+//! as parameters:
+//!
+//! ```rust,ignore
+//! #[motsu::test]
+//! fn transfers(contract: Erc20, to: Address, amount: U256) {
+//!     contract._mint(msg::sender(), amount).unwrap();
+//!     contract.transfer(to, amount).unwrap();
+//!     assert_eq!(contract.balance_of(to), amount);
+//! }
+//! ```
+//!
+//! The test runs for many generated inputs, each against a fresh contract.
+//! When it fails, the inputs are shrunk to a minimal failing case, which is
+//! reported in the panic message.
+//!
+//! The number of cases defaults to `256` and can be configured with the
+//! `PROPTEST_CASES` environment variable.
+//!
+//! Parameters are generated from their [`Arbitrary`] implementation by
+//! default. The strategies in this module generate values that are more
+//! likely to hit edge cases, and can be used instead with the `strategy`
+//! attribute:
+//!
+//! ```rust,ignore
+//! #[motsu::test]
+//! fn transfers(
+//!     contract: Erc20,
+//!     #[strategy(motsu::fuzz::address())] to: Address,
+//!     #[strategy(motsu::fuzz::u256())] amount: U256,
+//! ) {
+//!     // ...
+//! }
+//! ```
+//!
+//! [`Arbitrary`]: proptest::arbitrary::Arbitrary
+use core::fmt::Debug;
+use std::panic;
+
+use alloy_primitives::{Address, Bytes, B256, U256};
+pub use proptest::arbitrary::any;
+use proptest::{
+    collection::vec,
+    prop_oneof,
+    strategy::{Just, Strategy},
+    test_runner::{Config, TestError, TestRunner},
+};
+
+use crate::{
+    context::reset_vm,
+    shims::{CONTRACT_ADDRESS, MSG_SENDER},
+    storage::reset_storage,
+};
+
+/// Runs `test` for many values generated by `strategy`.
+///
+/// Storage and the VM state are reset before each case, so that every case
+/// starts from a pristine VM.
+///
+/// Note that the Stylus SDK caches values read from the
+/// [`Context`][crate::prelude::Context] for the whole process, so generated
+/// values shouldn't be used to configure it.
+///
+/// # Panics
+///
+/// If `test` panics for any of the generated values, in which case the panic
+/// message contains the minimal failing input.
+pub fn run<S>(strategy: S, test: impl Fn(S::Value))
+where
+    S: Strategy,
+    S::Value: Debug,
+{
+    let config = Config { failure_persistence: None, ..Config::default() };
+    let mut runner = TestRunner::new(config);
+
+    // Failing cases are reported once shrinking is done, so we don't print
+    // the panics of every case in the meantime.
+    let hook = panic::take_hook();
+    panic::set_hook(Box::new(|_| {}));
+    let result = runner.run(&strategy, |args| {
+        reset_storage();
+        reset_vm();
+        test(args);
+        Ok(())
+    });
+    panic::set_hook(hook);
+
+    match result {
+        Ok(()) => {}
+        Err(TestError::Fail(reason, args)) => {
+            panic!("{reason}; minimal failing input: {args:?}")
+        }
+        Err(TestError::Abort(reason)) => panic!("fuzzing aborted: {reason}"),
+    }
+}
+
+/// Generates addresses, biased towards the ones with a special meaning in
+/// tests: the zero address, `msg::sender()`, the contract under test and the
+/// precompiles.
+///
+/// # Panics
+///
+/// May panic if fails to parse `MSG_SENDER` as an address.
+pub fn address() -> impl Strategy<Value = Address> {
+    let sender = const_hex::const_decode_to_array::<20>(MSG_SENDER)
+        .map(Address::from)
+        .expect("should parse MSG_SENDER as an address");
+    let special = prop_oneof![
+        Just(Address::ZERO),
+        Just(sender),
+        Just(CONTRACT_ADDRESS),
+        (1u8..=9).prop_map(Address::with_last_byte),
+    ];
+    prop_oneof![
+        1 => special,
+        3 => any::<Address>(),
+    ]
+}
+
+/// Generates [`U256`] values over the full range, biased towards the
+/// boundaries where arithmetic usually goes wrong: `0`, `U256::MAX`, powers
+/// of two, and values around them.
+pub fn u256() -> impl Strategy<Value = U256> {
+    let power_of_two = (0..256usize).prop_map(|exp| U256::from(1) << exp);
+    let boundary = prop_oneof![
+        Just(U256::ZERO),
+        Just(U256::from(1)),
+        Just(U256::MAX),
+        Just(U256::MAX - U256::from(1)),
+        power_of_two.clone(),
+        power_of_two.prop_map(|p| p - U256::from(1)),
+    ];
+    prop_oneof![
+        2 => boundary,
+        1 => (0..=u64::from(u16::MAX)).prop_map(U256::from),
+        3 => any::<U256>(),
+    ]
+}
+
+/// Generates [`B256`] values, biased towards all zeroes and all ones.
+pub fn b256() -> impl Strategy<Value = B256> {
+    prop_oneof![
+        1 => Just(B256::ZERO),
+        1 => Just(B256::repeat_byte(0xff)),
+        6 => any::<B256>(),
+    ]
+}
+
+/// Generates [`Bytes`] of up to `1024` bytes, biased towards short and
+/// word-aligned lengths.
+pub fn bytes() -> impl Strategy<Value = Bytes> {
+    let len = prop_oneof![
+        0..=64usize,
+        (0..=32usize).prop_map(|words| words * 32),
+        0..=1024usize,
+    ];
+    len.prop_flat_map(|len| vec(any::<u8>(), len)).prop_map(Bytes::from)
+}
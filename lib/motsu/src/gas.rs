@@ -0,0 +1,137 @@
+//! Gas costs charged by the hostio shims.
+//!
+//! Stylus charges hostios that touch EVM state the same amount of gas as the
+//! equivalent EVM opcodes, so we use the [EVM gas schedule] to estimate how
+//! much gas a call consumes.
+//!
+//! Note that the computation performed by the `wasm` program itself is not
+//! accounted for, so the estimate is a lower bound of the gas an actual
+//! transaction would use. It is still useful to detect regressions, since
+//! storage accesses dominate the cost of most contracts.
+//!
+//! [EVM gas schedule]: https://www.evm.codes/
+use alloy_primitives::U256;
+
+use crate::shims::{Bytes32, WORD_BYTES};
+
+/// Cost of reading a storage slot for the first time in a transaction.
+pub(crate) const COLD_SLOAD: u64 = 2_100;
+/// Cost of reading a storage slot that was already accessed.
+pub(crate) const WARM_ACCESS: u64 = 100;
+/// Cost of setting a storage slot from zero to a non-zero value.
+pub(crate) const SSTORE_SET: u64 = 20_000;
+/// Cost of modifying a storage slot that holds a non-zero value.
+pub(crate) const SSTORE_RESET: u64 = 2_900;
+/// Static cost of the `KECCAK256` opcode.
+pub(crate) const KECCAK256: u64 = 30;
+/// Cost of every word hashed by the `KECCAK256` opcode.
+pub(crate) const KECCAK256_WORD: u64 = 6;
+/// Static cost of the `LOG*` opcodes.
+pub(crate) const LOG: u64 = 375;
+/// Cost of every topic emitted by the `LOG*` opcodes.
+pub(crate) const LOG_TOPIC: u64 = 375;
+/// Cost of every byte of data emitted by the `LOG*` opcodes.
+pub(crate) const LOG_DATA_BYTE: u64 = 8;
+/// Cost of a call that transfers a non-zero value.
+pub(crate) const CALL_VALUE: u64 = 9_000;
+/// Static cost of the `CREATE` and `CREATE2` opcodes.
+pub(crate) const CREATE: u64 = 32_000;
+/// Cost of the `ecrecover` precompile.
+pub(crate) const ECRECOVER: u64 = 3_000;
+/// Static cost of the `sha256` precompile.
+pub(crate) const SHA256: u64 = 60;
+/// Cost of every word hashed by the `sha256` precompile.
+pub(crate) const SHA256_WORD: u64 = 12;
+/// Static cost of the `ripemd160` precompile.
+pub(crate) const RIPEMD160: u64 = 600;
+/// Cost of every word hashed by the `ripemd160` precompile.
+pub(crate) const RIPEMD160_WORD: u64 = 120;
+/// Static cost of the `identity` precompile.
+pub(crate) const IDENTITY: u64 = 15;
+/// Cost of every word copied by the `identity` precompile.
+pub(crate) const IDENTITY_WORD: u64 = 3;
+/// Minimum cost of the `modexp` precompile.
+pub(crate) const MODEXP_MIN: u64 = 200;
+/// Cost of the `ecAdd` precompile.
+pub(crate) const EC_ADD: u64 = 150;
+/// Cost of the `ecMul` precompile.
+pub(crate) const EC_MUL: u64 = 6_000;
+/// Cost of every round of the `blake2f` precompile.
+pub(crate) const BLAKE2F_ROUND: u64 = 1;
+
+/// Returns the cost of hashing `len` bytes.
+pub(crate) fn keccak256(len: usize) -> u64 {
+    KECCAK256 + KECCAK256_WORD * words(len)
+}
+
+/// Returns the cost of reading a slot, depending on whether it was already
+/// accessed.
+pub(crate) fn sload(warm: bool) -> u64 {
+    if warm {
+        WARM_ACCESS
+    } else {
+        COLD_SLOAD
+    }
+}
+
+/// Returns the cost of overwriting `current` with `new`, following the
+/// simplified [EIP-2929] schedule (refunds are not accounted for).
+///
+/// [EIP-2929]: https://eips.ethereum.org/EIPS/eip-2929
+pub(crate) fn sstore(warm: bool, current: &Bytes32, new: &Bytes32) -> u64 {
+    let access = if warm { 0 } else { COLD_SLOAD };
+    let write = if current == new {
+        WARM_ACCESS
+    } else if *current == Bytes32::default() {
+        SSTORE_SET
+    } else {
+        SSTORE_RESET
+    };
+    access + write
+}
+
+/// Returns the cost of emitting a log with `topics` topics and `data_len`
+/// bytes of data.
+pub(crate) fn log(topics: usize, data_len: usize) -> u64 {
+    LOG + LOG_TOPIC * topics as u64 + LOG_DATA_BYTE * data_len as u64
+}
+
+/// Returns the cost of a call, depending on whether it transfers value.
+pub(crate) fn call(has_value: bool) -> u64 {
+    WARM_ACCESS + if has_value { CALL_VALUE } else { 0 }
+}
+
+/// Returns the cost of deploying `code_len` bytes of init code. `CREATE2`
+/// additionally pays for hashing the init code.
+pub(crate) fn create(code_len: usize, salted: bool) -> u64 {
+    let hashing = if salted { KECCAK256_WORD * words(code_len) } else { 0 };
+    CREATE + hashing
+}
+
+/// Returns the cost of a precompile that charges `base` plus `word` for every
+/// word of its `len` bytes of input.
+pub(crate) fn precompile(base: u64, word: u64, len: usize) -> u64 {
+    base + word * words(len)
+}
+
+/// Returns the cost of the `modexp` precompile, following [EIP-2565].
+///
+/// [EIP-2565]: https://eips.ethereum.org/EIPS/eip-2565
+pub(crate) fn modexp(base_len: usize, exponent: &[u8], mod_len: usize) -> u64 {
+    let words = base_len.max(mod_len).div_ceil(8) as u64;
+    let complexity = words.saturating_mul(words);
+
+    // Only the first 32 bytes of the exponent are inspected, longer exponents
+    // are charged for their length.
+    let (head, tail) = exponent.split_at(exponent.len().min(WORD_BYTES));
+    let head_bits = U256::from_be_slice(head).bit_len() as u64;
+    let iterations =
+        (8 * tail.len() as u64 + head_bits.saturating_sub(1)).max(1);
+
+    MODEXP_MIN.max(complexity.saturating_mul(iterations) / 3)
+}
+
+/// Returns the number of words needed to hold `len` bytes.
+fn words(len: usize) -> u64 {
+    len.div_ceil(WORD_BYTES) as u64
+}
@@ -0,0 +1,217 @@
+//! Storage layouts of contracts, to check that upgrades keep existing state
+//! where it is.
+//!
+//! Contracts deriving [`DefaultStorageLayout`] implement [`StorageLayout`],
+//! which resolves the slot and offset of every field the same way their
+//! `Default` implementation does. A layout can be saved as a JSON artifact
+//! with [`to_json`], and checked against the layout of a new implementation
+//! with [`assert_layout_compatible!`]:
+//!
+//! ```rust,ignore
+//! #[test]
+//! fn upgrade_keeps_layout() {
+//!     motsu::assert_layout_compatible!(Erc20, Erc20Capped);
+//! }
+//! ```
+//!
+//! [`DefaultStorageLayout`]: crate::DefaultStorageLayout
+//! [`assert_layout_compatible!`]: crate::assert_layout_compatible
+use std::fmt::Write;
+
+use alloy_primitives::U256;
+
+/// A field of a contract's storage, as laid out by
+/// [`crate::DefaultStorageLayout`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Field {
+    /// Name of the field.
+    pub name: &'static str,
+    /// Storage type of the field, as resolved by [`core::any::type_name`],
+    /// e.g., `stylus_sdk::storage::StorageUint<256, 4>` for both `uint256`
+    /// and `StorageU256`.
+    pub ty: &'static str,
+    /// Slot the field starts at.
+    pub slot: U256,
+    /// Offset of the field in its slot, in bytes.
+    pub offset: u8,
+    /// Size of the field in its slot, in bytes.
+    pub bytes: u8,
+    /// Number of whole slots the field takes, for nested structs and
+    /// fixed-size arrays, or zero if it fits in a slot.
+    pub slots: usize,
+}
+
+/// Places the fields of a storage struct in slots the way
+/// `#[solidity_storage]` does on-chain.
+///
+/// Fields are packed from the low-order end of their slot, so that the first
+/// field of a slot has the highest offset. A field that doesn't fit in what is
+/// left of the current slot starts a new one, and so do nested structs and
+/// fixed-size arrays, which take [`StorageType::REQUIRED_SLOTS`] whole slots.
+///
+/// [`StorageType::REQUIRED_SLOTS`]: stylus_sdk::storage::StorageType::REQUIRED_SLOTS
+#[doc(hidden)]
+#[derive(Debug)]
+pub struct Cursor {
+    /// Slot of the next field, relative to the struct.
+    slot: usize,
+    /// Number of bytes left in the slot.
+    space: usize,
+}
+
+impl Cursor {
+    /// Creates a cursor at the start of a struct.
+    #[must_use]
+    pub const fn new() -> Self {
+        Cursor { slot: 0, space: 32 }
+    }
+
+    /// Returns the slot and offset of a field of `bytes` bytes, taking
+    /// `words` whole slots, and moves past it.
+    ///
+    /// # Panics
+    ///
+    /// If `bytes` is larger than a slot.
+    pub fn place(&mut self, bytes: usize, words: usize) -> (U256, u8) {
+        if self.space < bytes {
+            self.space = 32;
+            self.slot += 1;
+        }
+        self.space -= bytes;
+        let placed = (
+            U256::from(self.slot),
+            u8::try_from(self.space).expect("offset should fit in a slot"),
+        );
+        if words > 0 {
+            self.slot += words;
+            self.space = 32;
+        }
+        placed
+    }
+}
+
+impl Default for Cursor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// A contract whose storage layout is known.
+pub trait StorageLayout {
+    /// Returns the fields of the contract's storage, in declaration order.
+    fn storage_layout() -> Vec<Field>;
+}
+
+/// Returns the storage layout of `T` as a JSON array, to be saved as an
+/// artifact and compared across versions.
+#[must_use]
+pub fn to_json<T: StorageLayout>() -> String {
+    let fields: Vec<String> = T::storage_layout()
+        .iter()
+        .map(|field| {
+            let mut json = String::new();
+            write!(
+                json,
+                "{{\"name\":{:?},\"type\":{:?},\"slot\":\"{:#x}\",\"offset\":{},\
+                 \"bytes\":{}}}",
+                field.name, field.ty, field.slot, field.offset, field.bytes
+            )
+            .expect("should write to a string");
+            json
+        })
+        .collect();
+    format!("[{}]", fields.join(","))
+}
+
+/// Name of the fields reserving slots for future versions of a contract.
+const GAP: &str = "__gap";
+
+/// Returns why the storage layout of `New` is incompatible with the layout of
+/// `Old`, if it is.
+///
+/// `New` is compatible with `Old` if every field of `Old` is found in `New` at
+/// the same position, slot and offset, and with the same type. Fields can be
+/// renamed, and new fields can be appended.
+///
+/// Fields named `__gap` reserve slots for future versions. New fields can be
+/// inserted before a gap, as long as they fit in it and the gap shrinks so
+/// that it still ends at the same slot.
+#[must_use]
+pub fn incompatibilities<Old: StorageLayout, New: StorageLayout>() -> Vec<String>
+{
+    let new = New::storage_layout();
+    let mut new = new.iter().peekable();
+    let mut incompatibilities = Vec::new();
+    for old in &Old::storage_layout() {
+        if old.name == GAP {
+            let end = old.slot + U256::from(old.slots);
+            while let Some(field) =
+                new.next_if(|field| field.name != GAP && field.slot < end)
+            {
+                let field_end = field.slot + U256::from(field.slots.max(1));
+                if field_end > end {
+                    incompatibilities.push(format!(
+                        "field `{}` at slot {:#x} overflows the gap ending at \
+                         slot {:#x}",
+                        field.name, field.slot, end
+                    ));
+                }
+            }
+            if let Some(gap) = new.next_if(|field| field.name == GAP) {
+                let gap_end = gap.slot + U256::from(gap.slots);
+                if gap_end != end {
+                    incompatibilities.push(format!(
+                        "gap `{}` at slot {:#x} ends at slot {:#x}, instead of \
+                         {:#x}",
+                        gap.name, gap.slot, gap_end, end
+                    ));
+                }
+            }
+            continue;
+        }
+
+        let Some(new) = new.next() else {
+            incompatibilities.push(format!("field `{}` was removed", old.name));
+            continue;
+        };
+        let moved = (old.slot, old.offset, old.bytes)
+            != (new.slot, new.offset, new.bytes);
+        if moved || old.ty != new.ty {
+            incompatibilities.push(format!(
+                "field `{}: {}` at slot {:#x}, offset {} became `{}: {}` at \
+                 slot {:#x}, offset {}",
+                old.name,
+                old.ty,
+                old.slot,
+                old.offset,
+                new.name,
+                new.ty,
+                new.slot,
+                new.offset
+            ));
+        }
+    }
+    incompatibilities
+}
+
+/// Asserts that the storage layout of `$new` is compatible with the layout of
+/// `$old`, i.e., that upgrading from `$old` to `$new` doesn't move existing
+/// state. See [`incompatibilities`] for what is compatible.
+///
+/// # Panics
+///
+/// If the layouts are incompatible, listing why.
+#[macro_export]
+macro_rules! assert_layout_compatible {
+    ($old:ty, $new:ty) => {{
+        let incompatibilities =
+            $crate::layout::incompatibilities::<$old, $new>();
+        assert!(
+            incompatibilities.is_empty(),
+            "storage layout of `{}` is incompatible with `{}`:\n{}",
+            stringify!($new),
+            stringify!($old),
+            incompatibilities.join("\n")
+        );
+    }};
+}
@@ -58,8 +58,111 @@
 //! }
 //! ```
 //!
-//! Note that currently, test suites using [`motsu::test`][test_attribute] will
-//! run serially because of global access to storage.
+//! ### Fuzzing
+//!
+//! Parameters following the contract are generated with `proptest`, turning the
+//! test into a property test that runs for many inputs, each against a fresh
+//! contract. Failing inputs are shrunk and reported in the panic message.
+//!
+//! ```rust,ignore
+//! #[motsu::test]
+//! fn mints(contract: Erc20, to: Address, amount: U256) {
+//!     contract._mint(to, amount).unwrap();
+//!     assert_eq!(amount, contract.balance_of(to));
+//! }
+//! ```
+//!
+//! ### Execution Context
+//!
+//! Values that depend on the block or message being processed can be
+//! configured through [`VM::context()`][prelude::VM::context] before calling
+//! into the contract:
+//!
+//! ```rust,ignore
+//! use motsu::prelude::VM;
+//!
+//! #[motsu::test]
+//! fn reads_timestamp(contract: Erc20) {
+//!     VM::context().set_block_timestamp(1_700_000_000);
+//!     VM::context().set_block_number(42);
+//!     VM::context().set_chain_id(42_161);
+//!     VM::context().set_msg_value(U256::from(1));
+//!     assert_eq!(block::timestamp(), 1_700_000_000);
+//! }
+//! ```
+//!
+//! Tests can impersonate another account with `set_msg_sender`, e.g., to check
+//! access control, and simulate calls made through another contract by setting
+//! a different `tx::origin()` with `set_tx_origin`.
+//!
+//! Schedules, like vesting or timelocks, can be tested with `advance_time`
+//! and `advance_blocks`. Block values are cached by the Stylus SDK once the
+//! contract reads them, so the time should be set up before the first call,
+//! and every point in time should be tested in its own test.
+//!
+//! The context also tracks ETH balances: accounts can be funded with
+//! `fund`, and balances are updated when value is sent to or from the
+//! contract. Use `balance_of` to assert ETH movements.
+//!
+//! Gas charged by the hostios a call uses (storage accesses, logs, calls and
+//! hashing) can be estimated with `measure_gas`, to catch gas regressions
+//! early. Note that the cost of executing the `wasm` program itself is not
+//! accounted for. Gas-sensitive code paths can be exercised with
+//! `with_gas_limit`, which reverts the call if it uses more gas than the given
+//! limit. Calls wrapped with `meter` are recorded by name and, when
+//! `MOTSU_GAS_REPORT` is set to a path, appended to it as JSON lines, in the
+//! format of the end-to-end `GAS_REPORT`. The `gas-report` binary of the `e2e`
+//! crate prints them and compares the gas used by every function across
+//! revisions.
+//! To target optimizations at the operations a call actually spends gas on,
+//! `measure_hostios` counts the storage reads and writes, calls and logs it
+//! makes.
+//!
+//! Calls to other contracts can be stubbed with `mock_call` and `mock_revert`,
+//! which match calls by target address and calldata prefix (e.g., a function
+//! selector). Calls to the `ecrecover`, `sha256`, `ripemd160`, `identity`,
+//! `modexp`, `ecAdd`, `ecMul` and `blake2f` precompiles are executed as
+//! on-chain, and other calls that aren't mocked, including calls to the
+//! `ecPairing` and point evaluation precompiles, succeed with empty return
+//! data. Conversely, `call_raw` calls the
+//! contract under test with raw calldata, dispatched like its entrypoint does,
+//! to test unknown selectors or malformed arguments.
+//!
+//! Contracts deployed with `CREATE` or `CREATE2` get the same address as
+//! on-chain. Their init code isn't executed, so calls to them should be mocked.
+//!
+//! Raw storage slots can be read and written with `load` and `store`, and token
+//! balances can be set without minting with `deal`. Storage can be captured
+//! with `snapshot`, and two snapshots compared with `storage_diff`.
+//! `assert_changes_only` checks that a call doesn't change any slots other than
+//! the expected ones. The functions in `motsu::slot` compute the slots of
+//! mapping values and array elements like Solidity does, and `assert_slot`
+//! checks their raw values against a reference layout.
+//!
+//! All contracts instantiated in a test share its storage, so upgrades can be
+//! simulated by instantiating the new implementation with `Default::default()`
+//! after setting up state with the old one. `assert_layout_compatible!` checks
+//! that the new implementation doesn't move the fields of the old one, based on
+//! the layouts resolved by `DefaultStorageLayout`. New fields can also take
+//! slots reserved by a field named `__gap`, as long as the gap shrinks
+//! accordingly.
+//!
+//! Instead of hardcoding addresses, tests can use `Account::named("alice")`,
+//! which derives a stable address from the name. `Account::builder` also sets
+//! the balance and code of the account in the VM.
+//!
+//! Libraries that call other contracts on behalf of the calling one can be
+//! tested by passing `Caller` as the calling contract.
+//!
+//! Emitted logs are recorded in order. They can be inspected with `logs`,
+//! decoded with `emitted_events`, and captured for a single call with
+//! `capture_logs`.
+//!
+//! Note that each test annotated with [`motsu::test`][test_attribute] runs in
+//! its own process. The Stylus SDK caches these values the first time they are
+//! read, so this is the only way to keep tests from observing each other's
+//! context. Since tests don't share any state, they run in parallel on all
+//! available cores.
 //!
 //! ### Notice
 //!
@@ -69,9 +172,17 @@
 //! requests.
 //!
 //! [test_attribute]: crate::test
+mod account;
+mod caller;
 mod context;
+pub mod fuzz;
+mod gas;
+pub mod layout;
+mod precompiles;
 pub mod prelude;
+mod revert;
 mod shims;
+pub mod slot;
 mod storage;
 
 pub use motsu_proc::{test, DefaultStorageLayout};
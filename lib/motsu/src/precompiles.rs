@@ -0,0 +1,363 @@
+//! Precompiled contracts answering calls made by the contract under test.
+//!
+//! Calls to the addresses of the standard [precompiles] are executed natively,
+//! so that code relying on them, e.g., signature recovery, behaves in tests as
+//! it does on-chain.
+//!
+//! Supported precompiles are `ecrecover` (`0x01`), `sha256` (`0x02`),
+//! `ripemd160` (`0x03`), `identity` (`0x04`), `modexp` (`0x05`), `ecAdd`
+//! (`0x06`), `ecMul` (`0x07`) and `blake2f` (`0x09`).
+//!
+//! The `ecPairing` (`0x08`) and point evaluation (`0x0a`) precompiles are not
+//! supported, since they would need a pairing library and the KZG trusted
+//! setup. Calls to them should be mocked.
+//!
+//! [precompiles]: https://www.evm.codes/precompiled
+use alloy_primitives::{hex, Address, U256};
+use k256::ecdsa::{RecoveryId, Signature, VerifyingKey};
+use num_bigint::BigUint;
+use ripemd::Ripemd160;
+use sha2::{Digest, Sha256};
+
+use crate::{
+    gas,
+    shims::{keccak256, WORD_BYTES},
+};
+
+/// Largest operand accepted by the `modexp` precompile, in bytes, as specified
+/// in [EIP-7823].
+///
+/// [EIP-7823]: https://eips.ethereum.org/EIPS/eip-7823
+const MODEXP_MAX_LEN: usize = 1024;
+
+/// Length of the input of the `blake2f` precompile, in bytes.
+const BLAKE2F_INPUT_LEN: usize = 213;
+
+/// A precompile was called with malformed input, e.g., a point that is not on
+/// the curve, so that the call fails with empty return data.
+#[derive(Debug, PartialEq, Eq)]
+pub(crate) struct InvalidInput;
+
+/// Executes the precompile at `address` with `input`.
+///
+/// Returns the output of the precompile along with the gas it consumed, or
+/// `None` if there is no supported precompile at `address`.
+pub(crate) fn run(
+    address: Address,
+    input: &[u8],
+) -> Option<Result<(Vec<u8>, u64), InvalidInput>> {
+    let [0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, id] =
+        address.into_array()
+    else {
+        return None;
+    };
+    let output = match id {
+        0x01 => (ecrecover(input), gas::ECRECOVER),
+        0x02 => {
+            let hash = Sha256::digest(input).to_vec();
+            (hash, gas::precompile(gas::SHA256, gas::SHA256_WORD, input.len()))
+        }
+        0x03 => {
+            let mut output = vec![0; 12];
+            output.extend(Ripemd160::digest(input));
+            let cost = gas::precompile(
+                gas::RIPEMD160,
+                gas::RIPEMD160_WORD,
+                input.len(),
+            );
+            (output, cost)
+        }
+        0x04 => {
+            let cost =
+                gas::precompile(gas::IDENTITY, gas::IDENTITY_WORD, input.len());
+            (input.to_vec(), cost)
+        }
+        0x05 => return Some(modexp(input)),
+        0x06 => return Some(ec_add(input)),
+        0x07 => return Some(ec_mul(input)),
+        0x09 => return Some(blake2f(input)),
+        _ => return None,
+    };
+    Some(Ok(output))
+}
+
+/// Recovers the address that signed a message hash.
+///
+/// The input is the message hash, followed by the `v`, `r` and `s` values of
+/// the signature, each padded to 32 bytes. The output is the signer padded to
+/// 32 bytes, or empty if the signature is invalid.
+fn ecrecover(input: &[u8]) -> Vec<u8> {
+    let input = padded(input, 0, 4 * WORD_BYTES);
+    let (hash, v, signature) = (&input[..32], &input[32..64], &input[64..]);
+
+    let v = U256::from_be_slice(v);
+    let Some(y_odd) = [27, 28].iter().position(|b| v == U256::from(*b)) else {
+        return Vec::new();
+    };
+    let Ok(signature) = Signature::from_slice(signature) else {
+        return Vec::new();
+    };
+    // Unlike the `ecrecover` precompile, `k256` only accepts signatures with a
+    // low `s` value, so we use the equivalent low-`s` signature instead.
+    let (signature, y_odd) = match signature.normalize_s() {
+        Some(normalized) => (normalized, y_odd == 0),
+        None => (signature, y_odd == 1),
+    };
+    let recovery_id = RecoveryId::new(y_odd, false);
+    let Ok(key) =
+        VerifyingKey::recover_from_prehash(hash, &signature, recovery_id)
+    else {
+        return Vec::new();
+    };
+
+    let point = key.to_encoded_point(false);
+    let mut output = keccak256(&point.as_bytes()[1..]).to_vec();
+    output[..12].fill(0);
+    output
+}
+
+/// Computes `base ** exponent % modulus` for arbitrarily large numbers, as
+/// specified in [EIP-198].
+///
+/// Returns the output along with the gas consumed, priced as specified in
+/// [EIP-2565].
+///
+/// # Errors
+///
+/// If an operand is longer than [`MODEXP_MAX_LEN`].
+///
+/// [EIP-198]: https://eips.ethereum.org/EIPS/eip-198
+/// [EIP-2565]: https://eips.ethereum.org/EIPS/eip-2565
+fn modexp(input: &[u8]) -> Result<(Vec<u8>, u64), InvalidInput> {
+    let header = padded(input, 0, 3 * WORD_BYTES);
+    let mut lens = [0; 3];
+    for (i, len) in lens.iter_mut().enumerate() {
+        let word = U256::from_be_slice(&header[i * 32..(i + 1) * 32]);
+        *len = usize::try_from(word)
+            .ok()
+            .filter(|len| *len <= MODEXP_MAX_LEN)
+            .ok_or(InvalidInput)?;
+    }
+    let [base_len, exp_len, mod_len] = lens;
+
+    let base = padded(input, 3 * WORD_BYTES, base_len);
+    let exponent = padded(input, 3 * WORD_BYTES + base_len, exp_len);
+    let modulus = padded(input, 3 * WORD_BYTES + base_len + exp_len, mod_len);
+    let cost = gas::modexp(base_len, &exponent, mod_len);
+
+    let modulus = BigUint::from_bytes_be(&modulus);
+    if modulus == BigUint::ZERO {
+        return Ok((vec![0; mod_len], cost));
+    }
+    let result = BigUint::from_bytes_be(&base)
+        .modpow(&BigUint::from_bytes_be(&exponent), &modulus)
+        .to_bytes_be();
+    Ok((left_padded(&result, mod_len), cost))
+}
+
+/// A point of the `alt_bn128` curve in affine coordinates, or `None` for the
+/// point at infinity.
+type G1 = Option<(BigUint, BigUint)>;
+
+/// Returns the modulus of the field the `alt_bn128` curve is defined over.
+fn bn254_modulus() -> BigUint {
+    BigUint::from_bytes_be(&hex!(
+        "30644e72e131a029b85045b68181585d97816a916871ca8d3c208c16d87cfd47"
+    ))
+}
+
+/// Adds two points of the `alt_bn128` curve, as specified in [EIP-196].
+///
+/// # Errors
+///
+/// If a point is not on the curve.
+///
+/// [EIP-196]: https://eips.ethereum.org/EIPS/eip-196
+fn ec_add(input: &[u8]) -> Result<(Vec<u8>, u64), InvalidInput> {
+    let field = bn254_modulus();
+    let input = padded(input, 0, 4 * WORD_BYTES);
+    let a = bn254_point(&input[..64], &field)?;
+    let b = bn254_point(&input[64..], &field)?;
+    Ok((bn254_encode(&bn254_add(&a, &b, &field)), gas::EC_ADD))
+}
+
+/// Multiplies a point of the `alt_bn128` curve by a scalar, as specified in
+/// [EIP-196].
+///
+/// # Errors
+///
+/// If the point is not on the curve.
+///
+/// [EIP-196]: https://eips.ethereum.org/EIPS/eip-196
+fn ec_mul(input: &[u8]) -> Result<(Vec<u8>, u64), InvalidInput> {
+    let field = bn254_modulus();
+    let input = padded(input, 0, 3 * WORD_BYTES);
+    let point = bn254_point(&input[..64], &field)?;
+    let scalar = U256::from_be_slice(&input[64..]);
+
+    let mut result = None;
+    for bit in (0..scalar.bit_len()).rev() {
+        result = bn254_add(&result, &result, &field);
+        if scalar.bit(bit) {
+            result = bn254_add(&result, &point, &field);
+        }
+    }
+    Ok((bn254_encode(&result), gas::EC_MUL))
+}
+
+/// Decodes a point of the `alt_bn128` curve from its 64 bytes coordinates,
+/// `(0, 0)` being the point at infinity.
+fn bn254_point(input: &[u8], field: &BigUint) -> Result<G1, InvalidInput> {
+    let x = BigUint::from_bytes_be(&input[..32]);
+    let y = BigUint::from_bytes_be(&input[32..64]);
+    if x >= *field || y >= *field {
+        return Err(InvalidInput);
+    }
+    if x == BigUint::ZERO && y == BigUint::ZERO {
+        return Ok(None);
+    }
+    // The curve is `y^2 = x^3 + 3`.
+    if &y * &y % field != (&x * &x * &x + 3u8) % field {
+        return Err(InvalidInput);
+    }
+    Ok(Some((x, y)))
+}
+
+/// Adds two points of the `alt_bn128` curve.
+fn bn254_add(a: &G1, b: &G1, field: &BigUint) -> G1 {
+    let (Some((x1, y1)), Some((x2, y2))) = (a, b) else {
+        return a.clone().or_else(|| b.clone());
+    };
+    let inverse = |n: BigUint| n.modpow(&(field - 2u8), field);
+    let slope = if x1 == x2 {
+        if (y1 + y2) % field == BigUint::ZERO {
+            return None;
+        }
+        3u8 * x1 * x1 % field * inverse(2u8 * y1) % field
+    } else {
+        (y2 + field - y1) * inverse(x2 + field - x1) % field
+    };
+    let x3 = (&slope * &slope + 2u8 * field - x1 - x2) % field;
+    let y3 = (slope * ((x1 + field - &x3) % field) + field - y1) % field;
+    Some((x3, y3))
+}
+
+/// Encodes a point of the `alt_bn128` curve as its 64 bytes coordinates.
+fn bn254_encode(point: &G1) -> Vec<u8> {
+    let Some((x, y)) = point else {
+        return vec![0; 2 * WORD_BYTES];
+    };
+    let mut output = left_padded(&x.to_bytes_be(), WORD_BYTES);
+    output.extend(left_padded(&y.to_bytes_be(), WORD_BYTES));
+    output
+}
+
+/// Initialization vector of `BLAKE2b`.
+const BLAKE2B_IV: [u64; 8] = [
+    0x6a09_e667_f3bc_c908,
+    0xbb67_ae85_84ca_a73b,
+    0x3c6e_f372_fe94_f82b,
+    0xa54f_f53a_5f1d_36f1,
+    0x510e_527f_ade6_82d1,
+    0x9b05_688c_2b3e_6c1f,
+    0x1f83_d9ab_fb41_bd6b,
+    0x5be0_cd19_137e_2179,
+];
+
+/// Message word permutations of the rounds of `BLAKE2b`.
+const BLAKE2B_SIGMA: [[usize; 16]; 10] = [
+    [0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15],
+    [14, 10, 4, 8, 9, 15, 13, 6, 1, 12, 0, 2, 11, 7, 5, 3],
+    [11, 8, 12, 0, 5, 2, 15, 13, 10, 14, 3, 6, 7, 1, 9, 4],
+    [7, 9, 3, 1, 13, 12, 11, 14, 2, 6, 5, 10, 4, 0, 15, 8],
+    [9, 0, 5, 7, 2, 4, 10, 15, 14, 1, 11, 12, 6, 8, 3, 13],
+    [2, 12, 6, 10, 0, 11, 8, 3, 4, 13, 7, 5, 15, 14, 1, 9],
+    [12, 5, 1, 15, 14, 13, 4, 10, 0, 7, 6, 3, 9, 2, 8, 11],
+    [13, 11, 7, 14, 12, 1, 3, 9, 5, 0, 15, 4, 8, 6, 2, 10],
+    [6, 15, 14, 9, 11, 3, 0, 8, 12, 2, 13, 7, 1, 4, 10, 5],
+    [10, 2, 8, 4, 7, 6, 1, 5, 15, 11, 9, 14, 3, 12, 13, 0],
+];
+
+/// Runs the compression function `F` of `BLAKE2b`, as specified in
+/// [EIP-152].
+///
+/// # Errors
+///
+/// If the input is not exactly 213 bytes long, or its final block indicator
+/// is neither `0` nor `1`.
+///
+/// [EIP-152]: https://eips.ethereum.org/EIPS/eip-152
+fn blake2f(input: &[u8]) -> Result<(Vec<u8>, u64), InvalidInput> {
+    if input.len() != BLAKE2F_INPUT_LEN {
+        return Err(InvalidInput);
+    }
+    let final_block = match input[212] {
+        0 => false,
+        1 => true,
+        _ => return Err(InvalidInput),
+    };
+    let rounds = u32::from_be_bytes(input[..4].try_into().unwrap());
+    let words = |bytes: &[u8]| -> Vec<u64> {
+        bytes
+            .chunks(8)
+            .map(|word| u64::from_le_bytes(word.try_into().unwrap()))
+            .collect()
+    };
+    let mut state = words(&input[4..68]);
+    let message = words(&input[68..196]);
+    let counter = words(&input[196..212]);
+
+    let mut v = [0u64; 16];
+    v[..8].copy_from_slice(&state);
+    v[8..].copy_from_slice(&BLAKE2B_IV);
+    v[12] ^= counter[0];
+    v[13] ^= counter[1];
+    if final_block {
+        v[14] = !v[14];
+    }
+    for round in 0..rounds as usize {
+        let sigma = &BLAKE2B_SIGMA[round % 10];
+        let mut mix = |a: usize, b: usize, c: usize, d: usize, x, y| {
+            v[a] = v[a].wrapping_add(v[b]).wrapping_add(message[sigma[x]]);
+            v[d] = (v[d] ^ v[a]).rotate_right(32);
+            v[c] = v[c].wrapping_add(v[d]);
+            v[b] = (v[b] ^ v[c]).rotate_right(24);
+            v[a] = v[a].wrapping_add(v[b]).wrapping_add(message[sigma[y]]);
+            v[d] = (v[d] ^ v[a]).rotate_right(16);
+            v[c] = v[c].wrapping_add(v[d]);
+            v[b] = (v[b] ^ v[c]).rotate_right(63);
+        };
+        mix(0, 4, 8, 12, 0, 1);
+        mix(1, 5, 9, 13, 2, 3);
+        mix(2, 6, 10, 14, 4, 5);
+        mix(3, 7, 11, 15, 6, 7);
+        mix(0, 5, 10, 15, 8, 9);
+        mix(1, 6, 11, 12, 10, 11);
+        mix(2, 7, 8, 13, 12, 13);
+        mix(3, 4, 9, 14, 14, 15);
+    }
+    for (i, word) in state.iter_mut().enumerate() {
+        *word ^= v[i] ^ v[i + 8];
+    }
+
+    let output = state.iter().flat_map(|word| word.to_le_bytes()).collect();
+    Ok((output, gas::BLAKE2F_ROUND * u64::from(rounds)))
+}
+
+/// Returns `len` bytes of `input` starting at `offset`, right-padded with
+/// zeroes when `input` is shorter.
+fn padded(input: &[u8], offset: usize, len: usize) -> Vec<u8> {
+    let mut bytes = vec![0; len];
+    if let Some(available) = input.get(offset..) {
+        let copied = available.len().min(len);
+        bytes[..copied].copy_from_slice(&available[..copied]);
+    }
+    bytes
+}
+
+/// Returns `bytes` left-padded with zeroes to `len` bytes.
+fn left_padded(bytes: &[u8], len: usize) -> Vec<u8> {
+    let mut padded = vec![0; len - bytes.len()];
+    padded.extend(bytes);
+    padded
+}
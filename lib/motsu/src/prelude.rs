@@ -1,6 +1,12 @@
 //! Common imports for `motsu` tests.
 pub use crate::{
-    context::{acquire_storage, with_context},
+    account::{Account, AccountBuilder},
+    caller::Caller,
+    context::{
+        acquire_storage, run_isolated, with_context, Context, GasMeasurement,
+        HostioCounts, Log, OutOfGas, SlotChange, Snapshot, VM,
+    },
+    revert::{ExpectRevert, Revert},
     shims::*,
     storage::reset_storage,
 };
@@ -0,0 +1,217 @@
+//! Assertions for contract calls that are expected to revert.
+use std::fmt::{self, Debug};
+
+use alloy_primitives::U256;
+use alloy_sol_types::{sol, SolError};
+
+sol! {
+    /// Revert reason of `require(condition, reason)` and `revert(reason)`.
+    error Error(string message);
+    /// Revert reason of failed assertions, arithmetic errors and the like.
+    error Panic(uint256 code);
+}
+
+/// Data a contract call reverted with.
+///
+/// Its [`fmt::Display`] implementation decodes the standard `Error(string)`
+/// and `Panic(uint256)` reverts, and falls back to the raw revert data
+/// otherwise.
+#[derive(Clone, PartialEq, Eq)]
+pub struct Revert(Vec<u8>);
+
+impl Revert {
+    /// Returns the raw revert data.
+    #[must_use]
+    pub fn data(&self) -> &[u8] {
+        &self.0
+    }
+
+    /// Decodes the revert data as the Solidity error `E`, returning `None` if
+    /// the call reverted with a different error.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,ignore
+    /// let err = contract.transfer(bob, one).motsu_unwrap_err();
+    /// let err = err.downcast::<ERC20InsufficientBalance>().unwrap();
+    /// assert_eq!(err.needed, one);
+    /// ```
+    #[must_use]
+    pub fn downcast<E: SolError>(&self) -> Option<E> {
+        E::decode(&self.0, true).ok()
+    }
+
+    /// Returns whether the call reverted with the Solidity error `E`.
+    #[must_use]
+    pub fn is<E: SolError>(&self) -> bool {
+        self.downcast::<E>().is_some()
+    }
+}
+
+impl From<Vec<u8>> for Revert {
+    fn from(data: Vec<u8>) -> Self {
+        Revert(data)
+    }
+}
+
+impl fmt::Display for Revert {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(Error { message }) = self.downcast() {
+            return write!(f, "Error({message:?})");
+        }
+        if let Some(Panic { code }) = self.downcast() {
+            return write!(f, "Panic({code:#x}): {}", panic_reason(code));
+        }
+        write!(f, "0x{}", const_hex::encode(&self.0))
+    }
+}
+
+impl Debug for Revert {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
+
+/// Returns the meaning of a Solidity panic `code`.
+fn panic_reason(code: U256) -> &'static str {
+    match u8::try_from(code) {
+        Ok(0x00) => "generic compiler panic",
+        Ok(0x01) => "assertion failed",
+        Ok(0x11) => "arithmetic overflow or underflow",
+        Ok(0x12) => "division or modulo by zero",
+        Ok(0x21) => "invalid enum value",
+        Ok(0x22) => "invalid storage byte array encoding",
+        Ok(0x31) => "pop on an empty array",
+        Ok(0x32) => "array index out of bounds",
+        Ok(0x41) => "out of memory",
+        Ok(0x51) => "call to an uninitialized function",
+        _ => "unknown panic code",
+    }
+}
+
+/// Extends the result of a contract call with typed revert assertions.
+///
+/// It is implemented for any `Result` whose error can be abi-encoded as revert
+/// data, like the errors deriving `SolidityError`.
+///
+/// # Examples
+///
+/// ```rust,ignore
+/// use motsu::prelude::ExpectRevert;
+///
+/// #[motsu::test]
+/// fn reverts_when_insufficient_balance(contract: Erc20) {
+///     let err = contract
+///         .transfer(bob, one)
+///         .motsu_expect_revert::<ERC20InsufficientBalance>();
+///     assert_eq!(err.needed, one);
+/// }
+/// ```
+pub trait ExpectRevert {
+    /// Value returned by the call when it succeeds.
+    type Output;
+
+    /// Returns the value returned by the call.
+    ///
+    /// # Panics
+    ///
+    /// If the call reverted, with a panic message starting with `msg` and
+    /// followed by the decoded revert.
+    #[track_caller]
+    fn motsu_expect(self, msg: &str) -> Self::Output;
+
+    /// Returns the data the call reverted with.
+    ///
+    /// # Panics
+    ///
+    /// If the call didn't revert.
+    #[track_caller]
+    fn motsu_unwrap_err(self) -> Revert;
+
+    /// Asserts that the call reverted with the Solidity error `E`, and
+    /// returns it decoded so that its fields can be checked.
+    ///
+    /// # Panics
+    ///
+    /// If the call didn't revert, or if it reverted with an error other than
+    /// `E`.
+    #[track_caller]
+    fn motsu_expect_revert<E: SolError>(self) -> E;
+
+    /// Asserts that the call reverted with the Solidity error `E`, and that
+    /// the decoded error satisfies `matcher`.
+    ///
+    /// # Panics
+    ///
+    /// If the call didn't revert, if it reverted with an error other than
+    /// `E`, or if `matcher` returns `false`.
+    #[track_caller]
+    fn motsu_expect_revert_with<E: SolError + Debug>(
+        self,
+        matcher: impl FnOnce(&E) -> bool,
+    ) where
+        Self: Sized,
+    {
+        let err = self.motsu_expect_revert::<E>();
+        assert!(
+            matcher(&err),
+            "call reverted with `{}`, but it didn't match: {err:?}",
+            E::SIGNATURE
+        );
+    }
+}
+
+impl<T, R> ExpectRevert for Result<T, R>
+where
+    T: Debug,
+    R: Debug + Into<Vec<u8>>,
+{
+    type Output = T;
+
+    fn motsu_expect(self, msg: &str) -> T {
+        match self {
+            Ok(value) => value,
+            Err(err) => {
+                let description = describe(err);
+                panic!("{msg}: call reverted with {description}")
+            }
+        }
+    }
+
+    fn motsu_unwrap_err(self) -> Revert {
+        match self {
+            Ok(value) => {
+                panic!("expected call to revert, but it returned {value:?}")
+            }
+            Err(err) => Revert(err.into()),
+        }
+    }
+
+    fn motsu_expect_revert<E: SolError>(self) -> E {
+        match self {
+            Ok(value) => panic!(
+                "expected call to revert with `{}`, but it returned {value:?}",
+                E::SIGNATURE
+            ),
+            Err(err) => {
+                let description = format!("{err:?}");
+                let revert = Revert(err.into());
+                revert.downcast().unwrap_or_else(|| {
+                    panic!(
+                        "expected call to revert with `{}`, but it reverted \
+                         with {description} ({revert})",
+                        E::SIGNATURE,
+                    )
+                })
+            }
+        }
+    }
+}
+
+/// Describes `err` with both its [`Debug`] representation, which names typed
+/// errors and their fields, and its decoded revert data.
+fn describe<R: Debug + Into<Vec<u8>>>(err: R) -> String {
+    let description = format!("{err:?}");
+    let revert = Revert(err.into());
+    format!("{description} ({revert})")
+}
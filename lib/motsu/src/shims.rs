@@ -38,41 +38,24 @@
 //! }
 //! ```
 //!
-//! Note that for proper usage, tests should have exclusive access to storage,
-//! since they run in parallel, which may cause undesired results.
+//! Each test annotated with `motsu::test` runs in its own process, with its
+//! own storage and [`crate::prelude::Context`], so tests run in parallel
+//! without observing each other's state.
 //!
-//! One solution is to wrap tests with a function that acquires a global mutex:
-//!
-//! ```rust,no_run
-//! use std::sync::{Mutex, MutexGuard};
-//!
-//! use motsu::prelude::reset_storage;
-//!
-//! pub static STORAGE_MUTEX: Mutex<()> = Mutex::new(());
-//!
-//! pub fn acquire_storage() -> MutexGuard<'static, ()> {
-//!     STORAGE_MUTEX.lock().unwrap()
-//! }
-//!
-//! pub fn with_context<C: Default>(closure: impl FnOnce(&mut C)) {
-//!     let _lock = acquire_storage();
-//!     let mut contract = C::default();
-//!     closure(&mut contract);
-//!     reset_storage();
-//! }
-//!
-//! #[motsu::test]
-//! fn reads_balance() {
-//!     let balance = token.balance_of(Address::ZERO);
-//!     assert_eq!(balance, U256::ZERO);
-//! }
-//! ```
+//! Tests that call [`crate::prelude::with_context`] directly, e.g., from a
+//! regular `#[test]`, share the process with the other tests of the binary.
+//! `with_context` serializes them with [`crate::prelude::acquire_storage`].
 #![allow(clippy::missing_safety_doc)]
 use std::slice;
 
+use alloy_primitives::{Address, B256, U256};
 use tiny_keccak::{Hasher, Keccak};
 
-use crate::storage::{read_bytes32, write_bytes32, STORAGE};
+use crate::{
+    context::{vm_state, Log},
+    gas, precompiles,
+    storage::{read_bytes32, write_bytes32, STORAGE},
+};
 
 pub(crate) const WORD_BYTES: usize = 32;
 pub(crate) type Bytes32 = [u8; WORD_BYTES];
@@ -88,13 +71,23 @@ pub unsafe extern "C" fn native_keccak256(
     len: usize,
     output: *mut u8,
 ) {
-    let mut hasher = Keccak::v256();
+    vm_state().charge(gas::keccak256(len));
 
     let data = unsafe { slice::from_raw_parts(bytes, len) };
-    hasher.update(data);
+    let hash = keccak256(data);
+    unsafe { write_bytes32(output, hash) };
+}
 
-    let output = unsafe { slice::from_raw_parts_mut(output, WORD_BYTES) };
-    hasher.finalize(output);
+/// Computes the `keccak256` hash of `data` without charging gas.
+///
+/// Used internally instead of [`alloy_primitives::keccak256`], which calls the
+/// [`native_keccak256`] shim when the `native-keccak` feature is enabled.
+pub(crate) fn keccak256(data: &[u8]) -> Bytes32 {
+    let mut hasher = Keccak::v256();
+    hasher.update(data);
+    let mut output = Bytes32::default();
+    hasher.finalize(&mut output);
+    output
 }
 
 /// Reads a 32-byte value from permanent storage. Stylus's storage format is
@@ -111,6 +104,11 @@ pub unsafe extern "C" fn native_keccak256(
 #[no_mangle]
 pub unsafe extern "C" fn storage_load_bytes32(key: *const u8, out: *mut u8) {
     let key = unsafe { read_bytes32(key) };
+    let mut state = vm_state();
+    let warm = state.access_slot(key);
+    state.charge(gas::sload(warm));
+    state.hostio_counts.storage_loads += 1;
+    drop(state);
 
     let value = STORAGE
         .lock()
@@ -142,7 +140,13 @@ pub unsafe extern "C" fn storage_cache_bytes32(
     value: *const u8,
 ) {
     let (key, value) = unsafe { (read_bytes32(key), read_bytes32(value)) };
-    STORAGE.lock().unwrap().insert(key, value);
+    let mut storage = STORAGE.lock().unwrap();
+    let current = storage.get(&key).copied().unwrap_or_default();
+    let mut state = vm_state();
+    let warm = state.access_slot(key);
+    state.charge(gas::sstore(warm, &current, &value));
+    state.hostio_counts.storage_stores += 1;
+    storage.insert(key, value);
 }
 
 /// Persists any dirty values in the storage cache to the EVM state trie,
@@ -157,6 +161,10 @@ pub fn storage_flush_cache(_: bool) {
 /// Dummy msg sender set for tests.
 pub const MSG_SENDER: &[u8; 42] = b"0xDeaDbeefdEAdbeefdEadbEEFdeadbeEFdEaDbeeF";
 
+/// Dummy address of the contract under test.
+pub const CONTRACT_ADDRESS: Address =
+    alloy_primitives::address!("dCE82b5f92C98F27F116F70491a487EFFDb6a2a9");
+
 /// Externally Owned Account (EOA) code hash.
 pub const EOA_CODEHASH: &[u8; 66] =
     b"0xc5d2460186f7233c927e7db2dcc703c0e500b653ca82273b7bfad8045d85a470";
@@ -173,15 +181,65 @@ pub const EOA_CODEHASH: &[u8; 66] =
 /// [`DELEGATE_CALL`]: https://www.evm.codes/#f4
 /// [aliasing]: https://developer.arbitrum.io/arbos/l1-to-l2-messaging#address-aliasing
 ///
-/// # Panics
-///
-/// May panic if fails to parse `MSG_SENDER` as an address.
+/// The sender defaults to [`MSG_SENDER`], and can be set with
+/// [`crate::prelude::Context::set_msg_sender`].
 #[no_mangle]
 pub unsafe extern "C" fn msg_sender(sender: *mut u8) {
-    let addr = const_hex::const_decode_to_array::<20>(MSG_SENDER).unwrap();
+    let addr = vm_state().msg_sender;
     std::ptr::copy(addr.as_ptr(), sender, 20);
 }
 
+/// Get the ETH value in wei sent to the program. The semantics are equivalent
+/// to that of the EVM's [`CALLVALUE`] opcode.
+///
+/// The value can be set with [`crate::prelude::Context::set_msg_value`].
+///
+/// [`CALLVALUE`]: https://www.evm.codes/#34
+#[no_mangle]
+pub unsafe extern "C" fn msg_value(value: *mut u8) {
+    let bytes: Bytes32 = vm_state().msg_value.to_be_bytes();
+    std::ptr::copy(bytes.as_ptr(), value, WORD_BYTES);
+}
+
+/// Gets the top-level sender of the transaction. The semantics are equivalent
+/// to that of the EVM's [`ORIGIN`] opcode.
+///
+/// The origin defaults to [`MSG_SENDER`], and can be set with
+/// [`crate::prelude::Context::set_tx_origin`].
+///
+/// [`ORIGIN`]: https://www.evm.codes/#32
+#[no_mangle]
+pub unsafe extern "C" fn tx_origin(origin: *mut u8) {
+    let addr = vm_state().tx_origin;
+    std::ptr::copy(addr.as_ptr(), origin, 20);
+}
+
+/// Gets the address of the current program. The semantics are equivalent to
+/// that of the EVM's [`ADDRESS`] opcode.
+///
+/// The value can be set with [`crate::prelude::Context::set_contract_address`].
+///
+/// [`ADDRESS`]: https://www.evm.codes/#30
+#[no_mangle]
+pub unsafe extern "C" fn contract_address(address: *mut u8) {
+    let addr = vm_state().contract_address;
+    std::ptr::copy(addr.as_ptr(), address, 20);
+}
+
+/// Gets the ETH balance in wei of the account at the given address.
+/// The semantics are equivalent to that of the EVM's [`BALANCE`] opcode.
+///
+/// Accounts can be funded with [`crate::prelude::Context::fund`].
+///
+/// [`BALANCE`]: https://www.evm.codes/#31
+#[no_mangle]
+pub unsafe extern "C" fn account_balance(address: *const u8, dest: *mut u8) {
+    let address =
+        Address::from_slice(unsafe { slice::from_raw_parts(address, 20) });
+    let balance: Bytes32 = vm_state().balance(address).to_be_bytes();
+    unsafe { write_bytes32(dest, balance) };
+}
+
 /// Emits an EVM log with the given number of topics and data, the first bytes
 /// of which should be the 32-byte-aligned topic data. The semantics are
 /// equivalent to that of the EVM's [`LOG0`], [`LOG1`], [`LOG2`], [`LOG3`], and
@@ -193,9 +251,58 @@ pub unsafe extern "C" fn msg_sender(sender: *mut u8) {
 /// [`LOG2`]: https://www.evm.codes/#a2
 /// [`LOG3`]: https://www.evm.codes/#a3
 /// [`LOG4`]: https://www.evm.codes/#a4
+///
+/// Logs can be inspected with [`crate::prelude::Context::logs`].
 #[no_mangle]
-pub unsafe extern "C" fn emit_log(_: *const u8, _: usize, _: usize) {
-    // No-op: we don't check for events in our unit-tests.
+pub unsafe extern "C" fn emit_log(data: *const u8, len: usize, topics: usize) {
+    let bytes = unsafe { slice::from_raw_parts(data, len) };
+    let (topics_bytes, data) = bytes.split_at(topics * WORD_BYTES);
+    let topics =
+        topics_bytes.chunks(WORD_BYTES).map(B256::from_slice).collect();
+
+    let mut state = vm_state();
+    state.charge(gas::log(topics_bytes.len() / WORD_BYTES, data.len()));
+    state.hostio_counts.logs += 1;
+    state.logs.push(Log { topics, data: data.to_vec() });
+}
+
+/// Gets a subset of the code from the account at the given address. The
+/// semantics are identical to that of the EVM's [`EXT_CODE_COPY`] opcode, aside
+/// from one small detail: the write to the buffer `dest` will stop after the
+/// last byte is written. This is unlike the EVM, which right pads with zeros in
+/// this scenario.
+///
+/// Returns the number of bytes written.
+///
+/// Code can be deployed with [`crate::prelude::Context::set_account_code`].
+///
+/// [`EXT_CODE_COPY`]: https://www.evm.codes/#3C
+#[no_mangle]
+pub unsafe extern "C" fn account_code(
+    address: *const u8,
+    offset: usize,
+    size: usize,
+    dest: *mut u8,
+) -> usize {
+    let address =
+        Address::from_slice(unsafe { slice::from_raw_parts(address, 20) });
+    let state = vm_state();
+    let code = state.codes.get(&address).map_or(&[][..], Vec::as_slice);
+    let code = code.get(offset..).unwrap_or_default();
+    let len = code.len().min(size);
+    std::ptr::copy(code.as_ptr(), dest, len);
+    len
+}
+
+/// Gets the size of the code in bytes at the given address. The semantics are
+/// equivalent to that of the EVM's [`EXT_CODESIZE`].
+///
+/// [`EXT_CODESIZE`]: https://www.evm.codes/#3B
+#[no_mangle]
+pub unsafe extern "C" fn account_code_size(address: *const u8) -> usize {
+    let address =
+        Address::from_slice(unsafe { slice::from_raw_parts(address, 20) });
+    vm_state().codes.get(&address).map_or(0, Vec::len)
 }
 
 /// Gets the code hash of the account at the given address.
@@ -210,13 +317,75 @@ pub unsafe extern "C" fn emit_log(_: *const u8, _: usize, _: usize) {
 ///
 /// May panic if fails to parse `ACCOUNT_CODEHASH` as a keccack hash.
 #[no_mangle]
-pub unsafe extern "C" fn account_codehash(_address: *const u8, dest: *mut u8) {
-    let account_codehash =
-        const_hex::const_decode_to_array::<32>(EOA_CODEHASH).unwrap();
+pub unsafe extern "C" fn account_codehash(address: *const u8, dest: *mut u8) {
+    let address =
+        Address::from_slice(unsafe { slice::from_raw_parts(address, 20) });
+    let account_codehash = match vm_state().codes.get(&address) {
+        Some(code) => keccak256(code),
+        None => const_hex::const_decode_to_array::<32>(EOA_CODEHASH).unwrap(),
+    };
 
     std::ptr::copy(account_codehash.as_ptr(), dest, 32);
 }
 
+/// Gets a bounded estimate of the Unix timestamp at which the Sequencer
+/// sequenced the transaction. See [`Block Numbers and Time`] for more
+/// information on how this value is determined.
+///
+/// The value can be set with [`crate::prelude::Context::set_block_timestamp`].
+///
+/// [`Block Numbers and Time`]: https://developer.arbitrum.io/time
+#[no_mangle]
+pub unsafe extern "C" fn block_timestamp() -> u64 {
+    vm_state().block_timestamp
+}
+
+/// Gets a bounded estimate of the L1 block number at which the Sequencer
+/// sequenced the transaction. See [`Block Numbers and Time`] for more
+/// information on how this value is determined.
+///
+/// The value can be set with [`crate::prelude::Context::set_block_number`].
+///
+/// [`Block Numbers and Time`]: https://developer.arbitrum.io/time
+#[no_mangle]
+pub unsafe extern "C" fn block_number() -> u64 {
+    vm_state().block_number
+}
+
+/// Gets the basefee of the current block. The semantics are equivalent to that
+/// of the EVM's [`BASEFEE`] opcode.
+///
+/// The value can be set with [`crate::prelude::Context::set_block_basefee`].
+///
+/// [`BASEFEE`]: https://www.evm.codes/#48
+#[no_mangle]
+pub unsafe extern "C" fn block_basefee(basefee: *mut u8) {
+    let bytes: Bytes32 = vm_state().block_basefee.to_be_bytes();
+    unsafe { write_bytes32(basefee, bytes) };
+}
+
+/// Gets the coinbase of the current block, which on Arbitrum chains is the L1
+/// batch poster's address. This differs from Ethereum where the validator
+/// including the transaction determines the coinbase.
+///
+/// The value can be set with [`crate::prelude::Context::set_block_coinbase`].
+#[no_mangle]
+pub unsafe extern "C" fn block_coinbase(coinbase: *mut u8) {
+    let addr = vm_state().block_coinbase;
+    std::ptr::copy(addr.as_ptr(), coinbase, 20);
+}
+
+/// Gets the unique chain identifier of the Arbitrum chain. The semantics are
+/// equivalent to that of the EVM's [`CHAIN_ID`] opcode.
+///
+/// The value can be set with [`crate::prelude::Context::set_chain_id`].
+///
+/// [`CHAIN_ID`]: https://www.evm.codes/#46
+#[no_mangle]
+pub unsafe extern "C" fn chainid() -> u64 {
+    vm_state().chain_id
+}
+
 /// Returns the length of the last EVM call or deployment return result, or `0`
 /// if neither have happened during the program's execution. The semantics are
 /// equivalent to that of the EVM's [`RETURN_DATA_SIZE`] opcode.
@@ -224,10 +393,7 @@ pub unsafe extern "C" fn account_codehash(_address: *const u8, dest: *mut u8) {
 /// [`RETURN_DATA_SIZE`]: https://www.evm.codes/#3d
 #[no_mangle]
 pub unsafe extern "C" fn return_data_size() -> usize {
-    // TODO: #156
-    // No-op: we do not use this function in our unit-tests,
-    // but the binary does include it.
-    0
+    vm_state().return_data.len()
 }
 
 /// Copies the bytes of the last EVM call or deployment return result. Does not
@@ -240,14 +406,52 @@ pub unsafe extern "C" fn return_data_size() -> usize {
 /// [`RETURN_DATA_COPY`]: https://www.evm.codes/#3e
 #[no_mangle]
 pub unsafe extern "C" fn read_return_data(
-    _dest: *mut u8,
-    _offset: usize,
-    _size: usize,
+    dest: *mut u8,
+    offset: usize,
+    size: usize,
 ) -> usize {
-    // TODO: #156
-    // No-op: we do not use this function in our unit-tests,
-    // but the binary does include it.
-    0
+    let state = vm_state();
+    let data = state.return_data.get(offset..).unwrap_or_default();
+    let len = data.len().min(size);
+    std::ptr::copy(data.as_ptr(), dest, len);
+    len
+}
+
+/// Resolves a call to `contract` with `calldata` against the calls mocked by
+/// the running test and the precompiles, in that order.
+///
+/// Returns the status of the call if it was mocked or executed by a
+/// precompile, in which case the return data of the VM is set to the result.
+/// Otherwise, the return data is cleared.
+unsafe fn resolve_call(
+    contract: *const u8,
+    calldata: *const u8,
+    calldata_len: usize,
+    return_data_len: *mut usize,
+) -> Option<u8> {
+    let target =
+        Address::from_slice(unsafe { slice::from_raw_parts(contract, 20) });
+    let calldata = unsafe { slice::from_raw_parts(calldata, calldata_len) };
+
+    let mocked = vm_state()
+        .mocked_call(target, calldata)
+        .map(|mock| (u8::from(mock.reverts), mock.return_data.clone()));
+    let (status, data) = match mocked {
+        Some((status, data)) => (Some(status), data),
+        None => match precompiles::run(target, calldata) {
+            Some(Ok((output, cost))) => {
+                vm_state().charge(cost);
+                (Some(0), output)
+            }
+            Some(Err(precompiles::InvalidInput)) => (Some(1), Vec::new()),
+            None => (None, Vec::new()),
+        },
+    };
+
+    let mut state = vm_state();
+    unsafe { *return_data_len = data.len() };
+    state.return_data = data;
+    status
 }
 
 /// Calls the contract at the given address with options for passing value and
@@ -264,19 +468,38 @@ pub unsafe extern "C" fn read_return_data(
 /// `u64::MAX` gas can be used to send as much as possible.
 ///
 /// [`CALL`]: https://www.evm.codes/#f1
+///
+/// Calls are resolved with the mocks registered through
+/// [`crate::prelude::Context::mock_call`] and
+/// [`crate::prelude::Context::mock_revert`], and calls to the standard
+/// precompiles are executed natively. Other calls succeed with empty return
+/// data. In all cases, `value` is moved from the contract
+/// to the target account, and the call fails if the contract can't afford it.
 #[no_mangle]
 pub unsafe extern "C" fn call_contract(
-    _contract: *const u8,
-    _calldata: *const u8,
-    _calldata_len: usize,
-    _value: *const u8,
+    contract: *const u8,
+    calldata: *const u8,
+    calldata_len: usize,
+    value: *const u8,
     _gas: u64,
-    _return_data_len: *mut usize,
+    return_data_len: *mut usize,
 ) -> u8 {
-    // TODO: #156
-    // No-op: we do not use this function in our unit-tests,
-    // but the binary does include it.
-    0
+    let status = unsafe {
+        resolve_call(contract, calldata, calldata_len, return_data_len)
+    };
+
+    let value = U256::from_be_bytes(unsafe { read_bytes32(value) });
+    let mut state = vm_state();
+    state.charge(gas::call(!value.is_zero()));
+    state.hostio_counts.calls += 1;
+    if status == Some(1) || value.is_zero() {
+        return status.unwrap_or(0);
+    }
+
+    let to =
+        Address::from_slice(unsafe { slice::from_raw_parts(contract, 20) });
+    let from = state.contract_address;
+    u8::from(!state.transfer(from, to, value))
 }
 
 /// Static calls the contract at the given address, with the option to limit the
@@ -293,18 +516,186 @@ pub unsafe extern "C" fn call_contract(
 /// be used to send as much as possible.
 ///
 /// [`STATIC_CALL`]: https://www.evm.codes/#FA
+///
+/// Calls are resolved with the mocks registered through
+/// [`crate::prelude::Context::mock_call`] and
+/// [`crate::prelude::Context::mock_revert`], and calls to the standard
+/// precompiles are executed natively. Other calls succeed with empty return
+/// data.
 #[no_mangle]
 pub unsafe extern "C" fn static_call_contract(
-    _contract: *const u8,
-    _calldata: *const u8,
-    _calldata_len: usize,
+    contract: *const u8,
+    calldata: *const u8,
+    calldata_len: usize,
     _gas: u64,
-    _return_data_len: *mut usize,
+    return_data_len: *mut usize,
 ) -> u8 {
-    // TODO: #156
-    // No-op: we do not use this function in our unit-tests,
-    // but the binary does include it.
-    0
+    let mut state = vm_state();
+    state.charge(gas::call(false));
+    state.hostio_counts.calls += 1;
+    drop(state);
+    unsafe { resolve_call(contract, calldata, calldata_len, return_data_len) }
+        .unwrap_or(0)
+}
+
+/// Deploys `code` at `contract`, moving `endowment` wei from the deployer to
+/// the new contract.
+///
+/// Returns `false` if the deployment fails, either because the deployer can't
+/// afford `endowment` or because there is already code at `contract`.
+fn deploy(contract: Address, code: &[u8], endowment: U256) -> bool {
+    let mut state = vm_state();
+    if state.codes.contains_key(&contract) {
+        return false;
+    }
+    let deployer = state.contract_address;
+    if !state.transfer(deployer, contract, endowment) {
+        return false;
+    }
+    state.codes.insert(contract, code.to_vec());
+    true
+}
+
+/// Deploys a new contract using the init code provided, which the EVM executes
+/// to construct the code of the newly deployed contract. The init code must be
+/// written in EVM bytecode, but the code it deploys can be that of a Stylus
+/// program. The code returned will be treated as WASM if it begins with the
+/// EOF-inspired header `0xEFF000`. Otherwise the code will be interpreted as
+/// that of a traditional EVM-style contract. See [`Deploying Stylus Programs`]
+/// for more information on writing init code.
+///
+/// On success, this hostio returns the address of the newly created account
+/// whose address is a function of the sender and nonce. On failure the address
+/// will be `0`, `return_data_len` will store the length of the revert data, the
+/// bytes of which can be read via the `read_return_data` hostio. The semantics
+/// are equivalent to that of the EVM's [`CREATE`] opcode, which notably
+/// includes the exact address returned.
+///
+/// [`Deploying Stylus Programs`]: https://docs.arbitrum.io/stylus/quickstart
+/// [`CREATE`]: https://www.evm.codes/#f0
+///
+/// The init code is not executed: it is stored as the code of the new
+/// account, and calls to it are resolved with the mocks registered through
+/// [`crate::prelude::Context::mock_call`].
+#[no_mangle]
+pub unsafe extern "C" fn create1(
+    code: *const u8,
+    code_len: usize,
+    endowment: *const u8,
+    contract: *mut u8,
+    revert_data_len: *mut usize,
+) {
+    let code = unsafe { slice::from_raw_parts(code, code_len) };
+    let endowment = U256::from_be_bytes(unsafe { read_bytes32(endowment) });
+
+    let address = {
+        let mut state = vm_state();
+        state.charge(gas::create(code_len, false));
+        state.return_data.clear();
+        let deployer = state.contract_address;
+        // Contract nonces start at `1`, as per EIP-161.
+        let nonce = state.nonces.entry(deployer).or_insert(1);
+        let address = create1_address(deployer, *nonce);
+        *nonce += 1;
+        address
+    };
+
+    let address =
+        if deploy(address, code, endowment) { address } else { Address::ZERO };
+    unsafe { *revert_data_len = 0 };
+    std::ptr::copy(address.as_ptr(), contract, 20);
+}
+
+/// Deploys a new contract using the init code provided, which the EVM executes
+/// to construct the code of the newly deployed contract. The init code must be
+/// written in EVM bytecode, but the code it deploys can be that of a Stylus
+/// program. The code returned will be treated as WASM if it begins with the
+/// EOF-inspired header `0xEFF000`. Otherwise the code will be interpreted as
+/// that of a traditional EVM-style contract. See [`Deploying Stylus Programs`]
+/// for more information on writing init code.
+///
+/// On success, this hostio returns the address of the newly created account
+/// whose address is a function of the sender, salt, and init code. On failure
+/// the address will be `0`, `return_data_len` will store the length of the
+/// revert data, the bytes of which can be read via the `read_return_data`
+/// hostio. The semantics are equivalent to that of the EVM's [`CREATE2`]
+/// opcode, which notably includes the exact address returned.
+///
+/// [`Deploying Stylus Programs`]: https://docs.arbitrum.io/stylus/quickstart
+/// [`CREATE2`]: https://www.evm.codes/#f5
+///
+/// The init code is not executed: it is stored as the code of the new
+/// account, and calls to it are resolved with the mocks registered through
+/// [`crate::prelude::Context::mock_call`].
+#[no_mangle]
+pub unsafe extern "C" fn create2(
+    code: *const u8,
+    code_len: usize,
+    endowment: *const u8,
+    salt: *const u8,
+    contract: *mut u8,
+    revert_data_len: *mut usize,
+) {
+    let code = unsafe { slice::from_raw_parts(code, code_len) };
+    let endowment = U256::from_be_bytes(unsafe { read_bytes32(endowment) });
+    let salt = unsafe { read_bytes32(salt) };
+
+    let address = {
+        let mut state = vm_state();
+        state.charge(gas::create(code_len, true));
+        state.return_data.clear();
+        create2_address(state.contract_address, salt, keccak256(code))
+    };
+
+    let address =
+        if deploy(address, code, endowment) { address } else { Address::ZERO };
+    unsafe { *revert_data_len = 0 };
+    std::ptr::copy(address.as_ptr(), contract, 20);
+}
+
+/// Computes the address of the contract deployed by `deployer` with `CREATE`
+/// when its nonce is `nonce`, i.e., `keccak256(rlp([deployer, nonce]))`.
+// Lengths are bounded by the size of an address and a `u64`.
+#[allow(clippy::cast_possible_truncation)]
+fn create1_address(deployer: Address, nonce: u64) -> Address {
+    // RLP encoding of `nonce` as an integer.
+    let nonce_bytes = nonce.to_be_bytes();
+    let significant = &nonce_bytes[nonce.leading_zeros() as usize / 8..];
+    let mut encoded_nonce = Vec::with_capacity(9);
+    match nonce {
+        0 => encoded_nonce.push(0x80),
+        1..=0x7f => encoded_nonce.extend_from_slice(significant),
+        _ => {
+            encoded_nonce.push(0x80 + significant.len() as u8);
+            encoded_nonce.extend_from_slice(significant);
+        }
+    }
+
+    // The list payload is always shorter than 56 bytes.
+    let payload_len = 21 + encoded_nonce.len();
+    let mut rlp = Vec::with_capacity(1 + payload_len);
+    rlp.push(0xc0 + payload_len as u8);
+    rlp.push(0x80 + 20);
+    rlp.extend_from_slice(deployer.as_slice());
+    rlp.extend_from_slice(&encoded_nonce);
+
+    Address::from_slice(&keccak256(&rlp)[12..])
+}
+
+/// Computes the address of the contract deployed by `deployer` with `CREATE2`,
+/// i.e., `keccak256(0xff ++ deployer ++ salt ++ keccak256(init_code))`.
+fn create2_address(
+    deployer: Address,
+    salt: Bytes32,
+    init_code_hash: Bytes32,
+) -> Address {
+    let mut preimage = Vec::with_capacity(85);
+    preimage.push(0xff);
+    preimage.extend_from_slice(deployer.as_slice());
+    preimage.extend_from_slice(&salt);
+    preimage.extend_from_slice(&init_code_hash);
+
+    Address::from_slice(&keccak256(&preimage)[12..])
 }
 
 /// Delegate calls the contract at the given address, with the option to limit
@@ -321,16 +712,24 @@ pub unsafe extern "C" fn static_call_contract(
 /// be used to send as much as possible.
 ///
 /// [`DELEGATE_CALL`]: https://www.evm.codes/#F4
+///
+/// Calls are resolved with the mocks registered through
+/// [`crate::prelude::Context::mock_call`] and
+/// [`crate::prelude::Context::mock_revert`], and calls to the standard
+/// precompiles are executed natively. Other calls succeed with empty return
+/// data.
 #[no_mangle]
 pub unsafe extern "C" fn delegate_call_contract(
-    _contract: *const u8,
-    _calldata: *const u8,
-    _calldata_len: usize,
+    contract: *const u8,
+    calldata: *const u8,
+    calldata_len: usize,
     _gas: u64,
-    _return_data_len: *mut usize,
+    return_data_len: *mut usize,
 ) -> u8 {
-    // TODO: #156
-    // No-op: we do not use this function in our unit-tests,
-    // but the binary does include it.
-    0
+    let mut state = vm_state();
+    state.charge(gas::call(false));
+    state.hostio_counts.calls += 1;
+    drop(state);
+    unsafe { resolve_call(contract, calldata, calldata_len, return_data_len) }
+        .unwrap_or(0)
 }
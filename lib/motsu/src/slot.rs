@@ -0,0 +1,117 @@
+//! Storage slots of values laid out like Solidity does.
+//!
+//! Contracts declared with `sol_storage!` are laid out exactly like their
+//! Solidity counterparts, so the functions in this module can be used to
+//! check raw storage against a Solidity reference layout, e.g., to test that
+//! an upgrade doesn't move existing state:
+//!
+//! ```rust,ignore
+//! use motsu::{prelude::VM, slot};
+//!
+//! #[motsu::test]
+//! fn keeps_balances_layout(contract: Erc20) {
+//!     contract._mint(alice, one).unwrap();
+//!     // `mapping(address => uint256) _balances` is at slot `0`.
+//!     VM::context().assert_slot(slot::mapping(U256::ZERO, alice), one);
+//! }
+//! ```
+//!
+//! See the [Solidity docs] for a description of the layout.
+//!
+//! [Solidity docs]: https://docs.soliditylang.org/en/latest/internals/layout_in_storage.html
+use alloy_primitives::{Address, FixedBytes, U256};
+
+use crate::shims::keccak256;
+
+/// A type that can be used as the key of a Solidity mapping.
+pub trait MappingKey {
+    /// Returns the bytes hashed along with the slot of the mapping: value
+    /// types are left-padded to 32 bytes, while strings and byte arrays are
+    /// used as is.
+    fn encode_key(self) -> Vec<u8>;
+}
+
+impl MappingKey for Address {
+    fn encode_key(self) -> Vec<u8> {
+        self.into_word().to_vec()
+    }
+}
+
+impl MappingKey for U256 {
+    fn encode_key(self) -> Vec<u8> {
+        self.to_be_bytes_vec()
+    }
+}
+
+impl<const N: usize> MappingKey for FixedBytes<N> {
+    /// Fixed-size byte arrays are right-padded, like `bytesN` values.
+    fn encode_key(self) -> Vec<u8> {
+        let mut key = self.to_vec();
+        key.resize(32, 0);
+        key
+    }
+}
+
+impl MappingKey for bool {
+    fn encode_key(self) -> Vec<u8> {
+        U256::from(u8::from(self)).encode_key()
+    }
+}
+
+macro_rules! impl_mapping_key_for_uint {
+    ($($ty:ty),*) => {$(
+        impl MappingKey for $ty {
+            fn encode_key(self) -> Vec<u8> {
+                U256::from(self).encode_key()
+            }
+        }
+    )*};
+}
+
+impl_mapping_key_for_uint!(u8, u16, u32, u64, u128);
+
+impl MappingKey for &str {
+    fn encode_key(self) -> Vec<u8> {
+        self.as_bytes().to_vec()
+    }
+}
+
+impl MappingKey for &[u8] {
+    fn encode_key(self) -> Vec<u8> {
+        self.to_vec()
+    }
+}
+
+/// Returns the slot of the value of a mapping at `slot` for `key`, that is
+/// `keccak256(key . slot)`.
+///
+/// The slot of a nested mapping value is computed by chaining calls, e.g.,
+/// `mapping(mapping(slot, owner), spender)`.
+///
+/// # Arguments
+///
+/// * `slot` - Slot of the mapping.
+/// * `key` - Key of the value.
+#[must_use]
+pub fn mapping(slot: U256, key: impl MappingKey) -> U256 {
+    let mut preimage = key.encode_key();
+    preimage.extend(slot.to_be_bytes::<32>());
+    U256::from_be_bytes(keccak256(&preimage))
+}
+
+/// Returns the slot of the element at `index` of a dynamic array at `slot`,
+/// that is `keccak256(slot) + index`.
+///
+/// The length of the array is stored at `slot` itself. Note that this
+/// assumes elements take a whole slot each, which isn't the case for packed
+/// elements smaller than 32 bytes, nor for structs.
+///
+/// # Arguments
+///
+/// * `slot` - Slot of the array.
+/// * `index` - Index of the element.
+#[must_use]
+pub fn array(slot: U256, index: U256) -> U256 {
+    let start = U256::from_be_bytes(keccak256(&slot.to_be_bytes::<32>()));
+    start.wrapping_add(index)
+}
@@ -0,0 +1,34 @@
+//! Tests for named accounts.
+use alloy_primitives::{address, keccak256, uint, Address};
+use motsu::prelude::{Account, VM};
+use stylus_sdk::types::AddressVM;
+
+#[motsu::test]
+fn derives_stable_addresses() {
+    let alice = Account::named("alice");
+    assert_eq!(
+        alice.address(),
+        address!("5daD7600C5D89fE3824fFa99ec1c3eB8BF3b0501")
+    );
+    assert_eq!(alice, Account::named("alice"));
+    assert_ne!(alice.address(), Account::named("bob").address());
+    assert_eq!(Address::from(alice), alice.address());
+    assert_eq!(alice.to_string(), format!("alice ({})", alice.address()));
+}
+
+#[motsu::test]
+fn builds_accounts() {
+    let alice = Account::builder("alice").balance(uint!(10_U256)).build();
+    assert_eq!(alice.address().balance(), uint!(10_U256));
+    assert!(!alice.address().has_code());
+
+    let code = vec![0xfe, 0xed];
+    let token = Account::builder("token").code(code.clone()).build();
+    assert!(token.address().has_code());
+    assert_eq!(token.address().code(), code);
+    assert_eq!(token.address().code_size(), 2);
+    assert_eq!(token.address().code_hash(), keccak256(&code));
+
+    VM::context().set_account_code(token.address(), Vec::new());
+    assert!(VM::context().account_code(token.address()).is_empty());
+}
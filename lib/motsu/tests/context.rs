@@ -0,0 +1,178 @@
+//! Tests for the execution context exposed by the VM.
+use std::time::Duration;
+
+use alloy_primitives::{address, uint, Address};
+use motsu::prelude::{CONTRACT_ADDRESS, MSG_SENDER, VM};
+use stylus_sdk::{
+    block, call::transfer_eth, contract, msg, tx, types::AddressVM,
+};
+
+const ALICE: Address = address!("A11CEacF9aa32246d767FCCD72e02d6bCbcC375d");
+
+fn msg_sender() -> Address {
+    const_hex::const_decode_to_array::<20>(MSG_SENDER).unwrap().into()
+}
+
+#[motsu::test]
+fn reads_default_context() {
+    assert_eq!(block::timestamp(), 0);
+    assert_eq!(block::number(), 0);
+    assert_eq!(block::chainid(), 0);
+    assert_eq!(block::coinbase(), Address::ZERO);
+    assert_eq!(msg::value(), uint!(0_U256));
+    assert_eq!(msg::sender(), msg_sender());
+    assert_eq!(tx::origin(), msg_sender());
+}
+
+#[motsu::test]
+fn sets_block_timestamp() {
+    VM::context().set_block_timestamp(1_700_000_000);
+    assert_eq!(block::timestamp(), 1_700_000_000);
+}
+
+#[motsu::test]
+fn sets_block_number() {
+    VM::context().set_block_number(42);
+    assert_eq!(block::number(), 42);
+    assert_eq!(VM::context().block_number(), 42);
+}
+
+#[motsu::test]
+fn advances_time_and_blocks() {
+    VM::context().set_block_timestamp(1_000);
+    VM::context().set_block_number(10);
+
+    VM::context().advance_time(Duration::from_hours(1));
+    VM::context().advance_time(Duration::from_millis(1_500));
+    VM::context().advance_blocks(5);
+
+    assert_eq!(block::timestamp(), 4_601);
+    assert_eq!(block::number(), 15);
+}
+
+#[motsu::test]
+#[should_panic = "block timestamp should not overflow"]
+fn advancing_time_checks_overflow() {
+    VM::context().set_block_timestamp(u64::MAX);
+    VM::context().advance_time(Duration::from_secs(1));
+}
+
+#[motsu::test]
+fn sets_chain_context() {
+    VM::context().set_chain_id(42_161);
+    VM::context().set_block_basefee(uint!(100_000_000_U256));
+    VM::context().set_block_coinbase(ALICE);
+
+    assert_eq!(block::chainid(), 42_161);
+    assert_eq!(block::basefee(), uint!(100_000_000_U256));
+    assert_eq!(block::coinbase(), ALICE);
+    assert_eq!(VM::context().chain_id(), 42_161);
+}
+
+#[motsu::test]
+fn sets_msg_value() {
+    let value = uint!(1_000_000_000_000_000_000_U256);
+    VM::context().fund(msg_sender(), value);
+    VM::context().set_msg_value(value);
+    assert_eq!(msg::value(), value);
+    assert_eq!(VM::context().msg_value(), value);
+
+    assert_eq!(VM::context().balance_of(msg_sender()), uint!(0_U256));
+    assert_eq!(VM::context().balance_of(CONTRACT_ADDRESS), value);
+}
+
+#[motsu::test]
+fn charges_msg_value_once() {
+    VM::context().fund(msg_sender(), uint!(10_U256));
+    VM::context().fund(CONTRACT_ADDRESS, uint!(1_U256));
+
+    VM::context().set_msg_value(uint!(4_U256));
+    VM::context().set_msg_value(uint!(4_U256));
+    assert_eq!(VM::context().balance_of(msg_sender()), uint!(6_U256));
+    assert_eq!(VM::context().balance_of(CONTRACT_ADDRESS), uint!(5_U256));
+
+    // Calls made with the value don't charge the sender again.
+    assert_eq!(msg::value(), uint!(4_U256));
+    transfer_eth(ALICE, uint!(3_U256)).unwrap();
+    assert_eq!(VM::context().balance_of(msg_sender()), uint!(6_U256));
+    assert_eq!(VM::context().balance_of(CONTRACT_ADDRESS), uint!(2_U256));
+    assert_eq!(VM::context().balance_of(ALICE), uint!(3_U256));
+}
+
+#[motsu::test]
+fn replaces_msg_value() {
+    VM::context().fund(msg_sender(), uint!(10_U256));
+    VM::context().set_msg_value(uint!(4_U256));
+    VM::context().set_msg_value(uint!(7_U256));
+    assert_eq!(VM::context().balance_of(msg_sender()), uint!(3_U256));
+    assert_eq!(VM::context().balance_of(CONTRACT_ADDRESS), uint!(7_U256));
+
+    VM::context().set_msg_value(uint!(0_U256));
+    assert_eq!(VM::context().balance_of(msg_sender()), uint!(10_U256));
+    assert_eq!(VM::context().balance_of(CONTRACT_ADDRESS), uint!(0_U256));
+}
+
+#[motsu::test]
+#[should_panic = "contract already spent the previous msg value"]
+fn msg_value_cannot_be_replaced_once_spent() {
+    VM::context().fund(msg_sender(), uint!(10_U256));
+    VM::context().set_msg_value(uint!(4_U256));
+    transfer_eth(ALICE, uint!(4_U256)).unwrap();
+    VM::context().set_msg_value(uint!(1_U256));
+}
+
+#[motsu::test]
+fn sets_msg_sender() {
+    VM::context().set_msg_sender(ALICE);
+    assert_eq!(msg::sender(), ALICE);
+    assert_eq!(VM::context().msg_sender(), ALICE);
+
+    // Value is sent by the configured sender.
+    VM::context().fund(ALICE, uint!(10_U256));
+    VM::context().set_msg_value(uint!(4_U256));
+    assert_eq!(VM::context().balance_of(ALICE), uint!(6_U256));
+}
+
+#[motsu::test]
+fn sets_tx_origin() {
+    VM::context().set_tx_origin(ALICE);
+    assert_eq!(tx::origin(), ALICE);
+    assert_eq!(VM::context().tx_origin(), ALICE);
+    assert_ne!(msg::sender(), tx::origin());
+}
+
+#[motsu::test]
+#[should_panic = "msg sender has insufficient balance"]
+fn msg_value_requires_funds() {
+    VM::context().set_msg_value(uint!(1_U256));
+}
+
+#[motsu::test]
+fn funds_accounts() {
+    let amount = uint!(10_U256);
+    VM::context().fund(ALICE, amount);
+    VM::context().fund(ALICE, amount);
+    assert_eq!(VM::context().balance_of(ALICE), uint!(20_U256));
+    assert_eq!(ALICE.balance(), uint!(20_U256));
+    assert_eq!(contract::address(), CONTRACT_ADDRESS);
+    assert_eq!(contract::balance(), uint!(0_U256));
+}
+
+#[motsu::test]
+fn transfers_value_from_contract() {
+    VM::context().fund(CONTRACT_ADDRESS, uint!(10_U256));
+
+    transfer_eth(ALICE, uint!(4_U256)).unwrap();
+    assert_eq!(ALICE.balance(), uint!(4_U256));
+    assert_eq!(CONTRACT_ADDRESS.balance(), uint!(6_U256));
+
+    assert!(transfer_eth(ALICE, uint!(7_U256)).is_err());
+    assert_eq!(ALICE.balance(), uint!(4_U256));
+    assert_eq!(CONTRACT_ADDRESS.balance(), uint!(6_U256));
+}
+
+#[motsu::test]
+#[should_panic = "block timestamp is not set"]
+fn propagates_panics() {
+    assert_ne!(block::timestamp(), 0, "block timestamp is not set");
+}
@@ -0,0 +1,64 @@
+//! Tests for deploying contracts from the contract under test.
+use alloy_primitives::{address, b256, uint, Address, B256};
+use motsu::prelude::VM;
+use stylus_sdk::{deploy::RawDeploy, types::AddressVM};
+
+const DEPLOYER: Address = address!("6ac7ea33f8831ea9dcc53393aaa88b25a785dbf0");
+
+#[motsu::test]
+fn deploys_with_create1() {
+    VM::context().set_contract_address(DEPLOYER);
+    let code = [0xfe];
+
+    // Addresses match the ones computed by the EVM for nonces `1` and `2`.
+    let first = unsafe { RawDeploy::new().deploy(&code, uint!(0_U256)) }
+        .expect("should deploy");
+    assert_eq!(first, address!("343c43a37d37dff08ae8c4a11544c718abb4fcf8"));
+    let second = unsafe { RawDeploy::new().deploy(&code, uint!(0_U256)) };
+    assert_eq!(
+        second,
+        Ok(address!("f778b86fa74e846c4f0a1fbd1335fe81c00a0c91"))
+    );
+
+    assert!(first.has_code());
+    assert_eq!(VM::context().account_code(first), code);
+}
+
+#[motsu::test]
+fn deploys_with_create2() {
+    VM::context().set_contract_address(Address::ZERO);
+    let code = [0x00];
+
+    // Example 0 of EIP-1014.
+    let deployed = unsafe {
+        RawDeploy::new().salt(B256::ZERO).deploy(&code, uint!(0_U256))
+    };
+    assert_eq!(
+        deployed,
+        Ok(address!("4D1A2e2bB4F88F0250f26Ffff098B0b30B26BF38"))
+    );
+
+    // Deploying twice to the same address fails.
+    let redeployed = unsafe {
+        RawDeploy::new().salt(B256::ZERO).deploy(&code, uint!(0_U256))
+    };
+    assert_eq!(redeployed, Err(vec![]));
+}
+
+#[motsu::test]
+fn transfers_endowment() {
+    let salt = b256!(
+        "00000000000000000000000000000000000000000000000000000000000000aa"
+    );
+    let contract = VM::context().contract_address();
+    VM::context().fund(contract, uint!(10_U256));
+
+    let deployed =
+        unsafe { RawDeploy::new().salt(salt).deploy(&[0xfe], uint!(4_U256)) }
+            .unwrap();
+    assert_eq!(deployed.balance(), uint!(4_U256));
+    assert_eq!(VM::context().balance_of(contract), uint!(6_U256));
+
+    let failed = unsafe { RawDeploy::new().deploy(&[0xfe], uint!(7_U256)) };
+    assert!(failed.is_err());
+}
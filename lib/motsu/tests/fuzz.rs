@@ -0,0 +1,47 @@
+//! Tests for the fuzzing strategies.
+use std::collections::HashSet;
+
+use alloy_primitives::{Address, B256, U256};
+use motsu::fuzz;
+use proptest::{
+    strategy::{Strategy, ValueTree},
+    test_runner::TestRunner,
+};
+
+fn sample<S: Strategy>(strategy: &S, count: usize) -> Vec<S::Value> {
+    let mut runner = TestRunner::deterministic();
+    (0..count)
+        .map(|_| strategy.new_tree(&mut runner).unwrap().current())
+        .collect()
+}
+
+#[test]
+fn u256_hits_boundaries() {
+    let values = sample(&fuzz::u256(), 1_000);
+    assert!(values.contains(&U256::ZERO));
+    assert!(values.contains(&U256::MAX));
+    assert!(values.iter().any(|v| *v > U256::from(u128::MAX)));
+}
+
+#[test]
+fn address_hits_special_addresses() {
+    let values = sample(&fuzz::address(), 1_000);
+    assert!(values.contains(&Address::ZERO));
+    assert!(values.contains(&Address::with_last_byte(1)));
+    assert!(values.iter().collect::<HashSet<_>>().len() > 100);
+}
+
+#[test]
+fn b256_hits_boundaries() {
+    let values = sample(&fuzz::b256(), 1_000);
+    assert!(values.contains(&B256::ZERO));
+    assert!(values.contains(&B256::repeat_byte(0xff)));
+}
+
+#[test]
+fn bytes_have_varied_lengths() {
+    let values = sample(&fuzz::bytes(), 1_000);
+    assert!(values.iter().any(|b| b.is_empty()));
+    assert!(values.iter().any(|b| b.len() > 64 && b.len() % 32 == 0));
+    assert!(values.iter().all(|b| b.len() <= 1024));
+}
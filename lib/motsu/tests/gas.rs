@@ -0,0 +1,166 @@
+//! Tests for the gas estimation exposed by the VM.
+use alloy_primitives::{address, uint, U256};
+use motsu::prelude::{GasMeasurement, HostioCounts, OutOfGas, VM};
+use serde_json::json;
+use stylus_sdk::{
+    crypto::keccak,
+    deploy::RawDeploy,
+    storage::{StorageType, StorageU256},
+};
+
+fn slot(index: U256) -> StorageU256 {
+    unsafe { StorageU256::new(index, 0) }
+}
+
+#[motsu::test]
+fn charges_cold_and_warm_reads() {
+    let (_, cold) = VM::context().measure_gas(|| slot(uint!(0_U256)).get());
+    assert_eq!(cold, 2_100);
+
+    // A new handle, so that the value is not cached by the SDK.
+    let (_, warm) = VM::context().measure_gas(|| slot(uint!(0_U256)).get());
+    assert_eq!(warm, 100);
+
+    assert_eq!(VM::context().gas_used(), 2_200);
+}
+
+#[motsu::test]
+fn charges_writes() {
+    let mut value = slot(uint!(1_U256));
+
+    let ((), set) = VM::context().measure_gas(|| value.set(uint!(1_U256)));
+    assert_eq!(set, 2_100 + 20_000);
+
+    let ((), reset) = VM::context().measure_gas(|| value.set(uint!(2_U256)));
+    assert_eq!(reset, 2_900);
+
+    let ((), noop) = VM::context().measure_gas(|| value.set(uint!(2_U256)));
+    assert_eq!(noop, 100);
+}
+
+#[motsu::test]
+fn charges_hashing() {
+    let (_, gas) = VM::context().measure_gas(|| keccak([0u8; 33]));
+    assert_eq!(gas, 30 + 2 * 6);
+}
+
+#[motsu::test]
+fn runs_within_gas_limit() {
+    let result =
+        VM::context().with_gas_limit(2_100, || slot(uint!(0_U256)).get());
+    assert_eq!(result, Ok(uint!(0_U256)));
+}
+
+#[motsu::test]
+fn reverts_when_out_of_gas() {
+    let result = VM::context().with_gas_limit(10_000, || {
+        slot(uint!(1_U256)).set(uint!(1_U256));
+        slot(uint!(2_U256)).set(uint!(1_U256));
+    });
+    assert_eq!(result, Err(OutOfGas { gas_limit: 10_000 }));
+
+    // Changes made before running out of gas are reverted.
+    assert_eq!(slot(uint!(1_U256)).get(), uint!(0_U256));
+}
+
+#[motsu::test]
+fn reverts_value_transfers_and_deployments_when_out_of_gas() {
+    let contract = stylus_sdk::contract::address();
+    let recipient = address!("a935CEC3c5Ef99D7F1016674DEFd455Ef06776C5");
+    VM::context().set_balance(contract, uint!(10_U256));
+
+    let mut reverted = None;
+    let result = VM::context().with_gas_limit(1_000, || {
+        stylus_sdk::call::transfer_eth(recipient, uint!(1_U256))
+            .expect("should transfer");
+        let deployed = unsafe { RawDeploy::new().deploy(&[0x00], U256::ZERO) };
+        reverted = Some(deployed.expect("should deploy"));
+    });
+    assert_eq!(result, Err(OutOfGas { gas_limit: 1_000 }));
+
+    assert_eq!(VM::context().balance_of(contract), uint!(10_U256));
+    assert_eq!(VM::context().balance_of(recipient), U256::ZERO);
+    // The deployment nonce is reverted too, so the address is reused.
+    let deployed = unsafe { RawDeploy::new().deploy(&[0x00], U256::ZERO) };
+    assert_eq!(deployed.ok(), reverted);
+}
+
+#[motsu::test]
+fn limits_nested_calls_to_outer_gas() {
+    let outer = VM::context().with_gas_limit(30_000, || {
+        slot(uint!(1_U256)).set(uint!(1_U256));
+        VM::context().with_gas_limit(100_000, || {
+            slot(uint!(2_U256)).set(uint!(1_U256));
+        })
+    });
+    assert_eq!(outer, Err(OutOfGas { gas_limit: 30_000 }));
+
+    let outer = VM::context().with_gas_limit(30_000, || {
+        VM::context().with_gas_limit(10_000, || {
+            slot(uint!(2_U256)).set(uint!(1_U256));
+        })
+    });
+    assert_eq!(outer, Ok(Err(OutOfGas { gas_limit: 10_000 })));
+}
+
+#[motsu::test]
+fn meters_calls() {
+    let value = VM::context().meter("read", || slot(uint!(0_U256)).get());
+    assert_eq!(value, uint!(0_U256));
+    VM::context().meter("write", || slot(uint!(0_U256)).set(uint!(1_U256)));
+
+    assert_eq!(
+        VM::context().gas_report(),
+        vec![
+            GasMeasurement { name: "read".to_owned(), gas: 2_100 },
+            GasMeasurement { name: "write".to_owned(), gas: 20_000 },
+        ]
+    );
+}
+
+#[motsu::test]
+fn writes_gas_report() {
+    let path = std::env::temp_dir()
+        .join(format!("motsu-gas-report-{}.jsonl", std::process::id()));
+    std::env::set_var("MOTSU_GAS_REPORT", &path);
+
+    VM::context().meter("read", || slot(uint!(0_U256)).get());
+    VM::context().meter("read \"again\"", || slot(uint!(0_U256)).get());
+
+    let report = std::fs::read_to_string(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+    let lines: Vec<serde_json::Value> = report
+        .lines()
+        .map(|line| serde_json::from_str(line).unwrap())
+        .collect();
+    assert_eq!(
+        lines,
+        [
+            json!({ "function": "read", "gas": 2100 }),
+            json!({ "function": "read \"again\"", "gas": 100 }),
+        ]
+    );
+}
+
+#[motsu::test]
+fn counts_hostios() {
+    let ((), counts) = VM::context().measure_hostios(|| {
+        let mut value = slot(uint!(0_U256));
+        value.set(value.get() + uint!(1_U256));
+        let _ = slot(uint!(1_U256)).get();
+    });
+    assert_eq!(
+        counts,
+        HostioCounts { storage_loads: 2, storage_stores: 1, calls: 0, logs: 0 }
+    );
+    assert_eq!(
+        serde_json::from_str::<serde_json::Value>(&counts.to_string()).unwrap(),
+        json!({
+            "storage_loads": 2,
+            "storage_stores": 1,
+            "calls": 0,
+            "logs": 0,
+        })
+    );
+    assert_eq!(VM::context().hostio_counts(), counts);
+}
@@ -0,0 +1,47 @@
+//! Tests for the logs recorded by the VM.
+use alloy_primitives::{address, uint, Address, U256};
+use alloy_sol_types::sol;
+use motsu::prelude::VM;
+use stylus_sdk::evm;
+
+const ALICE: Address = address!("A11CEacF9aa32246d767FCCD72e02d6bCbcC375d");
+
+sol! {
+    #[allow(missing_docs)]
+    event Deposited(address indexed account, uint256 amount);
+    #[allow(missing_docs)]
+    event Paused();
+}
+
+#[motsu::test]
+fn records_logs_in_order() {
+    evm::log(Deposited { account: ALICE, amount: uint!(1_U256) });
+    evm::log(Paused {});
+    evm::log(Deposited { account: ALICE, amount: uint!(2_U256) });
+
+    let logs = VM::context().logs();
+    assert_eq!(logs.len(), 3);
+    assert_eq!(logs[0].topics.len(), 2);
+    assert_eq!(logs[0].data, uint!(1_U256).to_be_bytes_vec());
+    assert!(logs[1].decode::<Paused>().is_some());
+
+    let deposits = VM::context().emitted_events::<Deposited>();
+    let amounts: Vec<U256> = deposits.iter().map(|d| d.amount).collect();
+    assert_eq!(amounts, vec![uint!(1_U256), uint!(2_U256)]);
+}
+
+#[motsu::test]
+fn captures_logs_of_a_call() {
+    evm::log(Paused {});
+
+    let ((), logs) = VM::context().capture_logs(|| {
+        evm::log(Deposited { account: ALICE, amount: uint!(1_U256) });
+    });
+    assert_eq!(logs.len(), 1);
+    assert!(logs[0].decode::<Paused>().is_none());
+    assert_eq!(logs[0].decode::<Deposited>().unwrap().account, ALICE);
+
+    assert_eq!(VM::context().logs().len(), 2);
+    VM::context().clear_logs();
+    assert!(VM::context().emitted_events::<Paused>().is_empty());
+}
@@ -0,0 +1,84 @@
+//! Tests for mocking calls to other contracts.
+use alloy_primitives::{address, uint, Address, U256};
+use alloy_sol_types::{sol, SolCall, SolError};
+use motsu::prelude::VM;
+use stylus_sdk::call::RawCall;
+
+const TOKEN: Address = address!("7e57C0dE000000000000000000000000000000AA");
+const ALICE: Address = address!("A11CEacF9aa32246d767FCCD72e02d6bCbcC375d");
+const BOB: Address = address!("B0B0cB49ec2e96DF5F5fFB081acaE66A2cBBc2e2");
+
+sol! {
+    #[allow(missing_docs)]
+    function balanceOf(address account) external view returns (uint256 balance);
+    #[allow(missing_docs)]
+    error Paused();
+}
+
+fn balance_of(account: Address) -> Vec<u8> {
+    balanceOfCall { account }.encode()
+}
+
+fn decode_balance(data: &[u8]) -> U256 {
+    balanceOfCall::decode_returns(data, true).unwrap().balance
+}
+
+#[motsu::test]
+fn mocks_calls() {
+    let selector = balanceOfCall::SELECTOR;
+    VM::context().mock_call(TOKEN, &selector, uint!(10_U256).to_be_bytes_vec());
+
+    let result = RawCall::new().call(TOKEN, &balance_of(ALICE)).unwrap();
+    assert_eq!(decode_balance(&result), uint!(10_U256));
+
+    let result = RawCall::new_static().call(TOKEN, &balance_of(BOB)).unwrap();
+    assert_eq!(decode_balance(&result), uint!(10_U256));
+}
+
+#[motsu::test]
+fn mocks_reverts() {
+    let selector = balanceOfCall::SELECTOR;
+    VM::context().mock_revert(TOKEN, &selector, Paused {}.encode());
+
+    let err = RawCall::new().call(TOKEN, &balance_of(ALICE)).unwrap_err();
+    assert_eq!(err, Paused {}.encode());
+}
+
+#[motsu::test]
+fn prefers_most_specific_mock() {
+    let selector = balanceOfCall::SELECTOR;
+    VM::context().mock_call(TOKEN, &selector, uint!(1_U256).to_be_bytes_vec());
+    VM::context().mock_call(
+        TOKEN,
+        &balance_of(ALICE),
+        uint!(2_U256).to_be_bytes_vec(),
+    );
+
+    let result = RawCall::new().call(TOKEN, &balance_of(ALICE)).unwrap();
+    assert_eq!(decode_balance(&result), uint!(2_U256));
+
+    let result = RawCall::new().call(TOKEN, &balance_of(BOB)).unwrap();
+    assert_eq!(decode_balance(&result), uint!(1_U256));
+}
+
+#[motsu::test]
+fn replaces_mocks() {
+    let selector = balanceOfCall::SELECTOR;
+    VM::context().mock_call(TOKEN, &selector, uint!(1_U256).to_be_bytes_vec());
+    VM::context().mock_revert(TOKEN, &selector, Paused {}.encode());
+
+    assert!(RawCall::new().call(TOKEN, &balance_of(ALICE)).is_err());
+
+    VM::context().clear_mocked_calls();
+    let result = RawCall::new().call(TOKEN, &balance_of(ALICE)).unwrap();
+    assert!(result.is_empty());
+}
+
+#[motsu::test]
+fn does_not_mock_other_targets() {
+    let selector = balanceOfCall::SELECTOR;
+    VM::context().mock_call(TOKEN, &selector, uint!(1_U256).to_be_bytes_vec());
+
+    let result = RawCall::new().call(ALICE, &balance_of(ALICE)).unwrap();
+    assert!(result.is_empty());
+}
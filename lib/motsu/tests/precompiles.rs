@@ -0,0 +1,228 @@
+//! Tests for calling the standard precompiles.
+use alloy_primitives::{hex, uint, Address, U256};
+use motsu::prelude::VM;
+use stylus_sdk::call::RawCall;
+
+/// Order of the `secp256k1` curve.
+const SECP256K1_N: U256 = uint!(
+    0xFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFEBAAEDCE6AF48A03BBFD25E8CD0364141_U256
+);
+
+fn precompile(id: u8) -> Address {
+    Address::with_last_byte(id)
+}
+
+/// Returns the input of a valid `ecrecover` call, signed by
+/// `0xa94f5374fce5edbc8e2a8697c15331677e6ebf0b`.
+fn ecrecover_input() -> Vec<u8> {
+    hex!(
+        "18c547e4f7b0f325ad1e56f57e26c745b09a3e503d86e00e5255ff7f715d3d1c"
+        "000000000000000000000000000000000000000000000000000000000000001c"
+        "73b1693892219d736caba55bdb67216e485557ea6b6af75f37096c9aa6a5a75f"
+        "eeb940b1d03b21e36b0e47e79769f095fe2ab855bd91e3a38756b7d75a9c4549"
+    )
+    .to_vec()
+}
+
+const SIGNER: [u8; 32] =
+    hex!("000000000000000000000000a94f5374fce5edbc8e2a8697c15331677e6ebf0b");
+
+#[motsu::test]
+fn recovers_signer() {
+    let signer = RawCall::new_static().call(precompile(1), &ecrecover_input());
+    assert_eq!(signer, Ok(SIGNER.to_vec()));
+}
+
+#[motsu::test]
+fn recovers_signer_of_high_s_signature() {
+    // `(r, n - s)` with the opposite parity is also a valid signature.
+    let mut input = ecrecover_input();
+    let s = U256::from_be_slice(&input[96..]);
+    input[63] = 0x1b;
+    input[96..].copy_from_slice(&(SECP256K1_N - s).to_be_bytes::<32>());
+
+    let signer = RawCall::new_static().call(precompile(1), &input);
+    assert_eq!(signer, Ok(SIGNER.to_vec()));
+}
+
+#[motsu::test]
+fn rejects_invalid_signature() {
+    let mut input = ecrecover_input();
+    input[63] = 0x1d;
+    let signer = RawCall::new_static().call(precompile(1), &input);
+    assert_eq!(signer, Ok(vec![]));
+
+    let signer = RawCall::new_static().call(precompile(1), &[0; 128]);
+    assert_eq!(signer, Ok(vec![]));
+}
+
+#[motsu::test]
+fn hashes_with_sha256() {
+    let hash = RawCall::new_static().call(precompile(2), b"abc");
+    let expected = hex!(
+        "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+    );
+    assert_eq!(hash, Ok(expected.to_vec()));
+}
+
+#[motsu::test]
+fn hashes_with_ripemd160() {
+    let hash = RawCall::new_static().call(precompile(3), b"abc");
+    let expected = hex!(
+        "0000000000000000000000008eb208f7e05d987a9b044a8e98c6b087f15a0bfc"
+    );
+    assert_eq!(hash, Ok(expected.to_vec()));
+}
+
+#[motsu::test]
+fn copies_with_identity() {
+    let data = RawCall::new().call(precompile(4), b"motsu");
+    assert_eq!(data, Ok(b"motsu".to_vec()));
+}
+
+#[motsu::test]
+fn computes_modexp() {
+    // `3 ** 5 % 7` with operands of one byte, and a two bytes modulus.
+    let input = hex!(
+        "0000000000000000000000000000000000000000000000000000000000000001"
+        "0000000000000000000000000000000000000000000000000000000000000001"
+        "0000000000000000000000000000000000000000000000000000000000000002"
+        "03"
+        "05"
+        "0007"
+    );
+    let (result, gas) = VM::context()
+        .measure_gas(|| RawCall::new_static().call(precompile(5), &input));
+    assert_eq!(result, Ok(vec![0x00, 0x05]));
+    // Warm call plus the minimum cost of the precompile.
+    assert_eq!(gas, 100 + 200);
+
+    // A zero modulus results in zero.
+    let mut input = input.to_vec();
+    input[99] = 0;
+    let result = RawCall::new_static().call(precompile(5), &input);
+    assert_eq!(result, Ok(vec![0x00, 0x00]));
+}
+
+#[motsu::test]
+fn rejects_oversized_modexp_operands() {
+    // A base of 1025 bytes, over the limit of EIP-7823.
+    let mut input = [0; 96];
+    input[30..32].copy_from_slice(&1025u16.to_be_bytes());
+    let result = RawCall::new_static().call(precompile(5), &input);
+    assert_eq!(result, Err(vec![]));
+
+    // A length that doesn't fit in memory.
+    let result = RawCall::new_static().call(precompile(5), &[0xff; 96]);
+    assert_eq!(result, Err(vec![]));
+}
+
+/// Generator of the `alt_bn128` curve.
+const BN254_G1: [u8; 64] = hex!(
+    "0000000000000000000000000000000000000000000000000000000000000001"
+    "0000000000000000000000000000000000000000000000000000000000000002"
+);
+
+/// Double of [`BN254_G1`].
+const BN254_2G1: [u8; 64] = hex!(
+    "030644e72e131a029b85045b68181585d97816a916871ca8d3c208c16d87cfd3"
+    "15ed738c0e0a7c92e7845f96b2ae9c0a68a6a449e3538fc7ff3ebf7a5a18a2c4"
+);
+
+/// Order of the `alt_bn128` curve.
+const BN254_N: [u8; 32] =
+    hex!("30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000001");
+
+#[motsu::test]
+fn adds_bn254_points() {
+    let input = [BN254_G1, BN254_G1].concat();
+    let (sum, gas) = VM::context()
+        .measure_gas(|| RawCall::new_static().call(precompile(6), &input));
+    assert_eq!(sum, Ok(BN254_2G1.to_vec()));
+    assert_eq!(gas, 100 + 150);
+
+    // The point at infinity is the identity.
+    let input = [BN254_G1, [0; 64]].concat();
+    let sum = RawCall::new_static().call(precompile(6), &input);
+    assert_eq!(sum, Ok(BN254_G1.to_vec()));
+}
+
+#[motsu::test]
+fn multiplies_bn254_points() {
+    let input = [&BN254_G1[..], &U256::from(2).to_be_bytes::<32>()].concat();
+    let (product, gas) = VM::context()
+        .measure_gas(|| RawCall::new_static().call(precompile(7), &input));
+    assert_eq!(product, Ok(BN254_2G1.to_vec()));
+    assert_eq!(gas, 100 + 6_000);
+
+    // Multiplying by the order of the curve results in the point at infinity.
+    let input = [&BN254_G1[..], &BN254_N].concat();
+    let product = RawCall::new_static().call(precompile(7), &input);
+    assert_eq!(product, Ok(vec![0; 64]));
+}
+
+#[motsu::test]
+fn rejects_points_not_on_bn254() {
+    let mut point = BN254_G1;
+    point[63] = 3;
+    let input = [point, BN254_G1].concat();
+    let sum = RawCall::new_static().call(precompile(6), &input);
+    assert_eq!(sum, Err(vec![]));
+
+    let input = [&point[..], &[0; 32]].concat();
+    let product = RawCall::new_static().call(precompile(7), &input);
+    assert_eq!(product, Err(vec![]));
+}
+
+/// Returns the input of a `blake2f` call compressing the only block of
+/// `"abc"`, taken from [EIP-152].
+///
+/// [EIP-152]: https://eips.ethereum.org/EIPS/eip-152
+fn blake2f_input() -> Vec<u8> {
+    hex!(
+        "0000000c"
+        "48c9bdf267e6096a3ba7ca8485ae67bb2bf894fe72f36e3cf1361d5f3af54fa5"
+        "d182e6ad7f520e511f6c3e2b8c68059b6bbd41fbabd9831f79217e1319cde05b"
+        "6162630000000000000000000000000000000000000000000000000000000000"
+        "0000000000000000000000000000000000000000000000000000000000000000"
+        "0000000000000000000000000000000000000000000000000000000000000000"
+        "0000000000000000000000000000000000000000000000000000000000000000"
+        "0300000000000000"
+        "0000000000000000"
+        "01"
+    )
+    .to_vec()
+}
+
+#[motsu::test]
+fn compresses_with_blake2f() {
+    let (hash, gas) = VM::context().measure_gas(|| {
+        RawCall::new_static().call(precompile(9), &blake2f_input())
+    });
+    // `BLAKE2b-512("abc")`.
+    let expected = hex!(
+        "ba80a53f981c4d0d6a2797b69f12f6e94c212f14685ac4b74b12bb6fdbffa2d1"
+        "7d87c5392aab792dc252d5de4533cc9518d38aa8dbf1925ab92386edd4009923"
+    );
+    assert_eq!(hash, Ok(expected.to_vec()));
+    // Warm call plus one gas per round.
+    assert_eq!(gas, 100 + 12);
+}
+
+#[motsu::test]
+fn rejects_malformed_blake2f_input() {
+    let mut input = blake2f_input();
+    input[212] = 2;
+    let hash = RawCall::new_static().call(precompile(9), &input);
+    assert_eq!(hash, Err(vec![]));
+
+    let hash = RawCall::new_static().call(precompile(9), &input[..212]);
+    assert_eq!(hash, Err(vec![]));
+}
+
+#[motsu::test]
+fn mocks_take_precedence_over_precompiles() {
+    VM::context().mock_call(precompile(2), &[], vec![0x01]);
+    let hash = RawCall::new_static().call(precompile(2), b"abc");
+    assert_eq!(hash, Ok(vec![0x01]));
+}
@@ -0,0 +1,115 @@
+//! Tests for typed revert assertions.
+use alloy_primitives::{uint, U256};
+use alloy_sol_types::{sol, SolError};
+use motsu::prelude::{ExpectRevert, Revert};
+
+sol! {
+    #[derive(Debug)]
+    #[allow(missing_docs)]
+    error InsufficientBalance(uint256 balance, uint256 needed);
+    #[derive(Debug)]
+    #[allow(missing_docs)]
+    error Unauthorized();
+    #[allow(missing_docs)]
+    error Error(string message);
+    #[allow(missing_docs)]
+    error Panic(uint256 code);
+}
+
+#[derive(Debug)]
+enum WithdrawError {
+    InsufficientBalance(InsufficientBalance),
+}
+
+impl From<WithdrawError> for Vec<u8> {
+    fn from(err: WithdrawError) -> Self {
+        match err {
+            WithdrawError::InsufficientBalance(e) => e.encode(),
+        }
+    }
+}
+
+fn withdraw(balance: U256, amount: U256) -> Result<U256, WithdrawError> {
+    if amount > balance {
+        return Err(WithdrawError::InsufficientBalance(InsufficientBalance {
+            balance,
+            needed: amount,
+        }));
+    }
+    Ok(balance - amount)
+}
+
+#[test]
+fn decodes_expected_revert() {
+    let err = withdraw(uint!(1_U256), uint!(2_U256))
+        .motsu_expect_revert::<InsufficientBalance>();
+    assert_eq!(err.balance, uint!(1_U256));
+    assert_eq!(err.needed, uint!(2_U256));
+}
+
+#[test]
+fn matches_expected_revert() {
+    withdraw(uint!(1_U256), uint!(2_U256)).motsu_expect_revert_with(
+        |err: &InsufficientBalance| err.needed == uint!(2_U256),
+    );
+}
+
+#[test]
+#[should_panic = "but it returned 0"]
+fn panics_when_call_succeeds() {
+    withdraw(uint!(1_U256), uint!(1_U256))
+        .motsu_expect_revert::<InsufficientBalance>();
+}
+
+#[test]
+#[should_panic = "expected call to revert with `Unauthorized()`"]
+fn panics_when_call_reverts_with_other_error() {
+    withdraw(uint!(1_U256), uint!(2_U256))
+        .motsu_expect_revert::<Unauthorized>();
+}
+
+#[test]
+#[should_panic = "but it didn't match"]
+fn panics_when_revert_does_not_match() {
+    withdraw(uint!(1_U256), uint!(2_U256)).motsu_expect_revert_with(
+        |err: &InsufficientBalance| err.needed == uint!(1_U256),
+    );
+}
+
+#[test]
+fn downcasts_reverts() {
+    let err = withdraw(uint!(1_U256), uint!(2_U256)).motsu_unwrap_err();
+    assert!(err.is::<InsufficientBalance>());
+    assert!(err.downcast::<Unauthorized>().is_none());
+    let err = err.downcast::<InsufficientBalance>().unwrap();
+    assert_eq!(err.needed, uint!(2_U256));
+}
+
+#[test]
+#[should_panic = "should withdraw: call reverted with InsufficientBalance"]
+fn expect_describes_typed_reverts() {
+    withdraw(uint!(1_U256), uint!(2_U256)).motsu_expect("should withdraw");
+}
+
+#[test]
+fn describes_standard_reverts() {
+    let err = Revert::from(Error { message: "paused".to_string() }.encode());
+    assert_eq!(err.to_string(), r#"Error("paused")"#);
+
+    let err = Revert::from(Panic { code: uint!(0x11_U256) }.encode());
+    assert_eq!(
+        err.to_string(),
+        "Panic(0x11): arithmetic overflow or underflow"
+    );
+
+    let err = Revert::from(vec![0xde, 0xad]);
+    assert_eq!(err.to_string(), "0xdead");
+}
+
+#[test]
+#[should_panic = r#"(Error("paused"))"#]
+fn expect_decodes_raw_reverts() {
+    let data = Error { message: "paused".to_string() }.encode();
+    let result: Result<(), Vec<u8>> = Err(data);
+    result.motsu_expect("should succeed");
+}
@@ -0,0 +1,80 @@
+//! Tests for calling contracts with raw calldata.
+// `#[external]` checks for the `export-abi` feature of the crate using it.
+#![allow(unexpected_cfgs)]
+extern crate alloc;
+
+use alloy_primitives::{uint, U256};
+use alloy_sol_types::{sol, SolCall};
+use motsu::prelude::VM;
+use stylus_sdk::{
+    prelude::{external, sol_storage},
+    storage::TopLevelStorage,
+};
+
+sol_storage! {
+    /// Contract keeping a count.
+    #[derive(motsu::DefaultStorageLayout)]
+    pub struct Counter {
+        /// Current count.
+        uint256 count;
+    }
+}
+
+unsafe impl TopLevelStorage for Counter {}
+
+#[external]
+impl Counter {
+    /// Increases the count by `by`, returning the new count.
+    pub fn increment(&mut self, by: U256) -> U256 {
+        let count = self.count.get() + by;
+        self.count.set(count);
+        count
+    }
+
+    /// Always reverts.
+    ///
+    /// # Errors
+    ///
+    /// Always returns `0xff` as revert data.
+    pub fn fail(&self) -> Result<(), Vec<u8>> {
+        Err(vec![0xff])
+    }
+}
+
+sol! {
+    #[allow(missing_docs)]
+    function increment(uint256 by) external returns (uint256 count);
+    #[allow(missing_docs)]
+    function fail() external;
+}
+
+#[motsu::test]
+fn dispatches_calldata(contract: Counter) {
+    let calldata = incrementCall { by: uint!(2_U256) }.encode();
+    let result = VM::context().call_raw(contract, &calldata).unwrap();
+
+    let count = incrementCall::decode_returns(&result, true).unwrap().count;
+    assert_eq!(count, uint!(2_U256));
+    assert_eq!(contract.count.get(), uint!(2_U256));
+}
+
+#[motsu::test]
+fn returns_revert_data(contract: Counter) {
+    let result = VM::context().call_raw(contract, &failCall {}.encode());
+    assert_eq!(result, Err(vec![0xff]));
+}
+
+#[motsu::test]
+fn reverts_on_unknown_selector(contract: Counter) {
+    let result = VM::context().call_raw(contract, &[0xde, 0xad, 0xbe, 0xef]);
+    assert_eq!(result, Err(vec![]));
+
+    let result = VM::context().call_raw(contract, &[0x00]);
+    assert_eq!(result, Err(vec![]));
+}
+
+#[motsu::test]
+fn reverts_on_malformed_arguments(contract: Counter) {
+    let result = VM::context().call_raw(contract, &incrementCall::SELECTOR);
+    assert!(result.is_err());
+}
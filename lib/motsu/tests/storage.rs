@@ -0,0 +1,119 @@
+//! Tests for the raw storage cheatcodes exposed by the VM.
+use alloy_primitives::{address, b256, uint, Address, B256, U256};
+use motsu::{
+    prelude::{SlotChange, VM},
+    slot,
+};
+use stylus_sdk::storage::{StorageMap, StorageType, StorageU256, StorageVec};
+
+#[motsu::test]
+fn stores_and_loads_slots() {
+    let slot = b256!(
+        "0000000000000000000000000000000000000000000000000000000000000007"
+    );
+    let value = b256!(
+        "00000000000000000000000000000000000000000000000000000000000000ff"
+    );
+    assert_eq!(VM::context().load(slot), B256::ZERO);
+
+    VM::context().store(slot, value);
+    assert_eq!(VM::context().load(slot), value);
+
+    let word = unsafe { StorageU256::new(uint!(7_U256), 0) };
+    assert_eq!(word.get(), uint!(255_U256));
+}
+
+#[motsu::test]
+fn deals_balances() {
+    let alice = address!("A11CEacF9aa32246d767FCCD72e02d6bCbcC375d");
+    let balances_slot = uint!(3_U256);
+    VM::context().deal(balances_slot, alice, uint!(10_U256));
+
+    let balances: StorageMap<_, StorageU256> =
+        unsafe { StorageMap::new(balances_slot, 0) };
+    assert_eq!(balances.get(alice), uint!(10_U256));
+}
+
+#[motsu::test]
+fn diffs_snapshots() {
+    let one = B256::with_last_byte(1);
+    let two = B256::with_last_byte(2);
+    VM::context().store(one, one);
+    VM::context().store(two, one);
+    let before = VM::context().snapshot();
+
+    let mut word = unsafe { StorageU256::new(uint!(1_U256), 0) };
+    word.set(uint!(0_U256));
+    let mut word = unsafe { StorageU256::new(uint!(2_U256), 0) };
+    word.set(uint!(1_U256));
+    let mut word = unsafe { StorageU256::new(uint!(3_U256), 0) };
+    word.set(uint!(2_U256));
+    let after = VM::context().snapshot();
+
+    let three = B256::with_last_byte(3);
+    assert_eq!(
+        VM::context().storage_diff(&before, &after),
+        vec![
+            SlotChange { slot: one, before: one, after: B256::ZERO },
+            SlotChange { slot: three, before: B256::ZERO, after: two },
+        ]
+    );
+    assert_eq!(after.get(two), one);
+}
+
+#[motsu::test]
+fn allows_expected_changes() {
+    let slot = B256::with_last_byte(1);
+    let value = VM::context().assert_changes_only(&[slot], || {
+        VM::context().store(slot, slot);
+        VM::context().load(slot)
+    });
+    assert_eq!(value, slot);
+}
+
+#[motsu::test]
+#[should_panic = "call changed unexpected storage slots"]
+fn rejects_unexpected_changes() {
+    let slot = B256::with_last_byte(1);
+    VM::context().assert_changes_only(&[slot], || {
+        VM::context().store(B256::with_last_byte(2), slot);
+    });
+}
+
+#[motsu::test]
+fn computes_solidity_slots() {
+    let alice = address!("A11CEacF9aa32246d767FCCD72e02d6bCbcC375d");
+    let bob = address!("B0B0cB49ec2e96DF5F5fFB081acaE66A2cBBc2e2");
+
+    let mut balances: StorageMap<Address, StorageU256> =
+        unsafe { StorageMap::new(uint!(0_U256), 0) };
+    balances.insert(alice, uint!(1_U256));
+    let mut allowances: StorageMap<Address, StorageMap<Address, StorageU256>> =
+        unsafe { StorageMap::new(uint!(1_U256), 0) };
+    allowances.setter(alice).insert(bob, uint!(2_U256));
+    let mut ids: StorageMap<U256, StorageU256> =
+        unsafe { StorageMap::new(uint!(2_U256), 0) };
+    ids.insert(uint!(7_U256), uint!(3_U256));
+    let mut values: StorageVec<StorageU256> =
+        unsafe { StorageVec::new(uint!(3_U256), 0) };
+    values.push(uint!(4_U256));
+    values.push(uint!(5_U256));
+
+    let context = VM::context();
+    context.assert_slot(slot::mapping(uint!(0_U256), alice), uint!(1_U256));
+    let allowance = slot::mapping(slot::mapping(uint!(1_U256), alice), bob);
+    context.assert_slot(allowance, uint!(2_U256));
+    context.assert_slot(
+        slot::mapping(uint!(2_U256), uint!(7_U256)),
+        uint!(3_U256),
+    );
+    context.assert_slot(uint!(3_U256), uint!(2_U256));
+    context
+        .assert_slot(slot::array(uint!(3_U256), uint!(1_U256)), uint!(5_U256));
+}
+
+#[motsu::test]
+#[should_panic = "unexpected value at storage slot"]
+fn asserts_slot_values() {
+    VM::context().assert_slot(uint!(0_U256), uint!(1_U256));
+}
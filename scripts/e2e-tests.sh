@@ -9,6 +9,12 @@ NIGHTLY_TOOLCHAIN=${NIGHTLY_TOOLCHAIN:-nightly}
 cargo +"$NIGHTLY_TOOLCHAIN" build --release --target wasm32-unknown-unknown -Z build-std=std,panic_abort -Z build-std-features=panic_immediate_abort
 
 export RPC_URL=http://localhost:8547
+export GAS_REPORT="$PWD/target/gas-report.jsonl"
+rm -f "$GAS_REPORT"
 # We should use stable here once nitro-testnode is updated and the contracts fit
 # the size limit. Work tracked [here](https://github.com/OpenZeppelin/rust-contracts-stylus/issues/87)
 cargo +"$NIGHTLY_TOOLCHAIN" test --features std,e2e --test "*"
+
+# Set `GAS_BASELINE` to a previous report to fail on gas regressions.
+cargo run --quiet -p e2e --bin gas-report -- "$GAS_REPORT" \
+  ${GAS_BASELINE:+--compare "$GAS_BASELINE" --threshold "${GAS_THRESHOLD:-0}"}
@@ -0,0 +1,80 @@
+#!/bin/bash
+# Reports the size of the wasm binary of every example contract, before and
+# after optimizing it with `wasm-opt`, as a markdown table.
+#
+# Stylus limits the brotli-compressed size of deployed code, so compressed
+# sizes are what matter. When `twiggy` is installed, the largest items of every
+# binary, optimized if possible, are listed as well, to find which library
+# modules contribute the most.
+#
+# Requires `brotli`, and `wasm-opt` from binaryen to report optimized sizes.
+set -e
+
+MYDIR=$(realpath "$(dirname "$0")")
+cd "$MYDIR"
+cd ..
+
+# Retrieve all alphanumeric contract's crate names in `./examples` directory.
+get_example_crate_names () {
+  # shellcheck disable=SC2038
+  # NOTE: optimistically relying on the 'name = ' string at Cargo.toml file
+  find ./examples -maxdepth 2 -type f -name "Cargo.toml" | xargs grep 'name = ' | grep -oE '".*"' | tr -d "'\""
+}
+
+# Print the brotli-compressed size of a file, in bytes.
+compressed_size () {
+  brotli --stdout --quality=11 "$1" | wc -c | tr -d ' '
+}
+
+NIGHTLY_TOOLCHAIN=${NIGHTLY_TOOLCHAIN:-nightly}
+TWIGGY_TOP=${TWIGGY_TOP:-10}
+WASM_DIR=./target/wasm32-unknown-unknown/release
+
+cargo +"$NIGHTLY_TOOLCHAIN" build --release --target wasm32-unknown-unknown -Z build-std=std,panic_abort -Z build-std-features=panic_immediate_abort
+
+HAS_WASM_OPT=false
+if command -v wasm-opt > /dev/null; then
+  HAS_WASM_OPT=true
+fi
+
+echo "| Contract | Uncompressed | Compressed | Optimized | Optimized compressed |"
+echo "| -------- | ------------ | ---------- | --------- | -------------------- |"
+
+BINARIES=()
+for CRATE_NAME in $(get_example_crate_names)
+do
+  WASM="$WASM_DIR/${CRATE_NAME//-/_}.wasm"
+  SIZE=$(wc -c < "$WASM" | tr -d ' ')
+  COMPRESSED=$(compressed_size "$WASM")
+
+  OPT_SIZE="-"
+  OPT_COMPRESSED="-"
+  BINARY="$WASM"
+  if $HAS_WASM_OPT; then
+    OPT_WASM="$WASM_DIR/${CRATE_NAME//-/_}.opt.wasm"
+    wasm-opt -Oz --strip-debug "$WASM" -o "$OPT_WASM"
+    OPT_SIZE=$(wc -c < "$OPT_WASM" | tr -d ' ')
+    OPT_COMPRESSED=$(compressed_size "$OPT_WASM")
+    BINARY="$OPT_WASM"
+  fi
+  BINARIES+=("$CRATE_NAME:$BINARY")
+
+  echo "| $CRATE_NAME | $SIZE | $COMPRESSED | $OPT_SIZE | $OPT_COMPRESSED |"
+done
+
+if ! $HAS_WASM_OPT; then
+  echo
+  echo "Install binaryen to report the sizes of optimized binaries."
+fi
+
+if command -v twiggy > /dev/null; then
+  for ENTRY in "${BINARIES[@]}"
+  do
+    echo
+    echo "### ${ENTRY%%:*}"
+    echo
+    echo '```'
+    twiggy top -n "$TWIGGY_TOP" "${ENTRY#*:}"
+    echo '```'
+  done
+fi